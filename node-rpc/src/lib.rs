@@ -113,11 +113,14 @@ where
     C::Api: node_rpc::identity::IdentityRuntimeApi<Block, IdentityId, Ticker, AccountId, Moment>,
     C::Api: pallet_protocol_fee_rpc::ProtocolFeeRuntimeApi<Block>,
     C::Api: node_rpc::asset::AssetRuntimeApi<Block, AccountId>,
+    C::Api: node_rpc::compliance::ComplianceRuntimeApi<Block>,
     C::Api: pallet_group_rpc::GroupRuntimeApi<Block>,
     C::Api: BabeApi<Block>,
     C::Api: BlockBuilder<Block>,
     C::Api: node_rpc::nft::NFTRuntimeApi<Block>,
+    C::Api: node_rpc::multisig::MultiSigRuntimeApi<Block, AccountId, Moment>,
     C::Api: node_rpc::settlement::SettlementRuntimeApi<Block>,
+    C::Api: node_rpc::treasury::TreasuryRuntimeApi<Block, BlockNumber>,
     P: TransactionPool + 'static,
     SC: SelectChain<Block> + 'static,
     B: sc_client_api::Backend<Block> + Send + Sync + 'static,
@@ -125,11 +128,14 @@ where
 {
     use node_rpc::{
         asset::{Asset, AssetApiServer},
+        compliance::{Compliance, ComplianceApiServer},
         identity::{Identity, IdentityApiServer},
+        multisig::{MultiSig, MultiSigApiServer},
         nft::{NFTApiServer, NFT},
         pips::{Pips, PipsApiServer},
         settlement::{Settlement, SettlementApiServer},
         transaction_payment::{TransactionPayment, TransactionPaymentApiServer},
+        treasury::{Treasury, TreasuryApiServer},
     };
     use pallet_group_rpc::{Group, GroupApiServer};
     use pallet_protocol_fee_rpc::{ProtocolFee, ProtocolFeeApiServer};
@@ -220,9 +226,12 @@ where
     io.merge(Identity::new(client.clone()).into_rpc())?;
     io.merge(ProtocolFee::new(client.clone()).into_rpc())?;
     io.merge(Asset::new(client.clone()).into_rpc())?;
+    io.merge(Compliance::new(client.clone()).into_rpc())?;
     io.merge(Group::from(client.clone()).into_rpc())?;
     io.merge(NFT::new(client.clone()).into_rpc())?;
-    io.merge(Settlement::new(client).into_rpc())?;
+    io.merge(MultiSig::new(client.clone()).into_rpc())?;
+    io.merge(Settlement::new(client.clone()).into_rpc())?;
+    io.merge(Treasury::new(client).into_rpc())?;
 
     Ok(io)
 }
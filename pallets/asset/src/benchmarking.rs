@@ -653,4 +653,29 @@ benchmarks! {
         let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
         Module::<T>::pre_approve_ticker(alice.clone().origin().into(), ticker).unwrap();
     }: _(alice.origin, ticker)
+
+    set_fee_asset_rate {
+        let (_, ticker) = owned_ticker::<T>();
+    }: _(RawOrigin::Root, ticker, Some(PosRatio(1, 10)))
+    verify {
+        assert_eq!(FeeAssetRates::get(&ticker), Some(PosRatio(1, 10)));
+    }
+
+    set_fee_collector {
+        let alice = UserBuilder::<T>::default().generate_did().build("Alice");
+    }: _(RawOrigin::Root, Some(alice.did()))
+    verify {
+        assert_eq!(FeeCollector::get(), Some(alice.did()));
+    }
+
+    set_fee_payment_asset {
+        let (alice, ticker) = owned_ticker::<T>();
+        Module::<T>::set_fee_asset_rate(RawOrigin::Root.into(), ticker, Some(PosRatio(1, 10))).unwrap();
+    }: _(alice.origin.clone(), Some((ticker, PortfolioKind::Default)))
+    verify {
+        assert_eq!(
+            FeePaymentAssets::<T>::get(&alice.account()),
+            Some((ticker, PortfolioId::default_portfolio(alice.did())))
+        );
+    }
 }
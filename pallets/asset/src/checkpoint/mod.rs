@@ -222,7 +222,7 @@ decl_module! {
         ///
         /// # Permissions
         /// * Asset
-        #[weight = T::CPWeightInfo::create_schedule()]
+        #[weight = T::CPWeightInfo::create_schedule().saturating_add(T::ProtocolFee::charge_fee_weight())]
         pub fn create_schedule(
             origin,
             ticker: Ticker,
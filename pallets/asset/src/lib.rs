@@ -55,6 +55,9 @@
 //! - `register_asset_metadata_local_type` - Register asset metadata local type.
 //! - `register_asset_metadata_global_type` - Register asset metadata global type.
 //! - `redeem_from_portfolio` - Redeems tokens from the caller's portfolio.
+//! - `set_fee_asset_rate` - Sets the conversion rate of an asset that can be used to pay fees.
+//! - `set_fee_collector` - Sets the identity receiving the fees paid in assets.
+//! - `set_fee_payment_asset` - Chooses the asset and portfolio used to pay the fees of a key.
 //!
 //! ### Public Functions
 //!
@@ -100,13 +103,14 @@ use sp_std::{convert::TryFrom, prelude::*};
 use pallet_base::{
     ensure_opt_string_limited, ensure_string_limited, try_next_pre, Error::CounterOverflow,
 };
-use pallet_identity::PermissionedCallOriginData;
+use pallet_identity::{KeyRecords, PermissionedCallOriginData};
 use polymesh_common_utilities::asset::AssetFnTrait;
 use polymesh_common_utilities::compliance_manager::ComplianceFnConfig;
 use polymesh_common_utilities::constants::*;
 use polymesh_common_utilities::protocol_fee::{ChargeProtocolFee, ProtocolOp};
 pub use polymesh_common_utilities::traits::asset::{Config, Event, RawEvent, WeightInfo};
 use polymesh_common_utilities::traits::nft::NFTTrait;
+use polymesh_common_utilities::transaction_payment::{FeeAssetPayer, FeeAssetPayment};
 use polymesh_common_utilities::with_transaction;
 
 use polymesh_primitives::agent::AgentGroup;
@@ -122,8 +126,8 @@ use polymesh_primitives::settlement::InstructionId;
 use polymesh_primitives::transfer_compliance::TransferConditionResult;
 use polymesh_primitives::{
     extract_auth, storage_migration_ver, AssetIdentifier, Balance, Document, DocumentId,
-    IdentityId, Memo, PortfolioId, PortfolioKind, PortfolioUpdateReason, SecondaryKey, Ticker,
-    WeightMeter,
+    IdentityId, KeyRecord, Memo, PortfolioId, PortfolioKind, PortfolioUpdateReason, PosRatio,
+    SecondaryKey, Ticker, WeightMeter,
 };
 
 type Checkpoint<T> = checkpoint::Module<T>;
@@ -131,6 +135,10 @@ type ExternalAgents<T> = pallet_external_agents::Module<T>;
 type Portfolio<T> = pallet_portfolio::Module<T>;
 type Statistics<T> = pallet_statistics::Module<T>;
 
+/// Maximum weight the transfer restrictions of a fee asset may consume when paying a fee with it.
+/// Fees in assets with more expensive restrictions are paid in POLYX instead.
+pub const MAX_FEE_ASSET_TRANSFER_WEIGHT: Weight = Weight::from_ref_time(500_000_000);
+
 /// Ownership status of a ticker/token.
 #[derive(Encode, Decode, TypeInfo, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum AssetOwnershipRelation {
@@ -291,6 +299,19 @@ decl_storage! {
         pub PreApprovedTicker get(fn pre_approved_tickers):
             double_map hasher(identity) IdentityId, hasher(blake2_128_concat) Ticker => bool;
 
+        /// Assets that can be used to pay fees, with their conversion rate as units of the asset per POLYX.
+        /// ticker -> rate
+        pub FeeAssetRates get(fn fee_asset_rate):
+            map hasher(blake2_128_concat) Ticker => Option<PosRatio>;
+
+        /// The identity receiving the fees paid in assets, in its default portfolio.
+        pub FeeCollector get(fn fee_collector): Option<IdentityId>;
+
+        /// The asset and portfolio used by a key to pay its fees.
+        /// (payer key) -> (ticker, portfolio)
+        pub FeePaymentAssets get(fn fee_payment_asset):
+            map hasher(blake2_128_concat) T::AccountId => Option<(Ticker, PortfolioId)>;
+
         /// Storage version.
         StorageVersion get(fn storage_version) build(|_| Version::new(3)): Version;
     }
@@ -352,7 +373,7 @@ decl_module! {
         ///
         /// # Permissions
         /// * Asset
-        #[weight = <T as Config>::WeightInfo::register_ticker()]
+        #[weight = <T as Config>::WeightInfo::register_ticker().saturating_add(T::ProtocolFee::charge_fee_weight())]
         pub fn register_ticker(origin, ticker: Ticker) -> DispatchResult {
             Self::base_register_ticker(origin, ticker)
         }
@@ -413,7 +434,7 @@ decl_module! {
             name.len() as u32,
             identifiers.len() as u32,
             funding_round.as_ref().map_or(0, |name| name.len()) as u32
-        )]
+        ).saturating_add(T::ProtocolFee::charge_fee_weight().saturating_mul(2))]
         pub fn create_asset(
             origin,
             name: AssetName,
@@ -488,7 +509,7 @@ decl_module! {
         /// # Permissions
         /// * Asset
         /// * Portfolio
-        #[weight = <T as Config>::WeightInfo::issue()]
+        #[weight = <T as Config>::WeightInfo::issue().saturating_add(T::ProtocolFee::charge_fee_weight())]
         pub fn issue(origin, ticker: Ticker, amount: Balance, portfolio_kind: PortfolioKind) -> DispatchResult {
             Self::base_issue(origin, ticker, amount, portfolio_kind)
         }
@@ -539,7 +560,7 @@ decl_module! {
         ///
         /// # Permissions
         /// * Asset
-        #[weight = <T as Config>::WeightInfo::add_documents(docs.len() as u32)]
+        #[weight = <T as Config>::WeightInfo::add_documents(docs.len() as u32).saturating_add(T::ProtocolFee::charge_fee_weight())]
         pub fn add_documents(origin, docs: Vec<Document>, ticker: Ticker) -> DispatchResult {
             Self::base_add_documents(origin, docs, ticker)
         }
@@ -630,7 +651,8 @@ decl_module! {
             name.len() as u32,
             identifiers.len() as u32,
             funding_round.as_ref().map_or(0, |name| name.len()) as u32
-        ) + <T as Config>::WeightInfo::register_custom_asset_type(custom_asset_type.len() as u32)]
+        ) + <T as Config>::WeightInfo::register_custom_asset_type(custom_asset_type.len() as u32)
+            + T::ProtocolFee::charge_fee_weight().saturating_mul(2)]
         pub fn create_asset_with_custom_type(
             origin,
             name: AssetName,
@@ -893,6 +915,60 @@ decl_module! {
         pub fn remove_ticker_pre_approval(origin, ticker: Ticker) -> DispatchResult {
             Self::base_remove_ticker_pre_approval(origin, ticker)
         }
+
+        /// Sets or removes the conversion rate of an asset that can be used to pay fees.
+        ///
+        /// # Arguments
+        /// * `origin` - the root origin.
+        /// * `ticker` - the [`Ticker`] of the fee asset.
+        /// * `rate` - the units of the asset charged per POLYX of fees, or `None` to stop accepting the asset.
+        ///
+        /// # Errors
+        /// * `NoSuchAsset` - if the asset doesn't exist.
+        /// * `InvalidFeeAssetRate` - if the rate has a zero numerator or denominator.
+        ///
+        /// # Permissions
+        /// * Root
+        #[weight = <T as Config>::WeightInfo::set_fee_asset_rate()]
+        pub fn set_fee_asset_rate(origin, ticker: Ticker, rate: Option<PosRatio>) -> DispatchResult {
+            Self::base_set_fee_asset_rate(origin, ticker, rate)
+        }
+
+        /// Sets or removes the identity receiving the fees paid in assets.
+        ///
+        /// # Arguments
+        /// * `origin` - the root origin.
+        /// * `collector` - the [`IdentityId`] of the fee collector, or `None` to disable fee payments in assets.
+        ///
+        /// # Permissions
+        /// * Root
+        #[weight = <T as Config>::WeightInfo::set_fee_collector()]
+        pub fn set_fee_collector(origin, collector: Option<IdentityId>) -> DispatchResult {
+            Self::base_set_fee_collector(origin, collector)
+        }
+
+        /// Chooses the asset and the portfolio used to pay the fees of the caller's key.
+        ///
+        /// Transaction and protocol fees are paid in POLYX whenever the payment in the asset fails,
+        /// e.g. if the key has lost its permissions over the portfolio.
+        /// The choice is removed once the key is unlinked from its identity.
+        ///
+        /// # Arguments
+        /// * `origin` - the secondary key of the sender.
+        /// * `fee_asset` - the [`Ticker`] of a fee asset and the caller's portfolio to pay from,
+        ///   or `None` to pay fees in POLYX.
+        ///
+        /// # Errors
+        /// * `NotAFeeAsset` - if the asset can't be used to pay fees.
+        /// * `PortfolioDoesNotExist` - if the portfolio doesn't exist.
+        /// * `UnauthorizedCustodian` - if the caller's identity isn't the custodian of the portfolio.
+        ///
+        /// # Permissions
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::set_fee_payment_asset()]
+        pub fn set_fee_payment_asset(origin, fee_asset: Option<(Ticker, PortfolioKind)>) -> DispatchResult {
+            Self::base_set_fee_payment_asset(origin, fee_asset)
+        }
     }
 }
 
@@ -972,6 +1048,18 @@ decl_error! {
         AssetMetadataKeyBelongsToNFTCollection,
         /// Attempt to lock a metadata value that is empty.
         AssetMetadataValueIsEmpty,
+        /// The supply of an asset representing the fractions of an NFT can't be changed while the NFT is in custody.
+        FractionsSupplyLocked,
+        /// The conversion rate of a fee asset can't be zero.
+        InvalidFeeAssetRate,
+        /// The asset can't be used to pay fees.
+        NotAFeeAsset,
+        /// No identity is collecting the fees paid in assets.
+        FeeCollectorMissing,
+        /// The key didn't choose an asset to pay its fees.
+        NoFeePaymentAsset,
+        /// The key is no longer permitted to pay fees from the portfolio of its fee asset.
+        FeePayerNotPermitted,
     }
 }
 
@@ -989,6 +1077,10 @@ impl<T: Config> Module<T> {
             portfolio_kind,
             false,
         )?;
+        ensure!(
+            !T::NFTFn::is_fractions_asset(&ticker),
+            Error::<T>::FractionsSupplyLocked
+        );
         let mut weight_meter = WeightMeter::max_limit_no_minimum();
         Self::_mint(
             &ticker,
@@ -1800,6 +1892,10 @@ impl<T: Config> Module<T> {
             portfolio_kind,
             true,
         )?;
+        ensure!(
+            !T::NFTFn::is_fractions_asset(&ticker),
+            Error::<T>::FractionsSupplyLocked
+        );
 
         Self::ensure_granular(&ticker, value)?;
 
@@ -1952,7 +2048,7 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
-    fn is_asset_metadata_locked(ticker: Ticker, key: AssetMetadataKey) -> bool {
+    pub fn is_asset_metadata_locked(ticker: Ticker, key: AssetMetadataKey) -> bool {
         AssetMetadataValueDetails::<T>::get(ticker, key).map_or(false, |details| {
             details.is_locked(<pallet_timestamp::Pallet<T>>::get())
         })
@@ -1966,7 +2062,7 @@ impl<T: Config> Module<T> {
     }
 
     /// Ensure asset metadata `value` is within the global limit.
-    fn ensure_asset_metadata_value_limited(value: &AssetMetadataValue) -> DispatchResult {
+    pub fn ensure_asset_metadata_value_limited(value: &AssetMetadataValue) -> DispatchResult {
         ensure!(
             value.len() <= T::AssetMetadataValueMaxLength::get() as usize,
             Error::<T>::AssetMetadataValueMaxLengthExceeded
@@ -2510,6 +2606,204 @@ impl<T: Config> Module<T> {
         Self::deposit_event(RawEvent::RemovePreApprovedAsset(caller_did, ticker));
         Ok(())
     }
+
+    /// Sets or removes the conversion rate of a fee asset.
+    fn base_set_fee_asset_rate(
+        origin: T::RuntimeOrigin,
+        ticker: Ticker,
+        rate: Option<PosRatio>,
+    ) -> DispatchResult {
+        ensure_root(origin)?;
+        match rate {
+            Some(rate) => {
+                Self::ensure_asset_exists(&ticker)?;
+                ensure!(rate.0 > 0 && rate.1 > 0, Error::<T>::InvalidFeeAssetRate);
+                FeeAssetRates::insert(&ticker, rate);
+            }
+            None => FeeAssetRates::remove(&ticker),
+        }
+        Self::deposit_event(RawEvent::FeeAssetRateSet(ticker, rate));
+        Ok(())
+    }
+
+    /// Sets or removes the fee collector.
+    fn base_set_fee_collector(
+        origin: T::RuntimeOrigin,
+        collector: Option<IdentityId>,
+    ) -> DispatchResult {
+        ensure_root(origin)?;
+        FeeCollector::set(collector);
+        Self::deposit_event(RawEvent::FeeCollectorSet(collector));
+        Ok(())
+    }
+
+    /// Chooses the asset and the portfolio used to pay the fees of the caller's key.
+    fn base_set_fee_payment_asset(
+        origin: T::RuntimeOrigin,
+        fee_asset: Option<(Ticker, PortfolioKind)>,
+    ) -> DispatchResult {
+        let PermissionedCallOriginData {
+            sender,
+            primary_did,
+            secondary_key,
+        } = Identity::<T>::ensure_origin_call_permissions(origin)?;
+
+        let fee_asset = match fee_asset {
+            Some((ticker, portfolio_kind)) => {
+                ensure!(
+                    FeeAssetRates::contains_key(&ticker),
+                    Error::<T>::NotAFeeAsset
+                );
+                let portfolio_id = PortfolioId::new(primary_did, portfolio_kind);
+                Portfolio::<T>::ensure_portfolio_validity(&portfolio_id)?;
+                Portfolio::<T>::ensure_portfolio_custody(portfolio_id, primary_did)?;
+                Portfolio::<T>::ensure_user_portfolio_permission(
+                    secondary_key.as_ref(),
+                    portfolio_id,
+                )?;
+                FeePaymentAssets::<T>::insert(&sender, (ticker, portfolio_id));
+                Some((ticker, portfolio_id))
+            }
+            None => {
+                FeePaymentAssets::<T>::remove(&sender);
+                None
+            }
+        };
+        Self::deposit_event(RawEvent::FeePaymentAssetSet(primary_did, sender, fee_asset));
+        Ok(())
+    }
+
+    /// Converts a `fee` in POLYX to the units of a fee asset with the conversion `rate`, rounding up.
+    fn fee_in_asset(fee: Balance, rate: PosRatio) -> Balance {
+        let (num, den) = (Balance::from(rate.0), Balance::from(rate.1.max(1)));
+        fee.saturating_mul(num)
+            .saturating_add(den - 1)
+            .checked_div(den)
+            .unwrap_or_default()
+    }
+
+    /// Returns a [`WeightMeter`] bounding the transfer restrictions checked when paying a fee in an asset.
+    fn fee_asset_weight_meter() -> Result<WeightMeter, DispatchError> {
+        WeightMeter::from_limit(Weight::zero(), MAX_FEE_ASSET_TRANSFER_WEIGHT)
+            .map_err(|_| Error::<T>::InvalidTransfer.into())
+    }
+
+    /// Returns the weight of paying a fee in an asset and refunding its unused part.
+    ///
+    /// Both are transfers whose restrictions are bounded by `MAX_FEE_ASSET_TRANSFER_WEIGHT`.
+    fn fee_asset_payment_weight() -> Weight {
+        <T as Config>::WeightInfo::base_transfer()
+            .saturating_add(MAX_FEE_ASSET_TRANSFER_WEIGHT)
+            .saturating_mul(2)
+            .saturating_add(T::DbWeight::get().reads(5))
+    }
+
+    /// Transfers the value of `fee` POLYX, in the fee asset chosen by `payer`, to the fee collector.
+    fn base_pay_fee_in_asset(
+        payer: &T::AccountId,
+        fee: Balance,
+    ) -> Result<FeeAssetPayment, DispatchError> {
+        let (ticker, portfolio_id) =
+            FeePaymentAssets::<T>::get(payer).ok_or(Error::<T>::NoFeePaymentAsset)?;
+        let rate = FeeAssetRates::get(&ticker).ok_or(Error::<T>::NotAFeeAsset)?;
+        let collector = FeeCollector::get().ok_or(Error::<T>::FeeCollectorMissing)?;
+        let amount = Self::fee_in_asset(fee, rate);
+
+        // The key may have left the identity or lost its permissions since the fee asset was chosen.
+        ensure!(
+            Identity::<T>::get_identity(payer) == Some(portfolio_id.did),
+            Error::<T>::FeePayerNotPermitted
+        );
+        if let Some(KeyRecord::SecondaryKey(_, permissions)) = KeyRecords::<T>::get(payer) {
+            let secondary_key = SecondaryKey::new(payer.clone(), permissions);
+            Portfolio::<T>::ensure_user_portfolio_permission(Some(&secondary_key), portfolio_id)?;
+        }
+
+        with_transaction(|| {
+            // The custody of the portfolio may have changed since the fee asset was chosen.
+            Portfolio::<T>::ensure_portfolio_custody(portfolio_id, portfolio_id.did)?;
+            Self::base_transfer(
+                portfolio_id,
+                PortfolioId::default_portfolio(collector),
+                &ticker,
+                amount,
+                None,
+                None,
+                portfolio_id.did,
+                &mut Self::fee_asset_weight_meter()?,
+            )
+        })?;
+
+        Self::deposit_event(RawEvent::FeePaidInAsset(
+            payer.clone(),
+            ticker,
+            portfolio_id,
+            amount,
+            fee,
+        ));
+        Ok(FeeAssetPayment {
+            ticker,
+            portfolio: portfolio_id,
+            collector,
+            rate,
+            amount,
+            weight: Self::fee_asset_payment_weight(),
+        })
+    }
+
+    /// Transfers back to `payer` the part of `payment` above the value of `actual_fee` POLYX.
+    fn base_refund_fee_in_asset(
+        payer: &T::AccountId,
+        payment: FeeAssetPayment,
+        actual_fee: Balance,
+    ) -> DispatchResult {
+        let refund = payment
+            .amount
+            .saturating_sub(Self::fee_in_asset(actual_fee, payment.rate));
+        if refund.is_zero() {
+            return Ok(());
+        }
+
+        with_transaction(|| {
+            Self::base_transfer(
+                PortfolioId::default_portfolio(payment.collector),
+                payment.portfolio,
+                &payment.ticker,
+                refund,
+                None,
+                None,
+                payment.collector,
+                &mut Self::fee_asset_weight_meter()?,
+            )
+        })?;
+
+        Self::deposit_event(RawEvent::FeeRefundedInAsset(
+            payer.clone(),
+            payment.ticker,
+            payment.portfolio,
+            refund,
+        ));
+        Ok(())
+    }
+}
+
+impl<T: Config> FeeAssetPayer<T::AccountId> for Module<T> {
+    fn fee_asset_payment_weight(payer: &T::AccountId) -> Option<Weight> {
+        FeePaymentAssets::<T>::contains_key(payer).then(Self::fee_asset_payment_weight)
+    }
+
+    fn pay_fee_in_asset(payer: &T::AccountId, fee: Balance) -> Option<FeeAssetPayment> {
+        Self::base_pay_fee_in_asset(payer, fee).ok()
+    }
+
+    fn refund_fee_in_asset(payer: &T::AccountId, payment: FeeAssetPayment, actual_fee: Balance) {
+        // The overcharged fee stays with the fee collector if the refund fails.
+        let _ = Self::base_refund_fee_in_asset(payer, payment, actual_fee);
+    }
+
+    fn remove_fee_payment_asset(payer: &T::AccountId) {
+        FeePaymentAssets::<T>::remove(payer);
+    }
 }
 
 impl<T: Config> AssetFnTrait<T::AccountId, T::RuntimeOrigin> for Module<T> {
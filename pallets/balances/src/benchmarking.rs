@@ -22,7 +22,7 @@ use crate::Module as Balances;
 use frame_benchmarking::benchmarks;
 use frame_system::RawOrigin;
 use polymesh_common_utilities::{
-    benchs::{AccountIdOf, UserBuilder},
+    benchs::{AccountIdOf, User, UserBuilder},
     traits::TestUtilsFn,
 };
 
@@ -30,6 +30,32 @@ fn make_worst_memo() -> Option<Memo> {
     Some(Memo([7u8; 32]))
 }
 
+/// Adds `n` revocable vesting schedules of `MinVestedTransfer` to `beneficiary`,
+/// and returns their grantor.
+fn add_vesting_schedules<T: Config + TestUtilsFn<AccountIdOf<T>>>(
+    beneficiary: &User<T>,
+    n: u32,
+) -> User<T> {
+    let grantor = UserBuilder::<T>::default().generate_did().build("grantor");
+    let amount = T::MinVestedTransfer::get().max(1);
+    Balances::<T>::make_free_balance_be(&grantor.account, amount * Balance::from(n + 1));
+    let kind = VestingKind::Linear {
+        duration: 100u32.into(),
+    };
+    for _ in 0..n {
+        Balances::<T>::vested_transfer(
+            grantor.origin().into(),
+            beneficiary.lookup(),
+            amount,
+            0u32.into(),
+            kind,
+            true,
+        )
+        .unwrap();
+    }
+    grantor
+}
+
 benchmarks! {
     where_clause { where T: TestUtilsFn<AccountIdOf<T>> }
 
@@ -93,4 +119,50 @@ benchmarks! {
     verify {
         assert_eq!(Balances::<T>::free_balance(&caller.account), (1000u32-500).into());
     }
+
+    vested_transfer {
+        let recipient = UserBuilder::<T>::default().balance(0u32).generate_did().build("recipient");
+        add_vesting_schedules::<T>(&recipient, T::MaxVestingSchedules::get() - 1);
+        let caller = UserBuilder::<T>::default().generate_did().build("caller");
+        let amount = T::MinVestedTransfer::get().max(1);
+        Balances::<T>::make_free_balance_be(&caller.account, amount);
+        let kind = VestingKind::Step { period: 10u32.into(), steps: 10 };
+    }: _(caller.origin(), recipient.lookup(), amount, 0u32.into(), kind, true)
+    verify {
+        assert_eq!(Balances::<T>::vesting_schedules(&recipient.account).len() as u32, T::MaxVestingSchedules::get());
+    }
+
+    vest {
+        let n in 1 .. T::MaxVestingSchedules::get();
+
+        let recipient = UserBuilder::<T>::default().balance(0u32).generate_did().build("recipient");
+        add_vesting_schedules::<T>(&recipient, n);
+        frame_system::Pallet::<T>::set_block_number(50u32.into());
+    }: _(recipient.origin())
+    verify {
+        let amount = T::MinVestedTransfer::get().max(1);
+        assert!(Balances::<T>::vesting_reserved(&recipient.account) < amount * Balance::from(n));
+    }
+
+    revoke_vesting {
+        let n in 1 .. T::MaxVestingSchedules::get();
+
+        let recipient = UserBuilder::<T>::default().balance(0u32).generate_did().build("recipient");
+        let grantor = add_vesting_schedules::<T>(&recipient, n);
+        frame_system::Pallet::<T>::set_block_number(50u32.into());
+    }: _(grantor.origin(), recipient.lookup(), (n - 1).into())
+    verify {
+        assert_eq!(Balances::<T>::vesting_schedules(&recipient.account).len() as u32, n - 1);
+    }
+
+    reject_vesting {
+        let n in 1 .. T::MaxVestingSchedules::get();
+
+        let recipient = UserBuilder::<T>::default().balance(0u32).generate_did().build("recipient");
+        add_vesting_schedules::<T>(&recipient, n);
+        frame_system::Pallet::<T>::set_block_number(50u32.into());
+    }: _(recipient.origin(), (n - 1).into())
+    verify {
+        assert_eq!(Balances::<T>::vesting_schedules(&recipient.account).len() as u32, n - 1);
+    }
 }
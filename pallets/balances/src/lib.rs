@@ -39,6 +39,7 @@
 //! - Added CDD check for POLYX recipients.
 //! - Added ability to attach a memo with a transfer.
 //! - Added ability to burn your tokens.
+//! - Added vesting schedules (cliff, linear and step) that lock POLYX until it is released.
 //!
 //! The Original Balances module provides functions for:
 //!
@@ -66,8 +67,10 @@
 //! simply dropped, it should automatically maintain any book-keeping such as total issuance.)
 //! - **Lock:** A freeze on a specified amount of an account's free balance until a specified block number. Multiple
 //! locks always operate over the same funds, so they "overlay" rather than "stack".
-//! - **Vesting:** Similar to a lock, this is another, but independent, liquidity restriction that reduces linearly
-//! over time.
+//! - **Vesting:** A lock under the `vesting ` identifier whose amount is reduced over time, following the
+//! vesting schedules of the account. The lock does not apply to transaction fees and, as locks overlay,
+//! vesting POLYX can still be bonded by the staking module. The unvested part of revocable schedules
+//! is reserved instead, so that it can always be returned to the grantor.
 //!
 //! ### Implementations
 //!
@@ -94,6 +97,10 @@
 //! - `deposit_block_reward_reserve_balance` - Transfer some liquid free balance to block rewards reserve.
 //! - `force_transfer` - Force transfer some balance from one account to another. The origin of this call must be root.
 //! - `burn_account_balance` - Burn some liquid free balance.
+//! - `vested_transfer` - Transfer some liquid free balance to another account under a vesting schedule.
+//! - `vest` - Release the vested part of the caller's vesting schedules.
+//! - `revoke_vesting` - Revoke a revocable vesting schedule, returning its unvested part to the grantor.
+//! - `reject_vesting` - Reject a vesting schedule, returning its unvested part to the grantor.
 
 //! ### Public Functions
 //!
@@ -179,7 +186,9 @@ use frame_system::{self as system, ensure_root, ensure_signed};
 pub use polymesh_common_utilities::traits::balances::WeightInfo;
 use polymesh_common_utilities::{
     traits::{
-        balances::{AccountData, BalancesTrait, CheckCdd, RawEvent, Reasons},
+        balances::{
+            AccountData, BalancesTrait, CheckCdd, RawEvent, Reasons, VestingKind, VestingSchedule,
+        },
         identity::IdentityFnTrait,
         NegativeImbalance, PositiveImbalance,
     },
@@ -199,6 +208,15 @@ pub use polymesh_common_utilities::traits::balances::{Config, LockableCurrencyEx
 pub type Event<T> = polymesh_common_utilities::traits::balances::Event<T>;
 type CallPermissions<T> = pallet_permissions::Module<T>;
 
+/// The vesting schedule type used by the pallet.
+pub type VestingScheduleOf<T> = VestingSchedule<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::BlockNumber,
+>;
+
+/// The identifier of the lock holding unvested POLYX.
+pub const VESTING_ID: LockIdentifier = *b"vesting ";
+
 decl_error! {
     pub enum Error for Module<T: Config> {
         /// Account liquidity restrictions prevent withdrawal
@@ -211,6 +229,20 @@ decl_error! {
         ExistentialDeposit,
         /// Receiver does not have a valid CDD
         ReceiverCddMissing,
+        /// The vesting schedule releases nothing or has an empty release period.
+        InvalidVestingSchedule,
+        /// The account already has the maximum number of vesting schedules.
+        TooManyVestingSchedules,
+        /// No vesting schedule was found.
+        VestingScheduleNotFound,
+        /// The caller is not the grantor of the vesting schedule.
+        NotVestingGrantor,
+        /// The vesting schedule cannot be revoked.
+        VestingNotRevocable,
+        /// The amount of the vesting schedule is below `MinVestedTransfer`.
+        VestingAmountTooSmall,
+        /// The vesting schedule starts or ends too far in the future.
+        VestingPeriodTooLong,
     }
 }
 
@@ -242,6 +274,17 @@ decl_storage! {
         /// NOTE: Should only be accessed when setting, changing and freeing a lock.
         pub Locks get(fn locks): map hasher(blake2_128_concat) T::AccountId => Vec<BalanceLock<Balance>>;
 
+        /// The vesting schedules of an account, not yet fully released.
+        pub VestingSchedules get(fn vesting_schedules):
+            map hasher(blake2_128_concat) T::AccountId => Vec<VestingScheduleOf<T>>;
+
+        /// The id of the next vesting schedule of an account.
+        pub NextVestingScheduleId get(fn next_vesting_schedule_id):
+            map hasher(blake2_128_concat) T::AccountId => u64;
+
+        /// The reserved balance of an account holding the unvested part of its revocable schedules.
+        pub VestingReserved get(fn vesting_reserved):
+            map hasher(blake2_128_concat) T::AccountId => Balance;
     }
     add_extra_genesis {
         /// Account balances at genesis.
@@ -263,6 +306,15 @@ decl_module! {
         /// The minimum amount required to keep an account open.
         const ExistentialDeposit: Balance = 0u32.into();
 
+        /// The maximum number of vesting schedules an account may have.
+        const MaxVestingSchedules: u32 = T::MaxVestingSchedules::get();
+
+        /// The minimum amount of a vesting schedule.
+        const MinVestedTransfer: Balance = T::MinVestedTransfer::get();
+
+        /// The maximum number of blocks until a vesting schedule starts, and of its release period.
+        const MaxVestingDuration: T::BlockNumber = T::MaxVestingDuration::get();
+
         fn deposit_event() = default;
 
         /// Transfer some liquid free balance to another account.
@@ -411,6 +463,119 @@ decl_module! {
             Self::deposit_event(RawEvent::AccountBalanceBurned(caller_id, who, amount));
             Ok(())
         }
+
+        /// Transfers `amount` to `dest` and locks it under a vesting schedule,
+        /// releasing it from block `start` according to `kind`.
+        ///
+        /// If `revocable`, the caller may later revoke the schedule and get its unvested part back,
+        /// and the unvested part is reserved rather than locked.
+        ///
+        /// # Errors
+        /// - `InvalidVestingSchedule` if `amount` is zero or the release period is empty.
+        /// - `VestingAmountTooSmall` if `amount` is below `MinVestedTransfer`.
+        /// - `VestingPeriodTooLong` if `start` is more than `MaxVestingDuration` blocks away,
+        ///   or if the release period is longer than `MaxVestingDuration`.
+        /// - `TooManyVestingSchedules` if `dest` already has `MaxVestingSchedules` schedules.
+        /// - `ReceiverCddMissing` if `dest` does not have a valid CDD.
+        #[weight = <T as Config>::WeightInfo::vested_transfer()]
+        pub fn vested_transfer(
+            origin,
+            dest: <T::Lookup as StaticLookup>::Source,
+            #[compact] amount: Balance,
+            start: T::BlockNumber,
+            kind: VestingKind<T::BlockNumber>,
+            revocable: bool
+        ) {
+            let transactor = ensure_signed(origin)?;
+            let dest = T::Lookup::lookup(dest)?;
+            let id = NextVestingScheduleId::<T>::get(&dest);
+            let schedule = VestingSchedule { id, grantor: transactor.clone(), amount, start, kind, revocable };
+            ensure!(schedule.is_valid(), Error::<T>::InvalidVestingSchedule);
+            ensure!(amount >= T::MinVestedTransfer::get(), Error::<T>::VestingAmountTooSmall);
+            let max_duration = T::MaxVestingDuration::get();
+            let now = system::Pallet::<T>::block_number();
+            ensure!(
+                start <= now.saturating_add(max_duration) && schedule.duration() <= max_duration,
+                Error::<T>::VestingPeriodTooLong
+            );
+            let mut schedules = VestingSchedules::<T>::get(&dest);
+            ensure!(
+                (schedules.len() as u32) < T::MaxVestingSchedules::get(),
+                Error::<T>::TooManyVestingSchedules
+            );
+
+            Self::safe_transfer_core(&transactor, &dest, amount, None, ExistenceRequirement::AllowDeath)?;
+            if revocable {
+                <Self as ReservableCurrency<T::AccountId>>::reserve(&dest, amount)?;
+                VestingReserved::<T>::mutate(&dest, |reserved| *reserved = reserved.saturating_add(amount));
+            }
+            schedules.push(schedule);
+            NextVestingScheduleId::<T>::insert(&dest, id.saturating_add(1));
+            Self::set_vesting(&dest, schedules);
+            Self::deposit_event(RawEvent::VestingScheduleAdded(transactor, dest, id, amount));
+        }
+
+        /// Releases the vested part of the caller's vesting schedules,
+        /// reducing their vesting lock accordingly.
+        ///
+        /// Fully released schedules are removed.
+        ///
+        /// # Errors
+        /// - `VestingScheduleNotFound` if the caller has no vesting schedule.
+        #[weight = <T as Config>::WeightInfo::vest(T::MaxVestingSchedules::get())]
+        pub fn vest(origin) {
+            let who = ensure_signed(origin)?;
+            let schedules = VestingSchedules::<T>::get(&who);
+            ensure!(!schedules.is_empty(), Error::<T>::VestingScheduleNotFound);
+            let locked = Self::set_vesting(&who, schedules);
+            Self::deposit_event(RawEvent::Vested(who, locked));
+        }
+
+        /// Revokes the vesting schedule `id` of `beneficiary`.
+        ///
+        /// The beneficiary keeps what has vested so far, and the unvested part is returned
+        /// to the caller from the reserved balance of the beneficiary.
+        ///
+        /// # Errors
+        /// - `VestingScheduleNotFound` if `beneficiary` has no schedule `id`.
+        /// - `NotVestingGrantor` if the caller is not the grantor of the schedule.
+        /// - `VestingNotRevocable` if the schedule was not created as revocable.
+        #[weight = <T as Config>::WeightInfo::revoke_vesting(T::MaxVestingSchedules::get())]
+        pub fn revoke_vesting(origin, beneficiary: <T::Lookup as StaticLookup>::Source, id: u64) {
+            let grantor = ensure_signed(origin)?;
+            let beneficiary = T::Lookup::lookup(beneficiary)?;
+            let schedules = VestingSchedules::<T>::get(&beneficiary);
+            let schedule = schedules
+                .iter()
+                .find(|s| s.id == id)
+                .ok_or(Error::<T>::VestingScheduleNotFound)?;
+            ensure!(schedule.grantor == grantor, Error::<T>::NotVestingGrantor);
+            ensure!(schedule.revocable, Error::<T>::VestingNotRevocable);
+
+            let returned = Self::remove_vesting_schedule(&beneficiary, schedules, id)?;
+            Self::deposit_event(RawEvent::VestingRevoked(grantor, beneficiary, id, returned));
+        }
+
+        /// Rejects the vesting schedule `id` of the caller, returning its unvested part to the grantor.
+        ///
+        /// The caller keeps what has vested so far. This lets a beneficiary drop unwanted schedules,
+        /// such as dust schedules taking the slots of its account.
+        ///
+        /// # Errors
+        /// - `VestingScheduleNotFound` if the caller has no schedule `id`.
+        #[weight = <T as Config>::WeightInfo::reject_vesting(T::MaxVestingSchedules::get())]
+        pub fn reject_vesting(origin, id: u64) {
+            let beneficiary = ensure_signed(origin)?;
+            let schedules = VestingSchedules::<T>::get(&beneficiary);
+            let grantor = schedules
+                .iter()
+                .find(|s| s.id == id)
+                .map(|s| s.grantor.clone())
+                .ok_or(Error::<T>::VestingScheduleNotFound)?;
+
+            let returned = Self::remove_vesting_schedule(&beneficiary, schedules, id)?;
+            Self::deposit_event(RawEvent::VestingRejected(beneficiary, grantor, id, returned));
+        }
     }
 }
 
@@ -620,8 +785,93 @@ impl<T: Config> Module<T> {
         }
     }
 
+    /// Stores the not fully released `schedules` of `who`, sets its vesting lock to the unvested
+    /// total of its irrevocable schedules and unreserves the vested part of its revocable ones.
+    /// Returns the unvested total of all the schedules.
+    fn set_vesting(who: &T::AccountId, mut schedules: Vec<VestingScheduleOf<T>>) -> Balance {
+        let now = system::Pallet::<T>::block_number();
+        schedules.retain(|s| !s.locked_at(now).is_zero());
+        let (locked, reserved) = schedules.iter().fold(
+            (Zero::zero(), Zero::zero()),
+            |(locked, reserved): (Balance, Balance), s| {
+                if s.revocable {
+                    (locked, reserved.saturating_add(s.locked_at(now)))
+                } else {
+                    (locked.saturating_add(s.locked_at(now)), reserved)
+                }
+            },
+        );
+
+        let previous = VestingReserved::<T>::get(who);
+        if previous > reserved {
+            <Self as ReservableCurrency<T::AccountId>>::unreserve(who, previous - reserved);
+        }
+        if reserved.is_zero() {
+            VestingReserved::<T>::remove(who);
+        } else {
+            VestingReserved::<T>::insert(who, reserved);
+        }
+
+        if locked.is_zero() {
+            <Self as LockableCurrency<T::AccountId>>::remove_lock(VESTING_ID, who);
+        } else {
+            <Self as LockableCurrency<T::AccountId>>::set_lock(
+                VESTING_ID,
+                who,
+                locked,
+                WithdrawReasons::except(WithdrawReasons::TRANSACTION_PAYMENT),
+            );
+        }
+        if schedules.is_empty() {
+            VestingSchedules::<T>::remove(who);
+        } else {
+            VestingSchedules::<T>::insert(who, schedules);
+        }
+        locked.saturating_add(reserved)
+    }
+
+    /// Removes the schedule `id` from the `schedules` of `beneficiary` and returns its unvested part
+    /// to its grantor, returning the amount transferred.
+    fn remove_vesting_schedule(
+        beneficiary: &T::AccountId,
+        mut schedules: Vec<VestingScheduleOf<T>>,
+        id: u64,
+    ) -> Result<Balance, DispatchError> {
+        let index = schedules
+            .iter()
+            .position(|s| s.id == id)
+            .ok_or(Error::<T>::VestingScheduleNotFound)?;
+        let schedule = schedules.remove(index);
+        let unvested = schedule.locked_at(system::Pallet::<T>::block_number());
+
+        if schedule.revocable {
+            // The unvested part is moved out of the reserve, so it must not be unreserved.
+            VestingReserved::<T>::mutate(beneficiary, |reserved| {
+                *reserved = reserved.saturating_sub(unvested)
+            });
+            Self::set_vesting(beneficiary, schedules);
+            let missing = <Self as ReservableCurrency<T::AccountId>>::repatriate_reserved(
+                beneficiary,
+                &schedule.grantor,
+                unvested,
+                Status::Free,
+            )?;
+            Ok(unvested.saturating_sub(missing))
+        } else {
+            Self::set_vesting(beneficiary, schedules);
+            Self::transfer_core(
+                beneficiary,
+                &schedule.grantor,
+                unvested,
+                None,
+                ExistenceRequirement::AllowDeath,
+            )?;
+            Ok(unvested)
+        }
+    }
+
     // Polymesh modified code. New wrapper function for the transfer_core function that checks for CDD.
-    /// Checks CDD and then only performs the transfer
+    /// Checks CDD and the spending limit of the transactor and then only performs the transfer
     fn safe_transfer_core(
         transactor: &T::AccountId,
         dest: &T::AccountId,
@@ -633,6 +883,8 @@ impl<T: Config> Module<T> {
             T::CddChecker::check_key_cdd(dest),
             Error::<T>::ReceiverCddMissing
         );
+        // Secondary keys may have a POLYX spending limit.
+        T::IdentityFn::charge_key_spending(transactor, None, value)?;
 
         Self::transfer_core(transactor, dest, value, memo, existence_requirement)
     }
@@ -0,0 +1,78 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymesh Association
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! Queues of entries processed in the background once their time has come.
+//!
+//! Entries are stored in a double map `Q` keyed by the hour they become due (their bucket),
+//! and a cursor `N` holds the earliest bucket that may still hold entries.
+//! Buckets are processed in order, a bounded number of entries per block.
+
+use frame_support::codec::{FullCodec, FullEncode};
+use frame_support::storage::{IterableStorageDoubleMap, StorageDoubleMap, StorageValue};
+use sp_std::vec::Vec;
+
+/// Width, in milliseconds, of a bucket.
+pub const BUCKET: u64 = 3_600_000;
+
+/// Returns the bucket of an entry due at `at`, in milliseconds.
+pub fn bucket_of(at: u64) -> u64 {
+    at / BUCKET
+}
+
+/// Queues `key` with `value` in `bucket` of `Q`, moving the cursor `N` back to it if needed,
+/// and returns the bucket it was queued in.
+///
+/// `earliest` is the first bucket that `take` has not processed yet. Entries due before it
+/// are queued in it instead, so that the cursor never goes back to processed buckets.
+pub fn queue<Q, N, K, V>(bucket: u64, earliest: u64, key: K, value: V) -> u64
+where
+    Q: StorageDoubleMap<u64, K, V>,
+    N: StorageValue<u64, Query = Option<u64>>,
+    K: FullEncode,
+    V: FullCodec,
+{
+    let bucket = bucket.max(earliest);
+    Q::insert(bucket, key, value);
+    N::mutate(|next| *next = Some(next.map_or(bucket, |next| next.min(bucket))));
+    bucket
+}
+
+/// Takes up to `limit` entries from the bucket of `Q` at the cursor `N`,
+/// provided that bucket is before `ready_until`.
+///
+/// The cursor is moved to the following bucket once the current one is empty.
+/// Returns the processed bucket along with the entries taken from it.
+pub fn take<Q, N, K, V>(ready_until: u64, limit: u32) -> (u64, Vec<(K, V)>)
+where
+    Q: IterableStorageDoubleMap<u64, K, V>,
+    N: StorageValue<u64, Query = Option<u64>>,
+    K: FullCodec,
+    V: FullCodec,
+{
+    let bucket = match N::get() {
+        Some(bucket) if bucket < ready_until => bucket,
+        _ => return (0, Vec::new()),
+    };
+    let entries = Q::iter_prefix(bucket)
+        .take(limit as usize)
+        .collect::<Vec<_>>();
+    for (key, _) in &entries {
+        Q::remove(bucket, key);
+    }
+    if entries.len() < limit as usize {
+        N::put(bucket + 1);
+    }
+    (bucket, entries)
+}
@@ -33,6 +33,8 @@ pub mod did {
     pub const CLASSIC_MIGRATION_DID: &[u8; 32] = b"system:polymath_classic_mig\0\0\0\0\0";
     /// Fiat Currency Reservation DID
     pub const FIAT_TICKERS_RESERVATION_DID: &[u8; 32] = b"system:fiat_tickers_reservation\0";
+    /// NFT custody DID. It holds the NFTs that have been fractionalised.
+    pub const NFT_CUSTODY_DID: &[u8; 32] = b"system:nft_custody_module_did\0\0\0";
 }
 
 /// Priorities for the task that get scheduled.
@@ -80,6 +82,7 @@ pub const CDD_PALLET_ID: PalletId = PalletId(*b"pm/cusdd");
 pub const SETTLEMENT_PALLET_ID: PalletId = PalletId(*b"pm/setmn");
 pub const CLASSIC_MIGRATION_PALLET_ID: PalletId = PalletId(*b"pm/ehmig");
 pub const FIAT_TICKERS_RESERVATION_PALLET_ID: PalletId = PalletId(*b"pm/ftres");
+pub const NFT_CUSTODY_PALLET_ID: PalletId = PalletId(*b"pm/nftcs");
 
 /// Base module constants
 pub const ENSURED_MAX_LEN: u32 = 2048;
@@ -70,7 +70,8 @@ impl<AccountId> Context<AccountId> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use polymesh_primitives::{AccountId, IdentityId};
+    use frame_support::dispatch::DispatchResult;
+    use polymesh_primitives::{AccountId, Balance, IdentityId, Ticker};
 
     use lazy_static::lazy_static;
     use sp_keyring::AccountKeyring;
@@ -118,6 +119,14 @@ mod test {
         fn has_valid_cdd(_target_did: IdentityId) -> bool {
             true
         }
+
+        fn charge_key_spending(
+            _key: &AccountId,
+            _ticker: Option<Ticker>,
+            _amount: Balance,
+        ) -> DispatchResult {
+            Ok(())
+        }
     }
 
     #[test]
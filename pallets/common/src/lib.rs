@@ -15,6 +15,7 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod bucket_queue;
 pub mod constants;
 
 pub mod traits;
@@ -76,6 +77,7 @@ pub enum SystematicIssuers {
     Settlement,
     ClassicMigration,
     FiatTickersReservation,
+    NFTCustody,
 }
 
 impl core::fmt::Display for SystematicIssuers {
@@ -88,6 +90,7 @@ impl core::fmt::Display for SystematicIssuers {
             SystematicIssuers::Settlement => "Settlement module",
             SystematicIssuers::ClassicMigration => "Polymath Classic Imports and Reservations",
             SystematicIssuers::FiatTickersReservation => "Fiat Ticker Reservation",
+            SystematicIssuers::NFTCustody => "NFT Custody",
         };
 
         write!(f, "'{}'", value)
@@ -102,6 +105,7 @@ pub const SYSTEMATIC_ISSUERS: &[SystematicIssuers] = &[
     SystematicIssuers::Settlement,
     SystematicIssuers::ClassicMigration,
     SystematicIssuers::FiatTickersReservation,
+    SystematicIssuers::NFTCustody,
 ];
 
 impl SystematicIssuers {
@@ -116,6 +120,7 @@ impl SystematicIssuers {
             SystematicIssuers::Settlement => did::SETTLEMENT_MODULE_DID,
             SystematicIssuers::ClassicMigration => did::CLASSIC_MIGRATION_DID,
             SystematicIssuers::FiatTickersReservation => did::FIAT_TICKERS_RESERVATION_DID,
+            SystematicIssuers::NFTCustody => did::NFT_CUSTODY_DID,
         }
     }
 
@@ -135,6 +140,7 @@ impl SystematicIssuers {
             SystematicIssuers::FiatTickersReservation => {
                 constants::FIAT_TICKERS_RESERVATION_PALLET_ID
             }
+            SystematicIssuers::NFTCustody => constants::NFT_CUSTODY_PALLET_ID,
         }
     }
 }
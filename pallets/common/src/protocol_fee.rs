@@ -14,7 +14,7 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use codec::{Decode, Encode};
-use frame_support::dispatch::DispatchResult;
+use frame_support::dispatch::{DispatchResult, Weight};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use sp_runtime::{Deserialize, Serialize};
@@ -56,4 +56,8 @@ pub trait ChargeProtocolFee<AccountId> {
     /// Computes the fee for `count` similar operations, and charges that fee to the given
     /// signatory.
     fn batch_charge_fee(op: ProtocolOp, count: usize) -> DispatchResult;
+
+    /// Returns the weight of charging the fee of one operation, or of a batch of similar
+    /// operations, to the current payer.
+    fn charge_fee_weight() -> Weight;
 }
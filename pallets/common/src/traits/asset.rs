@@ -26,7 +26,7 @@ use polymesh_primitives::asset_metadata::{
 };
 use polymesh_primitives::{
     AssetIdentifier, Balance, Document, DocumentId, IdentityId, PortfolioId, PortfolioKind,
-    PortfolioUpdateReason, Ticker,
+    PortfolioUpdateReason, PosRatio, Ticker,
 };
 
 use crate::traits::nft::NFTTrait;
@@ -177,6 +177,21 @@ decl_event! {
         /// An identity has removed an asset to the list of pre aprroved receivement.
         /// Parameters: [`IdentityId`] of caller, [`Ticker`] of the asset.
         RemovePreApprovedAsset(IdentityId, Ticker),
+        /// The conversion rate of a fee asset has been set or removed.
+        /// Parameters: [`Ticker`] of the fee asset, units of the asset per POLYX.
+        FeeAssetRateSet(Ticker, Option<PosRatio>),
+        /// The identity receiving the fees paid in assets has been changed.
+        /// Parameters: [`IdentityId`] of the fee collector.
+        FeeCollectorSet(Option<IdentityId>),
+        /// A key has chosen an asset and a portfolio to pay its fees.
+        /// Parameters: [`IdentityId`] of caller, payer key, [`Ticker`] and [`PortfolioId`] used for fees.
+        FeePaymentAssetSet(IdentityId, AccountId, Option<(Ticker, PortfolioId)>),
+        /// A fee has been paid with an asset instead of POLYX.
+        /// Parameters: payer key, [`Ticker`] of the fee asset, source [`PortfolioId`], amount of the asset, fee in POLYX.
+        FeePaidInAsset(AccountId, Ticker, PortfolioId, Balance, Balance),
+        /// The part of a fee paid with an asset above the actual fee has been refunded.
+        /// Parameters: payer key, [`Ticker`] of the fee asset, refunded [`PortfolioId`], amount of the asset.
+        FeeRefundedInAsset(AccountId, Ticker, PortfolioId, Balance),
     }
 }
 
@@ -211,6 +226,9 @@ pub trait WeightInfo {
     fn remove_ticker_affirmation_exemption() -> Weight;
     fn pre_approve_ticker() -> Weight;
     fn remove_ticker_pre_approval() -> Weight;
+    fn set_fee_asset_rate() -> Weight;
+    fn set_fee_collector() -> Weight;
+    fn set_fee_payment_asset() -> Weight;
 }
 
 pub trait AssetFnTrait<Account, Origin> {
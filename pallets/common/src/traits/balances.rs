@@ -26,7 +26,10 @@ use frame_support::{
 };
 use polymesh_primitives::{Balance, IdentityId, Memo};
 use scale_info::TypeInfo;
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{
+    traits::{AtLeast32BitUnsigned, Saturating, Zero},
+    Perquintill, RuntimeDebug, SaturatedConversion,
+};
 use sp_std::ops::BitOr;
 
 // POLYMESH-NOTE: Make `AccountData` public to access it from the outside module.
@@ -118,6 +121,93 @@ impl BitOr for Reasons {
     }
 }
 
+/// How the amount of a vesting schedule is released over time.
+#[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum VestingKind<BlockNumber> {
+    /// The whole amount is released at once, at the start block.
+    Cliff,
+    /// The amount is released linearly over `duration` blocks from the start block.
+    Linear { duration: BlockNumber },
+    /// The amount is released in `steps` equal parts, one every `period` blocks after the start block.
+    Step { period: BlockNumber, steps: u32 },
+}
+
+/// A vesting schedule locking POLYX of a beneficiary until it is released.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct VestingSchedule<AccountId, BlockNumber> {
+    /// The id of the schedule, unique among the schedules of the beneficiary.
+    pub id: u64,
+    /// The account which funded the schedule and may revoke it.
+    pub grantor: AccountId,
+    /// The total amount vested by the schedule.
+    pub amount: Balance,
+    /// The block from which the amount starts being released.
+    pub start: BlockNumber,
+    /// How the amount is released.
+    pub kind: VestingKind<BlockNumber>,
+    /// Whether the grantor may revoke the unvested part of the schedule.
+    /// The unvested part of a revocable schedule is reserved rather than locked,
+    /// so that it cannot be bonded.
+    pub revocable: bool,
+}
+
+impl<AccountId, BlockNumber> VestingSchedule<AccountId, BlockNumber>
+where
+    BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+    /// Returns `true` if the schedule vests a non-zero amount over a non-empty release period.
+    pub fn is_valid(&self) -> bool {
+        !self.amount.is_zero()
+            && match self.kind {
+                VestingKind::Cliff => true,
+                VestingKind::Linear { duration } => !duration.is_zero(),
+                VestingKind::Step { period, steps } => !period.is_zero() && steps > 0,
+            }
+    }
+
+    /// Returns the number of blocks from the start block until the whole amount is released.
+    pub fn duration(&self) -> BlockNumber {
+        match self.kind {
+            VestingKind::Cliff => Zero::zero(),
+            VestingKind::Linear { duration } => duration,
+            VestingKind::Step { period, steps } => period.saturating_mul(steps.into()),
+        }
+    }
+
+    /// Returns the amount released by the schedule at block `now`.
+    pub fn vested_at(&self, now: BlockNumber) -> Balance {
+        if now < self.start {
+            return Zero::zero();
+        }
+        let elapsed = now - self.start;
+        let released = match self.kind {
+            VestingKind::Cliff => return self.amount,
+            VestingKind::Linear { duration } => {
+                if elapsed >= duration {
+                    return self.amount;
+                }
+                Perquintill::from_rational(
+                    elapsed.saturated_into::<u64>(),
+                    duration.saturated_into::<u64>(),
+                )
+            }
+            VestingKind::Step { period, steps } => {
+                let passed = (elapsed / period).saturated_into::<u32>();
+                if passed >= steps {
+                    return self.amount;
+                }
+                Perquintill::from_rational(passed as u64, steps as u64)
+            }
+        };
+        released * self.amount
+    }
+
+    /// Returns the amount still locked by the schedule at block `now`.
+    pub fn locked_at(&self, now: BlockNumber) -> Balance {
+        self.amount.saturating_sub(self.vested_at(now))
+    }
+}
+
 decl_event!(
     pub enum Event<T> where
     <T as frame_system::Config>::AccountId
@@ -139,6 +229,19 @@ decl_event!(
         /// Final argument indicates the destination balance type.
         /// \[from, to, balance, destination_status]
         ReserveRepatriated(AccountId, AccountId, Balance, Status),
+        /// A vesting schedule was added to an account.
+        /// \[grantor, beneficiary, schedule id, amount]
+        VestingScheduleAdded(AccountId, AccountId, u64, Balance),
+        /// The vesting lock of an account was updated.
+        /// \[beneficiary, amount still locked]
+        Vested(AccountId, Balance),
+        /// A vesting schedule was revoked and its unvested part returned to the grantor.
+        /// \[grantor, beneficiary, schedule id, amount returned]
+        VestingRevoked(AccountId, AccountId, u64, Balance),
+        /// A vesting schedule was rejected by its beneficiary and its unvested part returned
+        /// to the grantor.
+        /// \[beneficiary, grantor, schedule id, amount returned]
+        VestingRejected(AccountId, AccountId, u64, Balance),
     }
 );
 
@@ -149,6 +252,10 @@ pub trait WeightInfo {
     fn set_balance() -> Weight;
     fn force_transfer() -> Weight;
     fn burn_account_balance() -> Weight;
+    fn vested_transfer() -> Weight;
+    fn vest(n: u32) -> Weight;
+    fn revoke_vesting(n: u32) -> Weight;
+    fn reject_vesting(n: u32) -> Weight;
 }
 
 pub trait Config: IdentityConfig {
@@ -174,6 +281,15 @@ pub trait Config: IdentityConfig {
     /// The maximum number of locks that should exist on an account.
     /// Not strictly enforced, but used for weight estimation.
     type MaxLocks: Get<u32>;
+
+    /// The maximum number of vesting schedules an account may have.
+    type MaxVestingSchedules: Get<u32>;
+
+    /// The minimum amount of a vesting schedule.
+    type MinVestedTransfer: Get<Balance>;
+
+    /// The maximum number of blocks until a vesting schedule starts, and of its release period.
+    type MaxVestingDuration: Get<Self::BlockNumber>;
 }
 
 pub trait BalancesTrait<A, NI> {
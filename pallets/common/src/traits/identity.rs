@@ -14,7 +14,7 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use codec::{Decode, Encode};
-use frame_support::dispatch::{GetDispatchInfo, PostDispatchInfo, Weight};
+use frame_support::dispatch::{DispatchResult, GetDispatchInfo, PostDispatchInfo, Weight};
 use frame_support::traits::{Currency, EnsureOrigin, Get, GetCallMetadata};
 use frame_support::{decl_event, Parameter};
 use scale_info::TypeInfo;
@@ -26,14 +26,15 @@ use polymesh_primitives::identity::limits::{
     MAX_ASSETS, MAX_EXTRINSICS, MAX_PALLETS, MAX_PORTFOLIOS,
 };
 use polymesh_primitives::{
-    secondary_key::SecondaryKey, AuthorizationData, Balance, CustomClaimTypeId, IdentityClaim,
-    IdentityId, Permissions, Ticker,
+    secondary_key::SecondaryKey, AuthorizationData, Balance, CddLifecycleConfig, Claim,
+    ClaimCommitment, CustomClaimTypeId, ExtrinsicPermissions, Hash, IdentityClaim, IdentityId,
+    Permissions, SecondaryKeyLimits, Signatory, Ticker,
 };
 
 use crate::traits::group::GroupTrait;
 use crate::traits::multisig::MultiSigSubTrait;
 use crate::traits::portfolio::PortfolioSubTrait;
-use crate::traits::transaction_payment::{CddAndFeeDetails, ChargeTxFee};
+use crate::traits::transaction_payment::{CddAndFeeDetails, ChargeTxFee, FeeAssetPayer};
 use crate::traits::CommonConfig;
 use crate::ChargeProtocolFee;
 
@@ -129,6 +130,27 @@ pub trait WeightInfo {
     fn add_secondary_keys_with_authorization(n: u32) -> Weight;
     fn revoke_claim_by_index() -> Weight;
     fn register_custom_claim_type(n: u32) -> Weight;
+    fn disclose_claim() -> Weight;
+    fn update_credential_signers(n: u32) -> Weight;
+    fn import_claim_credential() -> Weight;
+    fn set_recovery_config(n: u32) -> Weight;
+    fn remove_recovery_config() -> Weight;
+    fn approve_recovery() -> Weight;
+    fn cancel_recovery() -> Weight;
+    fn execute_recovery() -> Weight;
+    fn set_secondary_key_limits(n: u32) -> Weight;
+    fn add_session_key() -> Weight;
+    fn expire_session_keys(n: u32) -> Weight;
+    fn prune_expired_authorizations(n: u32) -> Weight;
+    fn set_cdd_lifecycle_config() -> Weight;
+    fn request_cdd_renewal() -> Weight;
+    fn renew_cdd() -> Weight;
+    fn notify_cdd_expiries(n: u32) -> Weight;
+    fn prune_cdd_expiries(n: u32) -> Weight;
+    fn set_parent_permissions() -> Weight;
+    fn call_as_child() -> Weight;
+    fn import_identity_export(n: u32) -> Weight;
+    fn set_export_attester() -> Weight;
 
     /// Add complexity cost of Permissions to `add_secondary_keys_with_authorization` extrinsic.
     fn add_secondary_keys_full<AccountId>(
@@ -160,6 +182,24 @@ pub trait WeightInfo {
         perm_cost.saturating_add(Self::add_authorization())
     }
 
+    /// Add complexity cost of the call filter to `add_session_key` extrinsic.
+    fn add_session_key_full(perms: &ExtrinsicPermissions) -> Weight {
+        let perms = Permissions {
+            extrinsic: perms.clone(),
+            ..Permissions::empty()
+        };
+        Self::permissions_cost_perms(&perms).saturating_add(Self::add_session_key())
+    }
+
+    /// Add complexity cost of the extrinsic permissions to `set_parent_permissions` extrinsic.
+    fn set_parent_permissions_full(perms: &Option<ExtrinsicPermissions>) -> Weight {
+        let perms = Permissions {
+            extrinsic: perms.clone().unwrap_or_default(),
+            ..Permissions::empty()
+        };
+        Self::permissions_cost_perms(&perms).saturating_add(Self::set_parent_permissions())
+    }
+
     /// Add complexity cost of Permissions to `set_secondary_key_permissions` extrinsic.
     fn set_secondary_key_permissions_full(perms: &Permissions) -> Weight {
         Self::permissions_cost_perms(perms).saturating_add(Self::set_secondary_key_permissions())
@@ -213,6 +253,12 @@ pub trait Config: CommonConfig + pallet_timestamp::Config + crate::traits::base:
     /// Only allow MultiSig primary/secondary keys to be removed from an identity
     /// if its POLYX balance is below this limit.
     type MultiSigBalanceLimit: Get<<Self::Balances as Currency<Self::AccountId>>::Balance>;
+
+    /// Maximum number of blocks a session key can stay linked to an identity.
+    type MaxSessionKeyLifetime: Get<Self::BlockNumber>;
+
+    /// Fee assets chosen by keys, forgotten when a key is unlinked from its identity.
+    type FeeAssetPayer: FeeAssetPayer<Self::AccountId>;
 }
 
 decl_event!(
@@ -333,6 +379,104 @@ decl_event!(
         ///
         /// (Caller DID, Parent DID, Child DID)
         ChildDidUnlinked(IdentityId, IdentityId, IdentityId),
+
+        /// The claim behind a commitment claim was disclosed for the current block.
+        ///
+        /// (DID, claim issuer DID, claim commitment)
+        ClaimDisclosed(IdentityId, IdentityId, ClaimCommitment),
+
+        /// A credential signer was added or removed for a claim issuer.
+        ///
+        /// (claim issuer DID, signer, added)
+        CredentialSignerUpdated(IdentityId, AccountId, bool),
+
+        /// A claim credential was imported.
+        ///
+        /// (claim issuer DID, target DID, credential uid)
+        ClaimCredentialImported(IdentityId, IdentityId, u64),
+
+        /// Social recovery configuration set.
+        ///
+        /// (DID, guardians, threshold)
+        RecoveryConfigSet(IdentityId, Vec<Signatory<AccountId>>, u32),
+
+        /// Social recovery configuration removed.
+        ///
+        /// (DID)
+        RecoveryConfigRemoved(IdentityId),
+
+        /// A guardian approved the recovery of an identity.
+        ///
+        /// (DID, guardian, new primary key)
+        RecoveryApproved(IdentityId, Signatory<AccountId>, AccountId),
+
+        /// An ongoing recovery was cancelled.
+        ///
+        /// (DID)
+        RecoveryCancelled(IdentityId),
+
+        /// A recovery was executed and the new primary key took over the identity.
+        ///
+        /// (DID, new primary key)
+        RecoveryExecuted(IdentityId, AccountId),
+
+        /// The validity window and spending limits of a secondary key were set or removed.
+        ///
+        /// (DID, secondary key, limits)
+        SecondaryKeyLimitsUpdated(IdentityId, AccountId, Option<SecondaryKeyLimits<Moment>>),
+
+        /// A session key was linked to an identity.
+        ///
+        /// (DID, session key, callable extrinsics, maximum number of calls)
+        SessionKeyAdded(IdentityId, AccountId, ExtrinsicPermissions, u32),
+
+        /// A session key expired and was unlinked from its identity.
+        ///
+        /// (DID, session key)
+        SessionKeyExpired(IdentityId, AccountId),
+
+        /// An expired authorization was removed.
+        ///
+        /// (authorized_identity, authorized_key, auth_id)
+        AuthorizationExpired(Option<IdentityId>, Option<AccountId>, u64),
+
+        /// The grace and notice periods of CDD claims were set.
+        CddLifecycleConfigSet(CddLifecycleConfig<Moment>),
+
+        /// An identity requested the renewal of its CDD.
+        ///
+        /// (DID)
+        CddRenewalRequested(IdentityId),
+
+        /// A CDD provider renewed the CDD of an identity.
+        ///
+        /// (CDD provider DID, DID)
+        CddRenewed(IdentityId, IdentityId),
+
+        /// The CDD of an identity expires soon.
+        ///
+        /// (DID, expiry)
+        CddExpiringSoon(IdentityId, Moment),
+
+        /// The extrinsics that the ancestors of a child identity can call on its behalf were updated.
+        ///
+        /// (Child DID, callable extrinsics)
+        ParentPermissionsSet(IdentityId, Option<ExtrinsicPermissions>),
+
+        /// An ancestor identity called an extrinsic on behalf of a child identity.
+        ///
+        /// (Ancestor DID, Child DID)
+        CalledAsChild(IdentityId, IdentityId),
+
+        /// A CDD provider re-issued the claims of an identity exported from another chain.
+        ///
+        /// (CDD provider DID, target DID, exported DID, number of claims re-issued)
+        IdentityExportImported(IdentityId, IdentityId, IdentityId, u32),
+
+        /// An account was trusted, or no longer trusted, to attest identity exports of a chain.
+        ///
+        /// (source chain genesis hash, attester, trusted)
+        ExportAttesterSet(Hash, AccountId, bool),
     }
 );
 
@@ -345,4 +489,12 @@ pub trait IdentityFnTrait<AccountId> {
 
     /// Provides the DID status for the given DID
     fn has_valid_cdd(target_did: IdentityId) -> bool;
+
+    /// Records that `key` spends `amount` of `ticker`, or of POLYX if `ticker` is `None`,
+    /// failing if it exceeds the spending limit of the key.
+    fn charge_key_spending(
+        key: &AccountId,
+        ticker: Option<Ticker>,
+        amount: Balance,
+    ) -> DispatchResult;
 }
@@ -35,7 +35,7 @@ pub mod multisig;
 pub mod nft;
 pub mod portfolio;
 pub mod transaction_payment;
-pub use transaction_payment::{CddAndFeeDetails, ChargeTxFee};
+pub use transaction_payment::{CddAndFeeDetails, ChargeTxFee, FeeAssetPayer};
 pub mod permissions;
 pub use permissions::{AccountCallPermissionsData, CheckAccountCallPermissions};
 pub mod relayer;
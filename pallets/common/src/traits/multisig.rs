@@ -23,12 +23,15 @@ use frame_support::dispatch::DispatchError;
 use frame_support::pallet_prelude::Weight;
 use sp_std::vec::Vec;
 
-use polymesh_primitives::{IdentityId, Signatory};
+use polymesh_primitives::multisig::ThresholdRule;
+use polymesh_primitives::{Balance, IdentityId, Signatory};
 
 decl_event!(
     pub enum Event<T>
     where
         AccountId = <T as frame_system::Config>::AccountId,
+        BlockNumber = <T as frame_system::Config>::BlockNumber,
+        Moment = <T as pallet_timestamp::Config>::Moment,
     {
         /// Event emitted after creation of a multisig.
         /// Arguments: caller DID, multisig address, signers (pending approval), signatures required.
@@ -70,6 +73,30 @@ decl_event!(
         ProposalExecutionFailed(DispatchError),
         /// Scheduling of proposal fails.
         SchedulingFailed(DispatchError),
+        /// Event emitted when the approval weight of a signer is changed.
+        /// Arguments: caller DID, multisig, signer, new weight.
+        MultiSigSignerWeightChanged(IdentityId, AccountId, Signatory<AccountId>, u64),
+        /// Event emitted when the per-call threshold rules of a multisig are replaced.
+        /// Arguments: caller DID, multisig, new rules.
+        MultiSigThresholdRulesChanged(IdentityId, AccountId, Vec<ThresholdRule>),
+        /// Event emitted when a proposal reaches its threshold and is queued for delayed execution.
+        /// Arguments: caller DID, multisig, proposal ID, execution block.
+        ProposalQueued(IdentityId, AccountId, u64, BlockNumber),
+        /// Event emitted when a queued proposal is vetoed.
+        /// Arguments: caller DID, multisig, proposal ID.
+        ProposalVetoed(IdentityId, AccountId, u64),
+        /// Event emitted when the execution delay of a multisig is changed.
+        /// Arguments: caller DID, multisig, new delay.
+        ExecutionDelayChanged(IdentityId, AccountId, Option<BlockNumber>),
+        /// Event emitted when the guardian of a multisig is changed.
+        /// Arguments: caller DID, multisig, new guardian.
+        GuardianChanged(IdentityId, AccountId, Option<IdentityId>),
+        /// Event emitted when the retention period of finished proposals is changed.
+        /// Arguments: new retention period.
+        ProposalRetentionPeriodChanged(Option<Moment>),
+        /// Event emitted when a finished proposal is pruned.
+        /// Arguments: multisig, proposal ID.
+        ProposalPruned(AccountId, u64),
     }
 );
 
@@ -95,6 +122,13 @@ pub trait WeightInfo {
     fn execute_scheduled_proposal() -> Weight;
     fn change_sigs_required_via_creator() -> Weight;
     fn remove_creator_controls() -> Weight;
+    fn set_signer_weight() -> Weight;
+    fn set_threshold_rules(rules: u32) -> Weight;
+    fn set_execution_delay() -> Weight;
+    fn set_guardian() -> Weight;
+    fn veto_proposal() -> Weight;
+    fn set_proposal_retention_period() -> Weight;
+    fn prune_proposals(n: u32) -> Weight;
 }
 
 /// This trait is used to add a signer to a multisig and enable unlinking multisig from an identity
@@ -102,3 +136,21 @@ pub trait MultiSigSubTrait<AccountId> {
     /// Returns `true` if the given `account_id` is a multisignature account, otherwise returns `false`.
     fn is_multisig(account_id: &AccountId) -> bool;
 }
+
+/// Determines how much POLYX a multisig proposal moves and which calls it wraps,
+/// so that threshold rules can match on them.
+pub trait ProposalValue<Proposal> {
+    /// Returns the amount of POLYX moved by `proposal`, or `None` if it does not move POLYX.
+    fn polyx_value(proposal: &Proposal) -> Option<Balance>;
+
+    /// Returns the calls dispatched by `proposal` when it wraps other calls, e.g. a batch.
+    fn inner_calls(_proposal: &Proposal) -> Vec<&Proposal> {
+        Vec::new()
+    }
+}
+
+impl<Proposal> ProposalValue<Proposal> for () {
+    fn polyx_value(_proposal: &Proposal) -> Option<Balance> {
+        None
+    }
+}
@@ -8,10 +8,10 @@ use polymesh_primitives::nft::NFTCollectionKeys;
 use frame_support::decl_event;
 use frame_support::traits::Get;
 use frame_support::weights::Weight;
-use polymesh_primitives::asset_metadata::AssetMetadataKey;
-use polymesh_primitives::nft::{NFTCollectionId, NFTs};
+use polymesh_primitives::asset_metadata::{AssetMetadataKey, AssetMetadataValue};
+use polymesh_primitives::nft::{NFTCollectionId, NFTId, NFTRoyalty, NFTs};
 use polymesh_primitives::ticker::Ticker;
-use polymesh_primitives::{IdentityId, PortfolioId, PortfolioUpdateReason};
+use polymesh_primitives::{Balance, IdentityId, PortfolioId, PortfolioUpdateReason};
 
 use crate::compliance_manager::ComplianceFnConfig;
 use crate::{asset, base, identity, portfolio};
@@ -28,6 +28,8 @@ pub trait Config:
     type MaxNumberOfCollectionKeys: Get<u8>;
 
     type MaxNumberOfNFTsCount: Get<u32>;
+
+    type MaxNFTsPerIssue: Get<u32>;
 }
 
 decl_event!(
@@ -44,6 +46,27 @@ decl_event!(
             Option<PortfolioId>,
             PortfolioUpdateReason,
         ),
+        /// Emitted when the metadata value of an NFT is updated.
+        /// Contains the [`IdentityId`] of the caller, the [`Ticker`] and [`NFTId`] of the NFT, the [`AssetMetadataKey`] and the new [`AssetMetadataValue`].
+        NFTMetadataValueUpdated(
+            IdentityId,
+            Ticker,
+            NFTId,
+            AssetMetadataKey,
+            AssetMetadataValue,
+        ),
+        /// Emitted when an NFT is frozen.
+        NFTFrozen(IdentityId, Ticker, NFTId),
+        /// Emitted when an NFT is unfrozen.
+        NFTUnfrozen(IdentityId, Ticker, NFTId),
+        /// Emitted when the royalty of a collection is set or removed.
+        CollectionRoyaltyUpdated(IdentityId, Ticker, Option<NFTRoyalty>),
+        /// Emitted when an NFT is moved into custody and fractions of it are issued.
+        /// Contains the [`IdentityId`] of the caller, the [`Ticker`] and [`NFTId`] of the NFT, the [`Ticker`] of the fractions and the amount issued.
+        NFTFractionalised(IdentityId, Ticker, NFTId, Ticker, Balance),
+        /// Emitted when all fractions of an NFT are redeemed and the NFT is released from custody.
+        /// Contains the [`IdentityId`] of the caller, the [`Ticker`] and [`NFTId`] of the NFT, the [`Ticker`] of the fractions and the amount redeemed.
+        NFTFractionsRedeemed(IdentityId, Ticker, NFTId, Ticker, Balance),
     }
 );
 
@@ -53,12 +76,22 @@ pub trait WeightInfo {
     fn redeem_nft(n: u32) -> Weight;
     fn base_nft_transfer(n: u32) -> Weight;
     fn controller_transfer(n: u32) -> Weight;
+    fn set_nft_metadata_value() -> Weight;
+    fn freeze_nft() -> Weight;
+    fn unfreeze_nft() -> Weight;
+    fn set_collection_royalty() -> Weight;
+    fn fractionalise_nft() -> Weight;
+    fn redeem_nft_fractions() -> Weight;
+    fn issue_nfts(n: u32, k: u32) -> Weight;
 }
 
 pub trait NFTTrait<Origin> {
     /// Returns `true` if the given `metadata_key` is a mandatory key for the ticker's NFT collection.
     fn is_collection_key(ticker: &Ticker, metadata_key: &AssetMetadataKey) -> bool;
 
+    /// Returns `true` if the given `ticker` represents the fractions of an NFT held in custody.
+    fn is_fractions_asset(ticker: &Ticker) -> bool;
+
     #[cfg(feature = "runtime-benchmarks")]
     fn create_nft_collection(
         origin: Origin,
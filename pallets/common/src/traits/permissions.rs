@@ -46,4 +46,17 @@ pub trait CheckAccountCallPermissions<AccountId> {
         pallet_name: impl FnOnce() -> PalletName,
         function_name: impl FnOnce() -> DispatchableName,
     ) -> Option<AccountCallPermissionsData<AccountId>>;
+
+    /// Records that `who` is about to dispatch an extrinsic.
+    ///
+    /// Returns `false` if `who` is not allowed to dispatch any more extrinsics.
+    fn note_extrinsic_call(_who: &AccountId) -> bool {
+        true
+    }
+
+    /// Returns `false` if `who` is not allowed to dispatch any more extrinsics,
+    /// without recording a call.
+    fn has_extrinsic_calls_left(_who: &AccountId) -> bool {
+        true
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{traits::identity, CommonConfig};
-use frame_support::{decl_event, weights::Weight};
-use polymesh_primitives::{Balance, EventDid};
+use frame_support::{decl_event, traits::CallMetadata, weights::Weight};
+use polymesh_primitives::{subsidy::SubsidyLimits, Balance, EventDid};
 use sp_runtime::transaction_validity::InvalidTransaction;
 
 pub trait WeightInfo {
@@ -10,14 +10,16 @@ pub trait WeightInfo {
     fn update_polyx_limit() -> Weight;
     fn increase_polyx_limit() -> Weight;
     fn decrease_polyx_limit() -> Weight;
+    fn set_subsidy_limits(c: u32) -> Weight;
 }
 
 pub trait SubsidiserTrait<AccountId> {
-    /// Check if a `user_key` has a subsidiser and that the subsidy can pay the `fee`.
+    /// Check if a `user_key` has a subsidiser and that the subsidy can pay the `fee`
+    /// of the call described by `metadata`, or a protocol fee if `metadata` is `None`.
     fn check_subsidy(
         user_key: &AccountId,
         fee: Balance,
-        pallet: Option<&[u8]>,
+        metadata: Option<&CallMetadata>,
     ) -> Result<Option<AccountId>, InvalidTransaction>;
     /// Debit `fee` from the remaining balance of the subsidy for `user_key`.
     fn debit_subsidy(
@@ -37,6 +39,7 @@ decl_event! {
     pub enum Event<T>
     where
         AccountId = <T as frame_system::Config>::AccountId,
+        Moment = <T as pallet_timestamp::Config>::Moment,
     {
         /// Authorization given for `paying_key` to `user_key`.
         ///
@@ -57,5 +60,10 @@ decl_event! {
         ///
         /// (Caller DID, User Key, Paying Key, POLYX limit, old remaining POLYX)
         UpdatedPolyxLimit(EventDid, AccountId, AccountId, Balance, Balance),
+
+        /// Updated subsidy limits.
+        ///
+        /// (Caller DID, User Key, Paying Key, New limits)
+        UpdatedSubsidyLimits(EventDid, AccountId, AccountId, Option<SubsidyLimits<Moment>>),
     }
 }
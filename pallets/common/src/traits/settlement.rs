@@ -119,7 +119,9 @@ pub trait WeightInfo {
         if let Some(weight_limit) = weight_limit {
             return *weight_limit;
         }
-        Self::execute_manual_instruction(*f, *n, *o)
+        // If nfts are transferred, each fungible leg may also pay a royalty.
+        let f = if *n > 0 { f.saturating_mul(2) } else { *f };
+        Self::execute_manual_instruction(f, *n, *o)
     }
     fn get_transfer_by_asset(legs: &[Leg]) -> (u32, u32, u32) {
         let asset_count =
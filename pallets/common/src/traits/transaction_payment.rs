@@ -1,6 +1,6 @@
-use polymesh_primitives::IdentityId;
+use polymesh_primitives::{Balance, IdentityId, PortfolioId, PosRatio, Ticker};
 
-use frame_support::dispatch::DispatchInfo;
+use frame_support::dispatch::{DispatchInfo, Weight};
 use sp_runtime::transaction_validity::{InvalidTransaction, TransactionValidity};
 
 // Polymesh note: This was specifically added for Polymesh
@@ -19,3 +19,53 @@ pub trait CddAndFeeDetails<AccountId, Call> {
 pub trait ChargeTxFee {
     fn charge_fee(len: u32, info: DispatchInfo) -> TransactionValidity;
 }
+
+/// A fee paid with a non-POLYX asset.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeAssetPayment {
+    /// The fee asset.
+    pub ticker: Ticker,
+    /// The portfolio the fee was paid from.
+    pub portfolio: PortfolioId,
+    /// The identity that received the fee, in its default portfolio.
+    pub collector: IdentityId,
+    /// The conversion rate used, as units of the asset per POLYX.
+    pub rate: PosRatio,
+    /// The amount of the asset paid.
+    pub amount: Balance,
+    /// The weight of paying the fee in the asset and refunding its unused part.
+    pub weight: Weight,
+}
+
+/// Pays fees with a non-POLYX asset chosen by the payer.
+pub trait FeeAssetPayer<AccountId> {
+    /// Returns the weight of paying a fee with the fee asset chosen by `payer` and refunding
+    /// its unused part, or `None` if `payer` didn't choose a fee asset.
+    fn fee_asset_payment_weight(payer: &AccountId) -> Option<Weight>;
+
+    /// Pays a `fee`, denominated in POLYX, with the fee asset chosen by `payer`.
+    ///
+    /// Returns `None` if `payer` didn't choose a fee asset or the payment failed,
+    /// in which case the fee should be paid in POLYX.
+    fn pay_fee_in_asset(payer: &AccountId, fee: Balance) -> Option<FeeAssetPayment>;
+
+    /// Refunds to `payer` the part of `payment` above the value of `actual_fee` POLYX.
+    fn refund_fee_in_asset(payer: &AccountId, payment: FeeAssetPayment, actual_fee: Balance);
+
+    /// Removes the fee asset chosen by `payer`, e.g. once the key is unlinked from its identity.
+    fn remove_fee_payment_asset(payer: &AccountId);
+}
+
+impl<AccountId> FeeAssetPayer<AccountId> for () {
+    fn fee_asset_payment_weight(_: &AccountId) -> Option<Weight> {
+        None
+    }
+
+    fn pay_fee_in_asset(_: &AccountId, _: Balance) -> Option<FeeAssetPayment> {
+        None
+    }
+
+    fn refund_fee_in_asset(_: &AccountId, _: FeeAssetPayment, _: Balance) {}
+
+    fn remove_fee_payment_asset(_: &AccountId) {}
+}
@@ -39,6 +39,7 @@
 //! - People from "Wakanda" should only be able to trade with people from "Wakanda".
 //! - People from "Gryffindor" should not be able to trade with people from "Slytherin" (But allowed to trade with anyone else).
 //! - Only "Marvel" supporters should be allowed to buy "Avengers" token.
+//! - Only investors whose KYC was attested by at least two independent providers should be able to trade.
 //!
 //! ### Terminology
 //!
@@ -139,7 +140,9 @@ decl_error! {
         /// The worst case scenario of the compliance requirement is too complex.
         ComplianceRequirementTooComplex,
         /// The maximum weight limit for executing the function was exceeded.
-        WeightLimitExceeded
+        WeightLimitExceeded,
+        /// The threshold of an attestation condition is zero or greater than its number of trusted issuers.
+        InvalidAttestationThreshold
     }
 }
 
@@ -163,7 +166,8 @@ decl_module! {
         ///
         /// # Permissions
         /// * Asset
-        #[weight = <T as Config>::WeightInfo::add_compliance_requirement_full(&sender_conditions, &receiver_conditions)]
+        #[weight = <T as Config>::WeightInfo::add_compliance_requirement_full(&sender_conditions, &receiver_conditions)
+            .saturating_add(T::ProtocolFee::charge_fee_weight())]
         pub fn add_compliance_requirement(
             origin,
             ticker: Ticker,
@@ -437,6 +441,7 @@ impl<T: Config> Module<T> {
 
     /// Fetches all claims of `target` identity with type
     /// and scope from `claim` and generated by any of `issuers`.
+    /// Commitment claims disclosed by `target` are followed by their disclosed claim.
     fn fetch_claims<'a>(
         target: IdentityId,
         claim: &'a Claim,
@@ -452,6 +457,37 @@ impl<T: Config> Module<T> {
                 Identity::<T>::fetch_claim(target, claim_type, issuer.issuer, scope.cloned())
                     .map(|id_claim| id_claim.claim)
             })
+            .flat_map(move |claim| {
+                let disclosed_claim = Identity::<T>::disclosed_claim_of(target, &claim);
+                core::iter::once(claim).chain(disclosed_claim)
+            })
+    }
+
+    /// Fetches, for each of the `issuers` that attested `claim` for `target`, the matching claim.
+    /// At most one claim is yielded per issuer identity, so that each issuer is counted once.
+    fn fetch_attestations<'a>(
+        target: IdentityId,
+        claim: &'a Claim,
+        issuers: &'a [TrustedIssuer],
+    ) -> impl 'a + Iterator<Item = Claim> {
+        let claim_type = claim.claim_type();
+        let trusted = move |issuer: &TrustedIssuer| issuer.is_trusted_for(claim_type);
+
+        issuers
+            .iter()
+            .enumerate()
+            .filter(move |(_, issuer)| trusted(issuer))
+            // Skip issuers already considered through a previous entry.
+            .filter(move |(idx, issuer)| {
+                !issuers[..*idx]
+                    .iter()
+                    .any(|prev| prev.issuer == issuer.issuer && trusted(prev))
+            })
+            .filter_map(move |(_, issuer)| {
+                let attestation = proposition::exists(claim);
+                Self::fetch_claims(target, claim, core::slice::from_ref(issuer))
+                    .find(|fetched| attestation.matches(fetched))
+            })
     }
 
     /// Returns trusted issuers specified in `condition` if any,
@@ -479,9 +515,10 @@ impl<T: Config> Module<T> {
         weight_meter: &mut WeightMeter,
     ) -> Result<proposition::Context<impl 'a + Iterator<Item = Claim>>, DispatchError> {
         // Because of `-> impl Iterator`, we need to return a **single type** in each of the branches below.
-        // To do this, we use `Either<Either<MatchArm1, MatchArm2>, MatchArm3>`,
-        // equivalent to a 3-variant enum with iterators in each variant corresponding to the branches below.
-        // `Left(Left(arm1))`, `Left(Right(arm2))` and `Right(arm3)` correspond to arms 1, 2 and 3 respectively.
+        // To do this, we use `Either<Either<MatchArm1, MatchArm2>, Either<MatchArm3, MatchArm4>>`,
+        // equivalent to a 4-variant enum with iterators in each variant corresponding to the branches below.
+        // `Left(Left(arm1))`, `Left(Right(arm2))`, `Right(Left(arm3))` and `Right(Right(arm4))`
+        // correspond to arms 1, 2, 3 and 4 respectively.
         use either::Either::{Left, Right};
 
         let claims = match &condition.condition_type {
@@ -511,13 +548,25 @@ impl<T: Config> Module<T> {
                     Self::fetch_claims(id, claim, trusted_issuers)
                 })))
             }
+            ConditionType::IsAttested(claim, _) => {
+                let trusted_issuers = Self::issuers_for(ticker, condition, slot);
+                // Consumes the weight for this condition
+                Self::consume_weight_meter(
+                    weight_meter,
+                    <T as Config>::WeightInfo::is_condition_satisfied(
+                        trusted_issuers.len() as u32,
+                        condition.issuers.is_empty() as u32,
+                    ),
+                )?;
+                Right(Left(Self::fetch_attestations(id, claim, trusted_issuers)))
+            }
             ConditionType::IsIdentity(TargetIdentity::ExternalAgent) => {
                 // Consumes the weight for this condition
                 Self::consume_weight_meter(
                     weight_meter,
                     <T as Config>::WeightInfo::is_identity_condition(1),
                 )?;
-                Right(core::iter::empty())
+                Right(Right(core::iter::empty()))
             }
             ConditionType::IsIdentity(TargetIdentity::Specific(_)) => {
                 // Consumes the weight for this condition
@@ -525,7 +574,7 @@ impl<T: Config> Module<T> {
                     weight_meter,
                     <T as Config>::WeightInfo::is_identity_condition(0),
                 )?;
-                Right(core::iter::empty())
+                Right(Right(core::iter::empty()))
             }
         };
 
@@ -557,8 +606,17 @@ impl<T: Config> Module<T> {
         weight_meter: &mut WeightMeter,
     ) -> Result<bool, DispatchError> {
         let context = Self::fetch_context(did, ticker, slot, &condition, weight_meter)?;
+        Ok(Self::run_condition(ticker, condition, context))
+    }
+
+    /// Evaluates `condition` for `ticker` in the given `context`.
+    fn run_condition<C: Iterator<Item = Claim>>(
+        ticker: &Ticker,
+        condition: &Condition,
+        context: Context<C>,
+    ) -> bool {
         let any_ea = |ctx: Context<_>| ExternalAgents::<T>::agents(ticker, ctx.id).is_some();
-        Ok(proposition::run(&condition, context, any_ea))
+        proposition::run(condition, context, any_ea)
     }
 
     /// Returns whether all conditions, in their proper context, hold when evaluated.
@@ -572,14 +630,28 @@ impl<T: Config> Module<T> {
     ) -> Result<bool, DispatchError> {
         let mut all_conditions_hold = true;
         for condition in conditions {
-            let condition_holds = Self::is_condition_satisfied(
-                ticker,
-                did,
-                &condition.condition,
-                &mut None,
-                weight_meter,
-            )?;
+            let slot = &mut None;
+            let context =
+                Self::fetch_context(did, ticker, slot, &condition.condition, weight_meter)?;
+            // Attestations are collected once, both to count them and to evaluate the condition.
+            let (condition_holds, attestations) = match &condition.condition.condition_type {
+                ConditionType::IsAttested(..) => {
+                    let claims = context.claims.collect::<Vec<_>>();
+                    let count = claims.len().try_into().unwrap_or(u32::MAX);
+                    let context = Context {
+                        claims: claims.into_iter(),
+                        id: context.id,
+                    };
+                    let holds = Self::run_condition(ticker, &condition.condition, context);
+                    (holds, Some(count))
+                }
+                _ => (
+                    Self::run_condition(ticker, &condition.condition, context),
+                    None,
+                ),
+            };
             condition.result = condition_holds;
+            condition.attestations = attestations;
             all_conditions_hold = all_conditions_hold & condition_holds;
         }
         Ok(all_conditions_hold)
@@ -653,7 +725,31 @@ impl<T: Config> Module<T> {
         req.dedup();
 
         // Ensure issuers are limited in length.
-        Self::ensure_issuers_in_req_limited(req)
+        Self::ensure_issuers_in_req_limited(req)?;
+
+        // Ensure attestation thresholds can be met.
+        Self::ensure_attestation_thresholds_valid(req)
+    }
+
+    /// Ensures that each attestation condition in `req` has a non-zero threshold
+    /// that does not exceed the number of its trusted issuers.
+    /// Conditions using the default trusted issuers are only checked for a non-zero threshold.
+    fn ensure_attestation_thresholds_valid(req: &ComplianceRequirement) -> DispatchResult {
+        req.conditions()
+            .try_for_each(|cond| match &cond.condition_type {
+                ConditionType::IsAttested(_, threshold) => {
+                    let mut issuers = cond.issuers.iter().map(|i| i.issuer).collect::<Vec<_>>();
+                    issuers.sort();
+                    issuers.dedup();
+                    ensure!(
+                        *threshold > 0
+                            && (issuers.is_empty() || *threshold as usize <= issuers.len()),
+                        Error::<T>::InvalidAttestationThreshold
+                    );
+                    Ok(())
+                }
+                _ => Ok(()),
+            })
     }
 
     fn ensure_issuers_in_req_limited(req: &ComplianceRequirement) -> DispatchResult {
@@ -681,7 +777,7 @@ impl<T: Config> Module<T> {
     }
 
     // Returns `true` if any requirement is satisfied, otherwise returns `false`.
-    fn is_any_requirement_compliant(
+    pub fn is_any_requirement_compliant(
         ticker: &Ticker,
         requirements: &[ComplianceRequirement],
         sender_did: IdentityId,
@@ -707,6 +803,28 @@ impl<T: Config> Module<T> {
 
         Ok(false)
     }
+
+    /// Returns `true` if `did` satisfies the receiver conditions of any of the `requirements`,
+    /// i.e. if `did` would be allowed to hold the asset under those requirements.
+    pub fn is_any_receiver_compliant(
+        ticker: &Ticker,
+        requirements: &[ComplianceRequirement],
+        did: IdentityId,
+        weight_meter: &mut WeightMeter,
+    ) -> Result<bool, DispatchError> {
+        for requirement in requirements {
+            if Self::are_all_conditions_satisfied(
+                ticker,
+                did,
+                &requirement.receiver_conditions,
+                weight_meter,
+            )? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
 }
 
 impl<T: Config> ComplianceFnConfig for Module<T> {
@@ -74,6 +74,7 @@ pub use chain_extension::{ExtrinsicId, PolymeshExtension};
 use pallet_contracts::Config as BConfig;
 use pallet_contracts_primitives::{Code, ContractResult};
 use pallet_identity::ParentDid;
+use polymesh_common_utilities::protocol_fee::ChargeProtocolFee;
 use polymesh_common_utilities::traits::identity::{
     Config as IdentityConfig, WeightInfo as IdentityWeightInfo,
 };
@@ -673,7 +674,8 @@ where
             None => <T as Config>::WeightInfo::instantiate_with_code_as_primary_key(
                 code.len() as u32,
                 salt.len() as u32,
-            ),
+            )
+            .saturating_add(T::ProtocolFee::charge_fee_weight()),
         }
     }
 
@@ -713,6 +715,7 @@ where
                 )),
             None => {
                 <T as Config>::WeightInfo::instantiate_with_hash_as_primary_key(salt.len() as u32)
+                    .saturating_add(T::ProtocolFee::charge_fee_weight())
             }
         }
     }
@@ -760,7 +763,7 @@ where
                 origin_data.secondary_key.is_none(),
                 Error::<T>::CallerNotAPrimaryKey
             );
-            Identity::<T>::ensure_no_parent(origin_data.primary_did)?;
+            Identity::<T>::ensure_child_depth(origin_data.primary_did)?;
             Identity::<T>::unverified_create_child_identity(contract_key, origin_data.primary_did)?;
         }
 
@@ -328,7 +328,8 @@ decl_module! {
         /// - `NumberOfChoicesOverflow` if the total choice in `meta` overflows `usize`.
         /// - `TooLong` if any of the embedded strings in `meta` are too long.
         /// - `InsufficientBalance` if the protocol fee couldn't be charged.
-        #[weight = <T as Config>::BallotWeightInfo::attach_ballot(meta.saturating_num_choices())]
+        #[weight = <T as Config>::BallotWeightInfo::attach_ballot(meta.saturating_num_choices())
+            .saturating_add(T::ProtocolFee::charge_fee_weight())]
         pub fn attach_ballot(origin, ca_id: CAId, range: BallotTimeRange, meta: BallotMeta, rcv: bool) {
             // Ensure origin is a permissioned agent, that `ca_id` exists, that its a notice, and the date invariant.
             let agent = <ExternalAgents<T>>::ensure_perms(origin, ca_id.ticker)?;
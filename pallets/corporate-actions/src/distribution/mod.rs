@@ -209,7 +209,7 @@ decl_module! {
         /// # Permissions
         /// * Asset
         /// * Portfolio
-        #[weight = <T as Config>::DistWeightInfo::distribute()]
+        #[weight = <T as Config>::DistWeightInfo::distribute().saturating_add(T::ProtocolFee::charge_fee_weight())]
         pub fn distribute(
             origin,
             ca_id: CAId,
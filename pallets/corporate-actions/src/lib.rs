@@ -105,8 +105,9 @@ use pallet_asset::checkpoint;
 use pallet_base::try_next_post;
 use pallet_identity::PermissionedCallOriginData;
 use polymesh_common_utilities::{
-    balances::Config as BalancesConfig, identity::Config as IdentityConfig, traits::asset,
-    traits::checkpoint::ScheduleId, with_transaction, GC_DID,
+    balances::Config as BalancesConfig, identity::Config as IdentityConfig,
+    protocol_fee::ChargeProtocolFee, traits::asset, traits::checkpoint::ScheduleId,
+    with_transaction, GC_DID,
 };
 use polymesh_primitives::{
     asset::CheckpointId, impl_checked_inc, storage_migration_ver, Balance, DocumentId, EventDid,
@@ -665,7 +666,8 @@ decl_module! {
         /// # Permissions
         /// * Asset
         #[weight = <T as Config>::WeightInfo::change_record_date_with_ballot()
-            .max(<T as Config>::WeightInfo::change_record_date_with_dist())]
+            .max(<T as Config>::WeightInfo::change_record_date_with_dist())
+            .saturating_add(T::ProtocolFee::charge_fee_weight())]
         pub fn change_record_date(origin, ca_id: CAId, record_date: Option<RecordDateSpec>) {
             // Ensure origin is a permissioned agent + CA exists.
             let caller_did = <ExternalAgents<T>>::ensure_perms(origin, ca_id.ticker)?;
@@ -705,7 +707,8 @@ decl_module! {
 
         /// Utility extrinsic to batch `initiate_corporate_action` and `distribute`
          #[weight = initiate_corporate_action_weight::<T>(&ca_args.targets, &ca_args.withholding_tax)
-            .saturating_add(<T as Config>::DistWeightInfo::distribute())]
+            .saturating_add(<T as Config>::DistWeightInfo::distribute())
+            .saturating_add(T::ProtocolFee::charge_fee_weight())]
         pub fn initiate_corporate_action_and_distribute(
             origin,
             ca_args: InitiateCorporateActionArgs,
@@ -1042,4 +1045,5 @@ fn initiate_corporate_action_weight<T: Config>(
             targets.as_ref().map_or(0, |t| t.identities.len() as u32),
         ),
     )
+    .saturating_add(T::ProtocolFee::charge_fee_weight())
 }
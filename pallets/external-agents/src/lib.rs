@@ -318,6 +318,7 @@ impl<T: Config> Module<T> {
         target: IdentityId,
         expiry: Option<T::Moment>,
     ) -> DispatchResult {
+        <Identity<T>>::ensure_auth_expiry(expiry)?;
         let (did, ag_id) = Self::base_create_group(origin, ticker, perms)?;
         <Identity<T>>::add_auth(
             did,
@@ -13,17 +13,20 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+use crate::types::RpcAuthorization;
 use crate::{
-    AuthorizationType, Authorizations, AuthorizationsGiven, Config, Error, KeyRecords, Module,
-    MultiPurposeNonce, RawEvent,
+    AuthorizationExpiries, AuthorizationType, Authorizations, AuthorizationsGiven, Config, Error,
+    KeyRecords, Module, MultiPurposeNonce, NextAuthorizationExpiryBucket, RawEvent,
 };
 use frame_support::dispatch::DispatchResult;
-use frame_support::{ensure, StorageDoubleMap, StorageMap, StorageValue};
+use frame_support::{ensure, IterableStorageDoubleMap, StorageDoubleMap, StorageMap, StorageValue};
 use frame_system::ensure_signed;
-use polymesh_common_utilities::Context;
+use polymesh_common_utilities::{bucket_queue, Context};
+use polymesh_primitives::identity::limits::MAX_EXPIRED_AUTHS_PER_BLOCK;
 use polymesh_primitives::{
     Authorization, AuthorizationData, AuthorizationError, IdentityId, Signatory,
 };
+use sp_runtime::traits::SaturatedConversion;
 use sp_runtime::DispatchError;
 use sp_std::vec::Vec;
 
@@ -36,6 +39,7 @@ impl<T: Config> Module<T> {
         expiry: Option<T::Moment>,
     ) -> Result<u64, DispatchError> {
         let from_did = Self::ensure_perms(origin)?;
+        Self::ensure_auth_expiry(expiry)?;
         if let AuthorizationData::JoinIdentity(perms)
         | AuthorizationData::RotatePrimaryKeyToSecondary(perms) = &authorization_data
         {
@@ -44,6 +48,16 @@ impl<T: Config> Module<T> {
         Ok(Self::add_auth(from_did, target, authorization_data, expiry))
     }
 
+    /// Ensures that an authorization expiring at `expiry` is not already expired.
+    pub fn ensure_auth_expiry(expiry: Option<T::Moment>) -> DispatchResult {
+        let now = <pallet_timestamp::Pallet<T>>::get();
+        ensure!(
+            expiry.map_or(true, |expiry| expiry > now),
+            Error::<T>::AuthorizationExpired
+        );
+        Ok(())
+    }
+
     /// Adds an authorization.
     pub fn add_auth(
         from: IdentityId,
@@ -64,6 +78,9 @@ impl<T: Config> Module<T> {
 
         <Authorizations<T>>::insert(target.clone(), new_nonce, auth);
         <AuthorizationsGiven<T>>::insert(from, new_nonce, target.clone());
+        if let Some(expiry) = expiry {
+            Self::queue_auth_expiry(target.clone(), new_nonce, expiry);
+        }
 
         // This event is split in order to help the event harvesters.
         Self::deposit_event(RawEvent::AuthorizationAdded(
@@ -143,6 +160,130 @@ impl<T: Config> Module<T> {
         }
     }
 
+    /// Returns up to `limit` authorizations received by `target`, following the authorization
+    /// `after` in storage order. The id of the last authorization returned can be used as `after`
+    /// to get the next page.
+    ///
+    /// Authorizations can be filtered by `auth_type`, and expired ones are skipped
+    /// unless `allow_expired` is set.
+    pub fn get_authorizations_inbox(
+        target: Signatory<T::AccountId>,
+        auth_type: Option<AuthorizationType>,
+        allow_expired: bool,
+        after: Option<u64>,
+        limit: u32,
+    ) -> Vec<RpcAuthorization<T::AccountId, T::Moment>> {
+        let auths = match after {
+            Some(after) => <Authorizations<T>>::iter_prefix_from(
+                &target,
+                <Authorizations<T>>::hashed_key_for(&target, after),
+            ),
+            None => <Authorizations<T>>::iter_prefix(&target),
+        }
+        .map(|(_, authorization)| RpcAuthorization {
+            target: target.clone(),
+            authorization,
+        });
+        Self::auths_page(auths, auth_type, allow_expired, limit)
+    }
+
+    /// Returns up to `limit` authorizations issued by `issuer`, following the authorization
+    /// `after` in storage order. The id of the last authorization returned can be used as `after`
+    /// to get the next page.
+    ///
+    /// Authorizations can be filtered by `auth_type`, and expired ones are skipped
+    /// unless `allow_expired` is set.
+    pub fn get_authorizations_outbox(
+        issuer: IdentityId,
+        auth_type: Option<AuthorizationType>,
+        allow_expired: bool,
+        after: Option<u64>,
+        limit: u32,
+    ) -> Vec<RpcAuthorization<T::AccountId, T::Moment>> {
+        let auths = match after {
+            Some(after) => <AuthorizationsGiven<T>>::iter_prefix_from(
+                issuer,
+                <AuthorizationsGiven<T>>::hashed_key_for(issuer, after),
+            ),
+            None => <AuthorizationsGiven<T>>::iter_prefix(issuer),
+        }
+        .filter_map(|(auth_id, target)| {
+            Self::authorizations(&target, auth_id).map(|authorization| RpcAuthorization {
+                target,
+                authorization,
+            })
+        });
+        Self::auths_page(auths, auth_type, allow_expired, limit)
+    }
+
+    /// Returns the first `limit` authorizations of `auths` that match `auth_type` and `allow_expired`.
+    fn auths_page(
+        auths: impl Iterator<Item = RpcAuthorization<T::AccountId, T::Moment>>,
+        auth_type: Option<AuthorizationType>,
+        allow_expired: bool,
+        limit: u32,
+    ) -> Vec<RpcAuthorization<T::AccountId, T::Moment>> {
+        let now = <pallet_timestamp::Pallet<T>>::get();
+        auths
+            .filter(|rpc_auth| {
+                let auth = &rpc_auth.authorization;
+                (allow_expired || auth.expiry.filter(|&e| e < now).is_none())
+                    && auth_type
+                        .as_ref()
+                        .map_or(true, |t| auth.authorization_data.auth_type() == *t)
+            })
+            .take(limit as usize)
+            .collect()
+    }
+
+    /// Returns the bucket of `AuthorizationExpiries` for an authorization expiring at `expiry`.
+    fn auth_expiry_bucket(expiry: T::Moment) -> u64 {
+        bucket_queue::bucket_of(expiry.saturated_into())
+    }
+
+    /// Queues the authorization `auth_id` of `target` so that it's removed once it expires.
+    ///
+    /// Authorizations expiring before the current bucket are queued in the current one.
+    pub(crate) fn queue_auth_expiry(
+        target: Signatory<T::AccountId>,
+        auth_id: u64,
+        expiry: T::Moment,
+    ) {
+        let now = Self::auth_expiry_bucket(<pallet_timestamp::Pallet<T>>::get());
+        bucket_queue::queue::<AuthorizationExpiries<T>, NextAuthorizationExpiryBucket, _, _>(
+            Self::auth_expiry_bucket(expiry),
+            now,
+            auth_id,
+            target,
+        );
+    }
+
+    /// Removes up to `MAX_EXPIRED_AUTHS_PER_BLOCK` expired authorizations.
+    ///
+    /// Buckets are processed in order, once all of their authorizations have expired.
+    /// Returns the number of queued authorizations that were processed.
+    pub(crate) fn prune_expired_auths() -> u32 {
+        let now = Self::auth_expiry_bucket(<pallet_timestamp::Pallet<T>>::get());
+        let (_, queued) =
+            bucket_queue::take::<AuthorizationExpiries<T>, NextAuthorizationExpiryBucket, _, _>(
+                now,
+                MAX_EXPIRED_AUTHS_PER_BLOCK,
+            );
+        for (auth_id, target) in &queued {
+            // Skip authorizations that were already accepted or removed.
+            if let Some(auth) = Self::authorizations(target, auth_id) {
+                <Authorizations<T>>::remove(target, auth_id);
+                <AuthorizationsGiven<T>>::remove(auth.authorized_by, auth_id);
+                Self::deposit_event(RawEvent::AuthorizationExpired(
+                    target.as_identity().cloned(),
+                    target.as_account().cloned(),
+                    *auth_id,
+                ));
+            }
+        }
+        queued.len() as u32
+    }
+
     /// Returns an auth id if it is present and not expired.
     pub fn get_non_expired_auth(
         target: &Signatory<T::AccountId>,
@@ -17,21 +17,26 @@ use crate::*;
 
 use frame_benchmarking::{account, benchmarks};
 use frame_system::RawOrigin;
+use sp_core::sr25519::Signature;
 use sp_core::H512;
+use sp_runtime::MultiSignature;
 use sp_std::prelude::*;
 
 use polymesh_common_utilities::benchs::{
-    cdd_provider, user, user_without_did, AccountIdOf, UserBuilder,
+    cdd_provider, user, user_without_did, AccountIdOf, User, UserBuilder,
 };
 use polymesh_common_utilities::traits::{identity::TargetIdAuthorization, TestUtilsFn};
 use polymesh_primitives::identity::limits::{
-    MAX_ASSETS, MAX_EXTRINSICS, MAX_PALLETS, MAX_PORTFOLIOS, MAX_SECONDARY_KEYS,
+    MAX_ASSETS, MAX_CDD_EXPIRIES_PER_BLOCK, MAX_CHILD_IDENTITY_DEPTH, MAX_EXPIRED_AUTHS_PER_BLOCK,
+    MAX_EXPORTED_CLAIMS, MAX_EXTRINSICS, MAX_PALLETS, MAX_PORTFOLIOS, MAX_RECOVERY_GUARDIANS,
+    MAX_SECONDARY_KEYS, MAX_SESSION_KEYS_PER_BLOCK,
 };
 use polymesh_primitives::secondary_key::DispatchableNames;
 use polymesh_primitives::{
-    AssetPermissions, AuthorizationData, Claim, CountryCode, DispatchableName,
-    ExtrinsicPermissions, PalletName, PalletPermissions, Permissions, PortfolioId, PortfolioNumber,
-    PortfolioPermissions, Scope, SecondaryKey, Signatory,
+    AssetPermissions, AuthorizationData, Claim, ClaimCommitment, ClaimCredential, ClaimType,
+    CountryCode, DispatchableName, ExtrinsicPermissions, Hash, IdentityClaim, IdentityExport,
+    PalletName, PalletPermissions, Permissions, PortfolioId, PortfolioNumber, PortfolioPermissions,
+    Scope, SecondaryKey, Signatory, IDENTITY_EXPORT_VERSION,
 };
 
 const SEED: u32 = 0;
@@ -47,6 +52,64 @@ pub fn generate_secondary_keys<T: Config>(n: usize) -> Vec<SecondaryKey<T::Accou
     secondary_keys
 }
 
+fn setup_recovery<T: Config>(target: &User<T>, guardian: &User<T>, new_key: T::AccountId) {
+    Module::<T>::set_recovery_config(
+        target.origin.clone().into(),
+        vec![Signatory::Identity(guardian.did())],
+        1,
+        0u32.into(),
+    )
+    .unwrap();
+    Module::<T>::approve_recovery(guardian.origin.clone().into(), target.did(), new_key).unwrap();
+}
+
+/// Returns the signature of `key` consenting to be a session key of `target` until `expires_at`.
+fn session_key_auth<T: Config>(target: &User<T>, key: &User<T>, expires_at: u32) -> H512 {
+    let authorization = TargetIdAuthorization::<T::BlockNumber> {
+        target_id: target.did(),
+        nonce: Module::<T>::offchain_authorization_nonce(target.did()),
+        expires_at: expires_at.into(),
+    };
+    H512::from(key.sign(&authorization.encode()).unwrap())
+}
+
+/// Adds a CDD claim expiring at `expiry` to `n` identities and returns them.
+fn setup_cdd_expiries<T: Config>(n: u32, expiry: u32) -> Vec<IdentityId> {
+    let cdd = cdd_provider::<T>("cdd", 0).did();
+    (0..n)
+        .map(|i| {
+            let did = IdentityId::from(i as u128 + 1);
+            Module::<T>::unverified_add_claim_with_scope(
+                did,
+                Claim::CustomerDueDiligence(CddId::default()),
+                None,
+                cdd,
+                Some(expiry.into()),
+            );
+            // Queue the notice, which is otherwise emitted right away for such a close expiry.
+            CddExpiryNotices::insert(0, did, true);
+            NextCddNoticeBucket::put(0);
+            did
+        })
+        .collect()
+}
+
+/// Creates `depth` nested child identities below `root`, returning the DID and primary key of each one.
+fn setup_child_identities<T: Config>(
+    root: &User<T>,
+    depth: u32,
+) -> Vec<(IdentityId, T::AccountId)> {
+    let mut parent_did = root.did();
+    (0..depth)
+        .map(|i| {
+            let child_key: T::AccountId = account("child", i, SEED);
+            Module::<T>::unverified_create_child_identity(child_key.clone(), parent_did).unwrap();
+            parent_did = Module::<T>::get_identity(&child_key).unwrap();
+            (parent_did, child_key)
+        })
+        .collect()
+}
+
 benchmarks! {
     where_clause { where T: TestUtilsFn<AccountIdOf<T>> }
 
@@ -397,4 +460,313 @@ benchmarks! {
         assert_ne!(id, Module::<T>::custom_claim_id_seq());
     }
 
+    disclose_claim {
+        let caller = user::<T>("caller", 0);
+        let issuer = user::<T>("issuer", 0);
+        let scope = Scope::Identity(caller.did());
+        let claim = Claim::Jurisdiction(CountryCode::BB, scope.clone());
+        let salt = [7u8; 32];
+        let commitment = Claim::Commitment(ClaimType::Jurisdiction, scope, ClaimCommitment::new(&claim, &salt));
+        Module::<T>::add_claim(issuer.origin.clone().into(), caller.did(), commitment, None).unwrap();
+    }: _(caller.origin, issuer.did(), claim.clone(), salt)
+    verify {
+        let commitment = ClaimCommitment::new(&claim, &salt);
+        assert_eq!(DisclosedClaims::get(caller.did(), commitment), Some(claim));
+        assert_eq!(BlockDisclosures::get(), vec![(caller.did(), commitment)]);
+    }
+
+    update_credential_signers {
+        let n in 1 .. 100;
+
+        let issuer = user::<T>("issuer", 0);
+        let signers: Vec<T::AccountId> = (0..n).map(|i| account("signer", i, SEED)).collect();
+    }: _(issuer.origin, signers.clone(), true)
+    verify {
+        for signer in signers {
+            assert!(CredentialSigners::<T>::get(issuer.did(), signer));
+        }
+    }
+
+    import_claim_credential {
+        let caller = user::<T>("caller", 0);
+        let issuer = user::<T>("issuer", 0);
+        Module::<T>::update_credential_signers(
+            issuer.origin.clone().into(),
+            vec![issuer.account()],
+            true,
+        ).unwrap();
+        let credential = ClaimCredential {
+            uid: 0,
+            issuer: issuer.did(),
+            target: caller.did(),
+            claim: Claim::Jurisdiction(CountryCode::BB, Scope::Identity(caller.did())),
+            expiry: Some(666u32.into()),
+            valid_until: 666u32.into(),
+        };
+        let payload = (Module::<T>::genesis_hash(), &credential).encode();
+        let raw_signature: [u8; 64] = issuer.sign(&payload).unwrap().0;
+        let encoded_signature = MultiSignature::from(Signature::from_raw(raw_signature)).encode();
+        let signature = T::OffChainSignature::decode(&mut &encoded_signature[..]).unwrap();
+    }: _(caller.origin, credential, issuer.account(), signature)
+    verify {
+        assert!(CredentialsUsed::get(issuer.did(), 0));
+    }
+
+    set_secondary_key_limits {
+        let n in 0 .. MAX_ASSETS as u32;
+
+        let target = user::<T>("target", 0);
+        let key = UserBuilder::<T>::default().build("key");
+        Module::<T>::unsafe_join_identity(target.did(), Permissions::default(), key.account());
+        let limits = SecondaryKeyLimits {
+            valid_from: Some(1u32.into()),
+            valid_until: Some(100u32.into()),
+            spend_period: 10u32.into(),
+            polyx_limit: Some(1_000),
+            asset_limits: (0..n as u64).map(|i| (Ticker::generate_into(i), 1_000)).collect(),
+        };
+    }: _(target.origin, key.account(), Some(limits.clone()))
+    verify {
+        assert_eq!(SecondaryKeysLimits::<T>::get(key.account()), Some(limits));
+    }
+
+    set_recovery_config {
+        let n in 1 .. MAX_RECOVERY_GUARDIANS;
+
+        let target = user::<T>("target", 0);
+        let guardians: Vec<_> = (0..n).map(|i| Signatory::Account(account("guardian", i, SEED))).collect();
+    }: _(target.origin, guardians, n, 10u32.into())
+    verify {
+        assert!(RecoveryConfigs::<T>::contains_key(target.did()));
+    }
+
+    remove_recovery_config {
+        let target = user::<T>("target", 0);
+        let guardian = user::<T>("guardian", 0);
+        setup_recovery::<T>(&target, &guardian, account("new", 0, SEED));
+    }: _(target.origin)
+    verify {
+        assert!(!RecoveryConfigs::<T>::contains_key(target.did()));
+        assert!(!ActiveRecoveries::<T>::contains_key(target.did()));
+    }
+
+    approve_recovery {
+        let target = user::<T>("target", 0);
+        let guardian = user::<T>("guardian", 0);
+        Module::<T>::set_recovery_config(
+            target.origin.clone().into(),
+            vec![Signatory::Identity(guardian.did())],
+            1,
+            0u32.into(),
+        ).unwrap();
+        let new_key: T::AccountId = account("new", 0, SEED);
+    }: _(guardian.origin, target.did(), new_key)
+    verify {
+        assert!(ActiveRecoveries::<T>::get(target.did()).unwrap().approved.is_some());
+    }
+
+    cancel_recovery {
+        let target = user::<T>("target", 0);
+        let guardian = user::<T>("guardian", 0);
+        setup_recovery::<T>(&target, &guardian, account("new", 0, SEED));
+    }: _(target.origin)
+    verify {
+        assert!(!ActiveRecoveries::<T>::contains_key(target.did()));
+    }
+
+    execute_recovery {
+        let target = user::<T>("target", 0);
+        let guardian = user::<T>("guardian", 0);
+        let new_key: T::AccountId = account("new", 0, SEED);
+        setup_recovery::<T>(&target, &guardian, new_key.clone());
+    }: _(RawOrigin::Signed(new_key.clone()), target.did())
+    verify {
+        assert_eq!(Module::<T>::get_primary_key(target.did()), Some(new_key));
+    }
+
+    add_session_key {
+        let target = user::<T>("target", 0);
+        let key = user_without_did::<T>("session", 0);
+        let permissions = ExtrinsicPermissions::elems(vec![
+            PalletPermissions::entire_pallet(b"Identity".into())
+        ]);
+        let auth_signature = session_key_auth::<T>(&target, &key, 10);
+    }: _(target.origin, key.account(), permissions, 10u32.into(), 10, auth_signature)
+    verify {
+        assert!(SessionKeys::<T>::contains_key(&key.account()));
+    }
+
+    expire_session_keys {
+        let n in 0 .. MAX_SESSION_KEYS_PER_BLOCK;
+
+        let target = user::<T>("target", 0);
+        let keys: Vec<T::AccountId> = (0..n).map(|i| {
+            let key = user_without_did::<T>("session", i);
+            Module::<T>::add_session_key(
+                target.origin.clone().into(),
+                key.account(),
+                ExtrinsicPermissions::default(),
+                10u32.into(),
+                10,
+                session_key_auth::<T>(&target, &key, 10),
+            ).unwrap();
+            key.account()
+        }).collect();
+    }: {
+        Module::<T>::expire_session_keys(10u32.into());
+    }
+    verify {
+        for key in &keys {
+            assert!(!SessionKeys::<T>::contains_key(key));
+            assert_eq!(Module::<T>::get_identity(key), None);
+        }
+    }
+
+    prune_expired_authorizations {
+        let n in 0 .. MAX_EXPIRED_AUTHS_PER_BLOCK;
+
+        let issuer = user::<T>("issuer", 0);
+        let target = user::<T>("target", 0);
+        let auth_ids: Vec<u64> = (0..n).map(|_| {
+            Module::<T>::add_auth(
+                issuer.did(),
+                Signatory::Identity(target.did()),
+                AuthorizationData::RotatePrimaryKey,
+                Some(1u32.into()),
+            )
+        }).collect();
+        <pallet_timestamp::Now<T>>::set(7_200_000u32.into());
+    }: {
+        Module::<T>::prune_expired_auths();
+    }
+    verify {
+        for auth_id in auth_ids {
+            assert!(!Authorizations::<T>::contains_key(Signatory::Identity(target.did()), auth_id));
+        }
+    }
+
+    set_cdd_lifecycle_config {
+        let config = CddLifecycleConfig {
+            grace_period: 1_000u32.into(),
+            notice_period: 1_000u32.into(),
+        };
+    }: _(RawOrigin::Root, config.clone())
+    verify {
+        assert_eq!(Module::<T>::cdd_lifecycle(), config);
+    }
+
+    request_cdd_renewal {
+        let target = user::<T>("target", 0);
+    }: _(target.origin)
+    verify {
+        assert!(CddRenewalRequests::<T>::contains_key(target.did()));
+    }
+
+    renew_cdd {
+        let cdd = cdd_provider::<T>("cdd", 0);
+        let target = user::<T>("target", 0);
+        Module::<T>::request_cdd_renewal(target.origin.clone().into()).unwrap();
+    }: _(cdd.origin, target.did(), Some(7_200_000u32.into()))
+    verify {
+        assert!(!CddRenewalRequests::<T>::contains_key(target.did()));
+    }
+
+    notify_cdd_expiries {
+        let n in 0 .. MAX_CDD_EXPIRIES_PER_BLOCK;
+
+        let dids = setup_cdd_expiries::<T>(n, 1);
+    }: {
+        Module::<T>::notify_cdd_expiries();
+    }
+    verify {
+        for did in dids {
+            assert!(!CddExpiryNotices::contains_key(0, did));
+        }
+    }
+
+    prune_cdd_expiries {
+        let n in 0 .. MAX_CDD_EXPIRIES_PER_BLOCK;
+
+        let dids = setup_cdd_expiries::<T>(n, 1);
+        <pallet_timestamp::Now<T>>::set(7_200_000u32.into());
+    }: {
+        Module::<T>::prune_cdd_expiries();
+    }
+    verify {
+        for did in dids {
+            assert!(!CddExpiries::contains_key(0, did));
+        }
+    }
+
+    set_parent_permissions {
+        let parent = user::<T>("parent", 0);
+        let (child_did, child_key) = setup_child_identities::<T>(&parent, 1).pop().unwrap();
+        let permissions = Some(ExtrinsicPermissions::default());
+    }: _(RawOrigin::Signed(child_key), permissions.clone())
+    verify {
+        assert_eq!(ParentPermissions::get(child_did), permissions);
+    }
+
+    call_as_child {
+        // The caller is the root of the deepest possible hierarchy.
+        let root = user::<T>("root", 0);
+        let (child_did, child_key) =
+            setup_child_identities::<T>(&root, MAX_CHILD_IDENTITY_DEPTH).pop().unwrap();
+        Module::<T>::set_parent_permissions(
+            RawOrigin::Signed(child_key).into(),
+            Some(ExtrinsicPermissions::default()),
+        ).unwrap();
+        let call: T::Proposal = frame_system::Call::<T>::remark { remark: vec![] }.into();
+    }: _(root.origin, child_did, Box::new(call))
+    verify {
+        assert_eq!(Module::<T>::ancestors(child_did).len() as u32, MAX_CHILD_IDENTITY_DEPTH);
+    }
+
+    import_identity_export {
+        let n in 0 .. MAX_EXPORTED_CLAIMS;
+
+        let cdd = cdd_provider::<T>("cdd", 0);
+        let target = user::<T>("target", 0);
+        let issuer = user::<T>("issuer", 0);
+        let claims = (0..n).map(|i| IdentityClaim {
+            claim_issuer: issuer.did(),
+            issuance_date: 0,
+            last_update_date: 0,
+            expiry: None,
+            claim: Claim::Jurisdiction(CountryCode::BB, Scope::Custom(i.encode())),
+        }).collect();
+        let attester = user::<T>("attester", 0);
+        let source_chain = Hash::repeat_byte(1);
+        ExportAttesters::<T>::insert(source_chain, attester.account(), true);
+        let export = IdentityExport {
+            version: IDENTITY_EXPORT_VERSION,
+            source_chain,
+            exported_at: 0u32.into(),
+            did: target.did(),
+            primary_key: target.account(),
+            secondary_keys: Vec::new(),
+            claims,
+            authorizations: Vec::new(),
+        };
+        let raw_signature: [u8; 64] = target.sign(&export.encode()).unwrap().0;
+        let encoded_signature = MultiSignature::from(Signature::from_raw(raw_signature)).encode();
+        let signature = T::OffChainSignature::decode(&mut &encoded_signature[..]).unwrap();
+        let raw_attestation: [u8; 64] = attester.sign(&export.encode()).unwrap().0;
+        let encoded_attestation = MultiSignature::from(Signature::from_raw(raw_attestation)).encode();
+        let attestation = T::OffChainSignature::decode(&mut &encoded_attestation[..]).unwrap();
+    }: _(cdd.origin, target.did(), export, signature, attester.account(), attestation)
+    verify {
+        if n > 0 {
+            let scope = Some(Scope::Custom(0u32.encode()));
+            assert!(Module::<T>::fetch_claim(target.did(), ClaimType::Jurisdiction, cdd.did(), scope).is_some());
+        }
+    }
+
+    set_export_attester {
+        let attester = user::<T>("attester", 0);
+        let source_chain = Hash::repeat_byte(1);
+    }: _(RawOrigin::Root, source_chain, attester.account(), true)
+    verify {
+        assert!(Module::<T>::export_attester(source_chain, attester.account()));
+    }
 }
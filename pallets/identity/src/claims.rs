@@ -14,17 +14,22 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-    Claim1stKey, Claim2ndKey, Claims, CustomClaimIdSequence, CustomClaims, CustomClaimsInverse,
-    DidRecords, Error, Event, Module, ParentDid,
+    BlockDisclosures, CddExpiries, CddExpiryNotices, CddLifecycle, CddRenewalRequests, Claim1stKey,
+    Claim2ndKey, Claims, CredentialSigners, CredentialsUsed, CustomClaimIdSequence, CustomClaims,
+    CustomClaimsInverse, DidRecords, DisclosedClaims, Error, Event, ExportAttesters,
+    ImportedIdentityExports, Module, NextCddExpiryBucket, NextCddNoticeBucket, ParentDid,
 };
+use codec::{Decode, Encode};
 use frame_support::{
     dispatch::{DispatchError, DispatchResult},
-    ensure, StorageDoubleMap, StorageMap, StorageValue,
+    ensure, IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap, StorageMap,
+    StorageValue,
 };
 use frame_system::ensure_root;
 use pallet_base::{ensure_string_limited, try_next_pre};
 pub use polymesh_common_utilities::traits::identity::WeightInfo;
 use polymesh_common_utilities::{
+    bucket_queue,
     protocol_fee::ProtocolOp,
     traits::{
         group::{GroupTrait, InactiveMember},
@@ -32,11 +37,17 @@ use polymesh_common_utilities::{
     },
     SystematicIssuers,
 };
+use polymesh_primitives::identity::limits::{
+    MAX_CDD_EXPIRIES_PER_BLOCK, MAX_CDD_EXPIRING_WITHIN_DAYS, MAX_CHILD_IDENTITY_DEPTH,
+    MAX_EXPORTED_CLAIMS,
+};
 use polymesh_primitives::identity_claim::CustomClaimTypeId;
 use polymesh_primitives::{
-    CddId, Claim, ClaimType, IdentityClaim, IdentityId, Scope, SecondaryKey,
+    CddId, Claim, ClaimCredential, ClaimSalt, ClaimType, Hash, IdentityClaim, IdentityExport,
+    IdentityId, Scope, SecondaryKey, Signatory, IDENTITY_EXPORT_VERSION,
 };
-use sp_runtime::traits::{CheckedAdd, SaturatedConversion, Zero};
+use sp_runtime::traits::{CheckedAdd, SaturatedConversion, Saturating, Verify, Zero};
+use sp_std::collections::btree_set::BTreeSet;
 use sp_std::prelude::*;
 
 struct CddClaimChecker<T: Config> {
@@ -48,9 +59,11 @@ struct CddClaimChecker<T: Config> {
 
 impl<T: Config> CddClaimChecker<T> {
     pub fn new(_claim_for: IdentityId, leeway: T::Moment, filter_cdd_id: Option<CddId>) -> Self {
+        // CDD claims remain valid during the grace period after they expire.
         let exp_with_leeway = <pallet_timestamp::Pallet<T>>::get()
             .checked_add(&leeway)
-            .unwrap_or_default();
+            .unwrap_or_default()
+            .saturating_sub(CddLifecycle::<T>::get().grace_period);
 
         // Supressing `mut` warning since we need mut in `runtime-benchmarks` feature but not otherwise.
         #[allow(unused_mut)]
@@ -142,6 +155,23 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    /// Ensure that a commitment claim doesn't commit to a CDD claim.
+    pub fn ensure_valid_claim_commitment(claim: &Claim) -> DispatchResult {
+        if let Claim::Commitment(ClaimType::CustomerDueDiligence, ..) = claim {
+            return Err(Error::<T>::InvalidClaimCommitment.into());
+        }
+        Ok(())
+    }
+
+    /// Returns the claim disclosed in the current block by `target` for its commitment claim `claim`.
+    /// Returns `None` if `claim` is not a commitment or it has not been disclosed.
+    pub fn disclosed_claim_of(target: IdentityId, claim: &Claim) -> Option<Claim> {
+        match claim {
+            Claim::Commitment(_, _, commitment) => DisclosedClaims::get(target, commitment),
+            _ => None,
+        }
+    }
+
     /// It fetches an specific `claim_type` claim type for target identity `id`, which was issued
     /// by `issuer`.
     /// It only returns non-expired claims.
@@ -194,33 +224,19 @@ impl<T: Config> Module<T> {
     }
 
     // Returns a lazy iterator that will return the CDD claims from the
-    // parent of `did` if they are a child identity.
+    // ancestors of `did` if they are a child identity, starting with its parent.
     //
     // If `include_parent` is `false` then the iterator will not return claims
-    // from the parent.
+    // from the ancestors.
     pub fn base_fetch_parent_cdd_claims(
         did: IdentityId,
         include_parent: bool,
     ) -> impl Iterator<Item = IdentityClaim> {
-        let mut first_call = include_parent;
-        let mut parent_claims = None;
-        core::iter::from_fn(move || -> Option<IdentityClaim> {
-            // The first time this iterator function is called
-            // we will initialize the `parent_claims` iterator.
-            if first_call {
-                first_call = false;
-                parent_claims = ParentDid::get(did).map(|parent_did| {
-                    Self::fetch_base_claims(parent_did, ClaimType::CustomerDueDiligence)
-                });
-            }
-
-            // If `parent_claims` is `None` then this returns early with `None`.
-            let claim = parent_claims.as_mut()?.next();
-            if claim.is_none() {
-                parent_claims = None;
-            }
-            claim
-        })
+        // Ancestors are only looked up once the claims of their descendants are exhausted.
+        core::iter::successors(include_parent.then(|| did), |did| ParentDid::get(did))
+            .skip(1)
+            .take(MAX_CHILD_IDENTITY_DEPTH as usize)
+            .flat_map(|ancestor| Self::fetch_base_claims(ancestor, ClaimType::CustomerDueDiligence))
     }
 
     pub(crate) fn base_fetch_valid_cdd_claims(
@@ -302,10 +318,173 @@ impl<T: Config> Module<T> {
             claim,
         };
 
+        // A replaced commitment claim invalidates its disclosure.
+        if let Some(old) = Claims::get(&pk, &sk).filter(|old| old.claim != id_claim.claim) {
+            Self::remove_disclosure(target, &old.claim);
+        }
         Claims::insert(&pk, &sk, id_claim.clone());
+        if claim_type == ClaimType::CustomerDueDiligence {
+            // Any new CDD claim fulfils a pending renewal request.
+            CddRenewalRequests::<T>::remove(target);
+            if let Some(expiry) = expiry {
+                Self::queue_cdd_expiry(target, expiry);
+            }
+        }
         Self::deposit_event(RawEvent::ClaimAdded(target, id_claim));
     }
 
+    /// Queues the CDD expiry of `target` for its `CddExpiringSoon` notice and for `cdd_expiring_within`.
+    ///
+    /// Expiries already past their grace period aren't queued and past expiries get no notice.
+    /// Expiries already within the notice period get their notice right away.
+    pub(crate) fn queue_cdd_expiry(target: IdentityId, expiry: u64) {
+        let now = <pallet_timestamp::Pallet<T>>::get();
+        let lifecycle = CddLifecycle::<T>::get();
+        let past_grace =
+            bucket_queue::bucket_of(now.saturating_sub(lifecycle.grace_period).saturated_into());
+        let bucket = bucket_queue::bucket_of(expiry);
+        if bucket < past_grace {
+            return;
+        }
+        bucket_queue::queue::<CddExpiries, NextCddExpiryBucket, _, _>(
+            bucket, past_grace, target, true,
+        );
+        if expiry <= now.saturated_into::<u64>() {
+            return;
+        }
+        let notice_from = Self::cdd_notice_ready_until(now);
+        if bucket < notice_from {
+            Self::deposit_event(RawEvent::CddExpiringSoon(target, expiry.saturated_into()));
+        } else {
+            bucket_queue::queue::<CddExpiryNotices, NextCddNoticeBucket, _, _>(
+                bucket,
+                notice_from,
+                target,
+                true,
+            );
+        }
+    }
+
+    /// Returns the bucket of `CddExpiryNotices` before which notices are due at `now`.
+    fn cdd_notice_ready_until(now: T::Moment) -> u64 {
+        let notice_until = now.saturating_add(CddLifecycle::<T>::get().notice_period);
+        bucket_queue::bucket_of(notice_until.saturated_into()) + 1
+    }
+
+    /// Returns when the CDD of `did` expires, i.e. the latest expiry of its CDD claims.
+    /// Returns `None` if `did` has no CDD claims or one of them never expires.
+    fn cdd_expiry(did: IdentityId) -> Option<u64> {
+        let mut expiry = None;
+        for id_claim in Self::fetch_base_claims(did, ClaimType::CustomerDueDiligence) {
+            let claim_expiry = id_claim.expiry?;
+            expiry = Some(expiry.map_or(claim_expiry, |e: u64| e.max(claim_expiry)));
+        }
+        expiry
+    }
+
+    /// Emits `CddExpiringSoon` for up to `MAX_CDD_EXPIRIES_PER_BLOCK` identities whose CDD
+    /// expires within the notice period.
+    ///
+    /// Identities whose CDD was renewed since it was queued are skipped.
+    /// Returns the number of queued notices that were processed.
+    pub(crate) fn notify_cdd_expiries() -> u32 {
+        let ready_until = Self::cdd_notice_ready_until(<pallet_timestamp::Pallet<T>>::get());
+        let (bucket, dids) = bucket_queue::take::<CddExpiryNotices, NextCddNoticeBucket, _, _>(
+            ready_until,
+            MAX_CDD_EXPIRIES_PER_BLOCK,
+        );
+        for (did, _) in &dids {
+            match Self::cdd_expiry(*did) {
+                Some(expiry) if bucket_queue::bucket_of(expiry) == bucket => {
+                    Self::deposit_event(RawEvent::CddExpiringSoon(*did, expiry.saturated_into()))
+                }
+                _ => {}
+            }
+        }
+        dids.len() as u32
+    }
+
+    /// Removes up to `MAX_CDD_EXPIRIES_PER_BLOCK` queued CDD expiries that are past their grace period.
+    /// Returns the number of queued expiries that were processed.
+    pub(crate) fn prune_cdd_expiries() -> u32 {
+        let now = <pallet_timestamp::Pallet<T>>::get();
+        let past_grace = now.saturating_sub(CddLifecycle::<T>::get().grace_period);
+        let (_, dids) = bucket_queue::take::<CddExpiries, NextCddExpiryBucket, _, _>(
+            bucket_queue::bucket_of(past_grace.saturated_into()),
+            MAX_CDD_EXPIRIES_PER_BLOCK,
+        );
+        dids.len() as u32
+    }
+
+    /// Returns the identities whose CDD expires within the next `days` days, or has expired
+    /// but is still within its grace period, along with their expiry, sorted by expiry.
+    ///
+    /// `days` is capped at `MAX_CDD_EXPIRING_WITHIN_DAYS`.
+    pub fn cdd_expiring_within(days: u32) -> Vec<(IdentityId, T::Moment)> {
+        let days = days.min(MAX_CDD_EXPIRING_WITHIN_DAYS);
+        let now = <pallet_timestamp::Pallet<T>>::get().saturated_into::<u64>();
+        let grace = CddLifecycle::<T>::get()
+            .grace_period
+            .saturated_into::<u64>();
+        let from = now.saturating_sub(grace);
+        let until = now.saturating_add(u64::from(days).saturating_mul(86_400_000));
+
+        let dids = (bucket_queue::bucket_of(from)..=bucket_queue::bucket_of(until))
+            .flat_map(|bucket| CddExpiries::iter_key_prefix(bucket))
+            .collect::<BTreeSet<_>>();
+        let mut expiring = dids
+            .into_iter()
+            .filter_map(|did| Some((did, Self::cdd_expiry(did)?)))
+            .filter(|(_, expiry)| from < *expiry && *expiry <= until)
+            .collect::<Vec<_>>();
+        expiring.sort_by_key(|(_, expiry)| *expiry);
+        expiring
+            .into_iter()
+            .map(|(did, expiry)| (did, expiry.saturated_into()))
+            .collect()
+    }
+
+    /// Records that the caller's identity requests the renewal of its CDD.
+    ///
+    /// # Errors
+    /// - `CddRenewalAlreadyRequested` if the identity already has a pending renewal request.
+    pub(crate) fn base_request_cdd_renewal(origin: T::RuntimeOrigin) -> DispatchResult {
+        let did = Self::ensure_perms(origin)?;
+        ensure!(
+            !CddRenewalRequests::<T>::contains_key(did),
+            Error::<T>::CddRenewalAlreadyRequested
+        );
+
+        CddRenewalRequests::<T>::insert(did, <pallet_timestamp::Pallet<T>>::get());
+        Self::deposit_event(RawEvent::CddRenewalRequested(did));
+        Ok(())
+    }
+
+    /// Renews the CDD of `target` by re-issuing its latest CDD claim with a new `expiry`.
+    ///
+    /// # Errors
+    /// - `NoCddRenewalRequest` if `target` didn't request a renewal.
+    /// - `NoCddClaimToRenew` if `target` has no CDD claim.
+    /// - `UnAuthorizedCddProvider` if the caller isn't a CDD provider.
+    pub(crate) fn base_renew_cdd(
+        origin: T::RuntimeOrigin,
+        target: IdentityId,
+        expiry: Option<T::Moment>,
+    ) -> DispatchResult {
+        let issuer = Self::ensure_signed_and_validate_claim_target(origin, target)?;
+        ensure!(
+            CddRenewalRequests::<T>::contains_key(target),
+            Error::<T>::NoCddRenewalRequest
+        );
+        let id_claim = Self::fetch_base_claims(target, ClaimType::CustomerDueDiligence)
+            .max_by_key(|id_claim| id_claim.expiry.unwrap_or(u64::MAX))
+            .ok_or(Error::<T>::NoCddClaimToRenew)?;
+
+        Self::base_add_cdd_claim(target, id_claim.claim, issuer, expiry)?;
+        Self::deposit_event(RawEvent::CddRenewed(issuer, target));
+        Ok(())
+    }
+
     /// Returns claim keys.
     pub fn get_claim_keys(
         target: IdentityId,
@@ -343,11 +522,298 @@ impl<T: Config> Module<T> {
         let (pk, sk) = Self::get_claim_keys(target, claim_type, issuer, scope);
         // Remove the claim.
         let claim = Claims::take(&pk, &sk).ok_or(Error::<T>::ClaimDoesNotExist)?;
+        Self::remove_disclosure(target, &claim.claim);
         // Emit claim revoked event.
         Self::deposit_event(RawEvent::ClaimRevoked(target, claim));
         Ok(())
     }
 
+    /// Removes the disclosure made by `target` for its commitment claim `claim`, if any.
+    fn remove_disclosure(target: IdentityId, claim: &Claim) {
+        if let Claim::Commitment(_, _, commitment) = claim {
+            DisclosedClaims::remove(target, commitment);
+        }
+    }
+
+    /// Discloses `claim` for the commitment claim of the caller issued by `issuer`.
+    pub(crate) fn base_disclose_claim(
+        origin: T::RuntimeOrigin,
+        issuer: IdentityId,
+        claim: Claim,
+        salt: ClaimSalt,
+    ) -> DispatchResult {
+        let did = Self::ensure_perms(origin)?;
+        ensure!(
+            !matches!(
+                claim,
+                Claim::CustomerDueDiligence(..) | Claim::Commitment(..)
+            ),
+            Error::<T>::InvalidClaimCommitment
+        );
+
+        let commitment =
+            match Self::fetch_claim(did, claim.claim_type(), issuer, claim.as_scope().cloned())
+                .map(|id_claim| id_claim.claim)
+            {
+                Some(Claim::Commitment(_, _, commitment)) => commitment,
+                _ => return Err(Error::<T>::ClaimDoesNotExist.into()),
+            };
+        ensure!(
+            commitment.is_opened_by(&claim, &salt),
+            Error::<T>::ClaimCommitmentMismatch
+        );
+
+        if !DisclosedClaims::contains_key(did, commitment) {
+            BlockDisclosures::append((did, commitment));
+        }
+        DisclosedClaims::insert(did, commitment, claim);
+        Self::deposit_event(RawEvent::ClaimDisclosed(did, issuer, commitment));
+        Ok(())
+    }
+
+    /// Adds or removes the credential signers of the caller's identity.
+    pub(crate) fn base_update_credential_signers(
+        origin: T::RuntimeOrigin,
+        signers: Vec<T::AccountId>,
+        add_signers: bool,
+    ) -> DispatchResult {
+        let issuer = Self::ensure_perms(origin)?;
+
+        for signer in &signers {
+            if add_signers {
+                ensure!(
+                    !CredentialSigners::<T>::get(issuer, signer),
+                    Error::<T>::CredentialSignerAlreadyExists
+                );
+            } else {
+                ensure!(
+                    CredentialSigners::<T>::get(issuer, signer),
+                    Error::<T>::CredentialSignerDoesNotExist
+                );
+            }
+        }
+
+        for signer in signers {
+            if add_signers {
+                CredentialSigners::<T>::insert(issuer, &signer, true);
+            } else {
+                CredentialSigners::<T>::remove(issuer, &signer);
+            }
+            Self::deposit_event(RawEvent::CredentialSignerUpdated(
+                issuer,
+                signer,
+                add_signers,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Verifies `signature` over `credential` and adds its claim on behalf of the credential issuer.
+    pub(crate) fn base_import_claim_credential(
+        origin: T::RuntimeOrigin,
+        credential: ClaimCredential<T::Moment>,
+        signer: T::AccountId,
+        signature: T::OffChainSignature,
+    ) -> DispatchResult {
+        Self::ensure_perms(origin)?;
+
+        let issuer = credential.issuer;
+        ensure!(
+            CredentialSigners::<T>::get(issuer, &signer),
+            Error::<T>::UnauthorizedCredentialSigner
+        );
+        ensure!(
+            !CredentialsUsed::get(issuer, credential.uid),
+            Error::<T>::CredentialAlreadyImported
+        );
+        ensure!(
+            credential.valid_until >= <pallet_timestamp::Pallet<T>>::get(),
+            Error::<T>::CredentialExpired
+        );
+        ensure!(
+            signature.verify(
+                (Self::genesis_hash(), &credential).encode().as_slice(),
+                &signer
+            ),
+            Error::<T>::InvalidCredentialSignature
+        );
+        ensure!(
+            DidRecords::<T>::contains_key(credential.target),
+            Error::<T>::DidMustAlreadyExist
+        );
+
+        let ClaimCredential {
+            uid,
+            target,
+            claim,
+            expiry,
+            ..
+        } = credential;
+        match &claim {
+            Claim::CustomerDueDiligence(..) => {
+                Self::base_add_cdd_claim(target, claim, issuer, expiry)?
+            }
+            _ => {
+                Self::ensure_custom_scopes_limited(&claim)?;
+                Self::ensure_valid_claim_commitment(&claim)?;
+                T::ProtocolFee::charge_fee(ProtocolOp::IdentityAddClaim)?;
+                Self::base_add_claim(target, claim, issuer, expiry)?
+            }
+        }
+
+        CredentialsUsed::insert(issuer, uid, true);
+        Self::deposit_event(RawEvent::ClaimCredentialImported(issuer, target, uid));
+        Ok(())
+    }
+
+    /// Returns a portable snapshot of the keys, unexpired claims and pending authorizations of `did`,
+    /// or `None` if `did` has no primary key.
+    pub fn export_identity(did: IdentityId) -> Option<IdentityExport<T::AccountId, T::Moment>> {
+        let primary_key = DidRecords::<T>::get(&did)?.primary_key?;
+        let now = <pallet_timestamp::Pallet<T>>::get();
+
+        let claims = [
+            ClaimType::Accredited,
+            ClaimType::Affiliate,
+            ClaimType::BuyLockup,
+            ClaimType::SellLockup,
+            ClaimType::CustomerDueDiligence,
+            ClaimType::KnowYourCustomer,
+            ClaimType::Jurisdiction,
+            ClaimType::Exempted,
+            ClaimType::Blocked,
+        ]
+        .into_iter()
+        .chain(CustomClaims::iter_keys().map(ClaimType::Custom))
+        .flat_map(|claim_type| Self::fetch_base_claims(did, claim_type))
+        .filter(|id_claim| CddClaimChecker::<T>::is_identity_claim_not_expired_at(id_claim, now))
+        .take(MAX_EXPORTED_CLAIMS as usize)
+        .collect();
+
+        Some(IdentityExport {
+            version: IDENTITY_EXPORT_VERSION,
+            source_chain: Self::genesis_hash(),
+            exported_at: now,
+            did,
+            primary_key,
+            secondary_keys: Self::rpc_secondary_keys(did),
+            claims,
+            authorizations: Self::get_filtered_authorizations(
+                Signatory::Identity(did),
+                false,
+                None,
+            ),
+        })
+    }
+
+    /// Returns the genesis hash of this chain, which identifies it in identity exports and credentials.
+    pub(crate) fn genesis_hash() -> Hash {
+        let hash = <frame_system::Pallet<T>>::block_hash(T::BlockNumber::zero());
+        Hash::decode(&mut hash.as_ref()).unwrap_or_default()
+    }
+
+    /// Verifies `signature` and `attestation` over `export` and re-issues its unexpired claims to `target`,
+    /// with the calling CDD provider as the issuer.
+    pub(crate) fn base_import_identity_export(
+        origin: T::RuntimeOrigin,
+        target: IdentityId,
+        export: IdentityExport<T::AccountId, T::Moment>,
+        signature: T::OffChainSignature,
+        attester: T::AccountId,
+        attestation: T::OffChainSignature,
+    ) -> DispatchResult {
+        let cdd_did = Self::ensure_perms(origin)?;
+        Self::ensure_authorized_cdd_provider(cdd_did)?;
+
+        ensure!(
+            export.version == IDENTITY_EXPORT_VERSION,
+            Error::<T>::UnsupportedIdentityExportVersion
+        );
+        ensure!(
+            export.claims.len() <= MAX_EXPORTED_CLAIMS as usize,
+            Error::<T>::TooManyExportedClaims
+        );
+        ensure!(
+            export.source_chain != Self::genesis_hash()
+                && ExportAttesters::<T>::get(export.source_chain, &attester),
+            Error::<T>::UnknownExportSourceChain
+        );
+        let encoded_export = export.encode();
+        ensure!(
+            signature.verify(encoded_export.as_slice(), &export.primary_key)
+                && attestation.verify(encoded_export.as_slice(), &attester),
+            Error::<T>::InvalidIdentityExportSignature
+        );
+        ensure!(
+            DidRecords::<T>::contains_key(target),
+            Error::<T>::DidMustAlreadyExist
+        );
+        ensure!(
+            Self::get_primary_key(target).as_ref() == Some(&export.primary_key),
+            Error::<T>::IdentityExportKeyMismatch
+        );
+        ensure!(
+            ImportedIdentityExports::get(export.source_chain, export.did)
+                .map_or(true, |did| did == target),
+            Error::<T>::IdentityExportDidMismatch
+        );
+        ImportedIdentityExports::insert(export.source_chain, export.did, target);
+
+        let now = <pallet_timestamp::Pallet<T>>::get();
+        let mut imported = 0u32;
+        for id_claim in export.claims {
+            // Claims that expired since the export are not re-issued.
+            if !CddClaimChecker::<T>::is_identity_claim_not_expired_at(&id_claim, now) {
+                continue;
+            }
+            // Custom claim types are local to the source chain.
+            if let ClaimType::Custom(_) = id_claim.claim.claim_type() {
+                continue;
+            }
+            let IdentityClaim { claim, expiry, .. } = id_claim;
+            let expiry = expiry.map(|expiry| expiry.saturated_into::<T::Moment>());
+            match &claim {
+                Claim::CustomerDueDiligence(..) => {
+                    Self::base_add_cdd_claim(target, claim, cdd_did, expiry)?
+                }
+                _ => {
+                    Self::ensure_custom_scopes_limited(&claim)?;
+                    Self::ensure_valid_claim_commitment(&claim)?;
+                    T::ProtocolFee::charge_fee(ProtocolOp::IdentityAddClaim)?;
+                    Self::base_add_claim(target, claim, cdd_did, expiry)?
+                }
+            }
+            imported += 1;
+        }
+
+        Self::deposit_event(RawEvent::IdentityExportImported(
+            cdd_did, target, export.did, imported,
+        ));
+        Ok(())
+    }
+
+    /// Trusts, or no longer trusts, `attester` to attest the identity exports of `source_chain`.
+    pub(crate) fn base_set_export_attester(
+        origin: T::RuntimeOrigin,
+        source_chain: Hash,
+        attester: T::AccountId,
+        trusted: bool,
+    ) -> DispatchResult {
+        ensure_root(origin)?;
+        ensure!(
+            source_chain != Self::genesis_hash(),
+            Error::<T>::UnknownExportSourceChain
+        );
+
+        if trusted {
+            ExportAttesters::<T>::insert(source_chain, &attester, true);
+        } else {
+            ExportAttesters::<T>::remove(source_chain, &attester);
+        }
+        Self::deposit_event(RawEvent::ExportAttesterSet(source_chain, attester, trusted));
+        Ok(())
+    }
+
     /// Ensure that the origin is signed and that the given `target` is already in the system.
     pub(crate) fn ensure_signed_and_validate_claim_target(
         origin: T::RuntimeOrigin,
@@ -14,40 +14,49 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-    types, AccountKeyRefCount, Config, DidKeys, DidRecords, Error, IsDidFrozen, KeyRecords, Module,
-    MultiPurposeNonce, OffChainAuthorizationNonce, ParentDid, PermissionedCallOriginData, RawEvent,
-    RpcDidRecords,
+    types, AccountKeyRefCount, ChildDids, Config, DidKeys, DidRecords, Error, IsDidFrozen,
+    KeyRecords, KeySpendings, Module, MultiPurposeNonce, OffChainAuthorizationNonce, ParentDid,
+    ParentPermissions, PermissionedCallOriginData, RawEvent, RpcDidRecords, SecondaryKeysLimits,
+    SessionKeyExpiries, SessionKeys,
 };
 use codec::{Decode, Encode as _};
 use core::mem;
-use frame_support::dispatch::DispatchResult;
-use frame_support::traits::{Currency as _, Get as _};
+use frame_support::dispatch::{
+    extract_actual_weight, DispatchResult, DispatchResultWithPostInfo, GetDispatchInfo as _,
+};
+use frame_support::traits::{Currency as _, Get as _, GetCallMetadata as _};
 use frame_support::{
     ensure, IterableStorageDoubleMap, StorageDoubleMap, StorageMap as _, StorageValue as _,
 };
-use frame_system::ensure_signed;
+use frame_system::{ensure_signed, RawOrigin};
 use pallet_base::{ensure_custom_length_ok, ensure_custom_string_limited};
+use pallet_permissions::with_call_metadata;
 use polymesh_common_utilities::constants::did::USER;
 use polymesh_common_utilities::group::GroupTrait;
 use polymesh_common_utilities::identity::{
-    CreateChildIdentityWithAuth, SecondaryKeyWithAuth, TargetIdAuthorization,
+    CreateChildIdentityWithAuth, SecondaryKeyWithAuth, TargetIdAuthorization, WeightInfo as _,
 };
 use polymesh_common_utilities::multisig::MultiSigSubTrait as _;
 use polymesh_common_utilities::protocol_fee::{ChargeProtocolFee as _, ProtocolOp};
 use polymesh_common_utilities::traits::{
-    AccountCallPermissionsData, CddAndFeeDetails, CheckAccountCallPermissions,
+    AccountCallPermissionsData, CddAndFeeDetails, CheckAccountCallPermissions, FeeAssetPayer as _,
 };
 use polymesh_common_utilities::{Context, SystematicIssuers};
 use polymesh_primitives::identity::limits::{
-    MAX_ASSETS, MAX_EXTRINSICS, MAX_PALLETS, MAX_PORTFOLIOS,
+    MAX_ASSETS, MAX_CHILD_IDENTITY_DEPTH, MAX_EXTRINSICS, MAX_PALLETS, MAX_PORTFOLIOS,
+    MAX_SESSION_KEYS_PER_BLOCK,
 };
 use polymesh_primitives::{
-    extract_auth, AuthorizationData, DidRecord, DispatchableName, ExtrinsicPermissions, IdentityId,
-    KeyRecord, PalletName, Permissions, SecondaryKey, Signatory, SubsetRestriction,
+    extract_auth, AuthorizationData, Balance, DidRecord, DispatchableName, ExtrinsicPermissions,
+    IdentityId, KeyRecord, KeySpending, PalletName, Permissions, SecondaryKey, SecondaryKeyLimits,
+    SessionKey, Signatory, SubsetRestriction, Ticker,
 };
-use sp_core::sr25519::Signature;
+use sp_core::{sr25519::Signature, H512};
 use sp_io::hashing::blake2_256;
-use sp_runtime::traits::{AccountIdConversion as _, IdentifyAccount, Verify};
+use sp_runtime::traits::{
+    AccountIdConversion as _, Dispatchable as _, IdentifyAccount, Saturating as _, Verify,
+    Zero as _,
+};
 use sp_runtime::{AnySignature, DispatchError};
 use sp_std::collections::btree_set::BTreeSet;
 use sp_std::{vec, vec::Vec};
@@ -77,6 +86,23 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    /// Returns the ancestors of `did`, starting with its parent.
+    pub fn ancestors(did: IdentityId) -> Vec<IdentityId> {
+        core::iter::successors(ParentDid::get(did), |did| ParentDid::get(did))
+            .take(MAX_CHILD_IDENTITY_DEPTH as usize)
+            .collect()
+    }
+
+    /// Ensures that a new child identity of `parent_did` wouldn't have more than
+    /// `MAX_CHILD_IDENTITY_DEPTH` ancestors.
+    pub fn ensure_child_depth(parent_did: IdentityId) -> DispatchResult {
+        ensure!(
+            (Self::ancestors(parent_did).len() as u32) < MAX_CHILD_IDENTITY_DEPTH,
+            Error::<T>::ChildIdentityTooDeep
+        );
+        Ok(())
+    }
+
     /// Returns `Err(DidDoesNotExist)` unless `id` has an associated record.
     pub(crate) fn ensure_id_record_exists(id: IdentityId) -> DispatchResult {
         ensure!(Self::is_identity_exists(&id), Error::<T>::DidDoesNotExist);
@@ -123,8 +149,10 @@ impl<T: Config> Module<T> {
     }
 
     /// RPC call to fetch some aggregate account data for fewer round trips.
-    pub fn get_key_identity_data(acc: T::AccountId) -> Option<types::KeyIdentityData<IdentityId>> {
-        let (identity, permissions) = match KeyRecords::<T>::get(acc)? {
+    pub fn get_key_identity_data(
+        acc: T::AccountId,
+    ) -> Option<types::KeyIdentityData<IdentityId, T::Moment>> {
+        let (identity, permissions) = match KeyRecords::<T>::get(&acc)? {
             KeyRecord::PrimaryKey(did) => Some((did, None)),
             KeyRecord::SecondaryKey(did, perms) => Some((did, Some(perms))),
             // Is a multisig signer.
@@ -133,6 +161,7 @@ impl<T: Config> Module<T> {
         Some(types::KeyIdentityData {
             identity,
             permissions,
+            limits: SecondaryKeysLimits::<T>::get(&acc),
         })
     }
 
@@ -153,22 +182,33 @@ impl<T: Config> Module<T> {
     /// Results limited to `RPC_MAX_KEYS` secondary keys.
     pub fn get_did_records(did: IdentityId) -> RpcDidRecords<T::AccountId> {
         if let Some(record) = DidRecords::<T>::get(&did) {
-            let secondary_keys = DidKeys::<T>::iter_key_prefix(&did)
+            let secondary_keys = Self::rpc_secondary_keys(did);
+            let child_dids = ChildDids::iter_key_prefix(&did)
                 .take(RPC_MAX_KEYS)
-                .filter_map(|key| {
-                    // Lookup the key's permissions and convert that into a `SecondaryKey` type.
-                    KeyRecords::<T>::get(&key).and_then(|r| r.into_secondary_key(key))
-                })
                 .collect();
             RpcDidRecords::Success {
                 primary_key: record.primary_key.unwrap_or_else(types::zero_account_id),
                 secondary_keys,
+                parent_did: ParentDid::get(&did),
+                child_dids,
+                parent_permissions: ParentPermissions::get(&did),
             }
         } else {
             RpcDidRecords::IdNotFound
         }
     }
 
+    /// Returns up to `RPC_MAX_KEYS` secondary keys of `did`, along with their permissions.
+    pub(crate) fn rpc_secondary_keys(did: IdentityId) -> Vec<SecondaryKey<T::AccountId>> {
+        DidKeys::<T>::iter_key_prefix(&did)
+            .take(RPC_MAX_KEYS)
+            .filter_map(|key| {
+                // Lookup the key's permissions and convert that into a `SecondaryKey` type.
+                KeyRecords::<T>::get(&key).and_then(|r| r.into_secondary_key(key))
+            })
+            .collect()
+    }
+
     /// Increment the reference counter for `key`.
     pub fn add_account_key_ref_count(key: &T::AccountId) {
         <AccountKeyRefCount<T>>::mutate(key, |n| *n = n.saturating_add(1_u64));
@@ -261,6 +301,9 @@ impl<T: Config> Module<T> {
             Some(_) | None => false,
         };
         if remove_key {
+            Self::remove_secondary_key_limits(key);
+            SessionKeys::<T>::remove(key);
+            T::FeeAssetPayer::remove_fee_payment_asset(key);
             KeyRecords::<T>::remove(key);
         }
     }
@@ -289,6 +332,38 @@ impl<T: Config> Module<T> {
         new_primary_key: T::AccountId,
         new_permissions: Option<Permissions>,
         optional_cdd_auth_id: Option<u64>,
+    ) -> DispatchResult {
+        Self::rotate_primary_key_with(target_did, new_primary_key, new_permissions, |signer| {
+            // Accept authorization from CDD service provider.
+            if Self::cdd_auth_for_primary_key_rotation() {
+                let auth_id = optional_cdd_auth_id
+                    .ok_or_else(|| Error::<T>::InvalidAuthorizationFromCddProvider)?;
+
+                Self::accept_auth_with(signer, auth_id, |data, auth_by| {
+                    let attestation_for_did = extract_auth!(data, AttestPrimaryKeyRotation(a));
+                    // Attestor must be a CDD service provider.
+                    ensure!(
+                        T::CddServiceProviders::is_member(&auth_by),
+                        Error::<T>::NotCddProviderAttestation
+                    );
+                    // Ensure authorizations are for the same DID.
+                    ensure!(
+                        target_did == attestation_for_did,
+                        Error::<T>::AuthorizationsNotForSameDids
+                    );
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        })
+    }
+
+    // Sets the new primary key once `attest` has validated the rotation for the new key.
+    pub(crate) fn rotate_primary_key_with(
+        target_did: IdentityId,
+        new_primary_key: T::AccountId,
+        new_permissions: Option<Permissions>,
+        attest: impl FnOnce(&Signatory<T::AccountId>) -> DispatchResult,
     ) -> DispatchResult {
         let old_primary_key =
             Self::get_primary_key(target_did).ok_or(Error::<T>::InvalidAccountKey)?;
@@ -318,33 +393,14 @@ impl<T: Config> Module<T> {
             Self::ensure_key_unlinkable_from_did(&old_primary_key)?;
         }
 
-        let signer = Signatory::Account(new_primary_key.clone());
-
-        // Accept authorization from CDD service provider.
-        if Self::cdd_auth_for_primary_key_rotation() {
-            let auth_id = optional_cdd_auth_id
-                .ok_or_else(|| Error::<T>::InvalidAuthorizationFromCddProvider)?;
-
-            Self::accept_auth_with(&signer, auth_id, |data, auth_by| {
-                let attestation_for_did = extract_auth!(data, AttestPrimaryKeyRotation(a));
-                // Attestor must be a CDD service provider.
-                ensure!(
-                    T::CddServiceProviders::is_member(&auth_by),
-                    Error::<T>::NotCddProviderAttestation
-                );
-                // Ensure authorizations are for the same DID.
-                ensure!(
-                    target_did == attestation_for_did,
-                    Error::<T>::AuthorizationsNotForSameDids
-                );
-                Ok(())
-            })?;
-        }
+        attest(&Signatory::Account(new_primary_key.clone()))?;
 
         // Replace primary key of the owner that initiated key rotation.
         let key_record = KeyRecord::PrimaryKey(target_did);
         if is_secondary_key {
             // Convert secondary key to primary key.
+            Self::remove_secondary_key_limits(&new_primary_key);
+            SessionKeys::<T>::remove(&new_primary_key);
             KeyRecords::<T>::insert(&new_primary_key, key_record);
             DidRecords::<T>::insert(target_did, DidRecord::new(new_primary_key.clone()));
 
@@ -437,8 +493,8 @@ impl<T: Config> Module<T> {
     ) -> DispatchResult {
         let (_, parent_did) = Self::ensure_primary_key(origin)?;
 
-        // Make sure `parent_did` has no parent.
-        Self::ensure_no_parent(parent_did)?;
+        // Make sure the hierarchy of `parent_did` isn't too deep.
+        Self::ensure_child_depth(parent_did)?;
 
         // Ensure that the key is a secondary key.
         Self::ensure_secondary_key(parent_did, &secondary_key)?;
@@ -471,12 +527,18 @@ impl<T: Config> Module<T> {
         Self::add_key_record(&key, KeyRecord::PrimaryKey(child_did));
         Self::deposit_event(RawEvent::DidCreated(child_did, key.clone(), vec![]));
         // Link new identity to parent identity.
-        ParentDid::insert(child_did, parent_did);
+        Self::link_child_identity(parent_did, child_did);
 
         Self::deposit_event(RawEvent::ChildDidCreated(parent_did, child_did, key));
         Ok(())
     }
 
+    /// Links `child_did` to its parent identity `parent_did`.
+    fn link_child_identity(parent_did: IdentityId, child_did: IdentityId) {
+        ParentDid::insert(child_did, parent_did);
+        ChildDids::insert(parent_did, child_did, true);
+    }
+
     /// Create a child identities.
     pub(crate) fn base_create_child_identities(
         origin: T::RuntimeOrigin,
@@ -485,8 +547,8 @@ impl<T: Config> Module<T> {
     ) -> DispatchResult {
         let (_, parent_did) = Self::ensure_primary_key(origin)?;
 
-        // Make sure `parent_did` has no parent.
-        Self::ensure_no_parent(parent_did)?;
+        // Make sure the hierarchy of `parent_did` isn't too deep.
+        Self::ensure_child_depth(parent_did)?;
 
         // Create authorization data that the child keys need to sign.
         let now = <pallet_timestamp::Pallet<T>>::get();
@@ -541,7 +603,7 @@ impl<T: Config> Module<T> {
             Self::deposit_event(RawEvent::DidCreated(child_did, key.clone(), vec![]));
 
             // Link new identity to parent identity.
-            ParentDid::insert(child_did, parent_did);
+            Self::link_child_identity(parent_did, child_did);
 
             Self::deposit_event(RawEvent::ChildDidCreated(parent_did, child_did, key));
         }
@@ -566,6 +628,8 @@ impl<T: Config> Module<T> {
 
         // Unlink child identity from parent identity.
         ParentDid::remove(child_did);
+        ChildDids::remove(parent_did, child_did);
+        ParentPermissions::remove(child_did);
 
         Self::deposit_event(RawEvent::ChildDidUnlinked(
             caller_did, parent_did, child_did,
@@ -574,6 +638,277 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    /// Sets the extrinsics that the ancestors of the caller's child identity can call on its behalf.
+    pub(crate) fn base_set_parent_permissions(
+        origin: T::RuntimeOrigin,
+        permissions: Option<ExtrinsicPermissions>,
+    ) -> DispatchResult {
+        let (_, child_did) = Self::ensure_primary_key(origin)?;
+        ensure!(
+            ParentDid::contains_key(child_did),
+            Error::<T>::NoParentIdentity
+        );
+
+        if let Some(perms) = &permissions {
+            Self::ensure_no_except_perms(perms)?;
+            Self::ensure_extrinsic_perms_length_limited(perms)?;
+            ParentPermissions::insert(child_did, perms);
+        } else {
+            ParentPermissions::remove(child_did);
+        }
+        Self::deposit_event(RawEvent::ParentPermissionsSet(child_did, permissions));
+        Ok(())
+    }
+
+    /// Dispatches `call` on behalf of `child_did`, as its primary key.
+    ///
+    /// The current identity is set to `child_did` while `call` is dispatched.
+    pub(crate) fn base_call_as_child(
+        origin: T::RuntimeOrigin,
+        child_did: IdentityId,
+        call: T::Proposal,
+    ) -> DispatchResultWithPostInfo {
+        let PermissionedCallOriginData {
+            sender,
+            primary_did: caller_did,
+            ..
+        } = Self::ensure_origin_call_permissions(origin)?;
+        ensure!(
+            Self::ancestors(child_did).contains(&caller_did),
+            Error::<T>::NotAnAncestorIdentity
+        );
+
+        // Ensure the child identity allows its ancestors to make this call.
+        let metadata = call.get_call_metadata();
+        let pallet_name: PalletName = metadata.pallet_name.as_bytes().into();
+        let function_name: DispatchableName = metadata.function_name.as_bytes().into();
+        ensure!(
+            ParentPermissions::get(child_did).map_or(false, |perms| perms
+                .sufficient_for(&pallet_name, &function_name)),
+            Error::<T>::CallNotPermittedForAncestors
+        );
+
+        // The calling key must also be allowed to make this call itself. As `call` is dispatched
+        // with the child's primary key, a secondary key must not be restricted to some assets or portfolios.
+        let AccountCallPermissionsData { secondary_key, .. } =
+            with_call_metadata(metadata, || {
+                pallet_permissions::Module::<T>::ensure_call_permissions(&sender)
+            })?;
+        ensure!(
+            secondary_key.map_or(true, |sk| sk.permissions.asset.is_unrestricted()
+                && sk.permissions.portfolio.is_unrestricted()),
+            Error::<T>::CallNotPermittedForKey
+        );
+        let child_key = DidRecords::<T>::get(child_did)
+            .and_then(|record| record.primary_key)
+            .ok_or(Error::<T>::DidDoesNotExist)?;
+
+        // Temporarily change the current identity to the child identity.
+        let dispatch_info = call.get_dispatch_info();
+        let original_did = Context::current_identity::<Self>();
+        Context::set_current_identity::<Self>(Some(child_did));
+        let call_result = with_call_metadata(metadata, || {
+            call.dispatch(RawOrigin::Signed(child_key).into())
+        });
+        Context::set_current_identity::<Self>(original_did);
+
+        let weight = <T as Config>::WeightInfo::call_as_child()
+            .saturating_add(extract_actual_weight(&call_result, &dispatch_info));
+        match call_result {
+            Ok(_) => {
+                Self::deposit_event(RawEvent::CalledAsChild(caller_did, child_did));
+                Ok(Some(weight).into())
+            }
+            Err(mut err) => {
+                err.post_info = Some(weight).into();
+                Err(err)
+            }
+        }
+    }
+
+    /// Sets or removes the validity window and spending limits of a secondary key.
+    pub(crate) fn base_set_secondary_key_limits(
+        origin: T::RuntimeOrigin,
+        key: T::AccountId,
+        limits: Option<SecondaryKeyLimits<T::Moment>>,
+    ) -> DispatchResult {
+        let (_, did) = Self::ensure_primary_key(origin)?;
+
+        // Ensure that the `key` is a secondary key of the caller's Identity
+        Self::ensure_secondary_key(did, &key)?;
+
+        if let Some(limits) = &limits {
+            ensure_custom_length_ok::<T>(limits.asset_limits.len(), MAX_ASSETS)?;
+            let unique_tickers = limits
+                .asset_limits
+                .iter()
+                .map(|(ticker, _)| ticker)
+                .collect::<BTreeSet<_>>();
+            ensure!(
+                unique_tickers.len() == limits.asset_limits.len(),
+                Error::<T>::InvalidSecondaryKeyLimits
+            );
+            if let (Some(from), Some(until)) = (&limits.valid_from, &limits.valid_until) {
+                ensure!(from <= until, Error::<T>::InvalidSecondaryKeyLimits);
+            }
+        }
+
+        Self::remove_secondary_key_limits(&key);
+        if let Some(limits) = &limits {
+            SecondaryKeysLimits::<T>::insert(&key, limits);
+        }
+        Self::deposit_event(RawEvent::SecondaryKeyLimitsUpdated(did, key, limits));
+        Ok(())
+    }
+
+    /// Removes the limits of a secondary key and resets what it has spent.
+    fn remove_secondary_key_limits(key: &T::AccountId) {
+        if SecondaryKeysLimits::<T>::take(key).is_some() {
+            #[allow(deprecated)]
+            KeySpendings::<T>::remove_prefix(key, None);
+        }
+    }
+
+    /// Records that `key` spends `amount` of `ticker`, or of POLYX if `ticker` is `None`.
+    ///
+    /// It fails if it would exceed the spending limit of the key in its current period.
+    /// Keys without limits, like primary keys, can spend any amount.
+    ///
+    /// Returns the start of the period `amount` was charged to, or `None` if the key has no limit.
+    pub fn charge_secondary_key_spending(
+        key: &T::AccountId,
+        ticker: Option<Ticker>,
+        amount: Balance,
+    ) -> Result<Option<T::Moment>, DispatchError> {
+        let limits = match SecondaryKeysLimits::<T>::get(key) {
+            Some(limits) => limits,
+            None => return Ok(None),
+        };
+        let limit = match limits.spend_limit(ticker.as_ref()) {
+            Some(limit) => limit,
+            None => return Ok(None),
+        };
+        let now = <pallet_timestamp::Pallet<T>>::get();
+
+        KeySpendings::<T>::try_mutate(key, ticker, |spending| {
+            // Start a new period if nothing was spent yet or the current one is over.
+            let period_end = spending.period_start.saturating_add(limits.spend_period);
+            if spending.spent.is_zero() || (!limits.spend_period.is_zero() && now >= period_end) {
+                *spending = KeySpending {
+                    period_start: now,
+                    spent: 0,
+                };
+            }
+            spending.spent = spending
+                .spent
+                .checked_add(amount)
+                .filter(|spent| *spent <= limit)
+                .ok_or(Error::<T>::SecondaryKeySpendLimitExceeded)?;
+            Ok(Some(spending.period_start))
+        })
+    }
+
+    /// Gives back to `key` the `amount` of `ticker` charged to its spending period starting at `period_start`.
+    ///
+    /// Nothing is given back if that period is over, since its spendings no longer count.
+    pub fn refund_secondary_key_spending(
+        key: &T::AccountId,
+        ticker: Option<Ticker>,
+        amount: Balance,
+        period_start: T::Moment,
+    ) {
+        KeySpendings::<T>::mutate_exists(key, ticker, |spending| {
+            if let Some(spending) = spending.as_mut().filter(|s| s.period_start == period_start) {
+                spending.spent = spending.spent.saturating_sub(amount);
+            }
+        });
+    }
+
+    /// Links `key` to the caller's identity as a session key.
+    pub(crate) fn base_add_session_key(
+        origin: T::RuntimeOrigin,
+        key: T::AccountId,
+        permissions: ExtrinsicPermissions,
+        expires_at: T::BlockNumber,
+        max_calls: u32,
+        auth_signature: H512,
+    ) -> DispatchResult {
+        let (_, did) = Self::ensure_primary_key(origin)?;
+
+        // Session keys only get the extrinsics in `permissions`, over all assets and portfolios.
+        let perms = Permissions {
+            extrinsic: permissions.clone(),
+            ..Permissions::default()
+        };
+        Self::ensure_perms_length_limited(&perms)?;
+        Self::ensure_key_did_unlinked(&key)?;
+        let now = System::<T>::block_number();
+        ensure!(
+            expires_at > now
+                && expires_at <= now.saturating_add(T::MaxSessionKeyLifetime::get())
+                && max_calls > 0,
+            Error::<T>::InvalidSessionKey
+        );
+
+        // The session key consents to join the identity by signing an off-chain authorization.
+        let authorization = TargetIdAuthorization {
+            target_id: did,
+            nonce: Self::offchain_authorization_nonce(did),
+            expires_at,
+        };
+        let signature = AnySignature::from(Signature::from_h512(auth_signature));
+        let signer: <<AnySignature as Verify>::Signer as IdentifyAccount>::AccountId =
+            Decode::decode(&mut &key.encode()[..])
+                .map_err(|_| Error::<T>::CannotDecodeSignerAccountId)?;
+        ensure!(
+            signature.verify(authorization.encode().as_slice(), &signer),
+            Error::<T>::InvalidAuthorizationSignature
+        );
+
+        SessionKeyExpiries::<T>::try_mutate(expires_at, |keys| -> DispatchResult {
+            ensure!(
+                keys.len() < MAX_SESSION_KEYS_PER_BLOCK as usize,
+                Error::<T>::TooManySessionKeysExpiring
+            );
+            keys.push(key.clone());
+            Ok(())
+        })?;
+        Self::add_key_record(&key, KeyRecord::SecondaryKey(did, perms));
+        OffChainAuthorizationNonce::mutate(did, |nonce| *nonce = authorization.nonce + 1);
+        SessionKeys::<T>::insert(
+            &key,
+            SessionKey {
+                expires_at,
+                remaining_calls: max_calls,
+            },
+        );
+
+        Self::deposit_event(RawEvent::SessionKeyAdded(did, key, permissions, max_calls));
+        Ok(())
+    }
+
+    /// Unlinks the session keys expiring at block `n`.
+    ///
+    /// Returns the number of keys that were scheduled to expire at `n`.
+    pub(crate) fn expire_session_keys(n: T::BlockNumber) -> u32 {
+        let keys = SessionKeyExpiries::<T>::take(n);
+        for key in &keys {
+            // Skip keys that were already unlinked or linked again with another expiry.
+            if SessionKeys::<T>::get(key)
+                .filter(|s| s.expires_at == n)
+                .is_none()
+            {
+                continue;
+            }
+            if let Some(KeyRecord::SecondaryKey(did, _)) = KeyRecords::<T>::get(key) {
+                Self::remove_key_record(key, Some(did));
+                Self::deposit_event(RawEvent::SessionKeyExpired(did, key.clone()));
+            }
+            SessionKeys::<T>::remove(key);
+        }
+        keys.len() as u32
+    }
+
     /// Removes specified secondary keys of a DID if present.
     pub(crate) fn base_remove_secondary_keys(
         origin: T::RuntimeOrigin,
@@ -818,6 +1153,18 @@ impl<T: Config> Module<T> {
         Self::do_register_id(acc, id, vec![]);
     }
 
+    /// Registers the systematic issuer with its DID, unless that DID already exists.
+    ///
+    /// Used by migrations of pallets that add a systematic issuer after genesis.
+    pub fn ensure_systematic_id(issuer: SystematicIssuers) {
+        let id = issuer.as_id();
+        if Self::is_identity_exists(&id) {
+            return;
+        }
+        let acc = issuer.as_pallet_id().into_account_truncating();
+        Self::do_register_id(acc, id, vec![]);
+    }
+
     /// Registers `primary_key` as `id` identity.
     #[allow(dead_code)]
     pub(crate) fn do_register_id(
@@ -843,7 +1190,7 @@ impl<T: Config> Module<T> {
     }
 
     /// Ensures that `origin`'s key is the primary key of a DID.
-    fn ensure_primary_key(
+    pub(crate) fn ensure_primary_key(
         origin: T::RuntimeOrigin,
     ) -> Result<(T::AccountId, IdentityId), DispatchError> {
         let sender = ensure_signed(origin)?;
@@ -932,7 +1279,7 @@ impl<T: Config> Module<T> {
 }
 
 impl<T: Config> CheckAccountCallPermissions<T::AccountId> for Module<T> {
-    // For weighting purposes, the function reads 4 storage values.
+    // For weighting purposes, the function reads 6 storage values.
     fn check_account_call_permissions(
         who: &T::AccountId,
         pallet_name: impl FnOnce() -> PalletName,
@@ -948,6 +1295,12 @@ impl<T: Config> CheckAccountCallPermissions<T::AccountId> for Module<T> {
             KeyRecord::PrimaryKey(did) => Some(data(did, None)),
             // Secondary Key. Ensure DID isn't frozen + key has sufficient permissions.
             KeyRecord::SecondaryKey(did, permissions) if !Self::is_did_frozen(&did) => {
+                // Secondary keys can only be used within their validity window.
+                if let Some(limits) = SecondaryKeysLimits::<T>::get(who) {
+                    if !limits.is_valid_at(&<pallet_timestamp::Pallet<T>>::get()) {
+                        return None;
+                    }
+                }
                 let sk = SecondaryKey {
                     key: who.clone(),
                     permissions,
@@ -959,4 +1312,21 @@ impl<T: Config> CheckAccountCallPermissions<T::AccountId> for Module<T> {
             _ => None,
         }
     }
+
+    // Session keys are charged one call, and rejected once they have no calls left.
+    fn note_extrinsic_call(who: &T::AccountId) -> bool {
+        match SessionKeys::<T>::get(who) {
+            Some(session) if session.remaining_calls == 0 => false,
+            Some(mut session) => {
+                session.remaining_calls -= 1;
+                SessionKeys::<T>::insert(who, session);
+                true
+            }
+            None => true,
+        }
+    }
+
+    fn has_extrinsic_calls_left(who: &T::AccountId) -> bool {
+        SessionKeys::<T>::get(who).map_or(true, |session| session.remaining_calls > 0)
+    }
 }
@@ -74,6 +74,17 @@
 //! - `add_authorization` - Adds an authorization.
 //! - `remove_authorization` - Removes an authorization.
 //! - `add_secondary_keys_with_authorization` - Adds secondary keys to target identity `id`.
+//! - `set_recovery_config` - Sets the guardians, threshold and delay of the caller's social recovery.
+//! - `remove_recovery_config` - Removes the caller's social recovery configuration.
+//! - `approve_recovery` - Approves, as a guardian, rotating the primary key of an identity.
+//! - `cancel_recovery` - Cancels the ongoing recovery of the caller's identity.
+//! - `execute_recovery` - Takes over an identity once its recovery is approved and its delay elapsed.
+//! - `add_session_key` - Links a short-lived, call limited secondary key to the caller's identity.
+//! - `set_cdd_lifecycle_config` - Sets the grace and notice periods of CDD claims.
+//! - `request_cdd_renewal` - Requests a CDD provider to renew the CDD of the caller's identity.
+//! - `renew_cdd` - Renews the CDD of an identity that requested it.
+//! - `set_parent_permissions` - Sets the extrinsics the ancestors of a child identity can call on its behalf.
+//! - `call_as_child` - Calls an extrinsic on behalf of a descendant child identity.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![recursion_limit = "256"]
@@ -84,21 +95,30 @@ pub mod benchmarking;
 mod auth;
 mod claims;
 mod keys;
+mod recovery;
 pub mod types;
 
 pub use polymesh_common_utilities::traits::identity::WeightInfo;
-pub use types::{Claim1stKey, Claim2ndKey, DidStatus, PermissionedCallOriginData, RpcDidRecords};
+pub use types::{
+    Claim1stKey, Claim2ndKey, DidStatus, PermissionedCallOriginData, RpcAuthorization,
+    RpcDidRecords,
+};
 
 use core::convert::From;
 
 use codec::{Decode, Encode};
 use frame_system::ensure_root;
-use sp_runtime::traits::Hash;
+use sp_core::H512;
+use sp_runtime::traits::Hash as _;
 use sp_std::convert::TryFrom;
 use sp_std::prelude::*;
 
 use frame_support::dispatch::DispatchClass::{Normal, Operational};
-use frame_support::dispatch::{DispatchResult, Pays, Weight};
+use frame_support::dispatch::{
+    extract_actual_weight, DispatchResult, DispatchResultWithPostInfo, GetDispatchInfo, Pays,
+    Weight,
+};
+use frame_support::storage::StoragePrefixedMap;
 use frame_support::traits::{ChangeMembers, Currency, EnsureOrigin, Get, InitializeMembers};
 use frame_support::{decl_error, decl_module, decl_storage};
 use polymesh_common_utilities::constants::did::SECURITY_TOKEN;
@@ -109,14 +129,16 @@ use polymesh_common_utilities::traits::identity::{
 };
 use polymesh_common_utilities::{SystematicIssuers, GC_DID};
 use polymesh_primitives::{
-    storage_migrate_on, storage_migration_ver, Authorization, AuthorizationData, AuthorizationType,
-    CddId, Claim, ClaimType, CustomClaimTypeId, DidRecord, IdentityClaim, IdentityId, KeyRecord,
-    Permissions, Scope, SecondaryKey, Signatory, Ticker,
+    storage_migrate_on, storage_migration_ver, ActiveRecovery, Authorization, AuthorizationData,
+    AuthorizationType, Balance, CddId, CddLifecycleConfig, Claim, ClaimCommitment, ClaimCredential,
+    ClaimSalt, ClaimType, CustomClaimTypeId, DidRecord, ExtrinsicPermissions, Hash, IdentityClaim,
+    IdentityExport, IdentityId, KeyRecord, KeySpending, Permissions, RecoveryConfig, Scope,
+    SecondaryKey, SecondaryKeyLimits, SessionKey, Signatory, Ticker,
 };
 
 pub type Event<T> = polymesh_common_utilities::traits::identity::Event<T>;
 
-storage_migration_ver!(3);
+storage_migration_ver!(6);
 
 decl_storage! {
     trait Store for Module<T: Config> as Identity {
@@ -185,6 +207,90 @@ decl_storage! {
         /// Parent identity if the DID is a child Identity.
         pub ParentDid get(fn parent_did):
             map hasher(identity) IdentityId => Option<IdentityId>;
+
+        /// (Parent DID, Child DID) -> true for all child identities.
+        pub ChildDids get(fn child_dids):
+            double_map hasher(identity) IdentityId, hasher(identity) IdentityId => bool;
+
+        /// Child DID -> the extrinsics its ancestors can call on its behalf.
+        pub ParentPermissions get(fn parent_permissions):
+            map hasher(identity) IdentityId => Option<ExtrinsicPermissions>;
+
+        /// Claims disclosed by their target in the current block, keyed by their commitment.
+        /// It is cleared at the end of every block.
+        pub DisclosedClaims get(fn disclosed_claim):
+            double_map hasher(identity) IdentityId, hasher(blake2_128_concat) ClaimCommitment => Option<Claim>;
+
+        /// The keys of `DisclosedClaims` added in the current block, removed in `on_finalize`.
+        pub BlockDisclosures get(fn block_disclosures): Vec<(IdentityId, ClaimCommitment)>;
+
+        /// Accounts allowed to sign claim credentials on behalf of a claim issuer.
+        /// (issuer DID, signer) -> bool
+        pub CredentialSigners get(fn credential_signers):
+            double_map hasher(identity) IdentityId, hasher(blake2_128_concat) T::AccountId => bool;
+
+        /// Tracks imported claim credentials. (issuer DID, credential uid) -> imported
+        pub CredentialsUsed get(fn credentials_used):
+            double_map hasher(identity) IdentityId, hasher(twox_64_concat) u64 => bool;
+
+        /// Social recovery configuration of an identity.
+        pub RecoveryConfigs get(fn recovery_config):
+            map hasher(identity) IdentityId => Option<RecoveryConfig<T::AccountId, T::BlockNumber>>;
+
+        /// Ongoing social recovery of an identity.
+        pub ActiveRecoveries get(fn active_recovery):
+            map hasher(identity) IdentityId => Option<ActiveRecovery<T::AccountId, T::BlockNumber>>;
+
+        /// Secondary key -> validity window and spending limits of the key.
+        pub SecondaryKeysLimits get(fn secondary_key_limits):
+            map hasher(twox_64_concat) T::AccountId => Option<SecondaryKeyLimits<T::Moment>>;
+
+        /// (secondary key, asset or `None` for POLYX) -> amount spent in the current period.
+        pub KeySpendings get(fn key_spending):
+            double_map hasher(twox_64_concat) T::AccountId, hasher(blake2_128_concat) Option<Ticker> => KeySpending<T::Moment>;
+
+        /// Session key -> expiry and remaining calls of the key.
+        pub SessionKeys get(fn session_key):
+            map hasher(twox_64_concat) T::AccountId => Option<SessionKey<T::BlockNumber>>;
+
+        /// Block number -> session keys to unlink at the start of that block.
+        pub SessionKeyExpiries get(fn session_key_expiries):
+            map hasher(twox_64_concat) T::BlockNumber => Vec<T::AccountId>;
+
+        /// (expiry bucket, auth_id) -> target of an authorization expiring within that bucket.
+        pub AuthorizationExpiries: double_map hasher(twox_64_concat) u64,
+            hasher(twox_64_concat) u64 => Option<Signatory<T::AccountId>>;
+
+        /// The earliest bucket of `AuthorizationExpiries` that may hold authorizations.
+        pub NextAuthorizationExpiryBucket get(fn next_auth_expiry_bucket): Option<u64>;
+
+        /// The grace and notice periods of CDD claims.
+        pub CddLifecycle get(fn cdd_lifecycle): CddLifecycleConfig<T::Moment>;
+
+        /// DID -> when it requested the renewal of its CDD.
+        pub CddRenewalRequests get(fn cdd_renewal_request):
+            map hasher(identity) IdentityId => Option<T::Moment>;
+
+        /// (expiry bucket, DID) -> true for identities with a CDD claim expiring within that bucket.
+        pub CddExpiries: double_map hasher(twox_64_concat) u64, hasher(identity) IdentityId => bool;
+
+        /// The earliest bucket of `CddExpiries` that may hold expiries not yet past their grace period.
+        pub NextCddExpiryBucket get(fn next_cdd_expiry_bucket): Option<u64>;
+
+        /// (expiry bucket, DID) -> true for CDD expiries whose `CddExpiringSoon` event is pending.
+        pub CddExpiryNotices: double_map hasher(twox_64_concat) u64, hasher(identity) IdentityId => bool;
+
+        /// The earliest bucket of `CddExpiryNotices` that may hold pending notices.
+        pub NextCddNoticeBucket get(fn next_cdd_notice_bucket): Option<u64>;
+
+        /// Accounts trusted to attest the identity exports of another chain.
+        /// (source chain genesis hash, attester) -> trusted
+        pub ExportAttesters get(fn export_attester):
+            double_map hasher(twox_64_concat) Hash, hasher(blake2_128_concat) T::AccountId => bool;
+
+        /// Identities that imported an identity export. (source chain genesis hash, exported DID) -> DID
+        pub ImportedIdentityExports get(fn imported_identity_export):
+            double_map hasher(twox_64_concat) Hash, hasher(identity) IdentityId => Option<IdentityId>;
     }
     add_extra_genesis {
         // Identities at genesis.
@@ -250,9 +356,40 @@ decl_module! {
             storage_migrate_on!(StorageVersion, 3, {
                 migration::migrate_to_v3::<T>();
             });
+            storage_migrate_on!(StorageVersion, 4, {
+                migration::migrate_to_v4::<T>();
+            });
+            storage_migrate_on!(StorageVersion, 5, {
+                migration::migrate_to_v5::<T>();
+            });
+            storage_migrate_on!(StorageVersion, 6, {
+                migration::migrate_to_v6::<T>();
+            });
             Weight::zero()
         }
 
+        fn on_initialize(n: T::BlockNumber) -> Weight {
+            let expired = Self::expire_session_keys(n);
+            let pruned = Self::prune_expired_auths();
+            let notified = Self::notify_cdd_expiries();
+            let pruned_cdd = Self::prune_cdd_expiries();
+            // Accounts for taking `BlockDisclosures` in `on_finalize`,
+            // each disclosure pays for its own removal.
+            <T as frame_system::Config>::DbWeight::get()
+                .reads_writes(1, 1)
+                .saturating_add(<T as Config>::WeightInfo::expire_session_keys(expired))
+                .saturating_add(<T as Config>::WeightInfo::prune_expired_authorizations(pruned))
+                .saturating_add(<T as Config>::WeightInfo::notify_cdd_expiries(notified))
+                .saturating_add(<T as Config>::WeightInfo::prune_cdd_expiries(pruned_cdd))
+        }
+
+        fn on_finalize() {
+            // Disclosures are only valid for the block in which they were made.
+            for (did, commitment) in BlockDisclosures::take() {
+                DisclosedClaims::remove(did, commitment);
+            }
+        }
+
         const InitialPOLYX: <T::Balances as Currency<T::AccountId>>::Balance = T::InitialPOLYX::get();
 
         /// Register `target_account` with a new Identity.
@@ -263,7 +400,7 @@ decl_module! {
         /// - `target_account` (primary key of the new Identity) can be linked to just one and only
         /// one identity.
         /// - External secondary keys can be linked to just one identity.
-        #[weight = <T as Config>::WeightInfo::cdd_register_did(secondary_keys.len() as u32)]
+        #[weight = <T as Config>::WeightInfo::cdd_register_did(secondary_keys.len() as u32).saturating_add(T::ProtocolFee::charge_fee_weight())]
         pub fn cdd_register_did(
             origin,
             target_account: T::AccountId,
@@ -316,8 +453,42 @@ decl_module! {
             Self::deposit_event(RawEvent::CddRequirementForPrimaryKeyUpdated(auth_required));
         }
 
+        /// Sets the grace and notice periods of CDD claims.
+        /// Callable via root (governance)
+        ///
+        /// Identities keep a valid CDD for `grace_period` after their CDD claims expire, and
+        /// a `CddExpiringSoon` event is emitted `notice_period` before a CDD claim expires.
+        #[weight = (<T as Config>::WeightInfo::set_cdd_lifecycle_config(), Operational, Pays::Yes)]
+        pub fn set_cdd_lifecycle_config(origin, config: CddLifecycleConfig<T::Moment>) {
+            ensure_root(origin)?;
+            CddLifecycle::<T>::put(&config);
+            Self::deposit_event(RawEvent::CddLifecycleConfigSet(config));
+        }
+
+        /// Requests a CDD provider to renew the CDD of the caller's identity.
+        ///
+        /// # Errors
+        /// - `CddRenewalAlreadyRequested` if the identity already has a pending renewal request.
+        #[weight = <T as Config>::WeightInfo::request_cdd_renewal()]
+        pub fn request_cdd_renewal(origin) {
+            Self::base_request_cdd_renewal(origin)?;
+        }
+
+        /// Renews the CDD of `target`, as requested with `request_cdd_renewal`.
+        ///
+        /// The most recent CDD claim of `target` is issued again by the caller with `expiry`.
+        ///
+        /// # Errors
+        /// - `UnAuthorizedCddProvider` if the caller is not a CDD provider.
+        /// - `NoCddRenewalRequest` if `target` didn't request a renewal.
+        /// - `NoCddClaimToRenew` if `target` has no CDD claim.
+        #[weight = <T as Config>::WeightInfo::renew_cdd()]
+        pub fn renew_cdd(origin, target: IdentityId, expiry: Option<T::Moment>) {
+            Self::base_renew_cdd(origin, target, expiry)?;
+        }
+
         /// Join an identity as a secondary key.
-        #[weight = <T as Config>::WeightInfo::join_identity_as_key()]
+        #[weight = <T as Config>::WeightInfo::join_identity_as_key().saturating_add(T::ProtocolFee::charge_fee_weight())]
         pub fn join_identity_as_key(origin, auth_id: u64) -> DispatchResult {
             Self::join_identity(origin, auth_id)
         }
@@ -331,7 +502,7 @@ decl_module! {
         /// Adds a new claim record or edits an existing one.
         ///
         /// Only called by did_issuer's secondary key.
-        #[weight = <T as Config>::WeightInfo::add_claim()]
+        #[weight = <T as Config>::WeightInfo::add_claim().saturating_add(T::ProtocolFee::charge_fee_weight())]
         pub fn add_claim(
             origin,
             target: IdentityId,
@@ -344,6 +515,7 @@ decl_module! {
                 Claim::CustomerDueDiligence(..) => Self::base_add_cdd_claim(target, claim, issuer, expiry),
                 _ => {
                     Self::ensure_custom_scopes_limited(&claim)?;
+                    Self::ensure_valid_claim_commitment(&claim)?;
                     T::ProtocolFee::charge_fee(ProtocolOp::IdentityAddClaim)?;
                     Self::base_add_claim(target, claim, issuer, expiry)
                 }
@@ -376,6 +548,9 @@ decl_module! {
 
         // Manage generic authorizations
         /// Adds an authorization.
+        ///
+        /// # Errors
+        /// - `AuthorizationExpired` if `expiry` isn't in the future.
         #[weight = <T as Config>::WeightInfo::add_authorization_full::<T::AccountId>(&data)]
         pub fn add_authorization(
             origin,
@@ -399,7 +574,7 @@ decl_module! {
         }
 
         /// Assuming this is executed by the GC voting majority, adds a new cdd claim record.
-        #[weight = (<T as Config>::WeightInfo::add_claim(), Operational, Pays::Yes)]
+        #[weight = (<T as Config>::WeightInfo::add_claim().saturating_add(T::ProtocolFee::charge_fee_weight()), Operational, Pays::Yes)]
         pub fn gc_add_cdd_claim(
             origin,
             target: IdentityId,
@@ -409,7 +584,7 @@ decl_module! {
         }
 
         /// Assuming this is executed by the GC voting majority, removes an existing cdd claim record.
-        #[weight = (<T as Config>::WeightInfo::add_claim(), Operational, Pays::Yes)]
+        #[weight = (<T as Config>::WeightInfo::add_claim().saturating_add(T::ProtocolFee::charge_fee_weight()), Operational, Pays::Yes)]
         pub fn gc_revoke_cdd_claim(origin, target: IdentityId) -> DispatchResult {
             T::GCVotingMajorityOrigin::ensure_origin(origin)?;
             Self::base_revoke_claim(target, ClaimType::CustomerDueDiligence, GC_DID, None)
@@ -457,7 +632,7 @@ decl_module! {
         /// # Errors
         ///     - Can only called by primary key owner.
         ///     - Keys should be able to linked to any identity.
-        #[weight = <T as Config>::WeightInfo::add_secondary_keys_full::<T::AccountId>(&additional_keys)]
+        #[weight = <T as Config>::WeightInfo::add_secondary_keys_full::<T::AccountId>(&additional_keys).saturating_add(T::ProtocolFee::charge_fee_weight())]
         pub fn add_secondary_keys_with_authorization(
             origin,
             additional_keys: Vec<SecondaryKeyWithAuth<T::AccountId>>,
@@ -474,6 +649,61 @@ decl_module! {
             Self::base_set_secondary_key_permissions(origin, key, perms)?;
         }
 
+        /// Sets, or removes with `None`, the validity window and spending limits of a secondary key.
+        ///
+        /// Outside its validity window the key can't call any extrinsic. Spending limits cap the
+        /// POLYX it transfers and the amount of each asset it sends through settlements per period.
+        /// Changing the limits resets the amounts already spent.
+        ///
+        /// Only the primary key of an identity is able to set secondary key limits.
+        ///
+        /// # Errors
+        /// - `KeyNotAllowed` if the caller is not the primary key.
+        /// - `NotASigner` if `key` is not a secondary key of the caller's identity.
+        /// - `InvalidSecondaryKeyLimits` if the window ends before it starts or an asset is repeated.
+        #[weight = <T as Config>::WeightInfo::set_secondary_key_limits(
+            limits.as_ref().map_or(0, |l| l.asset_limits.len() as u32)
+        )]
+        pub fn set_secondary_key_limits(
+            origin,
+            key: T::AccountId,
+            limits: Option<SecondaryKeyLimits<T::Moment>>
+        ) {
+            Self::base_set_secondary_key_limits(origin, key, limits)?;
+        }
+
+        /// Links `key` to the caller's identity as a session key, without a `JoinIdentity`
+        /// authorization.
+        ///
+        /// A session key is a secondary key that can only call the extrinsics in `permissions`,
+        /// at most `max_calls` times. It is unlinked at the start of block `expires_at`, and
+        /// can be unlinked before that with `remove_secondary_keys`.
+        ///
+        /// Only the primary key of an identity is able to add session keys.
+        ///
+        /// # Arguments
+        /// - `auth_signature` the signature of `key` over the SCALE encoded `TargetIdAuthorization`
+        /// of the caller's identity, with `expires_at` as its expiry block.
+        ///
+        /// # Errors
+        /// - `KeyNotAllowed` if the caller is not the primary key.
+        /// - `AlreadyLinked` if `key` is already linked to an identity or a multisig.
+        /// - `InvalidSessionKey` if `expires_at` isn't in the future or is more than
+        /// `MaxSessionKeyLifetime` blocks away, or if `max_calls` is zero.
+        /// - `InvalidAuthorizationSignature` if `auth_signature` is not a valid signature of `key`.
+        /// - `TooManySessionKeysExpiring` if too many session keys already expire at `expires_at`.
+        #[weight = <T as Config>::WeightInfo::add_session_key_full(&permissions)]
+        pub fn add_session_key(
+            origin,
+            key: T::AccountId,
+            permissions: ExtrinsicPermissions,
+            expires_at: T::BlockNumber,
+            max_calls: u32,
+            auth_signature: H512
+        ) {
+            Self::base_add_session_key(origin, key, permissions, expires_at, max_calls, auth_signature)?;
+        }
+
         /// Removes specified secondary keys of a DID if present.
         ///
         /// # Errors
@@ -503,7 +733,7 @@ decl_module! {
         /// - `target_account` (primary key of the new Identity) can be linked to just one and only
         /// one identity.
         /// - External secondary keys can be linked to just one identity.
-        #[weight = <T as Config>::WeightInfo::cdd_register_did(secondary_keys.len() as u32).saturating_add(<T as Config>::WeightInfo::add_claim())]
+        #[weight = <T as Config>::WeightInfo::cdd_register_did(secondary_keys.len() as u32).saturating_add(<T as Config>::WeightInfo::add_claim()).saturating_add(T::ProtocolFee::charge_fee_weight().saturating_mul(2))]
         pub fn cdd_register_did_with_cdd(
             origin,
             target_account: T::AccountId,
@@ -526,7 +756,7 @@ decl_module! {
         /// - `KeyNotAllowed` only the primary key can create a new identity.
         /// - `NotASigner` the `secondary_key` is not a secondary key of the caller's identity.
         /// - `AccountKeyIsBeingUsed` the `secondary_key` can't be unlinked from it's current identity.
-        /// - `IsChildIdentity` the caller's identity is already a child identity and can't create child identities.
+        /// - `ChildIdentityTooDeep` the new identity would have more than `MAX_CHILD_IDENTITY_DEPTH` ancestors.
         #[weight = <T as Config>::WeightInfo::create_child_identity()]
         pub fn create_child_identity(origin, secondary_key: T::AccountId) {
             Self::base_create_child_identity(origin, secondary_key)?;
@@ -546,7 +776,7 @@ decl_module! {
         /// - `KeyNotAllowed` only the primary key can create a new identity.
         /// - `AlreadyLinked` one of the keys is already linked to an identity.
         /// - `DuplicateKey` one of the keys is included multiple times.
-        /// - `IsChildIdentity` the caller's identity is already a child identity and can't create child identities.
+        /// - `ChildIdentityTooDeep` the new identities would have more than `MAX_CHILD_IDENTITY_DEPTH` ancestors.
         #[weight = <T as Config>::WeightInfo::create_child_identities(child_keys.len() as u32)]
         pub fn create_child_identities(
             origin,
@@ -571,6 +801,242 @@ decl_module! {
         pub fn unlink_child_identity(origin, child_did: IdentityId) {
             Self::base_unlink_child_identity(origin, child_did)?;
         }
+
+        /// Sets the extrinsics that the ancestors of the caller's child identity can call on its behalf.
+        ///
+        /// Only the primary key of the child identity can set these permissions.
+        /// They are removed when the child identity is unlinked from its parent.
+        ///
+        /// # Arguments
+        /// - `permissions` the callable extrinsics, or `None` to revoke them.
+        ///
+        /// # Errors
+        /// - `KeyNotAllowed` only the primary key of the child identity can set its parent permissions.
+        /// - `NoParentIdentity` the caller's identity doesn't have a parent identity.
+        /// - `ExceptNotAllowedForExtrinsics` `permissions` uses `Except`.
+        #[weight = <T as Config>::WeightInfo::set_parent_permissions_full(&permissions)]
+        pub fn set_parent_permissions(origin, permissions: Option<ExtrinsicPermissions>) {
+            Self::base_set_parent_permissions(origin, permissions)?;
+        }
+
+        /// Calls `call` on behalf of `child_did`, as the primary key of that child identity.
+        ///
+        /// The caller's identity has to be an ancestor of `child_did`, and `call` has to be
+        /// allowed both by the permissions `child_did` set with `set_parent_permissions` and by
+        /// the permissions of the calling key.
+        ///
+        /// # Arguments
+        /// - `child_did` the descendant child identity to act for.
+        /// - `call` the call to dispatch.
+        ///
+        /// # Errors
+        /// - `NotAnAncestorIdentity` the caller's identity isn't an ancestor of `child_did`.
+        /// - `CallNotPermittedForAncestors` `child_did` doesn't allow its ancestors to call `call`.
+        /// - `UnauthorizedCaller` the calling key isn't permitted to call `call` itself.
+        /// - `CallNotPermittedForKey` the calling key is restricted to some assets or portfolios.
+        #[weight = {
+            let dispatch_info = call.get_dispatch_info();
+            (
+                <T as Config>::WeightInfo::call_as_child().saturating_add(dispatch_info.weight),
+                dispatch_info.class,
+            )
+        }]
+        pub fn call_as_child(
+            origin,
+            child_did: IdentityId,
+            call: Box<T::Proposal>
+        ) -> DispatchResultWithPostInfo {
+            Self::base_call_as_child(origin, child_did, *call)
+        }
+
+        /// Discloses, for the current block, the claim behind a commitment claim of the caller's identity.
+        ///
+        /// Compliance rules evaluated in the same block will see the disclosed `claim`
+        /// as if `issuer` had issued it in plaintext. Only the commitment is emitted in the event.
+        ///
+        /// # Arguments
+        /// - `issuer` the issuer of the commitment claim.
+        /// - `claim` the committed claim.
+        /// - `salt` the salt used to build the commitment.
+        ///
+        /// # Errors
+        /// - `InvalidClaimCommitment` if `claim` is a CDD or a commitment claim.
+        /// - `ClaimDoesNotExist` if `issuer` has not issued an unexpired commitment claim
+        ///   with the type and scope of `claim`.
+        /// - `ClaimCommitmentMismatch` if `claim` and `salt` don't match the commitment.
+        ///
+        /// The weight includes removing the disclosure in `on_finalize`.
+        #[weight = <T as Config>::WeightInfo::disclose_claim()
+            .saturating_add(<T as frame_system::Config>::DbWeight::get().writes(1))]
+        pub fn disclose_claim(origin, issuer: IdentityId, claim: Claim, salt: ClaimSalt) {
+            Self::base_disclose_claim(origin, issuer, claim, salt)?;
+        }
+
+        /// Adds or removes accounts allowed to sign claim credentials on behalf of the caller's identity.
+        ///
+        /// # Arguments
+        /// - `signers` the accounts to add or remove.
+        /// - `add_signers` whether `signers` are added or removed.
+        ///
+        /// # Errors
+        /// - `CredentialSignerAlreadyExists` if adding a signer that was already added.
+        /// - `CredentialSignerDoesNotExist` if removing a signer that was never added.
+        #[weight = <T as Config>::WeightInfo::update_credential_signers(signers.len() as u32)]
+        pub fn update_credential_signers(origin, signers: Vec<T::AccountId>, add_signers: bool) {
+            Self::base_update_credential_signers(origin, signers, add_signers)?;
+        }
+
+        /// Imports a claim credential signed off-chain by one of its issuer's credential signers.
+        ///
+        /// The claim is recorded as if `credential.issuer` had called `add_claim`.
+        ///
+        /// # Arguments
+        /// - `credential` the claim credential.
+        /// - `signer` the account that signed `credential`.
+        /// - `signature` the signature of `signer` over the SCALE encoded `(genesis hash, credential)`.
+        ///
+        /// # Errors
+        /// - `UnauthorizedCredentialSigner` if `signer` is not a credential signer of the issuer.
+        /// - `CredentialAlreadyImported` if a credential with the same issuer and uid was already imported.
+        /// - `CredentialExpired` if `credential.valid_until` has passed.
+        /// - `InvalidCredentialSignature` if `signature` is not a valid signature of `signer` over `credential`.
+        /// - `DidMustAlreadyExist` if the target identity doesn't exist.
+        #[weight = <T as Config>::WeightInfo::import_claim_credential()]
+        pub fn import_claim_credential(
+            origin,
+            credential: ClaimCredential<T::Moment>,
+            signer: T::AccountId,
+            signature: T::OffChainSignature
+        ) {
+            Self::base_import_claim_credential(origin, credential, signer, signature)?;
+        }
+
+        /// Sets the social recovery configuration of the caller's identity.
+        ///
+        /// Once `threshold` of the `guardians` approve the same new primary key, that key can
+        /// take over the identity after `delay` blocks, unless the current primary key cancels it.
+        /// Any ongoing recovery is cancelled.
+        ///
+        /// # Arguments
+        /// - `guardians` the identities or keys allowed to approve a recovery.
+        /// - `threshold` the number of approvals needed.
+        /// - `delay` the number of blocks the current primary key has to cancel an approved recovery.
+        ///
+        /// # Errors
+        /// - `KeyNotAllowed` if the caller is not the primary key.
+        /// - `TooManyRecoveryGuardians` if there are more than `MAX_RECOVERY_GUARDIANS` guardians.
+        /// - `InvalidRecoveryConfig` if the guardians are repeated or include the caller's identity,
+        /// or if `threshold` is zero or greater than the number of guardians.
+        #[weight = <T as Config>::WeightInfo::set_recovery_config(guardians.len() as u32)]
+        pub fn set_recovery_config(
+            origin,
+            guardians: Vec<Signatory<T::AccountId>>,
+            threshold: u32,
+            delay: T::BlockNumber
+        ) {
+            Self::base_set_recovery_config(origin, guardians, threshold, delay)?;
+        }
+
+        /// Removes the social recovery configuration of the caller's identity.
+        /// Any ongoing recovery is cancelled.
+        ///
+        /// # Errors
+        /// - `KeyNotAllowed` if the caller is not the primary key.
+        /// - `NoRecoveryConfig` if the identity has no recovery configuration.
+        #[weight = <T as Config>::WeightInfo::remove_recovery_config()]
+        pub fn remove_recovery_config(origin) {
+            Self::base_remove_recovery_config(origin)?;
+        }
+
+        /// Approves, as a recovery guardian of `target_did`, rotating its primary key to `new_primary_key`.
+        ///
+        /// Approvals are counted per key and each guardian approves a single key, replacing
+        /// its previous approval if any. The first key approved by `threshold` guardians
+        /// is the only one that can execute the recovery.
+        ///
+        /// # Errors
+        /// - `NoRecoveryConfig` if `target_did` has no recovery configuration.
+        /// - `NotRecoveryGuardian` if the caller is not a guardian of `target_did`.
+        /// - `AlreadyLinked` if `new_primary_key` is already linked.
+        /// - `RecoveryKeyMismatch` if another key has already reached the threshold.
+        /// - `RecoveryAlreadyApproved` if the guardian has already approved `new_primary_key`.
+        #[weight = <T as Config>::WeightInfo::approve_recovery()]
+        pub fn approve_recovery(origin, target_did: IdentityId, new_primary_key: T::AccountId) {
+            Self::base_approve_recovery(origin, target_did, new_primary_key)?;
+        }
+
+        /// Cancels the ongoing recovery of the caller's identity.
+        ///
+        /// # Errors
+        /// - `KeyNotAllowed` if the caller is not the primary key.
+        /// - `NoActiveRecovery` if there is no ongoing recovery.
+        #[weight = <T as Config>::WeightInfo::cancel_recovery()]
+        pub fn cancel_recovery(origin) {
+            Self::base_cancel_recovery(origin)?;
+        }
+
+        /// Makes the caller the primary key of `target_did` once its recovery was approved
+        /// and the delay has elapsed. The old primary key is unlinked.
+        ///
+        /// # Errors
+        /// - `NoActiveRecovery` if there is no ongoing recovery.
+        /// - `RecoveryKeyMismatch` if the caller is not the approved key.
+        /// - `RecoveryNotExecutable` if the threshold hasn't been reached or the delay hasn't elapsed.
+        #[weight = <T as Config>::WeightInfo::execute_recovery()]
+        pub fn execute_recovery(origin, target_did: IdentityId) {
+            Self::base_execute_recovery(origin, target_did)?;
+        }
+
+        /// Re-issues, as the calling CDD provider, the claims of an identity exported from another chain.
+        ///
+        /// `export` is obtained with the `identity_exportIdentity` RPC on the source chain, and signed by
+        /// its primary key, which has to be the primary key of `target` on this chain.
+        /// It also has to be signed by an attester of the source chain, which vouches for its content.
+        /// Claims that expired since the export and custom claims, whose types are local to
+        /// the source chain, are skipped.
+        ///
+        /// # Arguments
+        /// - `target` the identity receiving the claims.
+        /// - `export` the exported identity.
+        /// - `signature` the signature of the exported primary key over the SCALE encoded `export`.
+        /// - `attester` an account trusted to attest the exports of `export.source_chain`.
+        /// - `attestation` the signature of `attester` over the SCALE encoded `export`.
+        ///
+        /// # Errors
+        /// - `UnAuthorizedCddProvider` if the caller is not a CDD provider.
+        /// - `UnsupportedIdentityExportVersion` if `export` uses an unknown format version.
+        /// - `TooManyExportedClaims` if `export` has more than `MAX_EXPORTED_CLAIMS` claims.
+        /// - `UnknownExportSourceChain` if `export` comes from this chain or `attester` is not trusted for its source chain.
+        /// - `InvalidIdentityExportSignature` if `signature` or `attestation` is not a valid signature of `export`.
+        /// - `DidMustAlreadyExist` if the target identity doesn't exist.
+        /// - `IdentityExportKeyMismatch` if the exported primary key is not the primary key of `target`.
+        /// - `IdentityExportDidMismatch` if the exported identity was already imported into another identity.
+        #[weight = <T as Config>::WeightInfo::import_identity_export(export.claims.len() as u32)]
+        pub fn import_identity_export(
+            origin,
+            target: IdentityId,
+            export: IdentityExport<T::AccountId, T::Moment>,
+            signature: T::OffChainSignature,
+            attester: T::AccountId,
+            attestation: T::OffChainSignature
+        ) {
+            Self::base_import_identity_export(origin, target, export, signature, attester, attestation)?;
+        }
+
+        /// Trusts, or no longer trusts, `attester` to attest the identity exports of `source_chain`.
+        ///
+        /// # Arguments
+        /// - `source_chain` the genesis hash of the chain the exports come from.
+        /// - `attester` the account signing the attestations.
+        /// - `trusted` whether `attester` is trusted.
+        ///
+        /// # Errors
+        /// - `BadOrigin` if the caller is not root.
+        /// - `UnknownExportSourceChain` if `source_chain` is this chain.
+        #[weight = (<T as Config>::WeightInfo::set_export_attester(), Operational, Pays::Yes)]
+        pub fn set_export_attester(origin, source_chain: Hash, attester: T::AccountId, trusted: bool) {
+            Self::base_set_export_attester(origin, source_chain, attester, trusted)?;
+        }
     }
 }
 
@@ -643,6 +1109,72 @@ decl_error! {
         DuplicateKey,
         /// Cannot use Except when specifying extrinsic permissions.
         ExceptNotAllowedForExtrinsics,
+        /// CDD claims can't be committed and only plaintext claims can be disclosed.
+        InvalidClaimCommitment,
+        /// The disclosed claim and salt don't match the commitment.
+        ClaimCommitmentMismatch,
+        /// The credential signer has already been added.
+        CredentialSignerAlreadyExists,
+        /// The credential signer has not been added.
+        CredentialSignerDoesNotExist,
+        /// The signer is not a credential signer of the issuer.
+        UnauthorizedCredentialSigner,
+        /// The credential has already been imported.
+        CredentialAlreadyImported,
+        /// The credential signature is invalid.
+        InvalidCredentialSignature,
+        /// The claim credential can no longer be imported.
+        CredentialExpired,
+        /// Too many recovery guardians.
+        TooManyRecoveryGuardians,
+        /// The recovery guardians or threshold are invalid.
+        InvalidRecoveryConfig,
+        /// The identity has no recovery configuration.
+        NoRecoveryConfig,
+        /// The caller is not a recovery guardian of the identity.
+        NotRecoveryGuardian,
+        /// The ongoing recovery is for a different key.
+        RecoveryKeyMismatch,
+        /// The guardian has already approved the recovery.
+        RecoveryAlreadyApproved,
+        /// The identity has no ongoing recovery.
+        NoActiveRecovery,
+        /// The recovery hasn't been approved or its delay hasn't elapsed.
+        RecoveryNotExecutable,
+        /// The secondary key validity window or asset limits are invalid.
+        InvalidSecondaryKeyLimits,
+        /// The secondary key would exceed its spending limit for the current period.
+        SecondaryKeySpendLimitExceeded,
+        /// The session key expiry isn't in the future or its maximum number of calls is zero.
+        InvalidSessionKey,
+        /// Too many session keys expire in the same block.
+        TooManySessionKeysExpiring,
+        /// The identity already has a pending CDD renewal request.
+        CddRenewalAlreadyRequested,
+        /// The identity didn't request the renewal of its CDD.
+        NoCddRenewalRequest,
+        /// The identity has no CDD claim to renew.
+        NoCddClaimToRenew,
+        /// The child identity would have more than `MAX_CHILD_IDENTITY_DEPTH` ancestors.
+        ChildIdentityTooDeep,
+        /// The caller's identity is not an ancestor of the child identity.
+        NotAnAncestorIdentity,
+        /// The child identity doesn't allow its ancestors to make this call on its behalf.
+        CallNotPermittedForAncestors,
+        /// The calling key is restricted to some assets or portfolios and can't act for a child identity.
+        CallNotPermittedForKey,
+        /// The identity export uses an unsupported format version.
+        UnsupportedIdentityExportVersion,
+        /// The identity export has too many claims.
+        TooManyExportedClaims,
+        /// The identity export signature is invalid.
+        InvalidIdentityExportSignature,
+        /// The exported primary key is not the primary key of the target identity.
+        IdentityExportKeyMismatch,
+        /// The identity export comes from this chain or from a chain the attester is not trusted for.
+        UnknownExportSourceChain,
+        /// The exported identity was already imported into another identity.
+        IdentityExportDidMismatch,
     }
 }
 
@@ -730,6 +1262,15 @@ impl<T: Config> IdentityFnTrait<T::AccountId> for Module<T> {
     fn has_valid_cdd(target_did: IdentityId) -> bool {
         Self::has_valid_cdd(target_did)
     }
+
+    /// Charges `amount` to the spending limit of `key`.
+    fn charge_key_spending(
+        key: &T::AccountId,
+        ticker: Option<Ticker>,
+        amount: Balance,
+    ) -> DispatchResult {
+        Self::charge_secondary_key_spending(key, ticker, amount).map(|_| ())
+    }
 }
 
 /// Used by the CDD Providers group
@@ -766,6 +1307,7 @@ fn revoke_claim_class(claim_type: ClaimType) -> frame_support::dispatch::Dispatc
 
 pub mod migration {
     use super::*;
+    use frame_support::{IterableStorageDoubleMap, IterableStorageMap};
     use sp_runtime::runtime_logger::RuntimeLogger;
 
     mod v2 {
@@ -874,6 +1416,46 @@ pub mod migration {
         log::info!(" >>> All Claims have been migrated.");
     }
 
+    pub fn migrate_to_v4<T: Config>() {
+        RuntimeLogger::init();
+        log::info!(" >>> Queueing expiring Identity.Authorizations.");
+        let mut queued = 0;
+        for (target, auth_id, auth) in Authorizations::<T>::iter() {
+            if let Some(expiry) = auth.expiry {
+                Module::<T>::queue_auth_expiry(target, auth_id, expiry);
+                queued += 1;
+            }
+        }
+        log::info!("> Queued {queued} expiring authorizations.");
+    }
+
+    pub fn migrate_to_v5<T: Config>() {
+        RuntimeLogger::init();
+        log::info!(" >>> Queueing expiring CDD claims.");
+        let mut queued = 0;
+        for (key1, _, id_claim) in Claims::iter() {
+            if key1.claim_type != ClaimType::CustomerDueDiligence {
+                continue;
+            }
+            if let Some(expiry) = id_claim.expiry {
+                Module::<T>::queue_cdd_expiry(key1.target, expiry);
+                queued += 1;
+            }
+        }
+        log::info!("> Queued {queued} expiring CDD claims.");
+    }
+
+    pub fn migrate_to_v6<T: Config>() {
+        RuntimeLogger::init();
+        log::info!(" >>> Indexing Identity.ParentDid by parent.");
+        let mut linked = 0;
+        for (child_did, parent_did) in ParentDid::iter() {
+            ChildDids::insert(parent_did, child_did, true);
+            linked += 1;
+        }
+        log::info!("> Indexed {linked} child identities.");
+    }
+
     fn migrate_claims<T: Config>() {
         let mut same = 0;
         let mut converted = 0;
@@ -0,0 +1,190 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymesh Association
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{ActiveRecoveries, Config, Error, Module, RawEvent, RecoveryConfigs};
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use frame_support::{ensure, StorageMap as _};
+use frame_system::ensure_signed;
+use polymesh_primitives::identity::limits::MAX_RECOVERY_GUARDIANS;
+use polymesh_primitives::{ActiveRecovery, IdentityId, RecoveryConfig, Signatory};
+use sp_runtime::traits::Saturating;
+use sp_std::collections::btree_set::BTreeSet;
+use sp_std::prelude::*;
+
+impl<T: Config> Module<T> {
+    /// Sets the recovery configuration of the caller's identity, cancelling any ongoing recovery.
+    pub(crate) fn base_set_recovery_config(
+        origin: T::RuntimeOrigin,
+        guardians: Vec<Signatory<T::AccountId>>,
+        threshold: u32,
+        delay: T::BlockNumber,
+    ) -> DispatchResult {
+        let (_, did) = Self::ensure_primary_key(origin)?;
+
+        ensure!(
+            guardians.len() <= MAX_RECOVERY_GUARDIANS as usize,
+            Error::<T>::TooManyRecoveryGuardians
+        );
+        let unique_guardians = guardians.iter().collect::<BTreeSet<_>>();
+        ensure!(
+            unique_guardians.len() == guardians.len(),
+            Error::<T>::InvalidRecoveryConfig
+        );
+        ensure!(
+            !guardians.contains(&Signatory::Identity(did)),
+            Error::<T>::InvalidRecoveryConfig
+        );
+        ensure!(
+            threshold > 0 && threshold as usize <= guardians.len(),
+            Error::<T>::InvalidRecoveryConfig
+        );
+
+        Self::cancel_active_recovery(did);
+        RecoveryConfigs::<T>::insert(
+            did,
+            RecoveryConfig {
+                guardians: guardians.clone(),
+                threshold,
+                delay,
+            },
+        );
+        Self::deposit_event(RawEvent::RecoveryConfigSet(did, guardians, threshold));
+        Ok(())
+    }
+
+    /// Removes the recovery configuration of the caller's identity, cancelling any ongoing recovery.
+    pub(crate) fn base_remove_recovery_config(origin: T::RuntimeOrigin) -> DispatchResult {
+        let (_, did) = Self::ensure_primary_key(origin)?;
+        ensure!(
+            RecoveryConfigs::<T>::contains_key(did),
+            Error::<T>::NoRecoveryConfig
+        );
+
+        Self::cancel_active_recovery(did);
+        RecoveryConfigs::<T>::remove(did);
+        Self::deposit_event(RawEvent::RecoveryConfigRemoved(did));
+        Ok(())
+    }
+
+    /// Records the approval of a guardian for rotating the primary key of `target_did` to `new_primary_key`.
+    pub(crate) fn base_approve_recovery(
+        origin: T::RuntimeOrigin,
+        target_did: IdentityId,
+        new_primary_key: T::AccountId,
+    ) -> DispatchResult {
+        let config = RecoveryConfigs::<T>::get(target_did).ok_or(Error::<T>::NoRecoveryConfig)?;
+        let guardian = Self::ensure_recovery_guardian(origin, &config)?;
+        Self::ensure_key_did_unlinked(&new_primary_key)?;
+
+        let mut recovery = ActiveRecoveries::<T>::get(target_did).unwrap_or(ActiveRecovery {
+            approvals: Vec::new(),
+            approved: None,
+        });
+        // Once a key reaches the threshold, approvals for other keys can't displace it.
+        ensure!(
+            recovery
+                .approved
+                .as_ref()
+                .map_or(true, |(key, _)| *key == new_primary_key),
+            Error::<T>::RecoveryKeyMismatch
+        );
+        match recovery.approvals.iter_mut().find(|(g, _)| *g == guardian) {
+            Some((_, key)) => {
+                ensure!(*key != new_primary_key, Error::<T>::RecoveryAlreadyApproved);
+                *key = new_primary_key.clone();
+            }
+            None => recovery
+                .approvals
+                .push((guardian.clone(), new_primary_key.clone())),
+        }
+
+        let approvals = recovery
+            .approvals
+            .iter()
+            .filter(|(_, key)| *key == new_primary_key)
+            .count();
+        if recovery.approved.is_none() && approvals >= config.threshold as usize {
+            let executable_at =
+                frame_system::Pallet::<T>::block_number().saturating_add(config.delay);
+            recovery.approved = Some((new_primary_key.clone(), executable_at));
+        }
+        ActiveRecoveries::<T>::insert(target_did, recovery);
+        Self::deposit_event(RawEvent::RecoveryApproved(
+            target_did,
+            guardian,
+            new_primary_key,
+        ));
+        Ok(())
+    }
+
+    /// Cancels the ongoing recovery of the caller's identity.
+    pub(crate) fn base_cancel_recovery(origin: T::RuntimeOrigin) -> DispatchResult {
+        let (_, did) = Self::ensure_primary_key(origin)?;
+        ensure!(
+            Self::cancel_active_recovery(did),
+            Error::<T>::NoActiveRecovery
+        );
+        Ok(())
+    }
+
+    /// Rotates the primary key of `target_did` to the approved key, which must be the caller.
+    pub(crate) fn base_execute_recovery(
+        origin: T::RuntimeOrigin,
+        target_did: IdentityId,
+    ) -> DispatchResult {
+        let sender = ensure_signed(origin)?;
+        let recovery =
+            ActiveRecoveries::<T>::get(target_did).ok_or(Error::<T>::NoActiveRecovery)?;
+        let (key, executable_at) = recovery.approved.ok_or(Error::<T>::RecoveryNotExecutable)?;
+        ensure!(key == sender, Error::<T>::RecoveryKeyMismatch);
+        ensure!(
+            frame_system::Pallet::<T>::block_number() >= executable_at,
+            Error::<T>::RecoveryNotExecutable
+        );
+
+        // The guardians' approvals replace the CDD provider attestation.
+        Self::rotate_primary_key_with(target_did, sender.clone(), None, |_| Ok(()))?;
+        ActiveRecoveries::<T>::remove(target_did);
+        Self::deposit_event(RawEvent::RecoveryExecuted(target_did, sender));
+        Ok(())
+    }
+
+    /// Returns the guardian of `config` behind `origin`, either as a key or as an identity.
+    fn ensure_recovery_guardian(
+        origin: T::RuntimeOrigin,
+        config: &RecoveryConfig<T::AccountId, T::BlockNumber>,
+    ) -> Result<Signatory<T::AccountId>, DispatchError> {
+        let sender = ensure_signed(origin.clone())?;
+        let key = Signatory::Account(sender);
+        if config.guardians.contains(&key) {
+            return Ok(key);
+        }
+        let did = Signatory::Identity(Self::ensure_perms(origin)?);
+        ensure!(
+            config.guardians.contains(&did),
+            Error::<T>::NotRecoveryGuardian
+        );
+        Ok(did)
+    }
+
+    /// Removes the ongoing recovery of `did`, if any, returning whether there was one.
+    fn cancel_active_recovery(did: IdentityId) -> bool {
+        let cancelled = ActiveRecoveries::<T>::take(did).is_some();
+        if cancelled {
+            Self::deposit_event(RawEvent::RecoveryCancelled(did));
+        }
+        cancelled
+    }
+}
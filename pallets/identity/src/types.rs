@@ -1,7 +1,10 @@
 //! Runtime API definition for Identity module.
 
 use codec::{Decode, Encode};
-use polymesh_primitives::{ClaimType, IdentityId, Permissions, Scope, SecondaryKey};
+use polymesh_primitives::{
+    Authorization, ClaimType, ExtrinsicPermissions, IdentityId, Permissions, Scope, SecondaryKey,
+    SecondaryKeyLimits, Signatory,
+};
 use scale_info::TypeInfo;
 use sp_std::{prelude::*, vec::Vec};
 
@@ -25,11 +28,35 @@ pub enum RpcDidRecords<AccountId> {
     Success {
         primary_key: AccountId,
         secondary_keys: Vec<SecondaryKey<AccountId>>,
+        /// The parent identity, if this is a child identity.
+        parent_did: Option<IdentityId>,
+        /// The child identities.
+        child_dids: Vec<IdentityId>,
+        /// The extrinsics the ancestors of this child identity can call on its behalf.
+        parent_permissions: Option<ExtrinsicPermissions>,
     },
     /// Error.
     IdNotFound,
 }
 
+impl<AccountId> From<v1::RpcDidRecords<AccountId>> for RpcDidRecords<AccountId> {
+    fn from(old: v1::RpcDidRecords<AccountId>) -> Self {
+        match old {
+            v1::RpcDidRecords::Success {
+                primary_key,
+                secondary_keys,
+            } => Self::Success {
+                primary_key,
+                secondary_keys,
+                parent_did: None,
+                child_dids: Vec::new(),
+                parent_permissions: None,
+            },
+            v1::RpcDidRecords::IdNotFound => Self::IdNotFound,
+        }
+    }
+}
+
 #[derive(Encode, Decode, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
 pub enum DidStatus {
@@ -41,12 +68,66 @@ pub enum DidStatus {
 /// Aggregate information about an `AccountId` in relation to an `IdentityId`.
 #[derive(Eq, PartialEq, Encode, Decode)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct KeyIdentityData<IdentityId> {
+pub struct KeyIdentityData<IdentityId, Moment> {
     /// The identity of the provided `AccountId`.
     pub identity: IdentityId,
     /// What permissions does the `AccountId` have within the `identity`?
     /// If `None`, then this is a primary key.
     pub permissions: Option<Permissions>,
+    /// The validity window and spending limits of a secondary key, if any.
+    pub limits: Option<SecondaryKeyLimits<Moment>>,
+}
+
+impl<IdentityId, Moment> From<v1::KeyIdentityData<IdentityId>>
+    for KeyIdentityData<IdentityId, Moment>
+{
+    fn from(old: v1::KeyIdentityData<IdentityId>) -> Self {
+        Self {
+            identity: old.identity,
+            permissions: old.permissions,
+            limits: None,
+        }
+    }
+}
+
+/// Deprecated v1 KeyIdentityData.
+pub mod v1 {
+    use super::*;
+
+    /// Aggregate information about an `AccountId` in relation to an `IdentityId`.
+    #[derive(Eq, PartialEq, Encode, Decode)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub struct KeyIdentityData<IdentityId> {
+        /// The identity of the provided `AccountId`.
+        pub identity: IdentityId,
+        /// What permissions does the `AccountId` have within the `identity`?
+        /// If `None`, then this is a primary key.
+        pub permissions: Option<Permissions>,
+    }
+
+    /// Deprecated v1 RpcDidRecords, without the identity hierarchy.
+    #[derive(Eq, PartialEq, Encode, Decode)]
+    #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub enum RpcDidRecords<AccountId> {
+        /// Id was found and has the following primary key and secondary keys.
+        Success {
+            primary_key: AccountId,
+            secondary_keys: Vec<SecondaryKey<AccountId>>,
+        },
+        /// Error.
+        IdNotFound,
+    }
+}
+
+/// An authorization along with its target, as listed in authorization inboxes and outboxes.
+#[derive(Encode, Decode, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RpcAuthorization<AccountId, Moment> {
+    /// The signatory the authorization was given to.
+    pub target: Signatory<AccountId>,
+    /// The authorization, including its issuer and expiry.
+    pub authorization: Authorization<AccountId, Moment>,
 }
 
 /// Result of a successful call permission check.
@@ -19,6 +19,8 @@ use frame_system::RawOrigin;
 use polymesh_common_utilities::benchs::{AccountIdOf, User, UserBuilder};
 use polymesh_common_utilities::TestUtilsFn;
 
+use polymesh_primitives::multisig::CallFilter;
+
 use crate::*;
 
 pub type MultiSig<T> = crate::Module<T>;
@@ -362,4 +364,96 @@ benchmarks! {
     remove_creator_controls {
         let (alice, multisig_account, _, _, _) = generate_multisig_for_alice::<T>(2, 2).unwrap();
     }: _(alice.origin(), multisig_account)
+
+    set_signer_weight {
+        let (_, multisig, signers, _, multisig_origin) = generate_multisig_for_alice::<T>(10, 2).unwrap();
+        let signer = signers.last().cloned().unwrap();
+    }: _(multisig_origin, signer.clone(), 2)
+    verify {
+        assert_eq!(<MultiSig<T>>::signer_weight(&multisig, &signer), 2);
+    }
+
+    set_threshold_rules {
+        let r in 0 .. MAX_THRESHOLD_RULES;
+
+        let (_, multisig, _, _, multisig_origin) = generate_multisig_for_alice::<T>(10, 2).unwrap();
+        let rules: Vec<_> = (0..r).map(|i| ThresholdRule {
+            filter: CallFilter {
+                pallet_name: b"Balances".into(),
+                dispatchable_name: Some(b"transfer".into()),
+                min_polyx: Some(i.into()),
+            },
+            threshold: 10,
+        }).collect();
+    }: _(multisig_origin, rules.clone())
+    verify {
+        assert_eq!(<ThresholdRules<T>>::get(&multisig), rules);
+    }
+
+    set_execution_delay {
+        let (_, multisig, _, _, multisig_origin) = generate_multisig_for_alice::<T>(2, 1).unwrap();
+    }: _(multisig_origin, Some(10u32.into()))
+    verify {
+        assert_eq!(<ExecutionDelay<T>>::get(&multisig), Some(10u32.into()));
+    }
+
+    set_guardian {
+        let (_, multisig, _, _, multisig_origin) = generate_multisig_for_alice::<T>(2, 1).unwrap();
+        let guardian = <UserBuilder<T>>::default().generate_did().build("guardian");
+    }: _(multisig_origin, Some(guardian.did()))
+    verify {
+        assert_eq!(<Guardian<T>>::get(&multisig), Some(guardian.did()));
+    }
+
+    veto_proposal {
+        let (alice, multisig, _, _, multisig_origin) = generate_multisig_for_alice::<T>(2, 1).unwrap();
+        <MultiSig<T>>::set_execution_delay(multisig_origin.into(), Some(10u32.into())).unwrap();
+        let proposal_id = <MultiSig<T>>::ms_tx_done(multisig.clone());
+        let proposal = Box::new(frame_system::Call::<T>::remark { remark: vec![] }.into());
+        <MultiSig<T>>::create_proposal_as_identity(
+            alice.origin().into(),
+            multisig.clone(),
+            proposal,
+            None,
+            false,
+        )
+        .unwrap();
+        assert!(<ProposalDetail<T>>::get(&multisig, proposal_id).status == ProposalStatus::Queued);
+    }: _(alice.origin(), multisig.clone(), proposal_id)
+    verify {
+        assert!(<ProposalDetail<T>>::get(&multisig, proposal_id).status == ProposalStatus::Rejected);
+    }
+
+    set_proposal_retention_period {
+    }: _(RawOrigin::Root, Some(1000u32.into()))
+    verify {
+        assert_eq!(<ProposalRetentionPeriod<T>>::get(), Some(1000u32.into()));
+    }
+
+    prune_proposals {
+        let n in 0 .. MAX_PRUNED_PROPOSALS_PER_BLOCK;
+
+        let (alice, multisig, _, _, _) = generate_multisig_for_alice::<T>(2, 2).unwrap();
+        <ProposalRetentionPeriod<T>>::put(T::Moment::from(1u32));
+        for i in 0..n {
+            let proposal = Box::new(frame_system::Call::<T>::remark { remark: vec![i as u8] }.into());
+            let proposal_id = <MultiSig<T>>::ms_tx_done(multisig.clone());
+            <MultiSig<T>>::create_proposal_as_identity(
+                alice.origin().into(),
+                multisig.clone(),
+                proposal,
+                Some(1u32.into()),
+                false,
+            )
+            .unwrap();
+            // Pretend the proposal was created with `ProposalIds` and executed.
+            <ProposalIds<T>>::insert(&multisig, <MultiSig<T>>::proposals(&multisig, proposal_id).unwrap(), proposal_id);
+        }
+        Timestamp::<T>::set_timestamp((2 * PRUNE_BUCKET as u32).into());
+    }: {
+        assert_eq!(<MultiSig<T>>::prune_proposals(), n);
+    }
+    verify {
+        assert_eq!(<ProposalDetail<T>>::iter_prefix(&multisig).count(), 0);
+    }
 }
@@ -32,6 +32,15 @@
 //! - **multisig**: a special type of account that can do transaction only if at least `n` of its `m`
 //! signers approve.
 //! - **proposal**: a general transaction that the multisig can vote on and accept.
+//! - **signer weight**: the number of votes a signer casts when approving or rejecting a
+//! proposal; defaults to 1.
+//! - **threshold rule**: a call filter (pallet, optional extrinsic and optional minimum POLYX
+//! amount) with the total signer weight required to execute a matching proposal.
+//! - **execution delay**: an optional number of blocks a proposal stays queued after reaching its
+//! threshold. During this window any signer or the multisig's guardian can veto it. Once queued,
+//! the proposal's `expiry` no longer applies and it can't be approved or rejected anymore.
+//! - **retention period**: how long executed, rejected and expired proposals are kept before
+//! they are pruned in the background, at most `MAX_PRUNED_PROPOSALS_PER_BLOCK` per block.
 //!
 //! ## Interface
 //!
@@ -63,6 +72,12 @@
 //! the creator of the multisig.
 //! - `make_multisig_primary` - Adds a multisig as the primary key of the current DID if the current DID
 //! is the creator of the multisig.
+//! - `set_signer_weight` - Changes the approval weight of a signer of the multisig.
+//! - `set_threshold_rules` - Sets the approval thresholds applied to proposals matching call filters.
+//! - `set_execution_delay` - Sets the number of blocks an approved proposal waits before execution.
+//! - `set_guardian` - Sets the identity allowed to veto queued proposals besides the signers.
+//! - `veto_proposal` - Vetoes a queued proposal before its execution delay has passed.
+//! - `set_proposal_retention_period` - Sets how long finished proposals are kept before being pruned.
 //!
 //! ### Other Public Functions
 //!
@@ -77,6 +92,10 @@
 //! not.
 //! - `is_changing_signers_allowed` - Checks whether changing the list of signers is allowed in a
 //! multisig.
+//! - `signer_weight` - Returns the approval weight of a multisig signer.
+//! - `total_signer_weight` - Returns the sum of the approval weights of all signers of a multisig.
+//! - `approvals_required` - Returns the total signer weight required to execute a proposal.
+//! - `active_proposals` - Returns a page of the proposals of a multisig that are open for voting or queued.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -86,12 +105,13 @@ pub mod benchmarking;
 use codec::{Decode, Encode};
 use core::convert::From;
 use frame_support::dispatch::{DispatchError, DispatchResult, GetDispatchInfo, Weight};
-use frame_support::storage::{StorageDoubleMap, StorageValue};
+use frame_support::storage::{IterableStorageDoubleMap, StorageDoubleMap, StorageValue};
 use frame_support::traits::schedule::{DispatchTime, Named as ScheduleNamed};
 use frame_support::traits::{Get, GetCallMetadata};
 use frame_support::{decl_error, decl_module, decl_storage, ensure};
 use frame_system::{ensure_root, ensure_signed, Config as FrameConfig, RawOrigin};
-use sp_runtime::traits::{Dispatchable, Hash, One};
+use sp_runtime::traits::{Dispatchable, Hash, One, Saturating, Zero};
+use sp_runtime::SaturatedConversion;
 use sp_std::convert::TryFrom;
 use sp_std::prelude::*;
 use sp_std::result::Result as StdResult;
@@ -99,15 +119,17 @@ use sp_std::result::Result as StdResult;
 use pallet_identity::PermissionedCallOriginData;
 use pallet_permissions::with_call_metadata;
 use polymesh_common_utilities::constants::queue_priority::MULTISIG_PROPOSAL_EXECUTION_PRIORITY;
-pub use polymesh_common_utilities::multisig::{Event, MultiSigSubTrait, RawEvent, WeightInfo};
+pub use polymesh_common_utilities::multisig::{
+    Event, MultiSigSubTrait, ProposalValue, RawEvent, WeightInfo,
+};
 use polymesh_common_utilities::traits::identity::Config as IdentityConfig;
 use polymesh_common_utilities::transaction_payment::CddAndFeeDetails;
-use polymesh_common_utilities::Context;
+use polymesh_common_utilities::{bucket_queue, Context};
 use polymesh_primitives::constants::MULTISIG_PROPOSAL_EXECUTION;
-use polymesh_primitives::multisig::{ProposalDetails, ProposalStatus};
+use polymesh_primitives::multisig::{ProposalDetails, ProposalInfo, ProposalStatus, ThresholdRule};
 use polymesh_primitives::{
-    extract_auth, storage_migration_ver, AuthorizationData, IdentityId, KeyRecord, Permissions,
-    Signatory,
+    extract_auth, storage_migrate_on, storage_migration_ver, AuthorizationData, IdentityId,
+    KeyRecord, Permissions, Signatory,
 };
 
 /// Either the ID of a successfully created multisig account or an error.
@@ -118,7 +140,13 @@ type Identity<T> = pallet_identity::Module<T>;
 
 pub const NAME: &[u8] = b"MultiSig";
 
-storage_migration_ver!(2);
+/// Maximum number of threshold rules a multisig can have.
+pub const MAX_THRESHOLD_RULES: u32 = 32;
+
+/// Maximum number of finished proposals pruned per block.
+pub const MAX_PRUNED_PROPOSALS_PER_BLOCK: u32 = 20;
+
+storage_migration_ver!(3);
 
 /// Convert multisig account and proposal id into a scheduler name.
 fn proposal_execution_name<AccountId: Encode>(multisig: &AccountId, proposal_id: u64) -> Vec<u8> {
@@ -134,6 +162,8 @@ pub trait Config: frame_system::Config + IdentityConfig {
     type SchedulerCall: From<Call<Self>> + Into<<Self as IdentityConfig>::Proposal>;
     /// Weight information for extrinsics in the multisig pallet.
     type WeightInfo: WeightInfo;
+    /// Extracts the POLYX amount moved by a proposal, used by threshold rules.
+    type ProposalValue: ProposalValue<<Self as IdentityConfig>::Proposal>;
 }
 
 decl_storage! {
@@ -170,11 +200,44 @@ decl_storage! {
             double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) u64 => ProposalDetails<T::Moment>;
         /// Tracks creators who are no longer allowed to call via_creator extrinsics.
         pub LostCreatorPrivileges get(fn lost_creator_privileges): map hasher(identity) IdentityId => bool;
+        /// Approval weight of a multisig signer, signers without an entry have a weight of 1.
+        ///
+        /// (multisig, signer) => weight.
+        pub SignerWeights get(fn signer_weights):
+            double_map hasher(identity) T::AccountId, hasher(twox_64_concat) Signatory<T::AccountId> => Option<u64>;
+        /// Approval thresholds applied to proposals matching a call filter.
+        ///
+        /// multisig => rules.
+        pub ThresholdRules get(fn threshold_rules): map hasher(identity) T::AccountId => Vec<ThresholdRule>;
+        /// Sum of the approval weights of the signers of a multisig above the default weight of 1,
+        /// so that the total weight of its signers is `NumberOfSigners + ExtraSignerWeight`.
+        ///
+        /// multisig => extra weight.
+        pub ExtraSignerWeight get(fn extra_signer_weight): map hasher(identity) T::AccountId => u64;
+        /// Number of blocks an approved proposal is queued before being executed.
+        ///
+        /// multisig => delay.
+        pub ExecutionDelay get(fn execution_delay): map hasher(identity) T::AccountId => Option<T::BlockNumber>;
+        /// Identity that can veto queued proposals, in addition to the signers.
+        ///
+        /// multisig => guardian.
+        pub Guardian get(fn guardian): map hasher(identity) T::AccountId => Option<IdentityId>;
+        /// How long finished proposals are kept before being pruned, `None` keeps them forever.
+        pub ProposalRetentionPeriod get(fn proposal_retention_period): Option<T::Moment>;
+        /// Proposals waiting to be pruned, bucketed by the hour they finished.
+        ///
+        /// The value is the status the proposal must still have for it to be pruned.
+        ///
+        /// bucket -> (multisig, proposal id) => status.
+        pub ProposalPruneQueue get(fn proposal_prune_queue):
+            double_map hasher(twox_64_concat) u64, hasher(twox_64_concat) (T::AccountId, u64) => Option<ProposalStatus>;
+        /// The earliest bucket of `ProposalPruneQueue` that may hold proposals.
+        pub NextProposalPruneBucket get(fn next_proposal_prune_bucket): Option<u64>;
 
         /// The last transaction version, used for `on_runtime_upgrade`.
         TransactionVersion get(fn transaction_version) config(): u32;
         /// Storage version.
-        StorageVersion get(fn storage_version) build(|_| Version::new(2)): Version;
+        StorageVersion get(fn storage_version) build(|_| Version::new(3)): Version;
     }
 }
 
@@ -201,10 +264,19 @@ decl_module! {
                 }
             }
 
+            storage_migrate_on!(StorageVersion, 3, {
+                migration::migrate_to_v3::<T>();
+            });
+
             //TODO placeholder weight
             Weight::from_ref_time(1_000)
         }
 
+        fn on_initialize(_n: T::BlockNumber) -> Weight {
+            let pruned = Self::prune_proposals();
+            <T as Config>::WeightInfo::prune_proposals(pruned)
+        }
+
         /// Creates a multisig
         ///
         /// # Arguments
@@ -404,6 +476,9 @@ decl_module! {
                 Error::<T>::NotEnoughSigners
             );
             ensure!(Self::is_changing_signers_allowed(&sender), Error::<T>::ChangeNotAllowed);
+            let remaining_weight = Self::total_signer_weight(&sender)
+                .saturating_sub(Self::signer_weight(&sender, &signer));
+            Self::ensure_rules_reachable(&Self::threshold_rules(&sender), remaining_weight)?;
             <NumberOfSigners<T>>::mutate(&sender, |x| *x -= 1u64);
             Self::unsafe_signer_removal(sender, signer);
         }
@@ -459,6 +534,13 @@ decl_module! {
             for signer in &signers {
                 Self::ensure_ms_signer(&multisig, &signer)?;
             }
+            let removed_weight = signers
+                .iter()
+                .fold(0u64, |acc, signer| acc.saturating_add(Self::signer_weight(&multisig, signer)));
+            Self::ensure_rules_reachable(
+                &Self::threshold_rules(&multisig),
+                Self::total_signer_weight(&multisig).saturating_sub(removed_weight),
+            )?;
 
             for signer in signers {
                 Self::unsafe_signer_removal(multisig.clone(), signer);
@@ -544,6 +626,78 @@ decl_module! {
             let caller_did = Self::ensure_ms_creator(origin, &multisig_account)?;
             Self::base_remove_creator_controls(caller_did);
         }
+
+        /// Changes the approval weight of a signer. This must be called by the multisig itself.
+        ///
+        /// # Arguments
+        /// * `signer` - Signatory whose weight is changed.
+        /// * `weight` - The new approval weight, must be at least 1.
+        #[weight = <T as Config>::WeightInfo::set_signer_weight()]
+        pub fn set_signer_weight(origin, signer: Signatory<T::AccountId>, weight: u64) {
+            let multisig = ensure_signed(origin)?;
+            Self::ensure_ms(&multisig)?;
+            Self::base_set_signer_weight(multisig, signer, weight)?;
+        }
+
+        /// Replaces the threshold rules of a multisig. This must be called by the multisig itself.
+        ///
+        /// A proposal needs the highest threshold among `sigs_required` and the rules matching it.
+        ///
+        /// # Arguments
+        /// * `rules` - The new threshold rules.
+        #[weight = <T as Config>::WeightInfo::set_threshold_rules(rules.len() as u32)]
+        pub fn set_threshold_rules(origin, rules: Vec<ThresholdRule>) {
+            let multisig = ensure_signed(origin)?;
+            Self::ensure_ms(&multisig)?;
+            Self::base_set_threshold_rules(multisig, rules)?;
+        }
+
+        /// Sets the number of blocks an approved proposal is queued before it is executed.
+        /// This must be called by the multisig itself.
+        ///
+        /// # Arguments
+        /// * `delay` - The execution delay in blocks, `None` or zero executes approved proposals
+        /// in the next block.
+        #[weight = <T as Config>::WeightInfo::set_execution_delay()]
+        pub fn set_execution_delay(origin, delay: Option<T::BlockNumber>) {
+            let multisig = ensure_signed(origin)?;
+            Self::ensure_ms(&multisig)?;
+            Self::base_set_execution_delay(multisig, delay);
+        }
+
+        /// Sets the identity that can veto queued proposals. This must be called by the multisig itself.
+        ///
+        /// # Arguments
+        /// * `guardian` - The guardian identity, `None` removes the current guardian.
+        #[weight = <T as Config>::WeightInfo::set_guardian()]
+        pub fn set_guardian(origin, guardian: Option<IdentityId>) {
+            let multisig = ensure_signed(origin)?;
+            Self::ensure_ms(&multisig)?;
+            Self::base_set_guardian(multisig, guardian)?;
+        }
+
+        /// Vetoes a queued proposal. This must be called by a signer of the multisig or its guardian.
+        ///
+        /// # Arguments
+        /// * `multisig` - MultiSig address.
+        /// * `proposal_id` - Proposal id to veto.
+        #[weight = <T as Config>::WeightInfo::veto_proposal()]
+        pub fn veto_proposal(origin, multisig: T::AccountId, proposal_id: u64) {
+            let caller_did = Self::ensure_signer_or_guardian(origin, &multisig)?;
+            Self::base_veto_proposal(caller_did, multisig, proposal_id)?;
+        }
+
+        /// Sets how long executed, rejected and expired proposals are kept before being pruned.
+        /// The period applies to all finished proposals, including those that finished before the change.
+        ///
+        /// # Arguments
+        /// * `period` - The retention period, `None` keeps finished proposals forever.
+        #[weight = <T as Config>::WeightInfo::set_proposal_retention_period()]
+        pub fn set_proposal_retention_period(origin, period: Option<T::Moment>) {
+            ensure_root(origin)?;
+            <ProposalRetentionPeriod<T>>::set(period);
+            Self::deposit_event(RawEvent::ProposalRetentionPeriodChanged(period));
+        }
     }
 }
 
@@ -602,6 +756,20 @@ decl_error! {
         TooManySigners,
         /// The creator is no longer allowed to call via creator extrinsics.
         CreatorControlsHaveBeenRemoved,
+        /// A signer weight must be at least 1.
+        InvalidSignerWeight,
+        /// A threshold rule requires zero or more weight than the signers hold in total.
+        ThresholdRuleUnreachable,
+        /// Too many threshold rules.
+        TooManyThresholdRules,
+        /// The proposal is queued for execution and can only be vetoed.
+        ProposalAlreadyQueued,
+        /// The proposal is not queued for execution.
+        ProposalNotQueued,
+        /// The caller is neither a signer nor the guardian of the multisig.
+        NotASignerOrGuardian,
+        /// The guardian identity does not exist.
+        GuardianMissing,
     }
 }
 
@@ -687,6 +855,11 @@ impl<T: Config> Module<T> {
             Identity::<T>::remove_key_record(signer_key, None);
         }
         <MultiSigSigners<T>>::remove(&multisig, &signer);
+        if let Some(weight) = <SignerWeights<T>>::take(&multisig, &signer) {
+            <ExtraSignerWeight<T>>::mutate(&multisig, |extra| {
+                *extra = extra.saturating_sub(weight.saturating_sub(1))
+            });
+        }
         Self::deposit_event(RawEvent::MultiSigSignerRemoved(
             Context::current_identity::<Identity<T>>().unwrap_or_default(),
             multisig,
@@ -739,6 +912,11 @@ impl<T: Config> Module<T> {
         proposal_to_id: bool,
     ) -> CreateProposalResult {
         Self::ensure_ms_signer(&multisig, &sender_signer)?;
+        let now = <pallet_timestamp::Pallet<T>>::get();
+        ensure!(
+            expiry.map_or(true, |expiry| expiry > now),
+            Error::<T>::ProposalExpired
+        );
         let caller_did = match sender_signer {
             Signatory::Identity(ref did) => *did,
             Signatory::Account(ref key) => Context::current_identity_or::<Identity<T>>(key)
@@ -755,6 +933,14 @@ impl<T: Config> Module<T> {
             proposal_id,
             ProposalDetails::new(expiry, auto_close),
         );
+        if let Some(expiry) = expiry {
+            Self::queue_proposal_pruning(
+                &multisig,
+                proposal_id,
+                ProposalStatus::ActiveOrExpired,
+                expiry,
+            );
+        }
         // Since proposal_ids are always only incremented by 1, they can not overflow.
         let next_proposal_id: u64 = proposal_id + 1u64;
         <MultiSigTxDone<T>>::insert(multisig.clone(), next_proposal_id);
@@ -802,7 +988,9 @@ impl<T: Config> Module<T> {
         );
 
         let mut proposal_details = Self::proposal_detail(&multisig, proposal_id);
-        proposal_details.approvals += 1u64;
+        proposal_details.approvals = proposal_details
+            .approvals
+            .saturating_add(Self::signer_weight(&multisig, &signer));
         let multisig_did = <MultiSigToIdentity<T>>::get(&multisig);
         match proposal_details.status {
             ProposalStatus::Invalid => return Err(Error::<T>::ProposalMissing.into()),
@@ -810,6 +998,7 @@ impl<T: Config> Module<T> {
             ProposalStatus::ExecutionSuccessful | ProposalStatus::ExecutionFailed => {
                 return Err(Error::<T>::ProposalAlreadyExecuted.into())
             }
+            ProposalStatus::Queued => return Err(Error::<T>::ProposalAlreadyQueued.into()),
             ProposalStatus::ActiveOrExpired => {
                 // Ensure proposal is not expired
                 if let Some(expiry) = proposal_details.expiry {
@@ -818,26 +1007,15 @@ impl<T: Config> Module<T> {
                         Error::<T>::ProposalExpired
                     );
                 }
-                if proposal_details.approvals >= Self::ms_signs_required(&multisig) {
-                    if let Some(proposal) = Self::proposals(&multisig, proposal_id) {
-                        let execution_at = frame_system::Pallet::<T>::block_number() + One::one();
-                        let call = Call::<T>::execute_scheduled_proposal {
-                            multisig: multisig.clone(),
+                if let Some(proposal) = Self::proposals(&multisig, proposal_id) {
+                    if proposal_details.approvals >= Self::approvals_required(&multisig, &proposal)
+                    {
+                        Self::schedule_execution(
+                            &multisig,
                             proposal_id,
                             multisig_did,
-                            _proposal_weight: proposal.get_dispatch_info().weight,
-                        }
-                        .into();
-
-                        // Scheduling will fail when it's already scheduled (had enough votes already).
-                        // We ignore the failure here.
-                        let _ = T::Scheduler::schedule_named(
-                            proposal_execution_name(&multisig, proposal_id),
-                            DispatchTime::At(execution_at),
-                            None,
-                            MULTISIG_PROPOSAL_EXECUTION_PRIORITY,
-                            RawOrigin::Root.into(),
-                            call,
+                            &proposal,
+                            &mut proposal_details,
                         );
                     }
                 }
@@ -856,6 +1034,51 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    /// Schedules the execution of an approved proposal, queuing it if the multisig has an execution delay.
+    fn schedule_execution(
+        multisig: &T::AccountId,
+        proposal_id: u64,
+        multisig_did: IdentityId,
+        proposal: &T::Proposal,
+        proposal_details: &mut ProposalDetails<T::Moment>,
+    ) {
+        let delay = Self::execution_delay(multisig);
+        let execution_at =
+            frame_system::Pallet::<T>::block_number() + delay.unwrap_or_else(One::one);
+        let call = Call::<T>::execute_scheduled_proposal {
+            multisig: multisig.clone(),
+            proposal_id,
+            multisig_did,
+            _proposal_weight: proposal.get_dispatch_info().weight,
+        }
+        .into();
+
+        // Scheduling will fail when it's already scheduled (had enough votes already).
+        // We ignore the failure here unless the proposal is being queued.
+        let scheduled = T::Scheduler::schedule_named(
+            proposal_execution_name(multisig, proposal_id),
+            DispatchTime::At(execution_at),
+            None,
+            MULTISIG_PROPOSAL_EXECUTION_PRIORITY,
+            RawOrigin::Root.into(),
+            call,
+        );
+        if delay.is_some() {
+            match scheduled {
+                Ok(_) => {
+                    proposal_details.status = ProposalStatus::Queued;
+                    Self::deposit_event(RawEvent::ProposalQueued(
+                        multisig_did,
+                        multisig.clone(),
+                        proposal_id,
+                        execution_at,
+                    ));
+                }
+                Err(e) => Self::deposit_event(RawEvent::SchedulingFailed(e)),
+            }
+        }
+    }
+
     /// Executes a proposal if it has enough approvals
     fn execute_proposal(
         multisig: T::AccountId,
@@ -870,10 +1093,16 @@ impl<T: Config> Module<T> {
         T::CddHandler::set_payer_context(Identity::<T>::get_primary_key(multisig_did));
 
         if let Some(proposal) = Self::proposals(&multisig, proposal_id) {
-            let update_proposal_status = |status| {
+            let update_proposal_status = |status: ProposalStatus| {
                 <ProposalDetail<T>>::mutate(&multisig, proposal_id, |proposal_details| {
-                    proposal_details.status = status
-                })
+                    proposal_details.status = status.clone()
+                });
+                Self::queue_proposal_pruning(
+                    &multisig,
+                    proposal_id,
+                    status,
+                    <pallet_timestamp::Pallet<T>>::get(),
+                );
             };
             let res = match with_call_metadata(proposal.get_call_metadata(), || {
                 proposal.dispatch(frame_system::RawOrigin::Signed(multisig.clone()).into())
@@ -910,7 +1139,9 @@ impl<T: Config> Module<T> {
             Error::<T>::AlreadyVoted
         );
         let mut proposal_details = Self::proposal_detail(&multisig, proposal_id);
-        proposal_details.rejections += 1u64;
+        proposal_details.rejections = proposal_details
+            .rejections
+            .saturating_add(Self::signer_weight(&multisig, &signer));
         let current_did = Context::current_identity::<Identity<T>>().unwrap_or_default();
         match proposal_details.status {
             ProposalStatus::Invalid => return Err(Error::<T>::ProposalMissing.into()),
@@ -918,6 +1149,7 @@ impl<T: Config> Module<T> {
             ProposalStatus::ExecutionSuccessful | ProposalStatus::ExecutionFailed => {
                 return Err(Error::<T>::ProposalAlreadyExecuted.into())
             }
+            ProposalStatus::Queued => return Err(Error::<T>::ProposalAlreadyQueued.into()),
             ProposalStatus::ActiveOrExpired => {
                 // Ensure proposal is not expired
                 if let Some(expiry) = proposal_details.expiry {
@@ -927,10 +1159,18 @@ impl<T: Config> Module<T> {
                     );
                 }
                 if proposal_details.auto_close {
-                    let approvals_needed = Self::ms_signs_required(multisig.clone());
-                    let ms_signers = Self::number_of_signers(multisig.clone());
-                    if proposal_details.rejections > ms_signers.saturating_sub(approvals_needed) {
+                    let approvals_needed = Self::proposals(&multisig, proposal_id)
+                        .map(|proposal| Self::approvals_required(&multisig, &proposal))
+                        .unwrap_or_else(|| Self::ms_signs_required(&multisig));
+                    let total_weight = Self::total_signer_weight(&multisig);
+                    if proposal_details.rejections > total_weight.saturating_sub(approvals_needed) {
                         proposal_details.status = ProposalStatus::Rejected;
+                        Self::queue_proposal_pruning(
+                            &multisig,
+                            proposal_id,
+                            ProposalStatus::Rejected,
+                            <pallet_timestamp::Pallet<T>>::get(),
+                        );
                         Self::deposit_event(RawEvent::ProposalRejected(
                             current_did,
                             multisig.clone(),
@@ -1074,6 +1314,286 @@ impl<T: Config> Module<T> {
     fn base_remove_creator_controls(creator_did: IdentityId) {
         LostCreatorPrivileges::insert(creator_did, true);
     }
+
+    /// Returns the approval weight of `signer` in `multisig`.
+    pub fn signer_weight(multisig: &T::AccountId, signer: &Signatory<T::AccountId>) -> u64 {
+        Self::signer_weights(multisig, signer).unwrap_or(1)
+    }
+
+    /// Returns the sum of the approval weights of all signers of `multisig`.
+    pub fn total_signer_weight(multisig: &T::AccountId) -> u64 {
+        Self::number_of_signers(multisig).saturating_add(Self::extra_signer_weight(multisig))
+    }
+
+    /// Returns the total signer weight required to execute `proposal`,
+    /// i.e. the highest of `sigs_required` and the thresholds of the rules matching
+    /// `proposal` or any of the calls it wraps.
+    pub fn approvals_required(multisig: &T::AccountId, proposal: &T::Proposal) -> u64 {
+        let rules = Self::threshold_rules(multisig);
+        let sigs_required = Self::ms_signs_required(multisig);
+        if rules.is_empty() {
+            return sigs_required;
+        }
+        let mut required = sigs_required;
+        let mut calls = sp_std::vec![proposal];
+        while let Some(call) = calls.pop() {
+            let metadata = call.get_call_metadata();
+            let polyx = T::ProposalValue::polyx_value(call);
+            required = rules
+                .iter()
+                .filter(|rule| rule.filter.matches(&metadata, polyx))
+                .map(|rule| rule.threshold)
+                .fold(required, u64::max);
+            calls.extend(T::ProposalValue::inner_calls(call));
+        }
+        required
+    }
+
+    /// Ensures every rule in `rules` can be met by signers holding `total_weight` in total.
+    fn ensure_rules_reachable(rules: &[ThresholdRule], total_weight: u64) -> DispatchResult {
+        ensure!(
+            rules
+                .iter()
+                .all(|rule| rule.threshold > 0 && rule.threshold <= total_weight),
+            Error::<T>::ThresholdRuleUnreachable
+        );
+        Ok(())
+    }
+
+    /// Changes the approval weight of `signer` in `multisig` to `weight`.
+    fn base_set_signer_weight(
+        multisig: T::AccountId,
+        signer: Signatory<T::AccountId>,
+        weight: u64,
+    ) -> DispatchResult {
+        Self::ensure_ms_signer(&multisig, &signer)?;
+        ensure!(weight > 0, Error::<T>::InvalidSignerWeight);
+        let old_weight = Self::signer_weight(&multisig, &signer);
+        let extra_weight = Self::extra_signer_weight(&multisig)
+            .saturating_sub(old_weight.saturating_sub(1))
+            .saturating_add(weight.saturating_sub(1));
+        let total_weight = Self::number_of_signers(&multisig).saturating_add(extra_weight);
+        Self::ensure_rules_reachable(&Self::threshold_rules(&multisig), total_weight)?;
+
+        if weight == 1 {
+            <SignerWeights<T>>::remove(&multisig, &signer);
+        } else {
+            <SignerWeights<T>>::insert(&multisig, &signer, weight);
+        }
+        if extra_weight == 0 {
+            <ExtraSignerWeight<T>>::remove(&multisig);
+        } else {
+            <ExtraSignerWeight<T>>::insert(&multisig, extra_weight);
+        }
+        Self::deposit_event(RawEvent::MultiSigSignerWeightChanged(
+            <MultiSigToIdentity<T>>::get(&multisig),
+            multisig,
+            signer,
+            weight,
+        ));
+        Ok(())
+    }
+
+    /// Ensures the caller is a signer or the guardian of `multisig`, returning the caller's identity.
+    fn ensure_signer_or_guardian(
+        origin: T::RuntimeOrigin,
+        multisig: &T::AccountId,
+    ) -> Result<IdentityId, DispatchError> {
+        let sender = ensure_signed(origin.clone())?;
+        if Self::ms_signers(multisig.clone(), Signatory::Account(sender)) {
+            return Ok(Context::current_identity::<Identity<T>>()
+                .unwrap_or_else(|| <MultiSigToIdentity<T>>::get(multisig)));
+        }
+        let did = <Identity<T>>::ensure_perms(origin)?;
+        ensure!(
+            Self::guardian(multisig) == Some(did)
+                || Self::ms_signers(multisig.clone(), Signatory::Identity(did)),
+            Error::<T>::NotASignerOrGuardian
+        );
+        Ok(did)
+    }
+
+    /// Sets the execution delay of `multisig` to `delay`.
+    fn base_set_execution_delay(multisig: T::AccountId, delay: Option<T::BlockNumber>) {
+        // A zero delay is the same as no delay.
+        let delay = delay.filter(|delay| !delay.is_zero());
+        match delay {
+            Some(delay) => <ExecutionDelay<T>>::insert(&multisig, delay),
+            None => <ExecutionDelay<T>>::remove(&multisig),
+        }
+        Self::deposit_event(RawEvent::ExecutionDelayChanged(
+            <MultiSigToIdentity<T>>::get(&multisig),
+            multisig,
+            delay,
+        ));
+    }
+
+    /// Sets the guardian of `multisig` to `guardian`.
+    fn base_set_guardian(multisig: T::AccountId, guardian: Option<IdentityId>) -> DispatchResult {
+        match guardian {
+            Some(did) => {
+                ensure!(
+                    <Identity<T>>::is_identity_exists(&did),
+                    Error::<T>::GuardianMissing
+                );
+                <Guardian<T>>::insert(&multisig, did);
+            }
+            None => <Guardian<T>>::remove(&multisig),
+        }
+        Self::deposit_event(RawEvent::GuardianChanged(
+            <MultiSigToIdentity<T>>::get(&multisig),
+            multisig,
+            guardian,
+        ));
+        Ok(())
+    }
+
+    /// Vetoes the queued proposal `proposal_id` of `multisig` and cancels its execution.
+    fn base_veto_proposal(
+        caller_did: IdentityId,
+        multisig: T::AccountId,
+        proposal_id: u64,
+    ) -> DispatchResult {
+        let mut proposal_details = Self::proposal_detail(&multisig, proposal_id);
+        ensure!(
+            proposal_details.status == ProposalStatus::Queued,
+            Error::<T>::ProposalNotQueued
+        );
+        let _ = T::Scheduler::cancel_named(proposal_execution_name(&multisig, proposal_id));
+        proposal_details.status = ProposalStatus::Rejected;
+        <ProposalDetail<T>>::insert(&multisig, proposal_id, proposal_details);
+        Self::queue_proposal_pruning(
+            &multisig,
+            proposal_id,
+            ProposalStatus::Rejected,
+            <pallet_timestamp::Pallet<T>>::get(),
+        );
+        Self::deposit_event(RawEvent::ProposalVetoed(caller_did, multisig, proposal_id));
+        Ok(())
+    }
+
+    /// Returns the bucket of `ProposalPruneQueue` for a proposal finished at `at`.
+    fn prune_bucket(at: T::Moment) -> u64 {
+        bucket_queue::bucket_of(at.saturated_into())
+    }
+
+    /// Queues `proposal_id` of `multisig`, which finished at `finished_at`, to be pruned once
+    /// the retention period has passed, provided it still has `status` by then.
+    fn queue_proposal_pruning(
+        multisig: &T::AccountId,
+        proposal_id: u64,
+        status: ProposalStatus,
+        finished_at: T::Moment,
+    ) {
+        bucket_queue::queue::<ProposalPruneQueue<T>, NextProposalPruneBucket, _, _>(
+            Self::prune_bucket(finished_at),
+            Self::next_proposal_prune_bucket().unwrap_or_default(),
+            (multisig.clone(), proposal_id),
+            status,
+        );
+    }
+
+    /// Removes up to `MAX_PRUNED_PROPOSALS_PER_BLOCK` finished proposals whose retention period has passed.
+    ///
+    /// Returns the number of processed queue entries.
+    fn prune_proposals() -> u32 {
+        let retention = match Self::proposal_retention_period() {
+            Some(retention) => retention,
+            None => return 0,
+        };
+        let now = <pallet_timestamp::Pallet<T>>::get();
+        let (_, queued) = bucket_queue::take::<ProposalPruneQueue<T>, NextProposalPruneBucket, _, _>(
+            Self::prune_bucket(now.saturating_sub(retention)),
+            MAX_PRUNED_PROPOSALS_PER_BLOCK,
+        );
+        for ((multisig, proposal_id), status) in &queued {
+            // Skip proposals that changed status since they were queued,
+            // they are queued again once they finish.
+            if Self::proposal_detail(multisig, proposal_id).status == *status {
+                Self::remove_proposal(multisig, *proposal_id);
+            }
+        }
+        queued.len() as u32
+    }
+
+    /// Removes `proposal_id` of `multisig` along with its details and votes.
+    fn remove_proposal(multisig: &T::AccountId, proposal_id: u64) {
+        if let Some(proposal) = <Proposals<T>>::take(multisig, proposal_id) {
+            if Self::proposal_ids(multisig, &proposal) == Some(proposal_id) {
+                <ProposalIds<T>>::remove(multisig, &proposal);
+            }
+        }
+        <ProposalDetail<T>>::remove(multisig, proposal_id);
+        #[allow(deprecated)]
+        <Votes<T>>::remove_prefix((multisig.clone(), proposal_id), None);
+        Self::deposit_event(RawEvent::ProposalPruned(multisig.clone(), proposal_id));
+    }
+
+    /// Returns up to `limit` proposals of `multisig` that are open for voting or queued for execution,
+    /// following the proposal `after` in storage order.
+    /// The id of the last proposal returned can be used as `after` to get the next page.
+    pub fn active_proposals(
+        multisig: &T::AccountId,
+        after: Option<u64>,
+        limit: u32,
+    ) -> Vec<ProposalInfo<T::AccountId, T::Moment, T::Proposal>> {
+        let now = <pallet_timestamp::Pallet<T>>::get();
+        match after {
+            Some(after) => <ProposalDetail<T>>::iter_prefix_from(
+                multisig,
+                <ProposalDetail<T>>::hashed_key_for(multisig, after),
+            ),
+            None => <ProposalDetail<T>>::iter_prefix(multisig),
+        }
+        .filter(|(_, details)| match details.status {
+            ProposalStatus::ActiveOrExpired => details.expiry.map_or(true, |expiry| expiry > now),
+            ProposalStatus::Queued => true,
+            _ => false,
+        })
+        .filter_map(|(proposal_id, details)| {
+            let proposal = Self::proposals(multisig, proposal_id)?;
+            let metadata = proposal.get_call_metadata();
+            let voters = <Votes<T>>::iter_prefix((multisig.clone(), proposal_id))
+                .filter(|(_, voted)| *voted)
+                .map(|(signer, _)| signer)
+                .collect();
+            Some(ProposalInfo {
+                proposal_id,
+                pallet_name: metadata.pallet_name.as_bytes().into(),
+                dispatchable_name: metadata.function_name.as_bytes().into(),
+                approvals_required: Self::approvals_required(multisig, &proposal),
+                call: proposal,
+                details,
+                voters,
+            })
+        })
+        .take(limit as usize)
+        .collect()
+    }
+
+    /// Replaces the threshold rules of `multisig` with `rules`.
+    fn base_set_threshold_rules(
+        multisig: T::AccountId,
+        rules: Vec<ThresholdRule>,
+    ) -> DispatchResult {
+        ensure!(
+            rules.len() <= MAX_THRESHOLD_RULES as usize,
+            Error::<T>::TooManyThresholdRules
+        );
+        Self::ensure_rules_reachable(&rules, Self::total_signer_weight(&multisig))?;
+
+        if rules.is_empty() {
+            <ThresholdRules<T>>::remove(&multisig);
+        } else {
+            <ThresholdRules<T>>::insert(&multisig, rules.clone());
+        }
+        Self::deposit_event(RawEvent::MultiSigThresholdRulesChanged(
+            <MultiSigToIdentity<T>>::get(&multisig),
+            multisig,
+            rules,
+        ));
+        Ok(())
+    }
 }
 
 impl<T: Config> MultiSigSubTrait<T::AccountId> for Module<T> {
@@ -1081,3 +1601,36 @@ impl<T: Config> MultiSigSubTrait<T::AccountId> for Module<T> {
         <MultiSigToIdentity<T>>::contains_key(account_id)
     }
 }
+
+pub mod migration {
+    use super::*;
+    use sp_runtime::runtime_logger::RuntimeLogger;
+
+    pub fn migrate_to_v3<T: Config>() {
+        RuntimeLogger::init();
+        log::info!(" >>> Queueing finished proposals for pruning.");
+        let now = <pallet_timestamp::Pallet<T>>::get();
+        let mut queued = 0;
+        for (multisig, proposal_id, details) in <ProposalDetail<T>>::iter() {
+            // Proposals without an expiry that are still open or queued are queued once they finish.
+            let finished_at = match details.status {
+                ProposalStatus::ExecutionSuccessful
+                | ProposalStatus::ExecutionFailed
+                | ProposalStatus::Rejected => now,
+                ProposalStatus::ActiveOrExpired => match details.expiry {
+                    Some(expiry) => expiry,
+                    None => continue,
+                },
+                ProposalStatus::Invalid | ProposalStatus::Queued => continue,
+            };
+            Module::<T>::queue_proposal_pruning(
+                &multisig,
+                proposal_id,
+                details.status,
+                finished_at,
+            );
+            queued += 1;
+        }
+        log::info!("> Queued {queued} proposals for pruning.");
+    }
+}
@@ -1,6 +1,7 @@
 use frame_benchmarking::benchmarks;
 use frame_system::RawOrigin;
 use scale_info::prelude::format;
+use sp_runtime::Permill;
 use sp_std::prelude::*;
 use sp_std::vec::Vec;
 
@@ -28,7 +29,7 @@ fn create_collection<T: Config>(
     n: u32,
 ) -> NFTCollectionId {
     let collection_keys: NFTCollectionKeys = creates_keys_register_metadata_types::<T>(n);
-    Module::<T>::create_nft_collection(origin, ticker, nft_type, collection_keys)
+    Module::<T>::create_nft_collection(origin, ticker, nft_type, collection_keys, None)
         .expect("failed to create nft collection");
     Module::<T>::collection_id()
 }
@@ -62,7 +63,7 @@ pub fn create_collection_issue_nfts<T: Config>(
     portfolio_kind: PortfolioKind,
 ) {
     let collection_keys: NFTCollectionKeys = creates_keys_register_metadata_types::<T>(n_keys);
-    Module::<T>::create_nft_collection(origin.clone(), ticker, nft_type, collection_keys)
+    Module::<T>::create_nft_collection(origin.clone(), ticker, nft_type, collection_keys, None)
         .expect("failed to create nft collection");
     let metadata_attributes: Vec<NFTMetadataAttribute> = (1..n_keys + 1)
         .map(|key| NFTMetadataAttribute {
@@ -115,6 +116,21 @@ where
     (sender_portfolio, receiver_portfolio)
 }
 
+/// Creates a fungible asset without supply that can hold the fractions of an NFT.
+fn create_fractions_asset<T: Config>(user: &User<T>, ticker: Ticker) {
+    Asset::<T>::create_asset(
+        user.origin().into(),
+        AssetName(ticker.as_slice().to_vec()),
+        ticker,
+        true,
+        AssetType::default(),
+        Vec::new(),
+        None,
+    )
+    .expect("failed to create the fractions asset");
+    T::Compliance::setup_ticker_compliance(user.did(), ticker, 0, true);
+}
+
 benchmarks! {
     where_clause { where T: TestUtilsFn<AccountIdOf<T>> }
 
@@ -125,7 +141,7 @@ benchmarks! {
         let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
         let nft_type: Option<NonFungibleType> = Some(NonFungibleType::Derivative);
         let collection_keys: NFTCollectionKeys = creates_keys_register_metadata_types::<T>(n);
-    }: _(user.origin, ticker, nft_type, collection_keys)
+    }: _(user.origin, ticker, nft_type, collection_keys, None)
     verify {
         assert!(Collection::contains_key(NFTCollectionId(1)));
         assert_eq!(CollectionKeys::get(NFTCollectionId(1)).len(), n as usize);
@@ -158,6 +174,28 @@ benchmarks! {
         }
     }
 
+    issue_nfts {
+        let n in 1..T::MaxNFTsPerIssue::get();
+        let k in 1..MAX_COLLECTION_KEYS;
+
+        let user = user::<T>("target", 0);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        let nft_type: Option<NonFungibleType> = Some(NonFungibleType::Derivative);
+        let collection_id = create_collection::<T>(user.origin().into(), ticker, nft_type, k);
+        let metadata_attributes: Vec<NFTMetadataAttribute> = (1..k + 1)
+            .map(|key| {
+                NFTMetadataAttribute{
+                    key: AssetMetadataKey::Global(AssetMetadataGlobalKey(key.into())),
+                    value: AssetMetadataValue(b"value".to_vec()),
+                }
+            })
+            .collect();
+        let nfts_metadata_attributes = vec![metadata_attributes; n as usize];
+    }: _(user.origin, ticker, nfts_metadata_attributes, PortfolioKind::Default)
+    verify {
+        assert_eq!(NFTsInCollection::get(&ticker), n as u64);
+    }
+
     redeem_nft {
         let n in 1..MAX_COLLECTION_KEYS;
 
@@ -255,4 +293,119 @@ benchmarks! {
         assert_eq!(NFTsInCollection::get(nfts.ticker()), n as u64);
     }
 
+    set_nft_metadata_value {
+        let user = user::<T>("target", 0);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        create_collection_issue_nfts::<T>(
+            user.origin().into(),
+            ticker,
+            Some(NonFungibleType::Derivative),
+            1,
+            1,
+            PortfolioKind::Default,
+        );
+        let key = AssetMetadataKey::Global(AssetMetadataGlobalKey(1));
+        let value = AssetMetadataValue(b"updated".to_vec());
+    }: _(user.origin, ticker, NFTId(1), key, value.clone(), None)
+    verify {
+        assert_eq!(MetadataValue::get((NFTCollectionId(1), NFTId(1)), key), value);
+    }
+
+    freeze_nft {
+        let user = user::<T>("target", 0);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        create_collection_issue_nfts::<T>(
+            user.origin().into(),
+            ticker,
+            Some(NonFungibleType::Derivative),
+            0,
+            1,
+            PortfolioKind::Default,
+        );
+    }: _(user.origin, ticker, NFTId(1))
+    verify {
+        assert!(FrozenNFT::get(ticker, NFTId(1)));
+    }
+
+    unfreeze_nft {
+        let user = user::<T>("target", 0);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        create_collection_issue_nfts::<T>(
+            user.origin().into(),
+            ticker,
+            Some(NonFungibleType::Derivative),
+            0,
+            1,
+            PortfolioKind::Default,
+        );
+        Module::<T>::freeze_nft(user.origin().into(), ticker, NFTId(1)).unwrap();
+    }: _(user.origin, ticker, NFTId(1))
+    verify {
+        assert!(!FrozenNFT::get(ticker, NFTId(1)));
+    }
+
+    set_collection_royalty {
+        let user = user::<T>("target", 0);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        create_collection_issue_nfts::<T>(
+            user.origin().into(),
+            ticker,
+            Some(NonFungibleType::Derivative),
+            0,
+            0,
+            PortfolioKind::Default,
+        );
+        let royalty = NFTRoyalty {
+            beneficiary: user.did(),
+            percentage: Permill::from_percent(10),
+        };
+    }: _(user.origin, ticker, Some(royalty.clone()))
+    verify {
+        assert_eq!(CollectionRoyalty::get(ticker), Some(royalty));
+    }
+
+    fractionalise_nft {
+        let user = user::<T>("target", 0);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        let fractions_ticker: Ticker = Ticker::from_slice_truncated(b"FRACTIONS".as_ref());
+        create_collection_issue_nfts::<T>(
+            user.origin().into(),
+            ticker,
+            Some(NonFungibleType::Derivative),
+            0,
+            1,
+            PortfolioKind::Default,
+        );
+        create_fractions_asset::<T>(&user, fractions_ticker);
+    }: _(user.origin, ticker, NFTId(1), PortfolioKind::Default, fractions_ticker, 1_000)
+    verify {
+        assert_eq!(FractionalisedNFT::get(fractions_ticker), Some((ticker, NFTId(1))));
+    }
+
+    redeem_nft_fractions {
+        let user = user::<T>("target", 0);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        let fractions_ticker: Ticker = Ticker::from_slice_truncated(b"FRACTIONS".as_ref());
+        create_collection_issue_nfts::<T>(
+            user.origin().into(),
+            ticker,
+            Some(NonFungibleType::Derivative),
+            0,
+            1,
+            PortfolioKind::Default,
+        );
+        create_fractions_asset::<T>(&user, fractions_ticker);
+        Module::<T>::fractionalise_nft(
+            user.origin().into(),
+            ticker,
+            NFTId(1),
+            PortfolioKind::Default,
+            fractions_ticker,
+            1_000,
+        )
+        .unwrap();
+    }: _(user.origin, fractions_ticker, PortfolioKind::Default)
+    verify {
+        assert!(!FractionalisedNFT::contains_key(fractions_ticker));
+    }
 }
@@ -1,7 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Decode, Encode};
-use frame_support::dispatch::DispatchResult;
+use frame_support::dispatch::{DispatchError, DispatchResult};
 use frame_support::storage::StorageDoubleMap;
 use frame_support::traits::Get;
 use frame_support::weights::Weight;
@@ -9,18 +9,23 @@ use frame_support::{decl_error, decl_module, decl_storage, ensure, require_trans
 
 use pallet_asset::Frozen;
 use pallet_base::try_next_pre;
-use pallet_portfolio::PortfolioNFT;
+use pallet_portfolio::{PortfolioLockedNFT, PortfolioNFT};
 use polymesh_common_utilities::compliance_manager::ComplianceFnConfig;
+use polymesh_common_utilities::protocol_fee::ChargeProtocolFee;
 pub use polymesh_common_utilities::traits::nft::{Config, Event, NFTTrait, WeightInfo};
+use polymesh_common_utilities::SystematicIssuers;
 use polymesh_primitives::asset::{AssetName, AssetType, NonFungibleType};
-use polymesh_primitives::asset_metadata::{AssetMetadataKey, AssetMetadataValue};
+use polymesh_primitives::asset_metadata::{
+    AssetMetadataKey, AssetMetadataValue, AssetMetadataValueDetail,
+};
 use polymesh_primitives::nft::{
-    NFTCollection, NFTCollectionId, NFTCollectionKeys, NFTCount, NFTId, NFTMetadataAttribute, NFTs,
+    NFTCollection, NFTCollectionId, NFTCollectionKeys, NFTCount, NFTId, NFTMetadataAttribute,
+    NFTRoyalty, NFTs,
 };
 use polymesh_primitives::settlement::InstructionId;
 use polymesh_primitives::{
-    storage_migrate_on, storage_migration_ver, IdentityId, Memo, PortfolioId, PortfolioKind,
-    PortfolioUpdateReason, Ticker, WeightMeter,
+    storage_migrate_on, storage_migration_ver, Balance, IdentityId, Memo, PortfolioId,
+    PortfolioKind, PortfolioUpdateReason, Ticker, WeightMeter,
 };
 use sp_std::collections::btree_map::BTreeMap;
 use sp_std::collections::btree_set::BTreeSet;
@@ -34,7 +39,7 @@ type Portfolio<T> = pallet_portfolio::Module<T>;
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
 
-storage_migration_ver!(1);
+storage_migration_ver!(2);
 
 decl_storage!(
     trait Store for Module<T: Config> as NFT {
@@ -65,8 +70,23 @@ decl_storage!(
         /// Tracks the owner of an NFT
         pub NFTOwner get(fn nft_owner): double_map hasher(blake2_128_concat) Ticker, hasher(blake2_128_concat) NFTId => Option<PortfolioId>;
 
+        /// Details (expire, lock status) of the metadata value of an nft given its collection id, token id and metadata key.
+        pub MetadataValueDetails get(fn metadata_value_details): double_map hasher(blake2_128_concat) (NFTCollectionId, NFTId), hasher(blake2_128_concat) AssetMetadataKey => Option<AssetMetadataValueDetail<T::Moment>>;
+
+        /// Tracks the NFTs that can't be transferred.
+        pub FrozenNFT get(fn frozen_nft): double_map hasher(blake2_128_concat) Ticker, hasher(blake2_128_concat) NFTId => bool;
+
+        /// The royalty paid to the beneficiary of a collection when its NFTs are exchanged for fungible assets.
+        pub CollectionRoyalty get(fn collection_royalty): map hasher(blake2_128_concat) Ticker => Option<NFTRoyalty>;
+
+        /// The NFT held in custody for each fungible ticker that represents its fractions.
+        pub FractionalisedNFT get(fn fractionalised_nft): map hasher(blake2_128_concat) Ticker => Option<(Ticker, NFTId)>;
+
+        /// The maximum number of NFTs that can exist in a collection, if set when the collection was created.
+        pub CollectionMaxSupply get(fn collection_max_supply): map hasher(blake2_128_concat) Ticker => Option<NFTCount>;
+
         /// Storage version.
-        StorageVersion get(fn storage_version) build(|_| Version::new(1)): Version;
+        StorageVersion get(fn storage_version) build(|_| Version::new(2)): Version;
     }
 );
 
@@ -85,6 +105,9 @@ decl_module! {
             storage_migrate_on!(StorageVersion, 1, {
                 migration::migrate_to_v1::<T>();
             });
+            storage_migrate_on!(StorageVersion, 2, {
+                migration::migrate_to_v2::<T>();
+            });
             Weight::zero()
         }
 
@@ -95,6 +118,7 @@ decl_module! {
         /// * `ticker` - the ticker associated to the new collection.
         /// * `nft_type` - in case the asset hasn't been created yet, one will be created with the given type.
         /// * `collection_keys` - all mandatory metadata keys that the tokens in the collection must have.
+        /// * `max_supply` - the maximum number of NFTs that can exist in the collection (unlimited if `None`).
         ///
         /// ## Errors
         /// - `CollectionAlredyRegistered` - if the ticker is already associated to an NFT collection.
@@ -102,12 +126,20 @@ decl_module! {
         /// - `MaxNumberOfKeysExceeded` - if the number of metadata keys for the collection is greater than the maximum allowed.
         /// - `UnregisteredMetadataKey` - if any of the metadata keys needed for the collection has not been registered.
         /// - `DuplicateMetadataKey` - if a duplicate metadata keys has been passed as input.
+        /// - `ZeroMaxSupply` - if `max_supply` is set to zero.
         ///
         /// # Permissions
         /// * Asset
-        #[weight = <T as Config>::WeightInfo::create_nft_collection(collection_keys.len() as u32)]
-        pub fn create_nft_collection(origin, ticker: Ticker, nft_type: Option<NonFungibleType>, collection_keys: NFTCollectionKeys) -> DispatchResult {
-            Self::base_create_nft_collection(origin, ticker, nft_type, collection_keys)
+        #[weight = <T as Config>::WeightInfo::create_nft_collection(collection_keys.len() as u32)
+            .saturating_add(T::ProtocolFee::charge_fee_weight().saturating_mul(2))]
+        pub fn create_nft_collection(
+            origin,
+            ticker: Ticker,
+            nft_type: Option<NonFungibleType>,
+            collection_keys: NFTCollectionKeys,
+            max_supply: Option<NFTCount>
+        ) -> DispatchResult {
+            Self::base_create_nft_collection(origin, ticker, nft_type, collection_keys, max_supply)
         }
 
         /// Issues an NFT to the caller.
@@ -129,7 +161,34 @@ decl_module! {
         /// * Portfolio
         #[weight = <T as Config>::WeightInfo::issue_nft(nft_metadata_attributes.len() as u32)]
         pub fn issue_nft(origin, ticker: Ticker, nft_metadata_attributes: Vec<NFTMetadataAttribute>, portfolio_kind: PortfolioKind) -> DispatchResult {
-            Self::base_issue_nft(origin, ticker, nft_metadata_attributes, portfolio_kind)
+            Self::base_issue_nfts(origin, ticker, vec![nft_metadata_attributes], portfolio_kind)
+        }
+
+        /// Issues multiple NFTs to the caller.
+        ///
+        /// # Arguments
+        /// * `origin` - is a signer that has permissions to act as an agent of `ticker`.
+        /// * `ticker` - the ticker of the NFT collection.
+        /// * `nfts_metadata_attributes` - all mandatory metadata keys and values for each NFT that will be issued.
+        /// * `portfolio_kind` - the portfolio that will receive the minted nfts.
+        ///
+        /// ## Errors
+        /// - `CollectionNotFound` - if the collection associated to the given ticker has not been created.
+        /// - `ZeroCount` - if no NFTs are given.
+        /// - `InvalidMetadataAttribute` - if the number of attributes is not equal to the number set in the collection or attempting to set a value for a key not definied in the collection.
+        /// - `DuplicateMetadataKey` - if a duplicate metadata keys has been passed as input.
+        /// - `MaxSupplyExceeded` - if issuing the NFTs would exceed the maximum supply of the collection.
+        /// - `MaxNFTsPerIssueExceeded` - if more than `MaxNFTsPerIssue` NFTs are given.
+        ///
+        /// # Permissions
+        /// * Asset
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::issue_nfts(
+            nfts_metadata_attributes.len() as u32,
+            nfts_metadata_attributes.iter().map(|attributes| attributes.len()).max().unwrap_or_default() as u32,
+        )]
+        pub fn issue_nfts(origin, ticker: Ticker, nfts_metadata_attributes: Vec<Vec<NFTMetadataAttribute>>, portfolio_kind: PortfolioKind) -> DispatchResult {
+            Self::base_issue_nfts(origin, ticker, nfts_metadata_attributes, portfolio_kind)
         }
 
         /// Redeems the given NFT from the caller's portfolio.
@@ -174,6 +233,146 @@ decl_module! {
         ) -> DispatchResult {
             Self::base_controller_transfer(origin, ticker, nfts, source_portfolio, callers_portfolio_kind)
         }
+
+        /// Updates the metadata value of an NFT.
+        ///
+        /// # Arguments
+        /// * `origin` - is a signer that has permissions to act as an agent of `ticker`.
+        /// * `ticker` - the ticker of the NFT collection.
+        /// * `nft_id` - the id of the NFT being updated.
+        /// * `key` - the collection key of the value being updated.
+        /// * `value` - the new metadata value.
+        /// * `detail` - optional metadata value details (expire, lock status). If `None`, the current details are removed.
+        ///
+        /// ## Errors
+        /// - `CollectionNotFound` - if the collection associated to the given ticker has not been created.
+        /// - `NFTNotFound` - if the given NFT does not exist.
+        /// - `InvalidMetadataAttribute` - if `key` is not one of the keys defined for the collection.
+        /// - `NFTMetadataValueIsLocked` - if the value is locked for the whole collection or for the given NFT.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::set_nft_metadata_value()]
+        pub fn set_nft_metadata_value(
+            origin,
+            ticker: Ticker,
+            nft_id: NFTId,
+            key: AssetMetadataKey,
+            value: AssetMetadataValue,
+            detail: Option<AssetMetadataValueDetail<T::Moment>>
+        ) -> DispatchResult {
+            Self::base_set_nft_metadata_value(origin, ticker, nft_id, key, value, detail)
+        }
+
+        /// Freezes the given NFT, preventing it from being transferred through settlement.
+        ///
+        /// # Arguments
+        /// * `origin` - is a signer that has permissions to act as an agent of `ticker`.
+        /// * `ticker` - the ticker of the NFT collection.
+        /// * `nft_id` - the id of the NFT being frozen.
+        ///
+        /// ## Errors
+        /// - `NFTNotFound` - if the given NFT does not exist.
+        /// - `NFTAlreadyFrozen` - if the given NFT is already frozen.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::freeze_nft()]
+        pub fn freeze_nft(origin, ticker: Ticker, nft_id: NFTId) -> DispatchResult {
+            Self::base_set_nft_frozen(origin, ticker, nft_id, true)
+        }
+
+        /// Unfreezes the given NFT.
+        ///
+        /// # Arguments
+        /// * `origin` - is a signer that has permissions to act as an agent of `ticker`.
+        /// * `ticker` - the ticker of the NFT collection.
+        /// * `nft_id` - the id of the NFT being unfrozen.
+        ///
+        /// ## Errors
+        /// - `NFTNotFound` - if the given NFT does not exist.
+        /// - `NFTNotFrozen` - if the given NFT is not frozen.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::unfreeze_nft()]
+        pub fn unfreeze_nft(origin, ticker: Ticker, nft_id: NFTId) -> DispatchResult {
+            Self::base_set_nft_frozen(origin, ticker, nft_id, false)
+        }
+
+        /// Sets or removes the royalty paid when NFTs of the collection are exchanged for fungible assets in settlement.
+        ///
+        /// # Arguments
+        /// * `origin` - is a signer that has permissions to act as an agent of `ticker`.
+        /// * `ticker` - the ticker of the NFT collection.
+        /// * `royalty` - the new [`NFTRoyalty`] of the collection, or `None` to remove it.
+        ///
+        /// ## Errors
+        /// - `CollectionNotFound` - if the collection associated to the given ticker has not been created.
+        /// - `RoyaltyBeneficiaryNotFound` - if the beneficiary of the royalty is not a valid identity.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::set_collection_royalty()]
+        pub fn set_collection_royalty(origin, ticker: Ticker, royalty: Option<NFTRoyalty>) -> DispatchResult {
+            Self::base_set_collection_royalty(origin, ticker, royalty)
+        }
+
+        /// Moves an NFT into the custody portfolio and issues `fractions` of `fractions_ticker` to the caller's default portfolio.
+        ///
+        /// # Arguments
+        /// * `origin` - is a signer that has permissions to act as an agent of both `ticker` and `fractions_ticker`.
+        /// * `ticker` - the ticker of the NFT collection.
+        /// * `nft_id` - the id of the NFT being fractionalised.
+        /// * `portfolio_kind` - the portfolio that contains the NFT.
+        /// * `fractions_ticker` - a fungible asset with no supply that will represent the fractions of the NFT.
+        /// Its supply can't be changed until the fractions are redeemed.
+        /// * `fractions` - the amount of `fractions_ticker` to issue.
+        ///
+        /// ## Errors
+        /// - `CollectionNotFound` - if the collection associated to the given ticker has not been created.
+        /// - `NFTNotFound` - if the NFT does not exist in the portfolio or is locked.
+        /// - `InvalidNFTTransferFrozenNFT` - if the NFT is frozen.
+        /// - `InvalidFractionsAsset` - if `fractions_ticker` is not a fungible asset without supply.
+        /// - `ZeroFractions` - if `fractions` is zero.
+        /// - `FractionsComplianceFailure` - if the caller can't receive `fractions_ticker`.
+        ///
+        /// # Permissions
+        /// * Asset
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::fractionalise_nft()]
+        pub fn fractionalise_nft(
+            origin,
+            ticker: Ticker,
+            nft_id: NFTId,
+            portfolio_kind: PortfolioKind,
+            fractions_ticker: Ticker,
+            fractions: Balance
+        ) -> DispatchResult {
+            Self::base_fractionalise_nft(origin, ticker, nft_id, portfolio_kind, fractions_ticker, fractions)
+        }
+
+        /// Redeems all fractions of `fractions_ticker` from the caller's portfolio and releases the NFT from custody into the same portfolio.
+        ///
+        /// # Arguments
+        /// * `origin` - is a signer that has permissions to act as an agent of both the NFT's ticker and `fractions_ticker`.
+        /// * `fractions_ticker` - the fungible asset representing the fractions of the NFT.
+        /// * `portfolio_kind` - the portfolio that holds all fractions and will receive the NFT.
+        ///
+        /// ## Errors
+        /// - `NFTNotFractionalised` - if `fractions_ticker` does not represent the fractions of an NFT.
+        /// - `InsufficientFractions` - if the portfolio doesn't hold the total supply of `fractions_ticker`.
+        /// - `InvalidNFTTransferFrozenAsset` - if the NFT's asset is frozen.
+        /// - `InvalidNFTTransferFrozenNFT` - if the NFT is frozen.
+        /// - `InvalidNFTTransferComplianceFailure` - if the caller can't receive the NFT.
+        ///
+        /// # Permissions
+        /// * Asset
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::redeem_nft_fractions()]
+        pub fn redeem_nft_fractions(origin, fractions_ticker: Ticker, portfolio_kind: PortfolioKind) -> DispatchResult {
+            Self::base_redeem_nft_fractions(origin, fractions_ticker, portfolio_kind)
+        }
     }
 }
 
@@ -217,12 +416,40 @@ decl_error! {
         NFTNotFound,
         /// At least one of the metadata keys has not been registered.
         UnregisteredMetadataKey,
-        /// It is not possible to transferr zero nft.
+        /// It is not possible to transfer or issue zero nft.
         ZeroCount,
         /// An overflow while calculating the updated supply.
         SupplyOverflow,
         /// An underflow while calculating the updated supply.
-        SupplyUnderflow
+        SupplyUnderflow,
+        /// The metadata value of the NFT is locked.
+        NFTMetadataValueIsLocked,
+        /// The NFT is already frozen.
+        NFTAlreadyFrozen,
+        /// The NFT is not frozen.
+        NFTNotFrozen,
+        /// Failed to transfer an NFT - NFT is frozen.
+        InvalidNFTTransferFrozenNFT,
+        /// The beneficiary of the royalty is not a valid identity.
+        RoyaltyBeneficiaryNotFound,
+        /// The asset used for the fractions must be a fungible asset without supply.
+        InvalidFractionsAsset,
+        /// It is not possible to fractionalise an NFT into zero fractions.
+        ZeroFractions,
+        /// The caller is not compliant with the rules of the fractions asset.
+        FractionsComplianceFailure,
+        /// The ticker does not represent the fractions of an NFT.
+        NFTNotFractionalised,
+        /// The portfolio does not hold all fractions of the NFT.
+        InsufficientFractions,
+        /// The NFT is held in custody until its fractions are redeemed.
+        NFTInCustody,
+        /// The number of NFTs being issued exceeds the maximum allowed.
+        MaxNFTsPerIssueExceeded,
+        /// The maximum supply of a collection must be greater than zero.
+        ZeroMaxSupply,
+        /// Issuing the NFTs would exceed the maximum supply of the collection.
+        MaxSupplyExceeded
     }
 }
 
@@ -232,6 +459,7 @@ impl<T: Config> Module<T> {
         ticker: Ticker,
         nft_type: Option<NonFungibleType>,
         collection_keys: NFTCollectionKeys,
+        max_supply: Option<NFTCount>,
     ) -> DispatchResult {
         // Verifies if the asset has already been created and the caller's permission to create the collection
         let (create_asset, caller_did) = {
@@ -256,6 +484,9 @@ impl<T: Config> Module<T> {
             Error::<T>::CollectionAlredyRegistered
         );
 
+        // Verifies that the maximum supply, if any, allows at least one NFT
+        ensure!(max_supply != Some(0), Error::<T>::ZeroMaxSupply);
+
         // Verifies if the maximum number of keys is respected
         ensure!(
             collection_keys.len() <= (T::MaxNumberOfCollectionKeys::get() as usize),
@@ -298,6 +529,9 @@ impl<T: Config> Module<T> {
         Collection::insert(&collection_id, nft_collection);
         CollectionKeys::insert(&collection_id, collection_keys);
         CollectionTicker::insert(&ticker, &collection_id);
+        if let Some(max_supply) = max_supply {
+            CollectionMaxSupply::insert(&ticker, max_supply);
+        }
 
         Self::deposit_event(Event::NftCollectionCreated(
             caller_did,
@@ -307,10 +541,10 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
-    fn base_issue_nft(
+    fn base_issue_nfts(
         origin: T::RuntimeOrigin,
         ticker: Ticker,
-        metadata_attributes: Vec<NFTMetadataAttribute>,
+        nfts_metadata_attributes: Vec<Vec<NFTMetadataAttribute>>,
         portfolio_kind: PortfolioKind,
     ) -> DispatchResult {
         // Verifies if the collection exists
@@ -327,8 +561,64 @@ impl<T: Config> Module<T> {
 
         Portfolio::<T>::ensure_portfolio_validity(&caller_portfolio)?;
 
+        ensure!(!nfts_metadata_attributes.is_empty(), Error::<T>::ZeroCount);
+        ensure!(
+            nfts_metadata_attributes.len() <= T::MaxNFTsPerIssue::get() as usize,
+            Error::<T>::MaxNFTsPerIssueExceeded
+        );
+
         // Verifies that all mandatory keys are being set and that there are no duplicated keys
         let mandatory_keys: BTreeSet<AssetMetadataKey> = Self::collection_keys(&collection_id);
+        let nfts_attributes = nfts_metadata_attributes
+            .into_iter()
+            .map(|metadata_attributes| {
+                Self::ensure_valid_metadata_attributes(&mandatory_keys, metadata_attributes)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Verifies that the maximum supply of the collection is respected
+        let n_nfts = nfts_attributes.len() as NFTCount;
+        let new_supply = NFTsInCollection::get(&ticker)
+            .checked_add(n_nfts)
+            .ok_or(Error::<T>::SupplyOverflow)?;
+        if let Some(max_supply) = CollectionMaxSupply::get(&ticker) {
+            ensure!(new_supply <= max_supply, Error::<T>::MaxSupplyExceeded);
+        }
+        let new_balance = NumberOfNFTs::get(&ticker, &caller_portfolio.did)
+            .checked_add(n_nfts)
+            .ok_or(Error::<T>::BalanceOverflow)?;
+
+        // Mints the NFTs and adds them to the caller's portfolio
+        let mut nft_ids = Vec::with_capacity(nfts_attributes.len());
+        for nft_attributes in nfts_attributes {
+            let nft_id = NextNFTId::try_mutate(&collection_id, try_next_pre::<T, _>)?;
+            for (metadata_key, metadata_value) in nft_attributes.into_iter() {
+                MetadataValue::insert((&collection_id, &nft_id), metadata_key, metadata_value);
+            }
+            PortfolioNFT::insert(caller_portfolio, (ticker, nft_id), true);
+            NFTOwner::insert(ticker, nft_id, caller_portfolio);
+            nft_ids.push(nft_id);
+        }
+        NFTsInCollection::insert(&ticker, new_supply);
+        NumberOfNFTs::insert(&ticker, &caller_portfolio.did, new_balance);
+
+        Self::deposit_event(Event::NFTPortfolioUpdated(
+            caller_portfolio.did,
+            NFTs::new_unverified(ticker, nft_ids),
+            None,
+            Some(caller_portfolio),
+            PortfolioUpdateReason::Issued {
+                funding_round_name: None,
+            },
+        ));
+        Ok(())
+    }
+
+    /// Returns the NFT attributes as a map, if all mandatory keys are set exactly once.
+    fn ensure_valid_metadata_attributes(
+        mandatory_keys: &BTreeSet<AssetMetadataKey>,
+        metadata_attributes: Vec<NFTMetadataAttribute>,
+    ) -> Result<BTreeMap<AssetMetadataKey, AssetMetadataValue>, DispatchError> {
         ensure!(
             mandatory_keys.len() == metadata_attributes.len(),
             Error::<T>::InvalidMetadataAttribute
@@ -350,33 +640,7 @@ impl<T: Config> Module<T> {
                 Error::<T>::InvalidMetadataAttribute
             );
         }
-
-        // Mints the NFT and adds it to the caller's portfolio
-        let new_supply = NFTsInCollection::get(&ticker)
-            .checked_add(1)
-            .ok_or(Error::<T>::SupplyOverflow)?;
-        let new_balance = NumberOfNFTs::get(&ticker, &caller_portfolio.did)
-            .checked_add(1)
-            .ok_or(Error::<T>::BalanceOverflow)?;
-        let nft_id = NextNFTId::try_mutate(&collection_id, try_next_pre::<T, _>)?;
-        NFTsInCollection::insert(&ticker, new_supply);
-        NumberOfNFTs::insert(&ticker, &caller_portfolio.did, new_balance);
-        for (metadata_key, metadata_value) in nft_attributes.into_iter() {
-            MetadataValue::insert((&collection_id, &nft_id), metadata_key, metadata_value);
-        }
-        PortfolioNFT::insert(caller_portfolio, (ticker, nft_id), true);
-        NFTOwner::insert(ticker, nft_id, caller_portfolio);
-
-        Self::deposit_event(Event::NFTPortfolioUpdated(
-            caller_portfolio.did,
-            NFTs::new_unverified(ticker, vec![nft_id]),
-            None,
-            Some(caller_portfolio),
-            PortfolioUpdateReason::Issued {
-                funding_round_name: None,
-            },
-        ));
-        Ok(())
+        Ok(nft_attributes)
     }
 
     fn base_redeem_nft(
@@ -415,7 +679,10 @@ impl<T: Config> Module<T> {
         PortfolioNFT::remove(&caller_portfolio, (&ticker, &nft_id));
         #[allow(deprecated)]
         MetadataValue::remove_prefix((&collection_id, &nft_id), None);
+        #[allow(deprecated)]
+        MetadataValueDetails::<T>::remove_prefix((&collection_id, &nft_id), None);
         NFTOwner::remove(ticker, nft_id);
+        FrozenNFT::remove(ticker, nft_id);
 
         Self::deposit_event(Event::NFTPortfolioUpdated(
             caller_portfolio.did,
@@ -457,7 +724,7 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
-    /// Returns `Ok` if the asset is not frozen, if `sender_portfolio` owns all `nfts`, all arithmetic updates succeed,
+    /// Returns `Ok` if neither the asset nor the `nfts` are frozen, if `sender_portfolio` owns all `nfts`, all arithmetic updates succeed,
     /// if `sender_portfolio` is different from `receiver_portfolio`, and if all compliance rules are being respected.
     pub fn validate_nft_transfer(
         sender_portfolio: &PortfolioId,
@@ -470,6 +737,13 @@ impl<T: Config> Module<T> {
             !Frozen::get(nfts.ticker()),
             Error::<T>::InvalidNFTTransferFrozenAsset
         );
+        // Verifies that none of the nfts are frozen
+        for nft_id in nfts.ids() {
+            ensure!(
+                !FrozenNFT::get(nfts.ticker(), nft_id),
+                Error::<T>::InvalidNFTTransferFrozenNFT
+            );
+        }
         // Verifies that the sender_portfolio owns all nfts being transferred
         Self::validate_nft_ownership(sender_portfolio, receiver_portfolio, nfts)?;
         // Verifies that all compliance rules are being respected
@@ -577,6 +851,11 @@ impl<T: Config> Module<T> {
             callers_portfolio_kind,
             true,
         )?;
+        // NFTs in custody can only be released by redeeming their fractions
+        ensure!(
+            source_portfolio != Self::custody_portfolio(),
+            Error::<T>::NFTInCustody
+        );
         // Verifies if all rules for transfering the NFTs are being respected
         Self::validate_nft_ownership(&source_portfolio, &caller_portfolio, &nfts)?;
         // Transfer ownership of the NFTs
@@ -591,6 +870,339 @@ impl<T: Config> Module<T> {
         ));
         Ok(())
     }
+
+    fn base_set_nft_metadata_value(
+        origin: T::RuntimeOrigin,
+        ticker: Ticker,
+        nft_id: NFTId,
+        key: AssetMetadataKey,
+        value: AssetMetadataValue,
+        detail: Option<AssetMetadataValueDetail<T::Moment>>,
+    ) -> DispatchResult {
+        // Verifies if the collection exists
+        let collection_id =
+            CollectionTicker::try_get(&ticker).map_err(|_| Error::<T>::CollectionNotFound)?;
+
+        // Ensure the caller has the correct permissions for this asset.
+        let caller_did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
+
+        // Verifies if the NFT exists
+        ensure!(
+            NFTOwner::contains_key(&ticker, &nft_id),
+            Error::<T>::NFTNotFound
+        );
+
+        // Verifies that the key belongs to the collection and that the new value is within limits
+        ensure!(
+            CollectionKeys::get(&collection_id).contains(&key),
+            Error::<T>::InvalidMetadataAttribute
+        );
+        Asset::<T>::ensure_asset_metadata_value_limited(&value)?;
+
+        // A locked asset metadata value locks the key for all NFTs in the collection
+        ensure!(
+            !Asset::<T>::is_asset_metadata_locked(ticker, key),
+            Error::<T>::NFTMetadataValueIsLocked
+        );
+        ensure!(
+            !Self::is_nft_metadata_locked(&collection_id, &nft_id, &key),
+            Error::<T>::NFTMetadataValueIsLocked
+        );
+
+        MetadataValue::insert((&collection_id, &nft_id), &key, &value);
+        match detail {
+            Some(detail) => {
+                MetadataValueDetails::<T>::insert((&collection_id, &nft_id), &key, detail)
+            }
+            None => MetadataValueDetails::<T>::remove((&collection_id, &nft_id), &key),
+        }
+
+        Self::deposit_event(Event::NFTMetadataValueUpdated(
+            caller_did, ticker, nft_id, key, value,
+        ));
+        Ok(())
+    }
+
+    /// Returns `true` if the metadata value of `key` for the given NFT is locked.
+    fn is_nft_metadata_locked(
+        collection_id: &NFTCollectionId,
+        nft_id: &NFTId,
+        key: &AssetMetadataKey,
+    ) -> bool {
+        MetadataValueDetails::<T>::get((collection_id, nft_id), key).map_or(false, |details| {
+            details.is_locked(<pallet_timestamp::Pallet<T>>::get())
+        })
+    }
+
+    fn base_set_nft_frozen(
+        origin: T::RuntimeOrigin,
+        ticker: Ticker,
+        nft_id: NFTId,
+        freeze: bool,
+    ) -> DispatchResult {
+        // Ensure the caller has the correct permissions for this asset.
+        let caller_did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
+
+        // Verifies if the NFT exists
+        ensure!(
+            NFTOwner::contains_key(&ticker, &nft_id),
+            Error::<T>::NFTNotFound
+        );
+
+        if freeze {
+            ensure!(
+                !FrozenNFT::get(&ticker, &nft_id),
+                Error::<T>::NFTAlreadyFrozen
+            );
+            FrozenNFT::insert(&ticker, &nft_id, true);
+            Self::deposit_event(Event::NFTFrozen(caller_did, ticker, nft_id));
+        } else {
+            ensure!(FrozenNFT::get(&ticker, &nft_id), Error::<T>::NFTNotFrozen);
+            FrozenNFT::remove(&ticker, &nft_id);
+            Self::deposit_event(Event::NFTUnfrozen(caller_did, ticker, nft_id));
+        }
+        Ok(())
+    }
+
+    fn base_set_collection_royalty(
+        origin: T::RuntimeOrigin,
+        ticker: Ticker,
+        royalty: Option<NFTRoyalty>,
+    ) -> DispatchResult {
+        // Verifies if the collection exists
+        ensure!(
+            CollectionTicker::contains_key(&ticker),
+            Error::<T>::CollectionNotFound
+        );
+
+        // Ensure the caller has the correct permissions for this asset.
+        let caller_did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
+
+        match &royalty {
+            Some(royalty) => {
+                ensure!(
+                    Identity::<T>::is_identity_exists(&royalty.beneficiary),
+                    Error::<T>::RoyaltyBeneficiaryNotFound
+                );
+                CollectionRoyalty::insert(&ticker, royalty);
+            }
+            None => CollectionRoyalty::remove(&ticker),
+        }
+
+        Self::deposit_event(Event::CollectionRoyaltyUpdated(caller_did, ticker, royalty));
+        Ok(())
+    }
+
+    /// Returns the NFTs of the collection identified by `ticker` with ids from `start` (inclusive) to `start + limit` (exclusive),
+    /// along with the id to start the next page at, if there are more ids to check.
+    /// Redeemed NFTs are skipped. Returns an empty vector if the collection doesn't exist.
+    pub fn collection_nfts(
+        ticker: &Ticker,
+        start: Option<NFTId>,
+        limit: u32,
+    ) -> (Vec<NFTId>, Option<NFTId>) {
+        let collection_id = match CollectionTicker::try_get(ticker) {
+            Ok(collection_id) => collection_id,
+            Err(_) => return (Vec::new(), None),
+        };
+        let last_id = NextNFTId::get(&collection_id).0;
+        let first_id = start.map_or(1, |id| id.0.max(1));
+        let end_id = last_id.min(first_id.saturating_add(limit as u64).saturating_sub(1));
+        let nfts = (first_id..=end_id)
+            .map(NFTId)
+            .filter(|nft_id| NFTOwner::contains_key(ticker, nft_id))
+            .collect();
+        let next_id = (end_id < last_id).then(|| NFTId(end_id + 1));
+        (nfts, next_id)
+    }
+
+    /// Returns up to `limit` NFTs held by `portfolio`, following `after` in storage order.
+    /// The last NFT returned can be used as `after` to get the next page.
+    pub fn portfolio_nfts(
+        portfolio: &PortfolioId,
+        after: Option<(Ticker, NFTId)>,
+        limit: u32,
+    ) -> Vec<(Ticker, NFTId)> {
+        match after {
+            Some(after) => PortfolioNFT::iter_prefix_from(
+                portfolio,
+                PortfolioNFT::hashed_key_for(portfolio, after),
+            ),
+            None => PortfolioNFT::iter_prefix(portfolio),
+        }
+        .take(limit as usize)
+        .map(|(ticker_nft, _)| ticker_nft)
+        .collect()
+    }
+
+    /// Returns all metadata attributes of the given NFT or `None` if the NFT doesn't exist.
+    pub fn nft_metadata(ticker: &Ticker, nft_id: &NFTId) -> Option<Vec<NFTMetadataAttribute>> {
+        if !NFTOwner::contains_key(ticker, nft_id) {
+            return None;
+        }
+        let collection_id = CollectionTicker::get(ticker);
+        Some(
+            MetadataValue::iter_prefix((collection_id, nft_id))
+                .map(|(key, value)| NFTMetadataAttribute { key, value })
+                .collect(),
+        )
+    }
+
+    /// Returns the [`PortfolioId`] that holds all fractionalised NFTs.
+    pub fn custody_portfolio() -> PortfolioId {
+        PortfolioId::default_portfolio(SystematicIssuers::NFTCustody.as_id())
+    }
+
+    fn base_fractionalise_nft(
+        origin: T::RuntimeOrigin,
+        ticker: Ticker,
+        nft_id: NFTId,
+        portfolio_kind: PortfolioKind,
+        fractions_ticker: Ticker,
+        fractions: Balance,
+    ) -> DispatchResult {
+        // Verifies if the collection exists
+        CollectionTicker::try_get(&ticker).map_err(|_| Error::<T>::CollectionNotFound)?;
+
+        // Ensure origin is agent with custody and permissions for portfolio.
+        let caller_portfolio = Asset::<T>::ensure_origin_ticker_and_portfolio_permissions(
+            origin.clone(),
+            ticker,
+            portfolio_kind,
+            true,
+        )?;
+
+        // Verifies that the NFT is in the portfolio, is not locked and is not frozen
+        ensure!(
+            PortfolioNFT::contains_key(&caller_portfolio, (&ticker, &nft_id)),
+            Error::<T>::NFTNotFound
+        );
+        ensure!(
+            !PortfolioLockedNFT::contains_key(&caller_portfolio, (&ticker, &nft_id)),
+            Error::<T>::NFTNotFound
+        );
+        ensure!(
+            !FrozenNFT::get(&ticker, &nft_id),
+            Error::<T>::InvalidNFTTransferFrozenNFT
+        );
+
+        // Verifies that the fractions are a new fungible asset the caller is allowed to receive
+        ensure!(fractions > 0, Error::<T>::ZeroFractions);
+        ensure!(
+            Asset::<T>::nft_asset(&fractions_ticker) == Some(false)
+                && Asset::<T>::total_supply(fractions_ticker) == 0
+                && !FractionalisedNFT::contains_key(&fractions_ticker),
+            Error::<T>::InvalidFractionsAsset
+        );
+        let mut weight_meter = WeightMeter::max_limit_no_minimum();
+        ensure!(
+            T::Compliance::is_compliant(
+                &fractions_ticker,
+                caller_portfolio.did,
+                caller_portfolio.did,
+                &mut weight_meter,
+            )?,
+            Error::<T>::FractionsComplianceFailure
+        );
+
+        // Moves the NFT into custody and issues the fractions
+        let custody_portfolio = Self::custody_portfolio();
+        let nfts = NFTs::new_unverified(ticker, vec![nft_id]);
+        Self::unverified_nfts_transfer(&caller_portfolio, &custody_portfolio, &nfts);
+        Asset::<T>::issue(origin, fractions_ticker, fractions, PortfolioKind::Default)?;
+        FractionalisedNFT::insert(&fractions_ticker, (ticker, nft_id));
+
+        Self::deposit_event(Event::NFTPortfolioUpdated(
+            caller_portfolio.did,
+            nfts,
+            Some(caller_portfolio),
+            Some(custody_portfolio),
+            PortfolioUpdateReason::Transferred {
+                instruction_id: None,
+                instruction_memo: None,
+            },
+        ));
+        Self::deposit_event(Event::NFTFractionalised(
+            caller_portfolio.did,
+            ticker,
+            nft_id,
+            fractions_ticker,
+            fractions,
+        ));
+        Ok(())
+    }
+
+    fn base_redeem_nft_fractions(
+        origin: T::RuntimeOrigin,
+        fractions_ticker: Ticker,
+        portfolio_kind: PortfolioKind,
+    ) -> DispatchResult {
+        let (ticker, nft_id) =
+            FractionalisedNFT::get(&fractions_ticker).ok_or(Error::<T>::NFTNotFractionalised)?;
+
+        // Ensure origin is agent with custody and permissions for portfolio.
+        let caller_portfolio = Asset::<T>::ensure_origin_ticker_and_portfolio_permissions(
+            origin.clone(),
+            ticker,
+            portfolio_kind,
+            true,
+        )?;
+
+        // Verifies that the portfolio holds all fractions
+        let fractions = Asset::<T>::total_supply(fractions_ticker);
+        ensure!(
+            Portfolio::<T>::portfolio_asset_balances(&caller_portfolio, &fractions_ticker)
+                >= fractions,
+            Error::<T>::InsufficientFractions
+        );
+
+        // Verifies that the NFT can be released to the caller
+        ensure!(
+            !Frozen::get(&ticker),
+            Error::<T>::InvalidNFTTransferFrozenAsset
+        );
+        ensure!(
+            !FrozenNFT::get(&ticker, &nft_id),
+            Error::<T>::InvalidNFTTransferFrozenNFT
+        );
+        let mut weight_meter = WeightMeter::max_limit_no_minimum();
+        ensure!(
+            T::Compliance::is_compliant(
+                &ticker,
+                caller_portfolio.did,
+                caller_portfolio.did,
+                &mut weight_meter,
+            )?,
+            Error::<T>::InvalidNFTTransferComplianceFailure
+        );
+
+        // Redeems the fractions and releases the NFT from custody.
+        // The supply of the fractions is locked until the NFT is removed from custody.
+        FractionalisedNFT::remove(&fractions_ticker);
+        Asset::<T>::redeem_from_portfolio(origin, fractions_ticker, fractions, portfolio_kind)?;
+        let custody_portfolio = Self::custody_portfolio();
+        let nfts = NFTs::new_unverified(ticker, vec![nft_id]);
+        Self::unverified_nfts_transfer(&custody_portfolio, &caller_portfolio, &nfts);
+
+        Self::deposit_event(Event::NFTPortfolioUpdated(
+            caller_portfolio.did,
+            nfts,
+            Some(custody_portfolio),
+            Some(caller_portfolio),
+            PortfolioUpdateReason::Transferred {
+                instruction_id: None,
+                instruction_memo: None,
+            },
+        ));
+        Self::deposit_event(Event::NFTFractionsRedeemed(
+            caller_portfolio.did,
+            ticker,
+            nft_id,
+            fractions_ticker,
+            fractions,
+        ));
+        Ok(())
+    }
 }
 
 impl<T: Config> NFTTrait<T::RuntimeOrigin> for Module<T> {
@@ -604,6 +1216,10 @@ impl<T: Config> NFTTrait<T::RuntimeOrigin> for Module<T> {
         }
     }
 
+    fn is_fractions_asset(ticker: &Ticker) -> bool {
+        FractionalisedNFT::contains_key(ticker)
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     fn create_nft_collection(
         origin: T::RuntimeOrigin,
@@ -611,7 +1227,7 @@ impl<T: Config> NFTTrait<T::RuntimeOrigin> for Module<T> {
         nft_type: Option<NonFungibleType>,
         collection_keys: NFTCollectionKeys,
     ) -> DispatchResult {
-        Module::<T>::create_nft_collection(origin, ticker, nft_type, collection_keys)
+        Module::<T>::create_nft_collection(origin, ticker, nft_type, collection_keys, None)
     }
 }
 
@@ -620,6 +1236,7 @@ pub mod migration {
     use crate::{Config, NFTOwner, NFTsInCollection, NumberOfNFTs};
     use frame_support::storage::{StorageDoubleMap, StorageMap};
     use pallet_portfolio::PortfolioNFT;
+    use polymesh_common_utilities::SystematicIssuers;
     use sp_runtime::runtime_logger::RuntimeLogger;
 
     pub fn migrate_to_v1<T: Config>() {
@@ -630,6 +1247,12 @@ pub mod migration {
         log::info!(">>> NFTsInCollection and NFTOwner were successfully updated");
     }
 
+    pub fn migrate_to_v2<T: Config>() {
+        RuntimeLogger::init();
+        log::info!(">>> Registering the NFT custody identity");
+        pallet_identity::Module::<T>::ensure_systematic_id(SystematicIssuers::NFTCustody);
+    }
+
     fn initialize_nfts_in_collection<T: Config>() {
         for (ticker, _, id_count) in NumberOfNFTs::iter() {
             NFTsInCollection::mutate(ticker, |collection_count| *collection_count += id_count);
@@ -31,11 +31,13 @@ use frame_support::{
     traits::{CallMetadata, GetCallMetadata},
 };
 use polymesh_common_utilities::traits::{AccountCallPermissionsData, CheckAccountCallPermissions};
-use polymesh_primitives::{DispatchableName, PalletName};
+use polymesh_primitives::{DispatchableName, PalletName, TransactionError};
 use scale_info::TypeInfo;
 use sp_runtime::{
     traits::{DispatchInfoOf, PostDispatchInfoOf, SignedExtension},
-    transaction_validity::{TransactionValidity, TransactionValidityError, ValidTransaction},
+    transaction_validity::{
+        InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+    },
 };
 use sp_std::{fmt, marker::PhantomData, result::Result, vec};
 
@@ -134,21 +136,35 @@ where
 
     fn validate(
         &self,
-        _: &Self::AccountId,
+        who: &Self::AccountId,
         _: &Self::Call,
         _: &DispatchInfoOf<Self::Call>,
         _: usize,
     ) -> TransactionValidity {
+        // Keep transactions of keys without calls left out of the pool.
+        if !T::Checker::has_extrinsic_calls_left(who) {
+            return Err(InvalidTransaction::Custom(
+                TransactionError::SessionKeyCallsExhausted as u8,
+            )
+            .into());
+        }
         Ok(ValidTransaction::default())
     }
 
     fn pre_dispatch(
         self,
-        _: &Self::AccountId,
+        who: &Self::AccountId,
         call: &Self::Call,
         _: &DispatchInfoOf<Self::Call>,
         _: usize,
     ) -> Result<Self::Pre, TransactionValidityError> {
+        // Keys with a limited number of calls, like session keys, are charged one call.
+        if !T::Checker::note_extrinsic_call(who) {
+            return Err(InvalidTransaction::Custom(
+                TransactionError::SessionKeyCallsExhausted as u8,
+            )
+            .into());
+        }
         let metadata = call.get_call_metadata();
         Self::set_call_metadata(
             metadata.pallet_name.as_bytes().into(),
@@ -715,7 +715,7 @@ decl_module! {
         /// * `proposal` a dispatchable call
         /// * `deposit` minimum deposit value, which is ignored if `proposer` is a committee.
         /// * `url` a link to a website for proposal discussion
-        #[weight = <T as Config>::WeightInfo::propose_from_community()]
+        #[weight = <T as Config>::WeightInfo::propose_from_community().saturating_add(T::ProtocolFee::charge_fee_weight())]
         pub fn propose(
             origin,
             proposal: Box<T::Proposal>,
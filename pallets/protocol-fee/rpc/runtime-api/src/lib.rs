@@ -19,6 +19,7 @@
 
 use codec::{Decode, Encode};
 use polymesh_common_utilities::protocol_fee::ProtocolOp;
+use polymesh_primitives::IdentityId;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::traits::{SaturatedConversion, UniqueSaturatedInto};
@@ -37,7 +38,13 @@ impl<T: UniqueSaturatedInto<u64>> From<T> for CappedFee {
 }
 
 sp_api::decl_runtime_apis! {
+    #[api_version(2)]
     pub trait ProtocolFeeApi {
+        /// Returns the fee of `op`, without any identity discount or waiver.
         fn compute_fee(op: ProtocolOp) -> CappedFee;
+
+        /// Returns the fee of `op` for the identity `did`, after its volume discount or fee waiver.
+        #[api_version(2)]
+        fn compute_fee_for_identity(op: ProtocolOp, did: IdentityId) -> CappedFee;
     }
 }
@@ -21,6 +21,7 @@ use jsonrpsee::{
 use node_rpc::Error;
 pub use pallet_protocol_fee_rpc_runtime_api::{CappedFee, ProtocolFeeApi as ProtocolFeeRuntimeApi};
 use polymesh_common_utilities::protocol_fee::ProtocolOp;
+use polymesh_primitives::IdentityId;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::traits::Block as BlockT;
@@ -30,6 +31,14 @@ use std::sync::Arc;
 pub trait ProtocolFeeApi<BlockHash> {
     #[method(name = "protocolFee_computeFee")]
     fn compute_fee(&self, op: ProtocolOp, at: Option<BlockHash>) -> RpcResult<CappedFee>;
+
+    #[method(name = "protocolFee_computeFeeForIdentity")]
+    fn compute_fee_for_identity(
+        &self,
+        op: ProtocolOp,
+        did: IdentityId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<CappedFee>;
 }
 
 /// A struct that implements the [`ProtocolFeeApi`].
@@ -73,4 +82,25 @@ where
             .into()
         })
     }
+
+    fn compute_fee_for_identity(
+        &self,
+        op: ProtocolOp,
+        did: IdentityId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<CappedFee> {
+        let api = self.client.runtime_api();
+        let at_hash = at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash);
+
+        api.compute_fee_for_identity(at_hash, op, did).map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                Error::RuntimeError.into(),
+                "Unable to query dispatch info.",
+                Some(e.to_string()),
+            ))
+            .into()
+        })
+    }
 }
@@ -16,10 +16,15 @@
 use crate::*;
 use frame_benchmarking::benchmarks;
 use frame_system::RawOrigin;
+use polymesh_common_utilities::benchs::{user, AccountIdOf};
 use polymesh_common_utilities::protocol_fee::ProtocolOp;
+use polymesh_common_utilities::traits::TestUtilsFn;
 use polymesh_primitives::PosRatio;
+use sp_runtime::Permill;
 
 benchmarks! {
+    where_clause { where T: TestUtilsFn<AccountIdOf<T>> }
+
     change_coefficient {
         let origin = RawOrigin::Root;
         let coefficient = PosRatio::from((0, 0));
@@ -29,4 +34,53 @@ benchmarks! {
         let origin = RawOrigin::Root;
         let op = ProtocolOp::AssetRegisterTicker;
     }: _(origin, op, 0)
+
+    set_discount_tiers {
+        let t in 0 .. T::MaxDiscountTiers::get();
+
+        let origin = RawOrigin::Root;
+        let op = ProtocolOp::AssetRegisterTicker;
+        let tiers = (0..t)
+            .map(|i| DiscountTier {
+                min_ops: (i + 1) * 10,
+                discount: Permill::from_percent(i + 1),
+            })
+            .collect::<Vec<_>>();
+    }: _(origin, op, tiers)
+
+    set_discount_period {
+        let origin = RawOrigin::Root;
+    }: _(origin, 100u32.into())
+
+    set_fee_waiver {
+        let origin = RawOrigin::Root;
+        let op = ProtocolOp::AssetRegisterTicker;
+    }: _(origin, IdentityId::from(1), op, true)
+
+    charge_fee {
+        // The payer has an identity and reached the last of the most discount tiers.
+        let payer = user::<T>("payer", 0);
+        let did = payer.did();
+        let op = ProtocolOp::AssetRegisterTicker;
+        let max_tiers = T::MaxDiscountTiers::get();
+        let tiers = (0..max_tiers)
+            .map(|i| DiscountTier {
+                min_ops: i + 1,
+                discount: Permill::from_percent(i + 1),
+            })
+            .collect::<Vec<_>>();
+        BaseFees::insert(op, 1_000);
+        DiscountTiers::insert(op, tiers);
+        DiscountPeriod::<T>::put(T::BlockNumber::from(100u32));
+        OpsUsage::<T>::insert(did, op, OpsCount {
+            period_start: frame_system::Pallet::<T>::block_number(),
+            count: max_tiers,
+        });
+        T::CddHandler::set_payer_context(Some(payer.account()));
+    }: {
+        Module::<T>::charge_fees(&[op]).unwrap();
+    }
+    verify {
+        assert_eq!(Module::<T>::ops_usage(did, op).count, max_tiers + 1);
+    }
 }
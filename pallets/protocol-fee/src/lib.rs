@@ -26,10 +26,14 @@
 //!
 //! - [change_coefficient](Module::change_coefficient) - It changes the fee coefficient.
 //! - [change_base_fee](Module::change_base_fee) - It changes the base fee.
+//! - [set_discount_tiers](Module::set_discount_tiers) - It sets the volume discount tiers of an operation.
+//! - [set_discount_period](Module::set_discount_period) - It sets the period over which operations are counted.
+//! - [set_fee_waiver](Module::set_fee_waiver) - It waives, or stops waiving, an operation fee for an identity.
 //!
 //! ### Public Functions
 //!
-//! - [compute_fee](Module::compute_fee) - It computes the fee of the operation.
+//! - [compute_fee](Module::compute_fee) - It computes the fee of the operation for the current payer.
+//! - [compute_fee_for](Module::compute_fee_for) - It computes the fee of the operation for an identity.
 //! - [charge_fee](Module::charge_fee) - It calculates the fee and charges it.
 //! - [batch_charge_fee](Module::batch_charge_fee) - It calculates the fee and charges it on a batch operation.
 //!
@@ -38,10 +42,12 @@
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
 
+use codec::{Decode, Encode};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
-    dispatch::{DispatchError, DispatchResult},
-    traits::{Currency, ExistenceRequirement, OnUnbalanced, WithdrawReasons},
+    dispatch::{DispatchClass, DispatchError, DispatchResult},
+    ensure,
+    traits::{Currency, ExistenceRequirement, Get, OnUnbalanced, WithdrawReasons},
     weights::Weight,
 };
 use frame_system::ensure_root;
@@ -49,22 +55,51 @@ use polymesh_common_utilities::{
     identity::Config as IdentityConfig,
     protocol_fee::{ChargeProtocolFee, ProtocolOp},
     traits::relayer::SubsidiserTrait,
-    transaction_payment::CddAndFeeDetails,
+    transaction_payment::{CddAndFeeDetails, FeeAssetPayer},
     GC_DID,
 };
 use polymesh_primitives::{Balance, IdentityId, PosRatio};
-use sp_runtime::{traits::Zero, Perbill};
-use sp_std::vec;
+use scale_info::TypeInfo;
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    Perbill, Permill,
+};
+use sp_std::{vec, vec::Vec};
+
+type Identity<T> = pallet_identity::Module<T>;
 
 type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
     <T as frame_system::Config>::AccountId,
 >>::NegativeImbalance;
-/// Either an imbalance or an error.
-type WithdrawFeeResult<T> = sp_std::result::Result<NegativeImbalanceOf<T>, DispatchError>;
+/// Either an imbalance, if the fee was paid in POLYX, or an error.
+type WithdrawFeeResult<T> = sp_std::result::Result<Option<NegativeImbalanceOf<T>>, DispatchError>;
 
 pub trait WeightInfo {
     fn change_coefficient() -> Weight;
     fn change_base_fee() -> Weight;
+    fn set_discount_tiers(t: u32) -> Weight;
+    fn set_discount_period() -> Weight;
+    fn set_fee_waiver() -> Weight;
+    fn charge_fee() -> Weight;
+}
+
+/// A volume discount applied to the base fee of an operation once an identity
+/// has performed at least `min_ops` of those operations in the current period.
+#[derive(Encode, Decode, TypeInfo, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct DiscountTier {
+    /// The number of operations already performed in the period for the tier to apply.
+    pub min_ops: u32,
+    /// The discount applied to the base fee.
+    pub discount: Permill,
+}
+
+/// The number of operations performed by an identity in a period.
+#[derive(Encode, Decode, TypeInfo, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct OpsCount<BlockNumber> {
+    /// The block at which the period started.
+    pub period_start: BlockNumber,
+    /// The number of operations performed since `period_start`.
+    pub count: u32,
 }
 
 pub trait Config: frame_system::Config + IdentityConfig {
@@ -78,6 +113,11 @@ pub trait Config: frame_system::Config + IdentityConfig {
     /// Connection to the `Relayer` pallet.
     /// Used to charge protocol fees to a subsidiser, if any, instead of the payer.
     type Subsidiser: SubsidiserTrait<Self::AccountId>;
+    /// Connection to the `Asset` pallet.
+    /// Used to charge protocol fees in the fee asset chosen by the payer, if any.
+    type FeeAssetPayer: FeeAssetPayer<Self::AccountId>;
+    /// Maximum number of discount tiers of an operation.
+    type MaxDiscountTiers: Get<u32>;
 }
 
 decl_error! {
@@ -88,6 +128,8 @@ decl_error! {
         UnHandledImbalances,
         /// Insufficient subsidy balance to pay the fee.
         InsufficientSubsidyBalance,
+        /// The discount tiers are too many or not strictly increasing in `min_ops` and discount.
+        InvalidDiscountTiers,
     }
 }
 
@@ -103,12 +145,25 @@ decl_storage! {
                 config.coefficient
             }
         }): PosRatio;
+        /// The volume discount tiers of an operation, sorted by `min_ops`.
+        pub DiscountTiers get(fn discount_tiers):
+            map hasher(twox_64_concat) ProtocolOp => Vec<DiscountTier>;
+        /// The length, in blocks, of the period over which operations are counted for discounts.
+        /// A period of zero means that the count is never reset.
+        pub DiscountPeriod get(fn discount_period): T::BlockNumber;
+        /// The number of operations performed by an identity in the current period.
+        pub OpsUsage get(fn ops_usage):
+            double_map hasher(identity) IdentityId, hasher(twox_64_concat) ProtocolOp => OpsCount<T::BlockNumber>;
+        /// The operations whose fee is waived for an identity.
+        pub FeeWaivers get(fn fee_waived):
+            double_map hasher(identity) IdentityId, hasher(twox_64_concat) ProtocolOp => bool;
     }
 }
 
 decl_event! {
     pub enum Event<T> where
         AccountId = <T as frame_system::Config>::AccountId,
+        BlockNumber = <T as frame_system::Config>::BlockNumber,
     {
         /// The protocol fee of an operation.
         FeeSet(IdentityId, Balance),
@@ -116,6 +171,15 @@ decl_event! {
         CoefficientSet(IdentityId, PosRatio),
         /// Fee charged.
         FeeCharged(AccountId, Balance),
+        /// The volume discount tiers of an operation.
+        /// (GC DID, op, tiers)
+        DiscountTiersSet(IdentityId, ProtocolOp, Vec<DiscountTier>),
+        /// The period over which operations are counted for discounts.
+        /// (GC DID, period)
+        DiscountPeriodSet(IdentityId, BlockNumber),
+        /// An operation fee was waived, or is no longer waived, for an identity.
+        /// (GC DID, identity, op, waived)
+        FeeWaiverSet(IdentityId, IdentityId, ProtocolOp, bool),
     }
 }
 
@@ -146,20 +210,137 @@ decl_module! {
             BaseFees::insert(op, &base_fee);
             Self::deposit_event(Event::<T>::FeeSet(GC_DID, base_fee));
         }
+
+        /// Sets the volume discount tiers of `op` for the root origin.
+        /// An empty `tiers` removes all discounts of `op`.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - Only root allowed.
+        /// * `InvalidDiscountTiers` - `tiers` are too many, or are not strictly increasing
+        ///   in both `min_ops` and `discount`.
+        #[weight = <T as Config>::WeightInfo::set_discount_tiers(tiers.len() as u32)]
+        pub fn set_discount_tiers(origin, op: ProtocolOp, tiers: Vec<DiscountTier>) {
+            ensure_root(origin)?;
+            ensure!(
+                tiers.len() <= T::MaxDiscountTiers::get() as usize
+                    && tiers.windows(2).all(|w| w[0].min_ops < w[1].min_ops && w[0].discount < w[1].discount),
+                Error::<T>::InvalidDiscountTiers
+            );
+            if tiers.is_empty() {
+                DiscountTiers::remove(op);
+            } else {
+                DiscountTiers::insert(op, &tiers);
+            }
+            Self::deposit_event(Event::<T>::DiscountTiersSet(GC_DID, op, tiers));
+        }
+
+        /// Sets the period, in blocks, over which operations are counted for discounts
+        /// for the root origin. A period of zero means that the count is never reset.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - Only root allowed.
+        #[weight = <T as Config>::WeightInfo::set_discount_period()]
+        pub fn set_discount_period(origin, period: T::BlockNumber) {
+            ensure_root(origin)?;
+            DiscountPeriod::<T>::put(period);
+            Self::deposit_event(Event::<T>::DiscountPeriodSet(GC_DID, period));
+        }
+
+        /// Waives, or stops waiving, the fee of `op` for the identity `did` for the root origin.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - Only root allowed.
+        #[weight = <T as Config>::WeightInfo::set_fee_waiver()]
+        pub fn set_fee_waiver(origin, did: IdentityId, op: ProtocolOp, waived: bool) {
+            ensure_root(origin)?;
+            if waived {
+                FeeWaivers::insert(did, op, true);
+            } else {
+                FeeWaivers::remove(did, op);
+            }
+            Self::deposit_event(Event::<T>::FeeWaiverSet(GC_DID, did, op, waived));
+        }
     }
 }
 
 impl<T: Config> Module<T> {
-    /// Computes the fee of the operation as `(base_fee * coefficient.0) / coefficient.1`.
+    /// Computes the fee of the operations for the identity of the current payer, if any.
     pub fn compute_fee(ops: &[ProtocolOp]) -> Balance {
+        Self::compute_fee_for(Self::payer_identity(), ops)
+    }
+
+    /// Computes the fee of the operations as `(base_fee * coefficient.0) / coefficient.1`.
+    ///
+    /// If `did` is given, waived operations are free and the base fee of every other operation
+    /// is reduced by the discount tier reached by `did` in the current period.
+    pub fn compute_fee_for(did: Option<IdentityId>, ops: &[ProtocolOp]) -> Balance {
         let coefficient = Self::coefficient();
         let ratio = Perbill::from_rational(coefficient.0, coefficient.1);
-        let base = ops
-            .iter()
-            .fold(Zero::zero(), |a: Balance, e| a + Self::base_fees(e));
+        let base = ops.iter().fold(Zero::zero(), |a: Balance, op| {
+            a.saturating_add(Self::discounted_base_fee(did, *op))
+        });
         ratio * base
     }
 
+    /// Returns the base fee of `op` for `did`, after any waiver or volume discount.
+    fn discounted_base_fee(did: Option<IdentityId>, op: ProtocolOp) -> Balance {
+        let base_fee = Self::base_fees(op);
+        let did = match did {
+            Some(did) => did,
+            None => return base_fee,
+        };
+        if Self::fee_waived(did, op) {
+            return Zero::zero();
+        }
+        let tiers = Self::discount_tiers(op);
+        if tiers.is_empty() {
+            return base_fee;
+        }
+        let count = Self::current_ops_count(did, op).count;
+        let discount = tiers
+            .into_iter()
+            .rev()
+            .find(|tier| count >= tier.min_ops)
+            .map_or_else(Permill::zero, |tier| tier.discount);
+        base_fee.saturating_sub(discount * base_fee)
+    }
+
+    /// Returns the operations count of `did` for `op`, reset if its period has elapsed.
+    fn current_ops_count(did: IdentityId, op: ProtocolOp) -> OpsCount<T::BlockNumber> {
+        let now = frame_system::Pallet::<T>::block_number();
+        let period = Self::discount_period();
+        let usage = Self::ops_usage(did, op);
+        if usage.count == 0
+            || (!period.is_zero() && now >= usage.period_start.saturating_add(period))
+        {
+            OpsCount {
+                period_start: now,
+                count: 0,
+            }
+        } else {
+            usage
+        }
+    }
+
+    /// Records that `did` performed `count` more operations `op` in the current period.
+    /// Only operations with volume discounts are counted.
+    fn record_ops(did: Option<IdentityId>, op: ProtocolOp, count: u32) {
+        if let Some(did) = did {
+            if Self::discount_tiers(op).is_empty() {
+                return;
+            }
+            let mut usage = Self::current_ops_count(did, op);
+            usage.count = usage.count.saturating_add(count);
+            OpsUsage::<T>::insert(did, op, usage);
+        }
+    }
+
+    /// Returns the identity of the current payer, if any.
+    fn payer_identity() -> Option<IdentityId> {
+        T::CddHandler::get_payer_from_context()
+            .and_then(|payer| Identity::<T>::get_identity(&payer))
+    }
+
     /// Computes the fee of the operations and charges it to the current payer. The fee is then
     /// credited to the intended recipients according to the implementation of
     /// `OnProtocolFeePayment`.
@@ -167,20 +348,26 @@ impl<T: Config> Module<T> {
         if ops.is_empty() {
             return Ok(());
         }
-        let fee = Self::compute_fee(ops);
-        if fee.is_zero() {
-            return Ok(());
+        let did = Self::payer_identity();
+        let fee = Self::compute_fee_for(did, ops);
+        if !fee.is_zero() {
+            Self::withdraw_from_payer(fee)?;
+        }
+        for op in ops {
+            Self::record_ops(did, *op, 1);
         }
-        Self::withdraw_from_payer(fee)
+        Ok(())
     }
 
     /// Computes the fee for `count` similar operations, and charges that fee to the current payer.
     pub fn batch_charge_fee(op: ProtocolOp, count: usize) -> DispatchResult {
-        let fee = Self::compute_fee(&[op]).saturating_mul(Balance::from(count as u32));
-        if fee.is_zero() {
-            return Ok(());
+        let did = Self::payer_identity();
+        let fee = Self::compute_fee_for(did, &[op]).saturating_mul(Balance::from(count as u32));
+        if !fee.is_zero() {
+            Self::withdraw_from_payer(fee)?;
         }
-        Self::withdraw_from_payer(fee)
+        Self::record_ops(did, op, count as u32);
+        Ok(())
     }
 
     /// Withdraws a precomputed fee from the current payer if it is defined or from the current
@@ -197,6 +384,14 @@ impl<T: Config> Module<T> {
             T::Subsidiser::debit_subsidy(&account, fee)
                 .map_err(|_| Error::<T>::InsufficientSubsidyBalance)?;
             subsidiser_key
+        } else if let Some(payment) = T::FeeAssetPayer::pay_fee_in_asset(&account, fee) {
+            // No subsidy, and the fee was paid with the fee asset of the payer.
+            // The weight of the payment isn't part of the calling extrinsic's weight.
+            frame_system::Pallet::<T>::register_extra_weight_unchecked(
+                payment.weight,
+                DispatchClass::Normal,
+            );
+            return Ok(None);
         } else {
             // No subsidy.
             account
@@ -212,19 +407,24 @@ impl<T: Config> Module<T> {
         .map_err(|_| Error::<T>::InsufficientAccountBalance)?;
 
         Self::deposit_event(RawEvent::FeeCharged(fee_key, fee));
-        Ok(ret)
+        Ok(Some(ret))
     }
 
     fn withdraw_from_payer(fee: Balance) -> DispatchResult {
         if let Some(payer) = T::CddHandler::get_payer_from_context() {
-            let imbalance = Self::withdraw_fee(payer, fee)?;
-            T::OnProtocolFeePayment::on_unbalanced(imbalance);
+            if let Some(imbalance) = Self::withdraw_fee(payer, fee)? {
+                T::OnProtocolFeePayment::on_unbalanced(imbalance);
+            }
         }
         Ok(())
     }
 }
 
 impl<T: Config> ChargeProtocolFee<T::AccountId> for Module<T> {
+    fn charge_fee_weight() -> Weight {
+        <T as Config>::WeightInfo::charge_fee()
+    }
+
     fn charge_fee(op: ProtocolOp) -> DispatchResult {
         Self::charge_fees(&[op])
     }
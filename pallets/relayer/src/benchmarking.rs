@@ -20,6 +20,10 @@ use polymesh_common_utilities::{
     benchs::{user, AccountIdOf, User},
     traits::{relayer::Config, TestUtilsFn},
 };
+use polymesh_primitives::identity::limits::MAX_PALLETS;
+use polymesh_primitives::{
+    subsidy::SubsidyLimits, ExtrinsicPermissions, PalletName, PalletPermissions, Ticker,
+};
 
 type Relayer<T> = crate::Module<T>;
 
@@ -106,4 +110,23 @@ benchmarks! {
     verify {
         assert_subsidy(user, Some((payer, limit)));
     }
+
+    set_subsidy_limits {
+        // Number of pallets the subsidy is restricted to.
+        let c in 0 .. MAX_PALLETS as u32;
+
+        let (payer, user) = setup_paying_key::<T>(1_000u128);
+        let calls = ExtrinsicPermissions::elems(
+            (0..c as u64).map(|p| PalletPermissions::entire_pallet(PalletName(Ticker::generate(p))))
+        );
+        let limits = SubsidyLimits {
+            calls,
+            expires_at: None,
+            refill_period: 1_000u32.into(),
+            period_limit: Some(100u128),
+        };
+    }: _(payer.origin(), user.account(), Some(limits.clone()))
+    verify {
+        assert_eq!(SubsidiesLimits::<T>::get(user.account()), Some(limits));
+    }
 }
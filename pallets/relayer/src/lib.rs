@@ -25,6 +25,8 @@
 //! - Adding or removing a subsidiser for another user's key.
 //! - Managing how much POLYX can be used by a user key to pay
 //!   transaction/protocol fees.
+//! - Restricting a subsidy to some pallets or extrinsics, to a refilling
+//!   amount of POLYX per period, and until an expiry time.
 //!
 //! ## Interface
 //!
@@ -37,6 +39,8 @@
 //! - `update_polyx_limit` updates the available POLYX for a `user_key`.
 //! - `increase_polyx_limit` increases the available POLYX for a `user_key`.
 //! - `decrease_polyx_limit` decreases the available POLYX for a `user_key`.
+//! - `set_subsidy_limits` restricts the calls, the POLYX spent per period and
+//!   the lifetime of the subsidy for a `user_key`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -48,6 +52,7 @@ use frame_support::{
     decl_error, decl_module, decl_storage,
     dispatch::{DispatchError, DispatchResult},
     ensure, fail,
+    traits::CallMetadata,
 };
 use frame_system::ensure_signed;
 use pallet_identity::PermissionedCallOriginData;
@@ -55,9 +60,11 @@ pub use polymesh_common_utilities::traits::relayer::{
     Config, Event, RawEvent, SubsidiserTrait, WeightInfo,
 };
 use polymesh_primitives::{
-    extract_auth, AuthorizationData, Balance, IdentityId, Signatory, TransactionError,
+    extract_auth, subsidy::SubsidyLimits, AuthorizationData, Balance, DispatchableName, IdentityId,
+    KeySpending, PalletName, Signatory, TransactionError,
 };
 use scale_info::TypeInfo;
+use sp_runtime::traits::{Saturating, Zero};
 use sp_runtime::transaction_validity::InvalidTransaction;
 use sp_std::vec;
 
@@ -96,6 +103,17 @@ decl_storage! {
         /// before they can accept a new subsidiser.
         pub Subsidies get(fn subsidies):
             map hasher(blake2_128_concat) T::AccountId => Option<Subsidy<T::AccountId>>;
+
+        /// The limits set by the paying key on the subsidy of a `user_key`,
+        /// as a map `user_key` => `SubsidyLimits`.
+        ///
+        /// A subsidy without limits can pay for any subsidised call.
+        pub SubsidiesLimits get(fn subsidy_limits):
+            map hasher(blake2_128_concat) T::AccountId => Option<SubsidyLimits<T::Moment>>;
+
+        /// The POLYX spent by the subsidy of a `user_key` in its current period.
+        pub SubsidySpendings get(fn subsidy_spending):
+            map hasher(blake2_128_concat) T::AccountId => KeySpending<T::Moment>;
     }
 }
 
@@ -199,6 +217,29 @@ decl_module! {
         pub fn decrease_polyx_limit(origin, user_key: T::AccountId, amount: Balance) -> DispatchResult {
             Self::base_update_polyx_limit(origin, user_key, UpdateAction::Sub, amount)
         }
+
+        /// Sets or clears the limits of the subsidy for a `user_key`.
+        ///
+        /// Calls outside of `limits.calls` are not subsidised, the subsidy can't pay more
+        /// than `limits.period_limit` POLYX per `limits.refill_period`, and it can't be
+        /// used after `limits.expires_at`.
+        ///
+        /// # Arguments
+        /// - `user_key` the user key of the subsidy to limit.
+        /// - `limits` the new limits of the subsidy, or `None` to remove them.
+        ///
+        /// # Errors
+        /// - `NoPayingKey` if the `user_key` doesn't have a `paying_key`.
+        /// - `NotPayingKey` if `origin` doesn't match the current `paying_key`.
+        /// - `UnauthorizedCaller` if `origin` is not authorized to call this extrinsic.
+        /// - `SubsidyExpiryInPast` if `limits.expires_at` is not in the future.
+        /// - `ExceptNotAllowedForExtrinsics` if `limits.calls` uses `Except`.
+        #[weight = <T as Config>::WeightInfo::set_subsidy_limits(
+            limits.as_ref().map_or(0, |l| l.calls.complexity() as u32)
+        )]
+        pub fn set_subsidy_limits(origin, user_key: T::AccountId, limits: Option<SubsidyLimits<T::Moment>>) -> DispatchResult {
+            Self::base_set_subsidy_limits(origin, user_key, limits)
+        }
     }
 }
 
@@ -218,6 +259,8 @@ decl_error! {
         NotAuthorizedForUserKey,
         /// The remaining POLYX for `user_key` overflowed.
         Overflow,
+        /// The expiry of the subsidy is not in the future.
+        SubsidyExpiryInPast,
     }
 }
 
@@ -298,7 +341,7 @@ impl<T: Config> Module<T> {
         <Identity<T>>::remove_account_key_ref_count(&user_key);
 
         // Remove paying key for user key.
-        <Subsidies<T>>::remove(&user_key);
+        Self::remove_subsidy(&user_key);
 
         Self::deposit_event(RawEvent::RemovedPayingKey(
             sender_did.for_event(),
@@ -347,6 +390,53 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    fn base_set_subsidy_limits(
+        origin: T::RuntimeOrigin,
+        user_key: T::AccountId,
+        limits: Option<SubsidyLimits<T::Moment>>,
+    ) -> DispatchResult {
+        let PermissionedCallOriginData {
+            sender: paying_key,
+            primary_did: paying_did,
+            ..
+        } = <Identity<T>>::ensure_origin_call_permissions(origin)?;
+
+        // Check if the current paying key matches.
+        Self::ensure_is_paying_key(&user_key, &paying_key)?;
+
+        if let Some(limits) = &limits {
+            <Identity<T>>::ensure_no_except_perms(&limits.calls)?;
+            <Identity<T>>::ensure_extrinsic_perms_length_limited(&limits.calls)?;
+            let now = <pallet_timestamp::Pallet<T>>::get();
+            ensure!(
+                limits.expires_at.map_or(true, |expiry| expiry > now),
+                Error::<T>::SubsidyExpiryInPast
+            );
+        }
+
+        // New limits start a new spending period.
+        <SubsidySpendings<T>>::remove(&user_key);
+        match &limits {
+            Some(limits) => <SubsidiesLimits<T>>::insert(&user_key, limits),
+            None => <SubsidiesLimits<T>>::remove(&user_key),
+        }
+
+        Self::deposit_event(RawEvent::UpdatedSubsidyLimits(
+            paying_did.for_event(),
+            user_key,
+            paying_key,
+            limits,
+        ));
+        Ok(())
+    }
+
+    /// Removes the subsidy of `user_key` together with its limits.
+    fn remove_subsidy(user_key: &T::AccountId) {
+        <Subsidies<T>>::remove(user_key);
+        <SubsidiesLimits<T>>::remove(user_key);
+        <SubsidySpendings<T>>::remove(user_key);
+    }
+
     /// Adds an authorization to add a `paying_key` to the `user_key`.
     pub fn unverified_add_auth_for_paying_key(
         from: IdentityId,
@@ -426,6 +516,8 @@ impl<T: Config> Module<T> {
         if let Some(subsidy) = <Subsidies<T>>::get(&user_key) {
             // Decrease old paying key usage.
             <Identity<T>>::remove_account_key_ref_count(&subsidy.paying_key);
+            // The limits of the old paying key don't apply to the new one.
+            Self::remove_subsidy(&user_key);
 
             Self::deposit_event(RawEvent::RemovedPayingKey(
                 user_did.for_event(),
@@ -467,18 +559,71 @@ impl<T: Config> Module<T> {
         }
     }
 
+    /// Returns the POLYX already spent by the subsidy of `user_key` in its current period,
+    /// together with the limit of that period.
+    ///
+    /// Returns `None` if the subsidy has no spending limit.
+    fn period_spending(
+        user_key: &T::AccountId,
+        limits: &SubsidyLimits<T::Moment>,
+        now: T::Moment,
+    ) -> Option<(KeySpending<T::Moment>, Balance)> {
+        let limit = limits.period_limit?;
+        let mut spending = <SubsidySpendings<T>>::get(user_key);
+        // Refill the subsidy if nothing was spent yet or the current period is over.
+        let period_end = spending.period_start.saturating_add(limits.refill_period);
+        if spending.spent.is_zero() || (!limits.refill_period.is_zero() && now >= period_end) {
+            spending = KeySpending {
+                period_start: now,
+                spent: 0,
+            };
+        }
+        Some((spending, limit))
+    }
+
     fn get_subsidy(
         user_key: &T::AccountId,
         fee: Balance,
     ) -> Result<Option<Subsidy<T::AccountId>>, InvalidTransaction> {
         // Get the Subsidy for `user_key`.
-        match <Subsidies<T>>::get(user_key) {
+        let subsidy = match <Subsidies<T>>::get(user_key) {
             // There was no subsidy.
-            None => Ok(None),
-            // Has subsidy, but not enough remaining POLYX.
-            Some(s) if s.remaining < fee => fail!(InvalidTransaction::Payment),
-            // Has subsidy and enough POLYX.
-            Some(s) => Ok(Some(s)),
+            None => return Ok(None),
+            Some(s) => s,
+        };
+        let limits = <SubsidiesLimits<T>>::get(user_key);
+        let now = <pallet_timestamp::Pallet<T>>::get();
+        // An expired subsidy doesn't pay anymore.
+        if limits.as_ref().map_or(false, |l| !l.is_valid_at(&now)) {
+            return Ok(None);
+        }
+        // Has subsidy, but not enough remaining POLYX.
+        ensure!(subsidy.remaining >= fee, InvalidTransaction::Payment);
+        // Has subsidy, but not enough POLYX left in the current period.
+        if let Some((spending, limit)) = limits
+            .as_ref()
+            .and_then(|l| Self::period_spending(user_key, l, now))
+        {
+            ensure!(
+                spending.spent.saturating_add(fee) <= limit,
+                InvalidTransaction::Payment
+            );
+        }
+        // Has subsidy and enough POLYX.
+        Ok(Some(subsidy))
+    }
+
+    /// Ensure that the limits of the subsidy for `user_key` cover the call `function_name` of `pallet_name`.
+    fn ensure_call_is_subsidised(
+        user_key: &T::AccountId,
+        pallet_name: &PalletName,
+        function_name: &DispatchableName,
+    ) -> Result<(), InvalidTransaction> {
+        match <SubsidiesLimits<T>>::get(user_key) {
+            Some(limits) if !limits.covers(pallet_name, function_name) => fail!(
+                InvalidTransaction::Custom(TransactionError::CallNotSubsidised as u8)
+            ),
+            _ => Ok(()),
         }
     }
 }
@@ -487,15 +632,30 @@ impl<T: Config> SubsidiserTrait<T::AccountId> for Module<T> {
     fn check_subsidy(
         user_key: &T::AccountId,
         fee: Balance,
-        pallet: Option<&[u8]>,
+        metadata: Option<&CallMetadata>,
     ) -> Result<Option<T::AccountId>, InvalidTransaction> {
-        match (Self::get_subsidy(user_key, fee)?, pallet) {
-            (Some(s), Some(pallet)) => {
+        match (Self::get_subsidy(user_key, fee)?, metadata) {
+            (Some(s), Some(metadata)) => {
                 // Ensure that the current pallet can be subsidised.
-                Ok(Self::ensure_pallet_is_subsidised(pallet)?.map(|_| s.paying_key))
+                if Self::ensure_pallet_is_subsidised(metadata.pallet_name.as_bytes())?.is_none() {
+                    return Ok(None);
+                }
+                // Ensure that the paying key subsidises the current call.
+                Self::ensure_call_is_subsidised(
+                    user_key,
+                    &metadata.pallet_name.as_bytes().into(),
+                    &metadata.function_name.as_bytes().into(),
+                )?;
+                Ok(Some(s.paying_key))
             }
             (Some(s), None) => {
-                // No pallet restriction applied (protocol fees).
+                // No pallet restriction applied (protocol fees),
+                // but the call being dispatched must still be subsidised by the paying key.
+                Self::ensure_call_is_subsidised(
+                    user_key,
+                    &pallet_permissions::Module::<T>::current_pallet_name(),
+                    &pallet_permissions::Module::<T>::current_dispatchable_name(),
+                )?;
                 Ok(Some(s.paying_key))
             }
             (None, _) => Ok(None),
@@ -511,6 +671,14 @@ impl<T: Config> SubsidiserTrait<T::AccountId> for Module<T> {
             // Debit the fee from the remaining POLYX of subsidy.
             subsidy.remaining = subsidy.remaining.saturating_sub(fee);
             <Subsidies<T>>::insert(user_key, subsidy);
+            // Record the fee in the current spending period.
+            if let Some(limits) = <SubsidiesLimits<T>>::get(user_key) {
+                let now = <pallet_timestamp::Pallet<T>>::get();
+                if let Some((mut spending, _)) = Self::period_spending(user_key, &limits, now) {
+                    spending.spent = spending.spent.saturating_add(fee);
+                    <SubsidySpendings<T>>::insert(user_key, spending);
+                }
+            }
             Ok(Some(paying_key))
         } else {
             Ok(None)
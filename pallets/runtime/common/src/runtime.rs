@@ -168,6 +168,7 @@ macro_rules! misc_pallet_impls {
             type GovernanceCommittee = PolymeshCommittee;
             type CddProviders = CddServiceProviders;
             type Identity = Identity;
+            type FeeAssetPayer = Asset;
         }
 
         impl polymesh_common_utilities::traits::CommonConfig for Runtime {
@@ -176,6 +177,9 @@ macro_rules! misc_pallet_impls {
 
         impl pallet_balances::Config for Runtime {
             type MaxLocks = MaxLocks;
+            type MaxVestingSchedules = MaxVestingSchedules;
+            type MinVestedTransfer = MinVestedTransfer;
+            type MaxVestingDuration = MaxVestingDuration;
             type DustRemoval = ();
             type RuntimeEvent = RuntimeEvent;
             type ExistentialDeposit = ExistentialDeposit;
@@ -190,6 +194,8 @@ macro_rules! misc_pallet_impls {
             type OnProtocolFeePayment = DealWithFees;
             type WeightInfo = polymesh_weights::pallet_protocol_fee::SubstrateWeight;
             type Subsidiser = Relayer;
+            type FeeAssetPayer = Asset;
+            type MaxDiscountTiers = frame_support::traits::ConstU32<10>;
         }
 
         impl pallet_timestamp::Config for Runtime {
@@ -281,6 +287,48 @@ macro_rules! misc_pallet_impls {
             type Scheduler = Scheduler;
             type SchedulerCall = RuntimeCall;
             type WeightInfo = polymesh_weights::pallet_multisig::SubstrateWeight;
+            type ProposalValue = MultiSigProposalValue;
+        }
+
+        /// Extracts the POLYX amount moved by a multisig proposal.
+        pub struct MultiSigProposalValue;
+
+        impl pallet_multisig::ProposalValue<RuntimeCall> for MultiSigProposalValue {
+            fn polyx_value(call: &RuntimeCall) -> Option<polymesh_primitives::Balance> {
+                match call {
+                    RuntimeCall::Balances(pallet_balances::Call::transfer { value, .. })
+                    | RuntimeCall::Balances(pallet_balances::Call::transfer_with_memo {
+                        value,
+                        ..
+                    }) => Some(*value),
+                    _ => None,
+                }
+            }
+
+            fn inner_calls(call: &RuntimeCall) -> Vec<&RuntimeCall> {
+                use pallet_utility::Call as UtilityCall;
+                match call {
+                    RuntimeCall::Utility(UtilityCall::batch { calls })
+                    | RuntimeCall::Utility(UtilityCall::batch_all { calls })
+                    | RuntimeCall::Utility(UtilityCall::force_batch { calls })
+                    | RuntimeCall::Utility(UtilityCall::batch_old { calls })
+                    | RuntimeCall::Utility(UtilityCall::batch_atomic { calls })
+                    | RuntimeCall::Utility(UtilityCall::batch_optimistic { calls })
+                    | RuntimeCall::Utility(UtilityCall::batch_if { calls, .. })
+                    | RuntimeCall::Utility(UtilityCall::schedule_batch { calls, .. })
+                    | RuntimeCall::Utility(UtilityCall::schedule_recurring { calls, .. }) => {
+                        calls.iter().collect()
+                    }
+                    RuntimeCall::Utility(UtilityCall::as_derivative { call, .. })
+                    | RuntimeCall::Utility(UtilityCall::schedule_call { call, .. })
+                    | RuntimeCall::Identity(pallet_identity::Call::call_as_child {
+                        call, ..
+                    }) => {
+                        sp_std::vec![&**call]
+                    }
+                    _ => Vec::new(),
+                }
+            }
         }
 
         impl pallet_bridge::Config for Runtime {
@@ -390,6 +438,57 @@ macro_rules! misc_pallet_impls {
             type RuntimeCall = RuntimeCall;
             type PalletsOrigin = OriginCaller;
             type WeightInfo = polymesh_weights::pallet_utility::SubstrateWeight;
+            type MaxScheduledCallsPerIdentity = frame_support::traits::ConstU32<50>;
+            type MinRecurrenceInterval = MinRecurrenceInterval;
+            type MaxRecurrences = MaxRecurrences;
+            type MaxScheduledExecutionsPerBlock = MaxScheduledExecutionsPerBlock;
+            type MaxScheduledExecutionsWeight = MaxScheduledExecutionsWeight;
+            type ConditionChecker = UtilityConditionChecker;
+            type MaxBatchConditions = frame_support::traits::ConstU32<32>;
+        }
+
+        /// Checks the conditions of `Utility::batch_if`.
+        pub struct UtilityConditionChecker;
+
+        impl
+            pallet_utility::ConditionChecker<
+                polymesh_primitives::AccountId,
+                polymesh_primitives::BlockNumber,
+            > for UtilityConditionChecker
+        {
+            fn check(
+                condition: &pallet_utility::BatchCondition<
+                    polymesh_primitives::AccountId,
+                    polymesh_primitives::BlockNumber,
+                >,
+            ) -> bool {
+                use pallet_utility::BatchCondition;
+                match condition {
+                    BatchCondition::PolyxBalanceAtLeast { account, amount } => {
+                        <Balances as frame_support::traits::Currency<_>>::free_balance(account)
+                            >= *amount
+                    }
+                    BatchCondition::PortfolioBalanceAtLeast {
+                        portfolio,
+                        ticker,
+                        amount,
+                    } => {
+                        Portfolio::portfolio_asset_balances(portfolio, ticker)
+                            .saturating_sub(Portfolio::locked_assets(portfolio, ticker))
+                            >= *amount
+                    }
+                    BatchCondition::InstructionStatusIs { id, status } => {
+                        Settlement::instruction_status(id) == *status
+                    }
+                    BatchCondition::ClaimPresent {
+                        target,
+                        claim_type,
+                        issuer,
+                        scope,
+                    } => Identity::fetch_claim(*target, *claim_type, *issuer, scope.clone())
+                        .is_some(),
+                }
+            }
         }
 
         impl pallet_scheduler::Config for Runtime {
@@ -469,6 +568,7 @@ macro_rules! misc_pallet_impls {
             type RuntimeEvent = RuntimeEvent;
             type Currency = Balances;
             type WeightInfo = polymesh_weights::pallet_treasury::SubstrateWeight;
+            type MaxMilestones = frame_support::traits::ConstU32<50>;
         }
 
         impl pallet_settlement::Config for Runtime {
@@ -559,6 +659,7 @@ macro_rules! misc_pallet_impls {
             type Compliance = pallet_compliance_manager::Module<Runtime>;
             type MaxNumberOfCollectionKeys = MaxNumberOfCollectionKeys;
             type MaxNumberOfNFTsCount = MaxNumberOfNFTsPerLeg;
+            type MaxNFTsPerIssue = MaxNFTsPerIssue;
         }
     };
 }
@@ -572,14 +673,20 @@ macro_rules! runtime_apis {
         use frame_support::dispatch::result::Result as FrameResult;
         use node_rpc_runtime_api::asset as rpc_api_asset;
 
-        use pallet_identity::types::{AssetDidResult, CddStatus, RpcDidRecords, DidStatus, KeyIdentityData};
+        use pallet_identity::types::{AssetDidResult, CddStatus, RpcAuthorization, RpcDidRecords, DidStatus, KeyIdentityData};
         use pallet_pips::{Vote, VoteCount};
         use pallet_protocol_fee_rpc_runtime_api::CappedFee;
         use polymesh_primitives::asset::GranularCanTransferResult;
+        use polymesh_primitives::compliance_manager::{AssetCompliance, ComplianceImpact};
+        use polymesh_primitives::multisig::ProposalInfo;
+        use polymesh_primitives::treasury::{PaymentPlan, PaymentPlanId};
+        use polymesh_primitives::transfer_compliance::{AssetTransferCompliance, TransferCondition};
+        use sp_std::collections::btree_set::BTreeSet;
         use polymesh_primitives::settlement::{InstructionId, ExecuteInstructionInfo, AffirmationCount};
         use polymesh_primitives::{
             asset::CheckpointId, compliance_manager::AssetComplianceResult, IdentityId, Index, NFTs,
-            PortfolioId, Signatory, Ticker, WeightMeter, IdentityClaim
+            NFTId, NFTMetadataAttribute, PortfolioId, Signatory, Ticker, WeightMeter, IdentityClaim,
+            IdentityExport
         };
 
         /// The address format for describing accounts.
@@ -895,7 +1002,11 @@ macro_rules! runtime_apis {
                 Block,
             > for Runtime {
                 fn compute_fee(op: ProtocolOp) -> CappedFee {
-                    ProtocolFee::compute_fee(&[op]).into()
+                    ProtocolFee::compute_fee_for(None, &[op]).into()
+                }
+
+                fn compute_fee_for_identity(op: ProtocolOp, did: IdentityId) -> CappedFee {
+                    ProtocolFee::compute_fee_for(Some(did), &[op]).into()
                 }
             }
 
@@ -930,7 +1041,7 @@ macro_rules! runtime_apis {
                     Identity::get_did_status(dids)
                 }
 
-                fn get_key_identity_data(acc: polymesh_primitives::AccountId) -> Option<KeyIdentityData<IdentityId>> {
+                fn get_key_identity_data(acc: polymesh_primitives::AccountId) -> Option<KeyIdentityData<IdentityId, Moment>> {
                     Identity::get_key_identity_data(acc)
                 }
 
@@ -947,6 +1058,34 @@ macro_rules! runtime_apis {
                 fn valid_cdd_claims(target_identity: IdentityId, cdd_checker_leeway: Option<u64>) -> Vec<IdentityClaim> {
                     Identity::valid_cdd_claims(target_identity, cdd_checker_leeway)
                 }
+
+                fn get_authorizations_inbox(
+                    signatory: Signatory<polymesh_primitives::AccountId>,
+                    auth_type: Option<polymesh_primitives::AuthorizationType>,
+                    allow_expired: bool,
+                    after: Option<u64>,
+                    limit: u32
+                ) -> Vec<RpcAuthorization<polymesh_primitives::AccountId, Moment>> {
+                    Identity::get_authorizations_inbox(signatory, auth_type, allow_expired, after, limit)
+                }
+
+                fn get_authorizations_outbox(
+                    issuer: IdentityId,
+                    auth_type: Option<polymesh_primitives::AuthorizationType>,
+                    allow_expired: bool,
+                    after: Option<u64>,
+                    limit: u32
+                ) -> Vec<RpcAuthorization<polymesh_primitives::AccountId, Moment>> {
+                    Identity::get_authorizations_outbox(issuer, auth_type, allow_expired, after, limit)
+                }
+
+                fn cdd_expiring_within(days: u32) -> Vec<(IdentityId, Moment)> {
+                    Identity::cdd_expiring_within(days)
+                }
+
+                fn export_identity(did: IdentityId) -> Option<IdentityExport<polymesh_primitives::AccountId, Moment>> {
+                    Identity::export_identity(did)
+                }
             }
 
             impl rpc_api_asset::AssetApi<Block, polymesh_primitives::AccountId> for Runtime {
@@ -973,6 +1112,46 @@ macro_rules! runtime_apis {
                 }
             }
 
+            impl node_rpc_runtime_api::compliance::ComplianceApi<Block> for Runtime {
+                #[inline]
+                fn simulate_asset_compliance(
+                    ticker: Ticker,
+                    asset_compliance: AssetCompliance,
+                    start: InstructionId,
+                    holders_after: Option<IdentityId>,
+                    limit: u32
+                ) -> FrameResult<ComplianceImpact, DispatchError> {
+                    Settlement::simulate_asset_compliance(
+                        ticker,
+                        asset_compliance,
+                        start,
+                        holders_after,
+                        limit
+                    )
+                }
+
+                #[inline]
+                fn simulate_transfer_compliance(
+                    ticker: Ticker,
+                    paused: bool,
+                    transfer_conditions: BTreeSet<TransferCondition>,
+                    start: InstructionId,
+                    holders_after: Option<IdentityId>,
+                    limit: u32
+                ) -> FrameResult<ComplianceImpact, DispatchError> {
+                    let requirements = transfer_conditions
+                        .try_into()
+                        .map_err(|_| pallet_statistics::Error::<Runtime>::TransferConditionLimitReached)?;
+                    Settlement::simulate_transfer_compliance(
+                        ticker,
+                        AssetTransferCompliance { paused, requirements },
+                        start,
+                        holders_after,
+                        limit
+                    )
+                }
+            }
+
             impl pallet_group_rpc_runtime_api::GroupApi<Block> for Runtime {
                 fn get_cdd_valid_members() -> Vec<pallet_group_rpc_runtime_api::Member> {
                     merge_active_and_inactive::<Block>(
@@ -997,6 +1176,56 @@ macro_rules! runtime_apis {
                     let mut weight_meter = WeightMeter::max_limit_no_minimum();
                     Nft::validate_nft_transfer(sender_portfolio, receiver_portfolio, nfts, &mut weight_meter)
                 }
+
+                #[inline]
+                fn collection_nfts(
+                    ticker: &Ticker,
+                    start: Option<NFTId>,
+                    limit: u32
+                ) -> (Vec<NFTId>, Option<NFTId>) {
+                    Nft::collection_nfts(ticker, start, limit)
+                }
+
+                #[inline]
+                fn portfolio_nfts(
+                    portfolio: &PortfolioId,
+                    after: Option<(Ticker, NFTId)>,
+                    limit: u32
+                ) -> Vec<(Ticker, NFTId)> {
+                    Nft::portfolio_nfts(portfolio, after, limit)
+                }
+
+                #[inline]
+                fn nft_metadata(
+                    ticker: &Ticker,
+                    nft_id: &NFTId
+                ) -> Option<Vec<NFTMetadataAttribute>> {
+                    Nft::nft_metadata(ticker, nft_id)
+                }
+
+                #[inline]
+                fn nft_owner(
+                    ticker: &Ticker,
+                    nft_id: &NFTId
+                ) -> Option<PortfolioId> {
+                    Nft::nft_owner(ticker, nft_id)
+                }
+            }
+
+            impl node_rpc_runtime_api::multisig::MultiSigApi<Block, polymesh_primitives::AccountId, Moment> for Runtime {
+                #[inline]
+                fn active_proposals(
+                    multisig: polymesh_primitives::AccountId,
+                    after: Option<u64>,
+                    limit: u32
+                ) -> Vec<ProposalInfo<polymesh_primitives::AccountId, Moment, Vec<u8>>> {
+                    use codec::Encode;
+
+                    MultiSig::active_proposals(&multisig, after, limit)
+                        .into_iter()
+                        .map(|proposal| proposal.map_call(|call| call.encode()))
+                        .collect()
+                }
             }
 
             impl node_rpc_runtime_api::settlement::SettlementApi<Block> for Runtime {
@@ -1016,6 +1245,15 @@ macro_rules! runtime_apis {
                 }
             }
 
+            impl node_rpc_runtime_api::treasury::TreasuryApi<Block, polymesh_primitives::BlockNumber> for Runtime {
+                #[inline]
+                fn payment_plans(
+                    beneficiary: IdentityId
+                ) -> Vec<(PaymentPlanId, PaymentPlan<polymesh_primitives::BlockNumber>)> {
+                    Treasury::beneficiary_payment_plans(beneficiary)
+                }
+            }
+
             $($extra)*
         }
     }
@@ -78,6 +78,9 @@ parameter_types! {
     // Balances:
     pub const ExistentialDeposit: Balance = 0u128;
     pub const MaxLocks: u32 = 50;
+    pub const MaxVestingSchedules: u32 = 20;
+    pub const MinVestedTransfer: Balance = 100 * POLY;
+    pub const MaxVestingDuration: BlockNumber = 4 * 365 * DAYS;
 
     // Timestamp:
     pub const MinimumPeriod: Moment = SLOT_DURATION / 2;
@@ -120,8 +123,15 @@ parameter_types! {
     pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * MaximumBlockWeight::get();
     pub const MaxScheduledPerBlock: u32 = 50;
 
+    // Utility:
+    pub const MinRecurrenceInterval: BlockNumber = HOURS;
+    pub const MaxRecurrences: u32 = 100;
+    pub const MaxScheduledExecutionsPerBlock: u32 = 20;
+    pub MaxScheduledExecutionsWeight: Weight = Perbill::from_percent(20) * MaximumBlockWeight::get();
+
     // Identity:
     pub const InitialPOLYX: Balance = 0;
+    pub const MaxSessionKeyLifetime: BlockNumber = 7 * DAYS;
 
     // Contracts:
     pub Schedule: pallet_contracts::Schedule<Runtime> = Default::default();
@@ -132,6 +142,7 @@ parameter_types! {
 
     // NFT:
     pub const MaxNumberOfCollectionKeys: u8 = u8::MAX;
+    pub const MaxNFTsPerIssue: u32 = 100;
 
     // Portfolio:
     pub const MaxNumberOfFungibleMoves: u32 = 10;
@@ -199,6 +210,8 @@ impl polymesh_common_utilities::traits::identity::Config for Runtime {
     type SchedulerOrigin = OriginCaller;
     type InitialPOLYX = InitialPOLYX;
     type MultiSigBalanceLimit = polymesh_runtime_common::MultiSigBalanceLimit;
+    type MaxSessionKeyLifetime = MaxSessionKeyLifetime;
+    type FeeAssetPayer = Asset;
 }
 
 impl pallet_committee::Config<GovernanceCommittee> for Runtime {
@@ -354,7 +367,7 @@ construct_runtime!(
         Settlement: pallet_settlement::{Pallet, Call, Storage, Event<T>, Config},
         Statistics: pallet_statistics::{Pallet, Call, Storage, Event, Config},
         Sto: pallet_sto::{Pallet, Call, Storage, Event<T>},
-        Treasury: pallet_treasury::{Pallet, Call, Event<T>},
+        Treasury: pallet_treasury::{Pallet, Call, Storage, Event<T>},
         Utility: pallet_utility::{Pallet, Call, Storage, Event<T>},
         Base: pallet_base::{Pallet, Call, Event},
         ExternalAgents: pallet_external_agents::{Pallet, Call, Storage, Event},
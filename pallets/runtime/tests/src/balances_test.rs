@@ -5,13 +5,13 @@ use super::{
 use pallet_balances as balances;
 use pallet_identity as identity;
 use pallet_test_utils as test_utils;
-use polymesh_common_utilities::traits::balances::RawEvent as BalancesRawEvent;
+use polymesh_common_utilities::traits::balances::{RawEvent as BalancesRawEvent, VestingKind};
 use polymesh_runtime_develop::{runtime, Runtime};
 
 use frame_support::{
     assert_noop, assert_ok,
     dispatch::{DispatchInfo, Weight},
-    traits::Currency,
+    traits::{Currency, LockableCurrency, WithdrawReasons},
 };
 use frame_system::{EventRecord, Phase};
 use pallet_transaction_payment::ChargeTransactionPayment;
@@ -326,3 +326,143 @@ fn transfer_with_memo_we() {
         assert!(system_events.contains(&expected));
     });
 }
+
+#[test]
+fn vesting_schedules_work() {
+    ExtBuilder::default()
+        .balance_factor(1_000)
+        .monied(true)
+        .cdd_providers(vec![AccountKeyring::Ferdie.to_account_id()])
+        .build()
+        .execute_with(vesting_schedules_work_we);
+}
+
+fn vesting_schedules_work_we() {
+    let alice = AccountKeyring::Alice.to_account_id();
+    let _ = register_keyring_account(AccountKeyring::Alice).unwrap();
+    let bob = AccountKeyring::Bob.to_account_id();
+    let _ = register_keyring_account(AccountKeyring::Bob).unwrap();
+    let bob_free = Balances::free_balance(&bob);
+    System::set_block_number(1);
+
+    let vested_transfer = |kind, revocable| {
+        Balances::vested_transfer(
+            Origin::signed(alice.clone()),
+            bob.clone().into(),
+            1_000,
+            10,
+            kind,
+            revocable,
+        )
+    };
+    let revoke = |caller: &_, id| {
+        Balances::revoke_vesting(Origin::signed(caller.clone()), bob.clone().into(), id)
+    };
+    let vest = || Balances::vest(Origin::signed(bob.clone()));
+
+    // Schedules must release something over a non-empty period.
+    assert_noop!(
+        vested_transfer(VestingKind::Linear { duration: 0 }, false),
+        Error::InvalidVestingSchedule
+    );
+    assert_noop!(
+        vested_transfer(
+            VestingKind::Step {
+                period: 25,
+                steps: 0
+            },
+            false
+        ),
+        Error::InvalidVestingSchedule
+    );
+
+    // Schedules must vest at least `MinVestedTransfer`, and start and end within `MaxVestingDuration`.
+    let vested_transfer_at = |amount, start, kind| {
+        Balances::vested_transfer(
+            Origin::signed(alice.clone()),
+            bob.clone().into(),
+            amount,
+            start,
+            kind,
+            false,
+        )
+    };
+    assert_noop!(
+        vested_transfer_at(999, 10, VestingKind::Cliff),
+        Error::VestingAmountTooSmall
+    );
+    assert_noop!(
+        vested_transfer_at(1_000, 1_002, VestingKind::Cliff),
+        Error::VestingPeriodTooLong
+    );
+    assert_noop!(
+        vested_transfer_at(1_000, 10, VestingKind::Linear { duration: 1_001 }),
+        Error::VestingPeriodTooLong
+    );
+
+    // Linear over 100 blocks, 4 steps of 25 blocks and a cliff, all starting at block 10.
+    assert_ok!(vested_transfer(VestingKind::Linear { duration: 100 }, true));
+    assert_ok!(vested_transfer(
+        VestingKind::Step {
+            period: 25,
+            steps: 4
+        },
+        false
+    ));
+    assert_ok!(vested_transfer(VestingKind::Cliff, false));
+    // The revocable schedule is reserved, the others are locked.
+    assert_eq!(Balances::free_balance(&bob), bob_free + 2_000);
+    assert_eq!(Balances::reserved_balance(&bob), 1_000);
+    assert_eq!(Balances::usable_balance(&bob), bob_free);
+    assert_noop!(
+        Balances::transfer(
+            Origin::signed(bob.clone()),
+            alice.clone().into(),
+            bob_free + 1
+        ),
+        Error::LiquidityRestrictions
+    );
+
+    // Half of the linear schedule, two steps and the cliff have vested at block 60.
+    System::set_block_number(60);
+    assert_ok!(vest());
+    assert_eq!(Balances::usable_balance(&bob), bob_free + 2_000);
+    assert_eq!(Balances::reserved_balance(&bob), 500);
+    assert_eq!(Balances::vesting_schedules(&bob).len(), 2);
+
+    // Only the grantor can revoke, and only revocable schedules.
+    assert_noop!(revoke(&bob, 0), Error::NotVestingGrantor);
+    assert_noop!(revoke(&alice, 1), Error::VestingNotRevocable);
+    assert_noop!(revoke(&alice, 2), Error::VestingScheduleNotFound);
+
+    // Revoking returns the unvested part to the grantor, even if the beneficiary bonded
+    // all its free balance, as locks overlay.
+    Balances::set_lock(*b"staking ", &bob, bob_free + 2_500, WithdrawReasons::all());
+    assert_eq!(Balances::usable_balance(&bob), 0);
+    let alice_free = Balances::free_balance(&alice);
+    assert_ok!(revoke(&alice, 0));
+    assert_eq!(Balances::free_balance(&alice), alice_free + 500);
+    assert_eq!(Balances::free_balance(&bob), bob_free + 2_500);
+    assert_eq!(Balances::reserved_balance(&bob), 0);
+    Balances::remove_lock(*b"staking ", &bob);
+    assert_eq!(Balances::usable_balance(&bob), bob_free + 2_000);
+
+    // Once fully vested, the schedules and the lock are removed.
+    System::set_block_number(110);
+    assert_ok!(vest());
+    assert_eq!(Balances::usable_balance(&bob), bob_free + 2_500);
+    assert!(Balances::vesting_schedules(&bob).is_empty());
+    assert_noop!(vest(), Error::VestingScheduleNotFound);
+
+    // The beneficiary can reject a schedule, returning its unvested part to the grantor.
+    // Ids are never reused.
+    assert_ok!(vested_transfer_at(1_000, 200, VestingKind::Cliff));
+    assert_eq!(Balances::vesting_schedules(&bob)[0].id, 3);
+    let reject = |id| Balances::reject_vesting(Origin::signed(bob.clone()), id);
+    assert_noop!(reject(0), Error::VestingScheduleNotFound);
+    let alice_free = Balances::free_balance(&alice);
+    assert_ok!(reject(3));
+    assert_eq!(Balances::free_balance(&alice), alice_free + 1_000);
+    assert_eq!(Balances::usable_balance(&bob), bob_free + 2_500);
+    assert!(Balances::vesting_schedules(&bob).is_empty());
+}
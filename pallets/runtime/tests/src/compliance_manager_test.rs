@@ -1,18 +1,19 @@
 use chrono::prelude::Utc;
-use frame_support::traits::Currency;
+use frame_support::traits::{Currency, OnFinalize};
 use frame_support::{assert_noop, assert_ok};
 use sp_std::prelude::*;
 
 use pallet_compliance_manager::Error as CMError;
 use polymesh_common_utilities::compliance_manager::ComplianceFnConfig;
 use polymesh_common_utilities::constants::ERC1400_TRANSFER_SUCCESS;
+use polymesh_common_utilities::traits::identity::RawEvent;
 use polymesh_primitives::agent::AgentGroup;
 use polymesh_primitives::compliance_manager::{
     AssetComplianceResult, ComplianceRequirement, ComplianceRequirementResult,
 };
 use polymesh_primitives::{
-    AuthorizationData, Claim, ClaimType, Condition, ConditionType, CountryCode, IdentityId,
-    PortfolioId, Scope, Signatory, TargetIdentity, Ticker, TrustedFor, WeightMeter,
+    AuthorizationData, Claim, ClaimCommitment, ClaimType, Condition, ConditionType, CountryCode,
+    IdentityId, PortfolioId, Scope, Signatory, TargetIdentity, Ticker, TrustedFor, WeightMeter,
 };
 use sp_keyring::AccountKeyring;
 
@@ -816,6 +817,135 @@ fn jurisdiction_asset_compliance_we() {
     assert_invalid_transfer!(ticker, owner.did, user.did, 10);
 }
 
+#[test]
+fn committed_jurisdiction_asset_compliance() {
+    ExtBuilder::default()
+        .cdd_providers(vec![AccountKeyring::Eve.to_account_id()])
+        .build()
+        .execute_with(committed_jurisdiction_asset_compliance_we);
+}
+fn committed_jurisdiction_asset_compliance_we() {
+    // 0. Create accounts
+    let owner = User::new(AccountKeyring::Alice);
+    let issuer = User::new(AccountKeyring::Bob);
+    let user = User::new(AccountKeyring::Charlie);
+    let other = User::new(AccountKeyring::Dave);
+
+    // 1. Create & mint token
+    let (ticker, _) = create_token(owner);
+
+    // 2. Set up compliance requirements for Asset transfer.
+    let scope = Scope::from(IdentityId::from(0));
+    let jurisdiction = Claim::Jurisdiction(CountryCode::CA, scope.clone());
+    let salt = [3u8; 32];
+    let commitment = ClaimCommitment::new(&jurisdiction, &salt);
+    assert_ok!(ComplianceManager::add_compliance_requirement(
+        owner.origin(),
+        ticker,
+        vec![],
+        vec![Condition::from_dids(
+            ConditionType::IsPresent(jurisdiction.clone()),
+            &[issuer.did],
+        )]
+    ));
+
+    // 3. The issuer commits to the user's jurisdiction, which is not enough to receive the asset.
+    assert_noop!(
+        Identity::add_claim(
+            issuer.origin(),
+            user.did,
+            Claim::Commitment(ClaimType::CustomerDueDiligence, scope.clone(), commitment),
+            None
+        ),
+        IdError::InvalidClaimCommitment
+    );
+    assert_add_claim!(
+        issuer.origin(),
+        user.did,
+        Claim::Commitment(ClaimType::Jurisdiction, scope.clone(), commitment),
+        None
+    );
+    assert_invalid_transfer!(ticker, owner.did, user.did, 10);
+
+    // 4. Only the right claim and salt open the commitment.
+    assert_noop!(
+        Identity::disclose_claim(user.origin(), issuer.did, jurisdiction.clone(), [4u8; 32]),
+        IdError::ClaimCommitmentMismatch
+    );
+    assert_noop!(
+        Identity::disclose_claim(
+            user.origin(),
+            issuer.did,
+            Claim::Jurisdiction(CountryCode::US, scope.clone()),
+            salt
+        ),
+        IdError::ClaimCommitmentMismatch
+    );
+    assert_noop!(
+        Identity::disclose_claim(other.origin(), issuer.did, jurisdiction.clone(), salt),
+        IdError::ClaimDoesNotExist
+    );
+
+    // 5. Once disclosed, the claim is valid until the end of the block.
+    let disclose = || {
+        assert_ok!(Identity::disclose_claim(
+            user.origin(),
+            issuer.did,
+            jurisdiction.clone(),
+            salt
+        ))
+    };
+    disclose();
+    assert_valid_transfer!(ticker, owner.did, user.did, 10);
+    // Only the commitment is published.
+    assert_eq!(
+        frame_system::Pallet::<TestStorage>::events()
+            .pop()
+            .unwrap()
+            .event,
+        super::storage::EventTest::Identity(RawEvent::ClaimDisclosed(
+            user.did, issuer.did, commitment
+        ))
+    );
+    <Identity as OnFinalize<_>>::on_finalize(0);
+    assert_eq!(Identity::disclosed_claim(user.did, commitment), None);
+    assert_eq!(Identity::block_disclosures(), vec![]);
+    assert_invalid_transfer!(ticker, owner.did, user.did, 10);
+
+    // 6. Revoking the commitment claim also removes its disclosure.
+    disclose();
+    assert_ok!(Identity::revoke_claim(
+        issuer.origin(),
+        user.did,
+        Claim::Commitment(ClaimType::Jurisdiction, scope.clone(), commitment)
+    ));
+    assert_eq!(Identity::disclosed_claim(user.did, commitment), None);
+    assert_add_claim!(
+        issuer.origin(),
+        user.did,
+        Claim::Commitment(ClaimType::Jurisdiction, scope.clone(), commitment),
+        None
+    );
+
+    // 7. The issuer's commitment is accepted as an attestation by a default commitment condition.
+    assert_ok!(ComplianceManager::replace_asset_compliance(
+        owner.origin(),
+        ticker,
+        Vec::new()
+    ));
+    assert_ok!(ComplianceManager::add_compliance_requirement(
+        owner.origin(),
+        ticker,
+        vec![],
+        vec![Condition::from_dids(
+            ConditionType::IsPresent(Claim::default_commitment(ClaimType::Jurisdiction, scope)),
+            &[issuer.did],
+        )]
+    ));
+    assert_valid_transfer!(ticker, owner.did, user.did, 10);
+    assert_invalid_transfer!(ticker, owner.did, other.did, 10);
+}
+
 #[test]
 fn scope_asset_compliance() {
     ExtBuilder::default()
@@ -1435,3 +1565,89 @@ fn check_new_return_type_of_rpc() {
         assert_valid_transfer!(ticker, owner.did, receiver.did, 100);
     });
 }
+
+#[test]
+fn attestation_threshold_conditions() {
+    ExtBuilder::default()
+        .build()
+        .execute_with(attestation_threshold_conditions_we);
+}
+
+fn attestation_threshold_conditions_we() {
+    let owner = User::new(AccountKeyring::Alice);
+    let receiver = User::new(AccountKeyring::Charlie);
+    let kyc1 = User::new(AccountKeyring::Bob);
+    let kyc2 = User::new(AccountKeyring::Dave);
+    let kyc3 = User::new(AccountKeyring::Ferdie);
+
+    let (ticker, _) = create_token(owner);
+    let issuers = [kyc1.did, kyc2.did, kyc3.did];
+    let claim = Claim::KnowYourCustomer(owner.scope());
+    let attested = |threshold| {
+        Condition::from_dids(
+            ConditionType::IsAttested(claim.clone(), threshold),
+            &issuers,
+        )
+    };
+
+    // Thresholds must be non-zero and reachable by the trusted issuers.
+    for threshold in [0, 4] {
+        assert_noop!(
+            ComplianceManager::add_compliance_requirement(
+                owner.origin(),
+                ticker,
+                vec![],
+                vec![attested(threshold)]
+            ),
+            CMError::<TestStorage>::InvalidAttestationThreshold
+        );
+    }
+
+    let condition = attested(2);
+    assert_ok!(ComplianceManager::add_compliance_requirement(
+        owner.origin(),
+        ticker,
+        vec![],
+        vec![condition.clone()]
+    ));
+
+    let check = |expected_result, expected_attestations| {
+        let result = ComplianceManager::verify_restriction_granular(
+            &ticker,
+            Some(owner.did),
+            Some(receiver.did),
+            &mut WeightMeter::max_limit_no_minimum(),
+        )
+        .unwrap();
+        let cond_result = &result.requirements[0].receiver_conditions[0];
+        assert_eq!(cond_result.condition, condition);
+        assert_eq!(cond_result.result, expected_result);
+        assert_eq!(cond_result.attestations, Some(expected_attestations));
+        assert_eq!(result.result, expected_result);
+    };
+
+    // A single attestation isn't enough.
+    assert_add_claim!(kyc1.origin(), receiver.did, claim.clone(), None);
+    assert_invalid_transfer!(ticker, owner.did, receiver.did, 10);
+    check(false, 1);
+
+    // An attestation of another claim doesn't count.
+    assert_add_claim!(
+        kyc2.origin(),
+        receiver.did,
+        Claim::KnowYourCustomer(receiver.scope()),
+        None
+    );
+    check(false, 1);
+
+    // Second independent attestation satisfies the threshold.
+    assert_add_claim!(kyc2.origin(), receiver.did, claim.clone(), None);
+    assert_valid_transfer!(ticker, owner.did, receiver.did, 10);
+    check(true, 2);
+
+    assert_add_claim!(kyc3.origin(), receiver.did, claim.clone(), None);
+    check(true, 3);
+
+    // Attestations count towards complexity for each trusted issuer.
+    assert_eq!(condition.complexity(0), issuers.len() as u32);
+}
@@ -6,18 +6,24 @@ use super::{
     storage::{
         account_from, add_secondary_key, add_secondary_key_with_perms, get_identity_id,
         get_last_auth_id, get_primary_key, get_secondary_keys, register_keyring_account,
-        register_keyring_account_with_balance, GovernanceCommittee, TestStorage, User,
+        register_keyring_account_with_balance, root, GovernanceCommittee, MaxSessionKeyLifetime,
+        TestStorage, User,
     },
     ExtBuilder,
 };
 use codec::Encode;
 use frame_support::{
-    assert_noop, assert_ok, dispatch::DispatchResult, traits::Currency, StorageDoubleMap,
-    StorageMap, StorageValue,
+    assert_noop, assert_ok,
+    dispatch::DispatchResult,
+    traits::{Currency, Get, OnInitialize},
+    StorageDoubleMap, StorageMap, StorageValue,
 };
 use pallet_asset::SecurityToken;
 use pallet_balances as balances;
-use pallet_identity::{CustomClaimIdSequence, CustomClaims, CustomClaimsInverse};
+use pallet_identity::{
+    CddExpiries, CustomClaimIdSequence, CustomClaims, CustomClaimsInverse, RpcAuthorization,
+    RpcDidRecords,
+};
 use polymesh_common_utilities::{
     constants::currency::POLY,
     traits::{
@@ -31,15 +37,20 @@ use polymesh_common_utilities::{
     SystematicIssuers, GC_DID,
 };
 use polymesh_primitives::{
-    AccountId, AssetPermissions, AuthorizationData, AuthorizationType, Claim, ClaimType,
-    CustomClaimTypeId, DispatchableName, ExtrinsicPermissions, IdentityClaim, IdentityId,
-    KeyRecord, PalletName, PalletPermissions, Permissions, PortfolioId, PortfolioNumber, Scope,
-    SecondaryKey, Signatory, SubsetRestriction, Ticker, TransactionError,
+    AccountId, AssetPermissions, AuthorizationData, AuthorizationType, CddLifecycleConfig, Claim,
+    ClaimCredential, ClaimType, CustomClaimTypeId, DispatchableName, ExtrinsicPermissions,
+    IdentityClaim, IdentityExport, IdentityId, KeyRecord, PalletName, PalletPermissions,
+    Permissions, PortfolioId, PortfolioNumber, Scope, SecondaryKey, SecondaryKeyLimits, Signatory,
+    SubsetRestriction, Ticker, TransactionError, IDENTITY_EXPORT_VERSION,
 };
 use polymesh_runtime_develop::runtime::{CddHandler, RuntimeCall};
-use sp_core::H512;
-use sp_keyring::AccountKeyring;
-use sp_runtime::transaction_validity::InvalidTransaction;
+use sp_core::{H256, H512};
+use sp_keyring::{AccountKeyring, Ed25519Keyring};
+use sp_runtime::{
+    traits::SignedExtension,
+    transaction_validity::{InvalidTransaction, TransactionValidityError},
+    DispatchError, MultiSignature,
+};
 use std::convert::From;
 
 type AuthorizationsGiven = pallet_identity::AuthorizationsGiven<TestStorage>;
@@ -56,6 +67,7 @@ type Origin = <TestStorage as frame_system::Config>::RuntimeOrigin;
 type CddServiceProviders = <TestStorage as IdentityConfig>::CddServiceProviders;
 type Error = pallet_identity::Error<TestStorage>;
 type PError = pallet_permissions::Error<TestStorage>;
+type StoreCallMetadata = pallet_permissions::StoreCallMetadata<TestStorage>;
 
 const MAX_ASSETS: u64 = 2000;
 const MAX_PORTFOLIOS: u64 = 2000;
@@ -2116,11 +2128,15 @@ fn do_child_identity_test() {
     let ferdie = User::new_with(bob.did, AccountKeyring::Ferdie);
     add_secondary_key(bob.did, ferdie.acc());
 
-    // Child identity can't create a child identity.
-    exec_noop!(
-        Identity::create_child_identity(bob.origin(), ferdie.acc()),
-        Error::IsChildIdentity,
+    // Child identity can create a grand-child identity, which inherits CDD from its ancestors.
+    let two = User::new_with(bob.did, AccountKeyring::Two);
+    add_secondary_key(bob.did, two.acc());
+    exec_ok!(Identity::create_child_identity(bob.origin(), two.acc()));
+    assert_eq!(
+        Identity::ancestors(did_of(two).unwrap()),
+        vec![bob.did, alice.did]
     );
+    assert!(valid_cdd(two));
 
     // Parent can force unlinking of child identity without CDD claim.
     exec_ok!(Identity::unlink_child_identity(alice.origin(), bob_did));
@@ -2249,3 +2265,921 @@ fn do_create_child_identities_with_auth_test() {
         expires_at
     ));
 }
+
+#[test]
+fn import_claim_credential() {
+    ExtBuilder::default().build().execute_with(|| {
+        let issuer = User::new(AccountKeyring::Alice);
+        let target = User::new(AccountKeyring::Bob);
+        let submitter = User::new(AccountKeyring::Charlie);
+        let signer = Ed25519Keyring::Dave;
+        let scope = Scope::from(IdentityId::from(0));
+
+        let credential = ClaimCredential {
+            uid: 1,
+            issuer: issuer.did,
+            target: target.did,
+            claim: Claim::Accredited(scope.clone()),
+            expiry: Some(100u64),
+            valid_until: 50,
+        };
+        let genesis_hash = System::block_hash(0);
+        let sign = |credential: &ClaimCredential<u64>| {
+            MultiSignature::from(signer.sign((genesis_hash, credential).encode().as_slice()))
+        };
+        let import = |credential: ClaimCredential<u64>, signature| {
+            Identity::import_claim_credential(
+                submitter.origin(),
+                credential,
+                signer.to_account_id(),
+                signature,
+            )
+        };
+
+        // The signer must be registered by the issuer.
+        assert_noop!(
+            import(credential.clone(), sign(&credential)),
+            Error::UnauthorizedCredentialSigner
+        );
+        assert_ok!(Identity::update_credential_signers(
+            issuer.origin(),
+            vec![signer.to_account_id()],
+            true
+        ));
+        assert_noop!(
+            Identity::update_credential_signers(
+                issuer.origin(),
+                vec![signer.to_account_id()],
+                true
+            ),
+            Error::CredentialSignerAlreadyExists
+        );
+
+        // The signature must cover the submitted credential.
+        let forged = ClaimCredential {
+            target: submitter.did,
+            ..credential.clone()
+        };
+        assert_noop!(
+            import(forged, sign(&credential)),
+            Error::InvalidCredentialSignature
+        );
+
+        // The signature must be bound to this chain.
+        let foreign = MultiSignature::from(
+            signer.sign((H256::repeat_byte(1), &credential).encode().as_slice()),
+        );
+        assert_noop!(
+            import(credential.clone(), foreign),
+            Error::InvalidCredentialSignature
+        );
+
+        // Credentials can be imported in any order.
+        let later = ClaimCredential {
+            uid: 2,
+            claim: Claim::Affiliate(scope.clone()),
+            ..credential.clone()
+        };
+        assert_ok!(import(later.clone(), sign(&later)));
+        assert_ok!(import(credential.clone(), sign(&credential)));
+        let claim = Identity::fetch_claim(
+            target.did,
+            ClaimType::Accredited,
+            issuer.did,
+            Some(scope.clone()),
+        )
+        .unwrap();
+        assert_eq!(claim.expiry, Some(100u64));
+
+        // Credentials can't be replayed.
+        assert_noop!(
+            import(credential.clone(), sign(&credential)),
+            Error::CredentialAlreadyImported
+        );
+
+        // Credentials can't be imported after `valid_until`.
+        let credential = ClaimCredential {
+            uid: 3,
+            ..credential
+        };
+        set_timestamp(51);
+        assert_noop!(
+            import(credential.clone(), sign(&credential)),
+            Error::CredentialExpired
+        );
+        set_timestamp(50);
+
+        // Removed signers can no longer sign credentials.
+        assert_ok!(Identity::update_credential_signers(
+            issuer.origin(),
+            vec![signer.to_account_id()],
+            false
+        ));
+        assert_noop!(
+            import(credential.clone(), sign(&credential)),
+            Error::UnauthorizedCredentialSigner
+        );
+    });
+}
+
+#[test]
+fn social_recovery() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let charlie = User::new(AccountKeyring::Charlie);
+        let dave = AccountKeyring::Dave.to_account_id();
+        let new_key = AccountKeyring::Eve.to_account_id();
+        let rogue_key = AccountKeyring::Ferdie.to_account_id();
+        let guardians = vec![
+            Signatory::Identity(bob.did),
+            Signatory::Account(dave.clone()),
+        ];
+
+        // Guardians must be unique and the threshold reachable.
+        assert_noop!(
+            Identity::set_recovery_config(alice.origin(), guardians.clone(), 3, 10),
+            Error::InvalidRecoveryConfig
+        );
+        assert_noop!(
+            Identity::set_recovery_config(
+                alice.origin(),
+                vec![Signatory::Identity(alice.did)],
+                1,
+                10
+            ),
+            Error::InvalidRecoveryConfig
+        );
+        assert_ok!(Identity::set_recovery_config(
+            alice.origin(),
+            guardians,
+            2,
+            10
+        ));
+
+        // The guardians' approval replaces the CDD provider attestation.
+        assert_ok!(Identity::change_cdd_requirement_for_mk_rotation(
+            frame_system::RawOrigin::Root.into(),
+            true
+        ));
+
+        let approve =
+            |origin, key: &AccountId| Identity::approve_recovery(origin, alice.did, key.clone());
+        let execute = || Identity::execute_recovery(Origin::signed(new_key.clone()), alice.did);
+
+        System::set_block_number(1);
+        assert_noop!(
+            approve(charlie.origin(), &new_key),
+            Error::NotRecoveryGuardian
+        );
+        assert_ok!(approve(bob.origin(), &new_key));
+        assert_noop!(
+            approve(bob.origin(), &new_key),
+            Error::RecoveryAlreadyApproved
+        );
+        assert_noop!(
+            approve(Origin::signed(dave.clone()), &charlie.acc()),
+            Error::AlreadyLinked
+        );
+        assert_noop!(execute(), Error::RecoveryNotExecutable);
+
+        // The current primary key can cancel the recovery.
+        assert_ok!(approve(Origin::signed(dave.clone()), &new_key));
+        assert_ok!(Identity::cancel_recovery(alice.origin()));
+        assert_noop!(execute(), Error::NoActiveRecovery);
+
+        // Approvals are counted per key, so a rogue first approval doesn't fix the key.
+        assert_ok!(approve(bob.origin(), &rogue_key));
+        assert_ok!(approve(Origin::signed(dave.clone()), &new_key));
+        assert_noop!(execute(), Error::RecoveryNotExecutable);
+        assert_ok!(approve(bob.origin(), &new_key));
+
+        // Once a key reaches the threshold, it can't be displaced.
+        assert_noop!(
+            approve(bob.origin(), &rogue_key),
+            Error::RecoveryKeyMismatch
+        );
+
+        // Wait for the delay.
+        System::set_block_number(10);
+        assert_noop!(execute(), Error::RecoveryNotExecutable);
+        assert_noop!(
+            Identity::execute_recovery(Origin::signed(dave.clone()), alice.did),
+            Error::RecoveryKeyMismatch
+        );
+        System::set_block_number(11);
+        assert_ok!(execute());
+
+        assert_eq!(get_primary_key(alice.did), new_key);
+        assert_ok!(Identity::ensure_key_did_unlinked(&alice.acc()));
+        assert_eq!(Identity::active_recovery(alice.did), None);
+    });
+}
+
+#[test]
+fn secondary_key_limits() {
+    ExtBuilder::default().monied(true).build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new_with(alice.did, AccountKeyring::Bob).balance(1_000 * POLY);
+        let charlie = User::new(AccountKeyring::Charlie);
+        add_secondary_key(alice.did, bob.acc());
+        set_timestamp(100);
+
+        let limits = SecondaryKeyLimits {
+            valid_from: Some(50),
+            valid_until: Some(2_000),
+            spend_period: 1_000,
+            polyx_limit: Some(10 * POLY),
+            asset_limits: vec![],
+        };
+        let set_limits = |key: AccountId, limits| {
+            Identity::set_secondary_key_limits(alice.origin(), key, Some(limits))
+        };
+
+        // The window must be consistent and only secondary keys can be limited.
+        let bad_window = SecondaryKeyLimits {
+            valid_from: Some(3_000),
+            ..limits.clone()
+        };
+        assert_noop!(
+            set_limits(bob.acc(), bad_window),
+            Error::InvalidSecondaryKeyLimits
+        );
+        assert_noop!(set_limits(charlie.acc(), limits.clone()), Error::NotASigner);
+        assert_ok!(set_limits(bob.acc(), limits.clone()));
+        assert_eq!(
+            Identity::get_key_identity_data(bob.acc()).unwrap().limits,
+            Some(limits)
+        );
+
+        // POLYX transfers are capped per period.
+        let transfer = |amount| Balances::transfer(bob.origin(), charlie.acc().into(), amount);
+        assert_ok!(transfer(6 * POLY));
+        assert_noop!(transfer(5 * POLY), Error::SecondaryKeySpendLimitExceeded);
+        set_timestamp(1_100);
+        assert_ok!(transfer(5 * POLY));
+
+        // The key can't be used outside its validity window.
+        let scope = Scope::Identity(alice.did);
+        let add_claim = || {
+            Identity::add_claim(
+                bob.origin(),
+                charlie.did,
+                Claim::Accredited(scope.clone()),
+                None,
+            )
+        };
+        assert_ok!(add_claim());
+        set_timestamp(2_001);
+        assert_noop!(add_claim(), PError::UnauthorizedCaller);
+
+        // Removing the limits lifts them.
+        assert_ok!(Identity::set_secondary_key_limits(
+            alice.origin(),
+            bob.acc(),
+            None
+        ));
+        assert_ok!(add_claim());
+        assert_ok!(transfer(20 * POLY));
+        assert_eq!(
+            Identity::get_key_identity_data(bob.acc()).unwrap().limits,
+            None
+        );
+    });
+}
+
+#[test]
+fn session_keys() {
+    ExtBuilder::default().monied(true).build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let dave = User::new_with(alice.did, AccountKeyring::Dave);
+        System::set_block_number(1);
+
+        let permissions =
+            SubsetRestriction::elem(PalletPermissions::entire_pallet(b"Identity".into()));
+        let sign = |key: &User, expires_at| {
+            let auth = TargetIdAuthorization {
+                target_id: alice.did,
+                nonce: Identity::offchain_authorization_nonce(alice.did),
+                expires_at,
+            };
+            H512::from(key.ring.sign(&auth.encode()))
+        };
+        let add_session_key = |key: &User, expires_at, max_calls, auth_signature| {
+            Identity::add_session_key(
+                alice.origin(),
+                key.acc(),
+                permissions.clone(),
+                expires_at,
+                max_calls,
+                auth_signature,
+            )
+        };
+
+        // The key must expire in the future, within the maximum lifetime,
+        // be callable and be unlinked.
+        let max_expiry = 1 + MaxSessionKeyLifetime::get();
+        assert_noop!(
+            add_session_key(&dave, 1, 2, sign(&dave, 1)),
+            Error::InvalidSessionKey
+        );
+        assert_noop!(
+            add_session_key(&dave, max_expiry + 1, 2, sign(&dave, max_expiry + 1)),
+            Error::InvalidSessionKey
+        );
+        assert_noop!(
+            add_session_key(&dave, 5, 0, sign(&dave, 5)),
+            Error::InvalidSessionKey
+        );
+        assert_noop!(
+            add_session_key(&bob, 5, 2, sign(&bob, 5)),
+            Error::AlreadyLinked
+        );
+
+        // The key must consent to join the identity until `expires_at`.
+        assert_noop!(
+            add_session_key(&dave, 5, 2, sign(&bob, 5)),
+            Error::InvalidAuthorizationSignature
+        );
+        assert_noop!(
+            add_session_key(&dave, 5, 2, sign(&dave, 6)),
+            Error::InvalidAuthorizationSignature
+        );
+        assert_ok!(add_session_key(&dave, 5, 2, sign(&dave, 5)));
+        assert_eq!(Identity::get_identity(&dave.acc()), Some(alice.did));
+
+        // The key can only call the extrinsics of its filter.
+        let claim = Claim::Accredited(Scope::Identity(alice.did));
+        let call = RuntimeCall::Identity(pallet_identity::Call::add_claim {
+            target: bob.did,
+            claim: claim.clone(),
+            expiry: None,
+        });
+        let pre_dispatch =
+            || StoreCallMetadata::new().pre_dispatch(&dave.acc(), &call, &Default::default(), 0);
+        let validate =
+            || StoreCallMetadata::new().validate(&dave.acc(), &call, &Default::default(), 0);
+        assert_ok!(pre_dispatch());
+        assert_ok!(Identity::add_claim(
+            dave.origin(),
+            bob.did,
+            claim.clone(),
+            None
+        ));
+        assert_ok!(pre_dispatch());
+        StoreCallMetadata::set_call_metadata(b"Asset".into(), b"issue".into());
+        assert_noop!(
+            Identity::add_claim(dave.origin(), bob.did, claim, None),
+            PError::UnauthorizedCaller
+        );
+
+        // The key can't dispatch more than its maximum number of calls,
+        // and its transactions are kept out of the pool.
+        let exhausted: TransactionValidityError =
+            InvalidTransaction::Custom(TransactionError::SessionKeyCallsExhausted as u8).into();
+        assert_eq!(pre_dispatch(), Err(exhausted));
+        assert_eq!(validate(), Err(exhausted));
+
+        // The key is unlinked once it expires.
+        System::set_block_number(5);
+        Identity::on_initialize(5);
+        assert_eq!(Identity::get_identity(&dave.acc()), None);
+        assert_eq!(Identity::session_key(dave.acc()), None);
+        assert_ok!(validate());
+        assert_ok!(pre_dispatch());
+    });
+}
+
+#[test]
+fn authorization_inbox_outbox_and_expiry() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let charlie = User::new(AccountKeyring::Charlie);
+        let hour = 3_600_000;
+        set_timestamp(hour);
+
+        let to_bob = Signatory::Identity(bob.did);
+        let add_auth = |target: &Signatory<AccountId>, data, expiry| {
+            Identity::add_auth(alice.did, target.clone(), data, expiry)
+        };
+        let expiring = add_auth(
+            &to_bob,
+            AuthorizationData::RotatePrimaryKey,
+            Some(hour + 10),
+        );
+        let join = add_auth(
+            &to_bob,
+            AuthorizationData::JoinIdentity(Permissions::default()),
+            None,
+        );
+        let to_charlie = add_auth(
+            &charlie.signatory_acc(),
+            AuthorizationData::RotatePrimaryKey,
+            None,
+        );
+
+        // Authorizations can't be added already expired.
+        assert_noop!(
+            Identity::add_authorization(
+                alice.origin(),
+                to_bob.clone(),
+                AuthorizationData::RotatePrimaryKey,
+                Some(hour)
+            ),
+            Error::AuthorizationExpired
+        );
+
+        // Inbox pages follow the storage order and can be filtered by type.
+        let auth_ids = |auths: Vec<RpcAuthorization<AccountId, u64>>| {
+            auths
+                .into_iter()
+                .map(|auth| auth.authorization.auth_id)
+                .collect::<Vec<_>>()
+        };
+        let sorted = |mut ids: Vec<u64>| {
+            ids.sort();
+            ids
+        };
+        let inbox = |auth_type, after, limit| {
+            auth_ids(Identity::get_authorizations_inbox(
+                to_bob.clone(),
+                auth_type,
+                false,
+                after,
+                limit,
+            ))
+        };
+        let all = inbox(None, None, 10);
+        assert_eq!(sorted(all.clone()), vec![expiring, join]);
+        assert_eq!(inbox(None, None, 1), all[..1].to_vec());
+        assert_eq!(inbox(None, Some(all[0]), 10), all[1..].to_vec());
+        assert_eq!(
+            inbox(Some(AuthorizationType::JoinIdentity), None, 10),
+            vec![join]
+        );
+
+        // The outbox includes the target and expiry of each authorization.
+        let outbox = Identity::get_authorizations_outbox(alice.did, None, false, None, 10);
+        assert_eq!(
+            sorted(auth_ids(outbox.clone())),
+            vec![expiring, join, to_charlie]
+        );
+        let outbox_auth = |auth_id| {
+            outbox
+                .iter()
+                .find(|auth| auth.authorization.auth_id == auth_id)
+                .unwrap()
+        };
+        assert_eq!(outbox_auth(expiring).authorization.expiry, Some(hour + 10));
+        assert_eq!(outbox_auth(to_charlie).target, charlie.signatory_acc());
+
+        // Authorizations already expired are queued in the current bucket,
+        // without moving the pruning cursor back.
+        let expired = add_auth(&to_bob, AuthorizationData::RotatePrimaryKey, Some(0));
+        assert_eq!(Identity::next_auth_expiry_bucket(), Some(1));
+
+        // Expired authorizations are hidden, and pruned once their whole bucket expired.
+        set_timestamp(hour + 20);
+        assert_eq!(inbox(None, None, 10), vec![join]);
+        Identity::on_initialize(1);
+        assert!(Identity::authorizations(&to_bob, expiring).is_some());
+        set_timestamp(2 * hour);
+        Identity::on_initialize(2);
+        assert!(Identity::authorizations(&to_bob, expiring).is_none());
+        assert!(Identity::authorizations(&to_bob, expired).is_none());
+        assert_eq!(
+            sorted(auth_ids(Identity::get_authorizations_outbox(
+                alice.did, None, true, None, 10
+            ))),
+            vec![join, to_charlie]
+        );
+    });
+}
+
+#[test]
+fn cdd_lifecycle() {
+    ExtBuilder::default()
+        .cdd_providers(vec![AccountKeyring::Eve.to_account_id()])
+        .build()
+        .execute_with(cdd_lifecycle_we);
+}
+
+fn cdd_lifecycle_we() {
+    let hour = 3_600_000;
+    let root = Origin::from(frame_system::RawOrigin::Root);
+    let eve = User::new_with(
+        get_identity_id(AccountKeyring::Eve).unwrap(),
+        AccountKeyring::Eve,
+    );
+    System::set_block_number(1);
+    set_timestamp(1);
+
+    // Register Ferdie with a single CDD claim expiring within the 4th hour.
+    assert_ok!(Identity::cdd_register_did(
+        eve.origin(),
+        AccountKeyring::Ferdie.to_account_id(),
+        vec![]
+    ));
+    let ferdie = User::new_with(
+        get_identity_id(AccountKeyring::Ferdie).unwrap(),
+        AccountKeyring::Ferdie,
+    );
+    let expiry = 3 * hour + 10;
+    assert_ok!(Identity::add_claim(
+        eve.origin(),
+        ferdie.did,
+        Claim::CustomerDueDiligence(Default::default()),
+        Some(expiry)
+    ));
+    let config = CddLifecycleConfig {
+        grace_period: hour,
+        notice_period: 2 * hour,
+    };
+    assert_ok!(Identity::set_cdd_lifecycle_config(root, config));
+
+    // The expiry notice is only emitted once the claim expires within the notice period.
+    let expiring_soon =
+        super::storage::EventTest::Identity(RawEvent::CddExpiringSoon(ferdie.did, expiry));
+    let notified = || System::events().iter().any(|e| e.event == expiring_soon);
+    Identity::on_initialize(1);
+    assert!(!notified());
+    set_timestamp(hour);
+    Identity::on_initialize(2);
+    assert!(notified());
+    assert_eq!(Identity::cdd_expiring_within(0), vec![]);
+    assert_eq!(Identity::cdd_expiring_within(1), vec![(ferdie.did, expiry)]);
+
+    // The CDD remains valid during the grace period.
+    set_timestamp(expiry + 10);
+    assert!(Identity::has_valid_cdd(ferdie.did));
+    assert_eq!(Identity::cdd_expiring_within(0), vec![(ferdie.did, expiry)]);
+    set_timestamp(expiry + hour);
+    assert!(!Identity::has_valid_cdd(ferdie.did));
+    assert_eq!(Identity::cdd_expiring_within(1), vec![]);
+
+    // Queued expiries are pruned once their whole bucket is past the grace period.
+    Identity::on_initialize(3);
+    assert!(CddExpiries::contains_key(3, ferdie.did));
+    set_timestamp(5 * hour);
+    Identity::on_initialize(4);
+    assert!(!CddExpiries::contains_key(3, ferdie.did));
+
+    // Renewals must be requested before a CDD provider renews the claim.
+    let renew = |expiry| Identity::renew_cdd(eve.origin(), ferdie.did, expiry);
+    assert_noop!(renew(Some(10 * hour)), Error::NoCddRenewalRequest);
+    assert_ok!(Identity::request_cdd_renewal(ferdie.origin()));
+    assert_noop!(
+        Identity::request_cdd_renewal(ferdie.origin()),
+        Error::CddRenewalAlreadyRequested
+    );
+    assert_noop!(
+        Identity::renew_cdd(ferdie.origin(), ferdie.did, Some(10 * hour)),
+        Error::UnAuthorizedCddProvider
+    );
+    assert_ok!(renew(Some(10 * hour)));
+    assert!(Identity::has_valid_cdd(ferdie.did));
+    assert_eq!(Identity::cdd_renewal_request(ferdie.did), None);
+    assert_noop!(renew(Some(20 * hour)), Error::NoCddRenewalRequest);
+
+    // The look-ahead of `cdd_expiring_within` is capped.
+    assert_eq!(
+        Identity::cdd_expiring_within(u32::MAX),
+        vec![(ferdie.did, 10 * hour)]
+    );
+
+    // Expiries already past their grace period don't move the cursors back.
+    let cursors = || {
+        (
+            Identity::next_cdd_expiry_bucket(),
+            Identity::next_cdd_notice_bucket(),
+        )
+    };
+    let before = cursors();
+    assert_ok!(Identity::add_claim(
+        eve.origin(),
+        ferdie.did,
+        Claim::CustomerDueDiligence(Default::default()),
+        Some(hour)
+    ));
+    assert_eq!(cursors(), before);
+    assert!(!CddExpiries::contains_key(1, ferdie.did));
+}
+
+#[test]
+fn child_identity_hierarchy() {
+    ExtBuilder::default()
+        .monied(true)
+        .build()
+        .execute_with(child_identity_hierarchy_we);
+}
+
+fn child_identity_hierarchy_we() {
+    let alice = User::new(AccountKeyring::Alice);
+    let create_child = |parent: User, ring| {
+        let key = User::new_with(parent.did, ring);
+        add_secondary_key(parent.did, key.acc());
+        assert_ok!(Identity::create_child_identity(parent.origin(), key.acc()));
+        User::new_with(Identity::get_identity(&key.acc()).unwrap(), ring)
+    };
+
+    // Child identities can be nested up to `MAX_CHILD_IDENTITY_DEPTH` levels.
+    let bob = create_child(alice, AccountKeyring::Bob);
+    let charlie = create_child(bob, AccountKeyring::Charlie);
+    let dave = create_child(charlie, AccountKeyring::Dave);
+    let ferdie = create_child(dave, AccountKeyring::Ferdie);
+    let two = User::new_with(ferdie.did, AccountKeyring::Two);
+    add_secondary_key(ferdie.did, two.acc());
+    assert_noop!(
+        Identity::create_child_identity(ferdie.origin(), two.acc()),
+        Error::ChildIdentityTooDeep
+    );
+    assert_eq!(
+        Identity::ancestors(ferdie.did),
+        vec![dave.did, charlie.did, bob.did, alice.did]
+    );
+
+    // CDD is inherited down the hierarchy.
+    assert!(Identity::has_valid_cdd(ferdie.did));
+
+    // The hierarchy is exposed through the DID records.
+    let records = |did| match Identity::get_did_records(did) {
+        RpcDidRecords::Success {
+            parent_did,
+            child_dids,
+            parent_permissions,
+            ..
+        } => (parent_did, child_dids, parent_permissions),
+        RpcDidRecords::IdNotFound => panic!("DID records not found"),
+    };
+    assert_eq!(records(charlie.did), (Some(bob.did), vec![dave.did], None));
+
+    // Ancestors can only make the calls allowed by the child identity.
+    let call = || {
+        Box::new(RuntimeCall::Identity(
+            pallet_identity::Call::request_cdd_renewal {},
+        ))
+    };
+    assert_noop!(
+        Identity::call_as_child(alice.origin(), dave.did, call()),
+        Error::CallNotPermittedForAncestors
+    );
+    let permissions = SubsetRestriction::elem(PalletPermissions::new(
+        b"Identity".into(),
+        SubsetRestriction::elem(b"request_cdd_renewal".into()),
+    ));
+    assert_noop!(
+        Identity::set_parent_permissions(alice.origin(), Some(permissions.clone())),
+        Error::NoParentIdentity
+    );
+    assert_ok!(Identity::set_parent_permissions(
+        dave.origin(),
+        Some(permissions.clone())
+    ));
+    assert_eq!(
+        records(dave.did),
+        (Some(charlie.did), vec![ferdie.did], Some(permissions))
+    );
+    assert_noop!(
+        Identity::call_as_child(ferdie.origin(), dave.did, call()),
+        Error::NotAnAncestorIdentity
+    );
+
+    // The calling key must be permitted to make the call itself.
+    let one = User::new_with(alice.did, AccountKeyring::One);
+    add_secondary_key_with_perms(
+        alice.did,
+        one.acc(),
+        Permissions::from_pallet_permissions(vec![PalletPermissions::entire_pallet(
+            b"Asset".into(),
+        )]),
+    );
+    assert_noop!(
+        Identity::call_as_child(one.origin(), dave.did, call()),
+        pallet_permissions::Error::<TestStorage>::UnauthorizedCaller
+    );
+    let eve = User::new_with(alice.did, AccountKeyring::Eve);
+    add_secondary_key_with_perms(
+        alice.did,
+        eve.acc(),
+        Permissions {
+            asset: AssetPermissions::elem(Ticker::from_slice_truncated(b"ACME")),
+            ..Permissions::from_pallet_permissions(vec![PalletPermissions::entire_pallet(
+                b"Identity".into(),
+            )])
+        },
+    );
+    assert_noop!(
+        Identity::call_as_child(eve.origin(), dave.did, call()),
+        Error::CallNotPermittedForKey
+    );
+    assert_ok!(Identity::call_as_child(alice.origin(), dave.did, call()));
+    assert!(Identity::cdd_renewal_request(dave.did).is_some());
+
+    // Unlinking a child identity removes it from its parent and revokes its parent permissions.
+    assert_ok!(Identity::unlink_child_identity(charlie.origin(), dave.did));
+    assert_eq!(records(charlie.did), (Some(bob.did), vec![], None));
+    assert_eq!(records(dave.did), (None, vec![ferdie.did], None));
+    assert_noop!(
+        Identity::call_as_child(alice.origin(), dave.did, call()),
+        Error::NotAnAncestorIdentity
+    );
+}
+
+#[test]
+fn identity_export_import() {
+    ExtBuilder::default()
+        .cdd_providers(vec![AccountKeyring::Eve.to_account_id()])
+        .build()
+        .execute_with(identity_export_import_we);
+}
+
+fn identity_export_import_we() {
+    let eve = User::new_with(
+        get_identity_id(AccountKeyring::Eve).unwrap(),
+        AccountKeyring::Eve,
+    );
+    let alice = User::new(AccountKeyring::Alice);
+    let bob = User::new(AccountKeyring::Bob);
+    let charlie = User::new(AccountKeyring::Charlie);
+    let attester = AccountKeyring::Ferdie;
+    let scope = Scope::from(IdentityId::from(0));
+    set_timestamp(10);
+
+    assert_ok!(Identity::add_claim(
+        bob.origin(),
+        alice.did,
+        Claim::Accredited(scope.clone()),
+        None
+    ));
+    assert_ok!(Identity::add_claim(
+        bob.origin(),
+        alice.did,
+        Claim::Affiliate(scope.clone()),
+        Some(100)
+    ));
+    assert_ok!(Identity::register_custom_claim_type(
+        bob.origin(),
+        b"export".into()
+    ));
+    let custom_claim_type = CustomClaimsInverse::get(b"export".to_vec()).unwrap();
+    assert_ok!(Identity::add_claim(
+        bob.origin(),
+        alice.did,
+        Claim::Custom(custom_claim_type, Some(scope.clone())),
+        None
+    ));
+
+    // Export Alice's identity.
+    assert!(Identity::export_identity(IdentityId::from(999)).is_none());
+    let export = Identity::export_identity(alice.did).unwrap();
+    assert_eq!(export.version, IDENTITY_EXPORT_VERSION);
+    assert_eq!(export.did, alice.did);
+    assert_eq!(export.primary_key, alice.acc());
+    assert_eq!(export.exported_at, 10);
+    assert!(export
+        .claims
+        .iter()
+        .any(|c| c.claim == Claim::Accredited(scope.clone()) && c.claim_issuer == bob.did));
+
+    let sign = |export: &IdentityExport<AccountId, u64>, signer: AccountKeyring| {
+        MultiSignature::from(signer.sign(export.encode().as_slice()))
+    };
+    let import = |caller: User, target, export: IdentityExport<_, _>, signer| {
+        let signature = sign(&export, signer);
+        let attestation = sign(&export, attester);
+        Identity::import_identity_export(
+            caller.origin(),
+            target,
+            export,
+            signature,
+            attester.to_account_id(),
+            attestation,
+        )
+    };
+
+    // Exports of this chain can't be imported, nor exports of chains the attester is not trusted for.
+    assert_noop!(
+        import(eve, alice.did, export.clone(), AccountKeyring::Alice),
+        Error::UnknownExportSourceChain
+    );
+    let source_chain = H256::repeat_byte(1);
+    assert_noop!(
+        Identity::set_export_attester(alice.origin(), source_chain, attester.to_account_id(), true),
+        DispatchError::BadOrigin
+    );
+    assert_noop!(
+        Identity::set_export_attester(root(), export.source_chain, attester.to_account_id(), true),
+        Error::UnknownExportSourceChain
+    );
+    let export = IdentityExport {
+        source_chain,
+        ..export
+    };
+    assert_noop!(
+        import(eve, alice.did, export.clone(), AccountKeyring::Alice),
+        Error::UnknownExportSourceChain
+    );
+    assert_ok!(Identity::set_export_attester(
+        root(),
+        source_chain,
+        attester.to_account_id(),
+        true
+    ));
+
+    // Only CDD providers can re-issue claims.
+    assert_noop!(
+        import(bob, alice.did, export.clone(), AccountKeyring::Alice),
+        Error::UnAuthorizedCddProvider
+    );
+
+    // The export has to be signed by its primary key and by the attester.
+    assert_noop!(
+        import(eve, alice.did, export.clone(), AccountKeyring::Bob),
+        Error::InvalidIdentityExportSignature
+    );
+    let tampered = IdentityExport {
+        claims: vec![],
+        ..export.clone()
+    };
+    assert_noop!(
+        Identity::import_identity_export(
+            eve.origin(),
+            alice.did,
+            tampered,
+            sign(&export, AccountKeyring::Alice),
+            attester.to_account_id(),
+            sign(&export, attester)
+        ),
+        Error::InvalidIdentityExportSignature
+    );
+    assert_noop!(
+        Identity::import_identity_export(
+            eve.origin(),
+            alice.did,
+            export.clone(),
+            sign(&export, AccountKeyring::Alice),
+            attester.to_account_id(),
+            sign(&export, AccountKeyring::Bob)
+        ),
+        Error::InvalidIdentityExportSignature
+    );
+
+    // The format version has to be supported.
+    let unsupported = IdentityExport {
+        version: IDENTITY_EXPORT_VERSION + 1,
+        ..export.clone()
+    };
+    assert_noop!(
+        import(eve, alice.did, unsupported, AccountKeyring::Alice),
+        Error::UnsupportedIdentityExportVersion
+    );
+
+    // The target has to be controlled by the exported primary key.
+    assert_noop!(
+        import(eve, charlie.did, export.clone(), AccountKeyring::Alice),
+        Error::IdentityExportKeyMismatch
+    );
+
+    // Claims expired since the export and custom claims are not re-issued.
+    set_timestamp(200);
+    assert_ok!(import(
+        eve,
+        alice.did,
+        export.clone(),
+        AccountKeyring::Alice
+    ));
+    let reissued = |claim_type, scope| Identity::fetch_claim(alice.did, claim_type, eve.did, scope);
+    assert!(reissued(ClaimType::Accredited, Some(scope.clone())).is_some());
+    assert!(reissued(ClaimType::Affiliate, Some(scope.clone())).is_none());
+    assert!(reissued(ClaimType::Custom(custom_claim_type), Some(scope)).is_none());
+    assert!(reissued(ClaimType::CustomerDueDiligence, None).is_some());
+    assert_eq!(
+        Identity::imported_identity_export(source_chain, alice.did),
+        Some(alice.did)
+    );
+
+    // The exported identity can't be imported into another identity.
+    let moved = IdentityExport {
+        primary_key: charlie.acc(),
+        ..export.clone()
+    };
+    assert_noop!(
+        import(eve, charlie.did, moved, AccountKeyring::Charlie),
+        Error::IdentityExportDidMismatch
+    );
+    assert_ok!(import(eve, alice.did, export, AccountKeyring::Alice));
+
+    // The attester can be revoked.
+    assert_ok!(Identity::set_export_attester(
+        root(),
+        source_chain,
+        attester.to_account_id(),
+        false
+    ));
+    assert!(!Identity::export_attester(
+        source_chain,
+        attester.to_account_id()
+    ));
+}
@@ -1,8 +1,9 @@
+use frame_support::traits::OnInitialize;
 use frame_support::{assert_noop, assert_ok, StorageMap};
 
 use pallet_multisig::{self as multisig, LostCreatorPrivileges};
 use polymesh_common_utilities::constants::currency::POLY;
-use polymesh_primitives::multisig::ProposalStatus;
+use polymesh_primitives::multisig::{CallFilter, ProposalStatus, ThresholdRule};
 use polymesh_primitives::{AccountId, AuthorizationData, Permissions, SecondaryKey, Signatory};
 use sp_keyring::AccountKeyring;
 
@@ -1320,6 +1321,372 @@ fn expired_proposals() {
     });
 }
 
+#[test]
+fn weighted_signers_and_threshold_rules() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice_did = register_keyring_account(AccountKeyring::Alice).unwrap();
+        let alice = Origin::signed(AccountKeyring::Alice.to_account_id());
+        let bob = Origin::signed(AccountKeyring::Bob.to_account_id());
+        let bob_signer = Signatory::Account(AccountKeyring::Bob.to_account_id());
+        let charlie = Origin::signed(AccountKeyring::Charlie.to_account_id());
+        let charlie_signer = Signatory::Account(AccountKeyring::Charlie.to_account_id());
+        let dave = Origin::signed(AccountKeyring::Dave.to_account_id());
+        let dave_signer = Signatory::Account(AccountKeyring::Dave.to_account_id());
+
+        let ms_address = MultiSig::get_next_multisig_address(AccountKeyring::Alice.to_account_id())
+            .expect("Next MS");
+        let ms_origin = Origin::signed(ms_address.clone());
+        setup_multisig(
+            alice.clone(),
+            1,
+            vec![
+                bob_signer.clone(),
+                charlie_signer.clone(),
+                dave_signer.clone(),
+            ],
+        );
+        assert_eq!(MultiSig::total_signer_weight(&ms_address), 3);
+
+        let large_transfers = |threshold| ThresholdRule {
+            filter: CallFilter {
+                pallet_name: b"Balances".into(),
+                dispatchable_name: Some(b"transfer".into()),
+                min_polyx: Some(100 * POLY),
+            },
+            threshold,
+        };
+        let transfer = |value| {
+            Box::new(RuntimeCall::Balances(pallet_balances::Call::transfer {
+                dest: AccountKeyring::Alice.to_account_id().into(),
+                value,
+            }))
+        };
+
+        // Rules must be reachable by the signers' total weight.
+        set_curr_did(Some(alice_did));
+        assert_noop!(
+            MultiSig::set_threshold_rules(ms_origin.clone(), vec![large_transfers(4)]),
+            Error::ThresholdRuleUnreachable
+        );
+        assert_noop!(
+            MultiSig::set_threshold_rules(ms_origin.clone(), vec![large_transfers(0)]),
+            Error::ThresholdRuleUnreachable
+        );
+        assert_ok!(MultiSig::set_threshold_rules(
+            ms_origin.clone(),
+            vec![large_transfers(3)]
+        ));
+
+        // Bob gets a weight of 2.
+        assert_noop!(
+            MultiSig::set_signer_weight(ms_origin.clone(), bob_signer.clone(), 0),
+            Error::InvalidSignerWeight
+        );
+        assert_ok!(MultiSig::set_signer_weight(
+            ms_origin.clone(),
+            bob_signer.clone(),
+            2
+        ));
+        assert_eq!(MultiSig::signer_weight(&ms_address, &bob_signer), 2);
+        assert_eq!(MultiSig::total_signer_weight(&ms_address), 4);
+
+        // Small transfers only need `sigs_required`.
+        assert_ok!(MultiSig::create_proposal_as_key(
+            dave.clone(),
+            ms_address.clone(),
+            transfer(10 * POLY),
+            None,
+            false
+        ));
+        let small_id = MultiSig::ms_tx_done(ms_address.clone()) - 1;
+        next_block();
+        assert_ne!(
+            MultiSig::proposal_detail(&ms_address, small_id).status,
+            ProposalStatus::ActiveOrExpired
+        );
+
+        // Large transfers need a total weight of 3.
+        assert_ok!(MultiSig::create_proposal_as_key(
+            charlie.clone(),
+            ms_address.clone(),
+            transfer(500 * POLY),
+            None,
+            false
+        ));
+        let large_id = MultiSig::ms_tx_done(ms_address.clone()) - 1;
+        next_block();
+        let details = MultiSig::proposal_detail(&ms_address, large_id);
+        assert_eq!(details.approvals, 1);
+        assert_eq!(details.status, ProposalStatus::ActiveOrExpired);
+
+        set_curr_did(Some(alice_did));
+        assert_ok!(MultiSig::approve_as_key(
+            bob.clone(),
+            ms_address.clone(),
+            large_id
+        ));
+        next_block();
+        let details = MultiSig::proposal_detail(&ms_address, large_id);
+        assert_eq!(details.approvals, 3);
+        assert_ne!(details.status, ProposalStatus::ActiveOrExpired);
+
+        // Rules also apply to the calls wrapped in a batch.
+        set_curr_did(Some(alice_did));
+        assert_ok!(MultiSig::create_proposal_as_key(
+            dave.clone(),
+            ms_address.clone(),
+            Box::new(RuntimeCall::Utility(pallet_utility::Call::batch {
+                calls: vec![*transfer(10 * POLY), *transfer(500 * POLY)],
+            })),
+            None,
+            false
+        ));
+        let batch_id = MultiSig::ms_tx_done(ms_address.clone()) - 1;
+        next_block();
+        let details = MultiSig::proposal_detail(&ms_address, batch_id);
+        assert_eq!(details.approvals, 1);
+        assert_eq!(details.status, ProposalStatus::ActiveOrExpired);
+
+        // A weighted rejection closes the proposal once the threshold is out of reach.
+        set_curr_did(Some(alice_did));
+        assert_ok!(MultiSig::create_proposal_as_key(
+            dave.clone(),
+            ms_address.clone(),
+            transfer(200 * POLY),
+            None,
+            true
+        ));
+        let rejected_id = MultiSig::ms_tx_done(ms_address.clone()) - 1;
+        assert_ok!(MultiSig::reject_as_key(
+            bob.clone(),
+            ms_address.clone(),
+            rejected_id
+        ));
+        let details = MultiSig::proposal_detail(&ms_address, rejected_id);
+        assert_eq!(details.rejections, 2);
+        assert_eq!(details.status, ProposalStatus::Rejected);
+
+        // Bob can't be removed while a rule needs his weight.
+        assert_noop!(
+            MultiSig::remove_multisig_signer(ms_origin.clone(), bob_signer.clone()),
+            Error::ThresholdRuleUnreachable
+        );
+        assert_ok!(MultiSig::set_threshold_rules(ms_origin.clone(), vec![]));
+        assert_ok!(MultiSig::remove_multisig_signer(
+            ms_origin,
+            bob_signer.clone()
+        ));
+        assert_eq!(MultiSig::signer_weights(&ms_address, &bob_signer), None);
+        assert_eq!(MultiSig::extra_signer_weight(&ms_address), 0);
+        assert_eq!(MultiSig::total_signer_weight(&ms_address), 2);
+    });
+}
+
+#[test]
+fn queued_proposals_and_vetoes() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice_did = register_keyring_account(AccountKeyring::Alice).unwrap();
+        let alice = Origin::signed(AccountKeyring::Alice.to_account_id());
+        let bob_did = register_keyring_account(AccountKeyring::Bob).unwrap();
+        let bob = Origin::signed(AccountKeyring::Bob.to_account_id());
+        let charlie_did = register_keyring_account(AccountKeyring::Charlie).unwrap();
+        let charlie = Origin::signed(AccountKeyring::Charlie.to_account_id());
+        let _ = register_keyring_account(AccountKeyring::Dave).unwrap();
+        let dave = Origin::signed(AccountKeyring::Dave.to_account_id());
+
+        let ms_address = MultiSig::get_next_multisig_address(AccountKeyring::Alice.to_account_id())
+            .expect("Next MS");
+        let ms_origin = Origin::signed(ms_address.clone());
+        setup_multisig(
+            alice.clone(),
+            1,
+            vec![Signatory::from(alice_did), Signatory::from(bob_did)],
+        );
+
+        // A 5 block delay, with Charlie as the guardian.
+        assert_ok!(MultiSig::set_execution_delay(ms_origin.clone(), Some(5)));
+        assert_ok!(MultiSig::set_guardian(ms_origin.clone(), Some(charlie_did)));
+        assert_eq!(MultiSig::execution_delay(&ms_address), Some(5));
+
+        let change_sigs = || {
+            Box::new(RuntimeCall::MultiSig(
+                multisig::Call::change_sigs_required { sigs_required: 2 },
+            ))
+        };
+        let status = |id| MultiSig::proposal_detail(&ms_address, id).status;
+
+        // Reaching the threshold queues the proposal.
+        set_curr_did(Some(alice_did));
+        assert_ok!(MultiSig::create_proposal_as_identity(
+            alice.clone(),
+            ms_address.clone(),
+            change_sigs(),
+            None,
+            false
+        ));
+        let vetoed_id = MultiSig::ms_tx_done(ms_address.clone()) - 1;
+        assert_eq!(status(vetoed_id), ProposalStatus::Queued);
+
+        // Queued proposals can't be voted on anymore.
+        set_curr_did(Some(bob_did));
+        assert_noop!(
+            MultiSig::approve_as_identity(bob.clone(), ms_address.clone(), vetoed_id),
+            Error::ProposalAlreadyQueued
+        );
+        assert_noop!(
+            MultiSig::reject_as_identity(bob.clone(), ms_address.clone(), vetoed_id),
+            Error::ProposalAlreadyQueued
+        );
+
+        // Only signers and the guardian can veto.
+        assert_noop!(
+            MultiSig::veto_proposal(dave, ms_address.clone(), vetoed_id),
+            Error::NotASignerOrGuardian
+        );
+        next_block();
+        assert_eq!(status(vetoed_id), ProposalStatus::Queued);
+        assert_ok!(MultiSig::veto_proposal(
+            charlie.clone(),
+            ms_address.clone(),
+            vetoed_id
+        ));
+        assert_eq!(status(vetoed_id), ProposalStatus::Rejected);
+        assert_noop!(
+            MultiSig::veto_proposal(bob.clone(), ms_address.clone(), vetoed_id),
+            Error::ProposalNotQueued
+        );
+
+        // The vetoed proposal is never executed.
+        for _ in 0..6 {
+            next_block();
+        }
+        assert_eq!(status(vetoed_id), ProposalStatus::Rejected);
+        assert_eq!(MultiSig::ms_signs_required(&ms_address), 1);
+
+        // Without a veto the proposal is executed once the delay has passed.
+        set_curr_did(Some(alice_did));
+        assert_ok!(MultiSig::create_proposal_as_identity(
+            alice.clone(),
+            ms_address.clone(),
+            change_sigs(),
+            None,
+            false
+        ));
+        let executed_id = MultiSig::ms_tx_done(ms_address.clone()) - 1;
+        for _ in 0..4 {
+            next_block();
+        }
+        assert_eq!(status(executed_id), ProposalStatus::Queued);
+        next_block();
+        assert_eq!(status(executed_id), ProposalStatus::ExecutionSuccessful);
+        assert_eq!(MultiSig::ms_signs_required(&ms_address), 2);
+    });
+}
+
+#[test]
+fn active_proposals_and_pruning() {
+    ExtBuilder::default().build().execute_with(|| {
+        let hour = 3_600_000u64;
+        let alice_did = register_keyring_account(AccountKeyring::Alice).unwrap();
+        let alice = Origin::signed(AccountKeyring::Alice.to_account_id());
+        let bob_did = register_keyring_account(AccountKeyring::Bob).unwrap();
+        let bob = Origin::signed(AccountKeyring::Bob.to_account_id());
+
+        let ms_address = MultiSig::get_next_multisig_address(AccountKeyring::Alice.to_account_id())
+            .expect("Next MS");
+        setup_multisig(
+            alice.clone(),
+            2,
+            vec![Signatory::from(alice_did), Signatory::from(bob_did)],
+        );
+        set_timestamp(0);
+        assert_ok!(MultiSig::set_proposal_retention_period(
+            Origin::root(),
+            Some(hour)
+        ));
+
+        // A rejected proposal.
+        set_curr_did(Some(alice_did));
+        assert_ok!(MultiSig::create_proposal_as_identity(
+            alice.clone(),
+            ms_address.clone(),
+            Box::new(RuntimeCall::MultiSig(
+                multisig::Call::change_sigs_required { sigs_required: 1 },
+            )),
+            None,
+            true
+        ));
+        let rejected_id = MultiSig::ms_tx_done(ms_address.clone()) - 1;
+        set_curr_did(Some(bob_did));
+        assert_ok!(MultiSig::reject_as_identity(
+            bob.clone(),
+            ms_address.clone(),
+            rejected_id
+        ));
+
+        // An active proposal, expiring in half an hour.
+        let remark = RuntimeCall::System(frame_system::Call::remark {
+            remark: b"pay".to_vec(),
+        });
+        set_curr_did(Some(alice_did));
+        assert_ok!(MultiSig::create_proposal_as_identity(
+            alice.clone(),
+            ms_address.clone(),
+            Box::new(remark.clone()),
+            Some(hour / 2),
+            false
+        ));
+        let active_id = MultiSig::ms_tx_done(ms_address.clone()) - 1;
+
+        // A proposal can't be created already expired.
+        assert_noop!(
+            MultiSig::create_proposal_as_identity(
+                alice.clone(),
+                ms_address.clone(),
+                Box::new(remark.clone()),
+                Some(0),
+                false
+            ),
+            Error::ProposalExpired
+        );
+
+        // Only the active proposal is listed.
+        let active = MultiSig::active_proposals(&ms_address, None, 10);
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].proposal_id, active_id);
+        assert_eq!(active[0].pallet_name.as_slice(), b"System");
+        assert_eq!(active[0].dispatchable_name.as_slice(), b"remark");
+        assert_eq!(active[0].call, remark);
+        assert_eq!(active[0].details.expiry, Some(hour / 2));
+        assert_eq!(active[0].approvals_required, 2);
+        assert_eq!(active[0].voters, vec![Signatory::from(alice_did)]);
+        assert!(MultiSig::active_proposals(&ms_address, Some(active_id), 10).is_empty());
+        assert!(MultiSig::active_proposals(&ms_address, None, 0).is_empty());
+
+        // Expired proposals aren't active anymore, but are kept for the retention period.
+        set_timestamp(hour);
+        assert!(MultiSig::active_proposals(&ms_address, None, 10).is_empty());
+        MultiSig::on_initialize(1);
+        assert!(MultiSig::proposals(&ms_address, rejected_id).is_some());
+        assert!(MultiSig::proposals(&ms_address, active_id).is_some());
+
+        // Both are pruned once their retention period has passed.
+        set_timestamp(2 * hour);
+        MultiSig::on_initialize(2);
+        for id in [rejected_id, active_id] {
+            assert!(MultiSig::proposals(&ms_address, id).is_none());
+            assert_eq!(
+                MultiSig::proposal_detail(&ms_address, id).status,
+                ProposalStatus::Invalid
+            );
+            assert!(!MultiSig::votes(
+                (ms_address.clone(), id),
+                Signatory::from(alice_did)
+            ));
+        }
+    });
+}
+
 fn setup_multisig(creator_origin: Origin, sigs_required: u64, signers: Vec<Signatory<AccountId>>) {
     assert_ok!(MultiSig::create_multisig(
         creator_origin,
@@ -1,16 +1,18 @@
 use chrono::prelude::Utc;
+use frame_support::traits::Get;
 use frame_support::{assert_noop, assert_ok, StorageDoubleMap, StorageMap};
 
 use pallet_nft::{
-    Collection, CollectionKeys, MetadataValue, NFTOwner, NFTsInCollection, NumberOfNFTs,
+    Collection, CollectionKeys, MetadataValue, MetadataValueDetails, NFTOwner, NFTsInCollection,
+    NumberOfNFTs,
 };
 use pallet_portfolio::PortfolioNFT;
 use polymesh_common_utilities::traits::nft::Event;
 use polymesh_common_utilities::with_transaction;
 use polymesh_primitives::asset::{AssetType, NonFungibleType};
 use polymesh_primitives::asset_metadata::{
-    AssetMetadataKey, AssetMetadataLocalKey, AssetMetadataName, AssetMetadataSpec,
-    AssetMetadataValue,
+    AssetMetadataKey, AssetMetadataLocalKey, AssetMetadataLockStatus, AssetMetadataName,
+    AssetMetadataSpec, AssetMetadataValue, AssetMetadataValueDetail,
 };
 use polymesh_primitives::settlement::InstructionId;
 use polymesh_primitives::{
@@ -20,11 +22,12 @@ use polymesh_primitives::{
 };
 use sp_keyring::AccountKeyring;
 
-use super::asset_test::{set_timestamp, token_details};
+use super::asset_test::{set_timestamp, token_details, transfer};
 use crate::ext_builder::ExtBuilder;
 use crate::storage::{TestStorage, User};
 
 type Asset = pallet_asset::Module<TestStorage>;
+type AssetError = pallet_asset::Error<TestStorage>;
 type ComplianceManager = pallet_compliance_manager::Module<TestStorage>;
 type EAError = pallet_external_agents::Error<TestStorage>;
 type Identity = pallet_identity::Module<TestStorage>;
@@ -49,7 +52,8 @@ fn create_collection_unregistered_ticker() {
             alice.origin(),
             ticker.clone(),
             Some(nft_type),
-            collection_keys
+            collection_keys,
+            None
         ));
         assert_eq!(token_details(&ticker).divisible, false);
         assert_eq!(
@@ -81,7 +85,7 @@ fn create_collection_invalid_asset_type() {
         .expect("failed to create an asset");
 
         assert_noop!(
-            NFT::create_nft_collection(alice.origin(), ticker, None, collection_keys),
+            NFT::create_nft_collection(alice.origin(), ticker, None, collection_keys, None),
             NFTError::InvalidAssetType
         );
     });
@@ -102,10 +106,17 @@ fn create_collection_already_registered() {
             alice.origin(),
             ticker,
             Some(nft_type),
-            collection_keys.clone()
+            collection_keys.clone(),
+            None
         ));
         assert_noop!(
-            NFT::create_nft_collection(alice.origin(), ticker, Some(nft_type), collection_keys),
+            NFT::create_nft_collection(
+                alice.origin(),
+                ticker,
+                Some(nft_type),
+                collection_keys,
+                None
+            ),
             NFTError::CollectionAlredyRegistered
         );
     });
@@ -128,7 +139,8 @@ fn create_collection_max_keys_exceeded() {
                 alice.origin(),
                 ticker,
                 Some(nft_type),
-                collection_keys.into()
+                collection_keys.into(),
+                None
             ),
             NFTError::MaxNumberOfKeysExceeded
         );
@@ -155,7 +167,8 @@ fn create_collection_duplicate_key() {
                 alice.origin(),
                 ticker,
                 Some(nft_type),
-                collection_keys.into()
+                collection_keys.into(),
+                None
             ),
             NFTError::DuplicateMetadataKey
         );
@@ -175,7 +188,13 @@ fn create_collection_unregistered_key() {
             vec![AssetMetadataKey::Local(AssetMetadataLocalKey(0))].into();
 
         assert_noop!(
-            NFT::create_nft_collection(alice.origin(), ticker, Some(nft_type), collection_keys),
+            NFT::create_nft_collection(
+                alice.origin(),
+                ticker,
+                Some(nft_type),
+                collection_keys,
+                None
+            ),
             NFTError::UnregisteredMetadataKey
         );
     });
@@ -216,7 +235,8 @@ pub(crate) fn create_nft_collection(
         owner.origin(),
         ticker,
         None,
-        collection_keys
+        collection_keys,
+        None
     ));
     assert!(Collection::contains_key(NFTCollectionId(1)));
     assert_eq!(CollectionKeys::get(NFTCollectionId(1)).len(), n_keys);
@@ -1097,3 +1117,499 @@ fn controller_transfer_nft_not_owned() {
         );
     });
 }
+
+/// The metadata value of an NFT can be updated until it gets locked.
+#[test]
+fn set_nft_metadata_value() {
+    ExtBuilder::default().build().execute_with(|| {
+        set_timestamp(Utc::now().timestamp() as _);
+
+        let alice: User = User::new(AccountKeyring::Alice);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        let key = AssetMetadataKey::Local(AssetMetadataLocalKey(1));
+        create_nft_collection(
+            alice.clone(),
+            ticker.clone(),
+            AssetType::NonFungible(NonFungibleType::Derivative),
+            vec![key].into(),
+        );
+        let nfts_metadata: Vec<NFTMetadataAttribute> = vec![NFTMetadataAttribute {
+            key,
+            value: AssetMetadataValue(b"test".to_vec()),
+        }];
+        mint_nft(
+            alice.clone(),
+            ticker.clone(),
+            nfts_metadata,
+            PortfolioKind::Default,
+        );
+
+        assert_noop!(
+            NFT::set_nft_metadata_value(
+                alice.origin(),
+                ticker,
+                NFTId(2),
+                key,
+                AssetMetadataValue(b"updated".to_vec()),
+                None
+            ),
+            NFTError::NFTNotFound
+        );
+        assert_noop!(
+            NFT::set_nft_metadata_value(
+                alice.origin(),
+                ticker,
+                NFTId(1),
+                AssetMetadataKey::Local(AssetMetadataLocalKey(2)),
+                AssetMetadataValue(b"updated".to_vec()),
+                None
+            ),
+            NFTError::InvalidMetadataAttribute
+        );
+
+        // Details are removed when the value is updated without them.
+        let unlocked_detail = AssetMetadataValueDetail {
+            expire: None,
+            lock_status: AssetMetadataLockStatus::Unlocked,
+        };
+        assert_ok!(NFT::set_nft_metadata_value(
+            alice.origin(),
+            ticker,
+            NFTId(1),
+            key,
+            AssetMetadataValue(b"updated".to_vec()),
+            Some(unlocked_detail.clone())
+        ));
+        assert_eq!(
+            MetadataValueDetails::<TestStorage>::get((NFTCollectionId(1), NFTId(1)), key),
+            Some(unlocked_detail)
+        );
+        assert_ok!(NFT::set_nft_metadata_value(
+            alice.origin(),
+            ticker,
+            NFTId(1),
+            key,
+            AssetMetadataValue(b"updated".to_vec()),
+            None
+        ));
+        assert_eq!(
+            MetadataValueDetails::<TestStorage>::get((NFTCollectionId(1), NFTId(1)), key),
+            None
+        );
+
+        let locked_detail = AssetMetadataValueDetail {
+            expire: None,
+            lock_status: AssetMetadataLockStatus::Locked,
+        };
+        assert_ok!(NFT::set_nft_metadata_value(
+            alice.origin(),
+            ticker,
+            NFTId(1),
+            key,
+            AssetMetadataValue(b"updated".to_vec()),
+            Some(locked_detail)
+        ));
+        assert_eq!(
+            MetadataValue::get((NFTCollectionId(1), NFTId(1)), key),
+            AssetMetadataValue(b"updated".to_vec())
+        );
+        assert_noop!(
+            NFT::set_nft_metadata_value(
+                alice.origin(),
+                ticker,
+                NFTId(1),
+                key,
+                AssetMetadataValue(b"locked".to_vec()),
+                None
+            ),
+            NFTError::NFTMetadataValueIsLocked
+        );
+    });
+}
+
+/// A frozen NFT can't be transferred until it gets unfrozen.
+#[test]
+fn transfer_frozen_nft() {
+    ExtBuilder::default().build().execute_with(|| {
+        set_timestamp(Utc::now().timestamp() as _);
+
+        let alice: User = User::new(AccountKeyring::Alice);
+        let bob: User = User::new(AccountKeyring::Bob);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        let mut weight_meter = WeightMeter::max_limit_no_minimum();
+        create_nft_collection(
+            alice.clone(),
+            ticker.clone(),
+            AssetType::NonFungible(NonFungibleType::Derivative),
+            Vec::new().into(),
+        );
+        mint_nft(
+            alice.clone(),
+            ticker.clone(),
+            Vec::new(),
+            PortfolioKind::Default,
+        );
+        ComplianceManager::pause_asset_compliance(alice.origin(), ticker.clone()).unwrap();
+
+        assert_noop!(
+            NFT::freeze_nft(bob.origin(), ticker, NFTId(1)),
+            EAError::UnauthorizedAgent
+        );
+        assert_noop!(
+            NFT::unfreeze_nft(alice.origin(), ticker, NFTId(1)),
+            NFTError::NFTNotFrozen
+        );
+        assert_ok!(NFT::freeze_nft(alice.origin(), ticker, NFTId(1)));
+        assert_noop!(
+            NFT::freeze_nft(alice.origin(), ticker, NFTId(1)),
+            NFTError::NFTAlreadyFrozen
+        );
+
+        let sender_portfolio = PortfolioId::default_portfolio(alice.did);
+        let receiver_portfolio = PortfolioId::default_portfolio(bob.did);
+        let nfts = NFTs::new(ticker, vec![NFTId(1)]).unwrap();
+        assert_noop!(
+            with_transaction(|| {
+                NFT::base_nft_transfer(
+                    sender_portfolio,
+                    receiver_portfolio,
+                    nfts.clone(),
+                    InstructionId(0),
+                    None,
+                    IdentityId::default(),
+                    &mut weight_meter,
+                )
+            }),
+            NFTError::InvalidNFTTransferFrozenNFT
+        );
+
+        assert_ok!(NFT::unfreeze_nft(alice.origin(), ticker, NFTId(1)));
+        assert_ok!(with_transaction(|| {
+            NFT::base_nft_transfer(
+                sender_portfolio,
+                receiver_portfolio,
+                nfts,
+                InstructionId(0),
+                None,
+                IdentityId::default(),
+                &mut weight_meter,
+            )
+        }));
+        assert_eq!(NFTOwner::get(ticker, NFTId(1)), Some(receiver_portfolio));
+    });
+}
+
+/// An NFT can be fractionalised into a fungible asset and recovered by redeeming all of its fractions.
+#[test]
+fn fractionalise_and_redeem_nft() {
+    ExtBuilder::default().build().execute_with(|| {
+        set_timestamp(Utc::now().timestamp() as _);
+
+        let alice: User = User::new(AccountKeyring::Alice);
+        let bob: User = User::new(AccountKeyring::Bob);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        let fractions_ticker: Ticker = Ticker::from_slice_truncated(b"FRACTIONS".as_ref());
+        create_nft_collection(
+            alice.clone(),
+            ticker.clone(),
+            AssetType::NonFungible(NonFungibleType::Derivative),
+            Vec::new().into(),
+        );
+        mint_nft(
+            alice.clone(),
+            ticker.clone(),
+            Vec::new(),
+            PortfolioKind::Default,
+        );
+        mint_nft(
+            alice.clone(),
+            ticker.clone(),
+            Vec::new(),
+            PortfolioKind::Default,
+        );
+        ComplianceManager::pause_asset_compliance(alice.origin(), ticker.clone()).unwrap();
+        Asset::create_asset(
+            alice.origin(),
+            fractions_ticker.as_ref().into(),
+            fractions_ticker.clone(),
+            true,
+            AssetType::default(),
+            Vec::new(),
+            None,
+        )
+        .expect("failed to create an asset");
+        ComplianceManager::pause_asset_compliance(alice.origin(), fractions_ticker.clone())
+            .unwrap();
+
+        assert_noop!(
+            NFT::fractionalise_nft(
+                alice.origin(),
+                ticker,
+                NFTId(1),
+                PortfolioKind::Default,
+                fractions_ticker,
+                0
+            ),
+            NFTError::ZeroFractions
+        );
+        assert_ok!(NFT::fractionalise_nft(
+            alice.origin(),
+            ticker,
+            NFTId(1),
+            PortfolioKind::Default,
+            fractions_ticker,
+            1_000
+        ));
+        assert_eq!(
+            NFTOwner::get(ticker, NFTId(1)),
+            Some(NFT::custody_portfolio())
+        );
+        assert_eq!(NumberOfNFTs::get(&ticker, alice.did), 1);
+        assert_eq!(Asset::balance_of(&fractions_ticker, alice.did), 1_000);
+
+        // The fractions ticker can only represent one NFT and the NFT can't leave custody
+        assert_noop!(
+            NFT::fractionalise_nft(
+                alice.origin(),
+                ticker,
+                NFTId(2),
+                PortfolioKind::Default,
+                fractions_ticker,
+                1_000
+            ),
+            NFTError::InvalidFractionsAsset
+        );
+        assert_noop!(
+            NFT::controller_transfer(
+                alice.origin(),
+                ticker,
+                NFTs::new(ticker, vec![NFTId(1)]).unwrap(),
+                NFT::custody_portfolio(),
+                PortfolioKind::Default
+            ),
+            NFTError::NFTInCustody
+        );
+
+        // The supply of the fractions is locked while the NFT is in custody
+        assert_noop!(
+            Asset::issue(
+                alice.origin(),
+                fractions_ticker,
+                1_000,
+                PortfolioKind::Default
+            ),
+            AssetError::FractionsSupplyLocked
+        );
+        assert_noop!(
+            Asset::redeem(alice.origin(), fractions_ticker, 1_000),
+            AssetError::FractionsSupplyLocked
+        );
+
+        // A frozen NFT can't be released from custody
+        assert_ok!(NFT::freeze_nft(alice.origin(), ticker, NFTId(1)));
+        assert_noop!(
+            NFT::redeem_nft_fractions(alice.origin(), fractions_ticker, PortfolioKind::Default),
+            NFTError::InvalidNFTTransferFrozenNFT
+        );
+        assert_ok!(NFT::unfreeze_nft(alice.origin(), ticker, NFTId(1)));
+
+        // All fractions must be held to recover the NFT
+        assert_ok!(transfer(fractions_ticker, alice, bob, 100));
+        assert_noop!(
+            NFT::redeem_nft_fractions(alice.origin(), fractions_ticker, PortfolioKind::Default),
+            NFTError::InsufficientFractions
+        );
+        assert_ok!(transfer(fractions_ticker, bob, alice, 100));
+        assert_ok!(NFT::redeem_nft_fractions(
+            alice.origin(),
+            fractions_ticker,
+            PortfolioKind::Default
+        ));
+        assert_eq!(
+            NFTOwner::get(ticker, NFTId(1)),
+            Some(PortfolioId::default_portfolio(alice.did))
+        );
+        assert_eq!(NumberOfNFTs::get(&ticker, alice.did), 2);
+        assert_eq!(Asset::total_supply(fractions_ticker), 0);
+        assert_eq!(NFT::fractionalised_nft(fractions_ticker), None);
+    });
+}
+
+/// NFTs can be enumerated by collection and portfolio, and their metadata and owner queried.
+#[test]
+fn query_nfts() {
+    ExtBuilder::default().build().execute_with(|| {
+        set_timestamp(Utc::now().timestamp() as _);
+
+        let alice: User = User::new(AccountKeyring::Alice);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        let alice_default_portfolio = PortfolioId::default_portfolio(alice.did);
+        let metadata_key = AssetMetadataKey::Local(AssetMetadataLocalKey(1));
+        create_nft_collection(
+            alice.clone(),
+            ticker.clone(),
+            AssetType::NonFungible(NonFungibleType::Derivative),
+            vec![metadata_key].into(),
+        );
+        for value in [b"a", b"b", b"c"] {
+            mint_nft(
+                alice.clone(),
+                ticker.clone(),
+                vec![NFTMetadataAttribute {
+                    key: metadata_key,
+                    value: AssetMetadataValue(value.to_vec()),
+                }],
+                PortfolioKind::Default,
+            );
+        }
+        assert_ok!(NFT::redeem_nft(
+            alice.origin(),
+            ticker,
+            NFTId(2),
+            PortfolioKind::Default
+        ));
+
+        assert_eq!(
+            NFT::collection_nfts(&ticker, None, 10),
+            (vec![NFTId(1), NFTId(3)], None)
+        );
+        assert_eq!(
+            NFT::collection_nfts(&ticker, None, 1),
+            (vec![NFTId(1)], Some(NFTId(2)))
+        );
+        // Redeemed NFTs are skipped but still count towards the limit.
+        assert_eq!(
+            NFT::collection_nfts(&ticker, Some(NFTId(2)), 1),
+            (vec![], Some(NFTId(3)))
+        );
+        assert_eq!(
+            NFT::collection_nfts(&ticker, Some(NFTId(2)), 10),
+            (vec![NFTId(3)], None)
+        );
+        assert_eq!(
+            NFT::collection_nfts(&Ticker::from_slice_truncated(b"NOPE".as_ref()), None, 10),
+            (Vec::new(), None)
+        );
+
+        let mut portfolio_nfts = NFT::portfolio_nfts(&alice_default_portfolio, None, 10);
+        portfolio_nfts.sort();
+        assert_eq!(portfolio_nfts, vec![(ticker, NFTId(1)), (ticker, NFTId(3))]);
+        let first_page = NFT::portfolio_nfts(&alice_default_portfolio, None, 1);
+        assert_eq!(first_page.len(), 1);
+        let second_page =
+            NFT::portfolio_nfts(&alice_default_portfolio, first_page.last().copied(), 10);
+        assert_eq!(second_page.len(), 1);
+        assert_ne!(first_page, second_page);
+
+        assert_eq!(
+            NFT::nft_metadata(&ticker, &NFTId(3)),
+            Some(vec![NFTMetadataAttribute {
+                key: metadata_key,
+                value: AssetMetadataValue(b"c".to_vec()),
+            }])
+        );
+        assert_eq!(NFT::nft_metadata(&ticker, &NFTId(2)), None);
+
+        assert_eq!(
+            NFT::nft_owner(&ticker, &NFTId(1)),
+            Some(alice_default_portfolio)
+        );
+        assert_eq!(NFT::nft_owner(&ticker, &NFTId(2)), None);
+    });
+}
+
+/// Multiple NFTs can be issued at once, up to the maximum supply of the collection.
+#[test]
+fn issue_nfts_max_supply() {
+    ExtBuilder::default().build().execute_with(|| {
+        set_timestamp(Utc::now().timestamp() as _);
+
+        let alice: User = User::new(AccountKeyring::Alice);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        let alice_default_portfolio = PortfolioId::default_portfolio(alice.did);
+        let nft_type = NonFungibleType::Derivative;
+
+        assert_noop!(
+            NFT::create_nft_collection(
+                alice.origin(),
+                ticker,
+                Some(nft_type),
+                Vec::new().into(),
+                Some(0)
+            ),
+            NFTError::ZeroMaxSupply
+        );
+        assert_ok!(NFT::create_nft_collection(
+            alice.origin(),
+            ticker,
+            Some(nft_type),
+            Vec::new().into(),
+            Some(3)
+        ));
+        assert_eq!(NFT::collection_max_supply(&ticker), Some(3));
+
+        assert_noop!(
+            NFT::issue_nfts(alice.origin(), ticker, Vec::new(), PortfolioKind::Default),
+            NFTError::ZeroCount
+        );
+        let max_nfts_per_issue = <TestStorage as pallet_nft::Config>::MaxNFTsPerIssue::get();
+        assert_noop!(
+            NFT::issue_nfts(
+                alice.origin(),
+                ticker,
+                vec![Vec::new(); max_nfts_per_issue as usize + 1],
+                PortfolioKind::Default
+            ),
+            NFTError::MaxNFTsPerIssueExceeded
+        );
+        assert_noop!(
+            NFT::issue_nfts(
+                alice.origin(),
+                ticker,
+                vec![Vec::new(); 4],
+                PortfolioKind::Default
+            ),
+            NFTError::MaxSupplyExceeded
+        );
+        assert_ok!(NFT::issue_nfts(
+            alice.origin(),
+            ticker,
+            vec![Vec::new(); 2],
+            PortfolioKind::Default
+        ));
+        assert_eq!(
+            super::storage::EventTest::Nft(Event::NFTPortfolioUpdated(
+                alice.did,
+                NFTs::new_unverified(ticker, vec![NFTId(1), NFTId(2)]),
+                None,
+                Some(alice_default_portfolio),
+                PortfolioUpdateReason::Issued {
+                    funding_round_name: None
+                }
+            )),
+            System::events().last().unwrap().event,
+        );
+        assert_eq!(NFTsInCollection::get(&ticker), 2);
+        assert_eq!(NumberOfNFTs::get(&ticker, alice.did), 2);
+        assert_eq!(
+            NFTOwner::get(ticker, NFTId(2)),
+            Some(alice_default_portfolio)
+        );
+
+        mint_nft(alice.clone(), ticker, Vec::new(), PortfolioKind::Default);
+        assert_noop!(
+            NFT::issue_nft(alice.origin(), ticker, Vec::new(), PortfolioKind::Default),
+            NFTError::MaxSupplyExceeded
+        );
+
+        // Redeeming an NFT frees up supply for a new one.
+        assert_ok!(NFT::redeem_nft(
+            alice.origin(),
+            ticker,
+            NFTId(1),
+            PortfolioKind::Default
+        ));
+        mint_nft(alice.clone(), ticker, Vec::new(), PortfolioKind::Default);
+        assert_eq!(NFTsInCollection::get(&ticker), 3);
+    });
+}
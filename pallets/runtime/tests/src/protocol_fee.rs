@@ -1,16 +1,20 @@
 use super::{
     ext_builder::PROTOCOL_OP_BASE_FEE,
-    storage::{register_keyring_account_with_balance, TestStorage},
+    storage::{register_keyring_account_with_balance, root, TestStorage},
     ExtBuilder,
 };
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchError};
+use pallet_protocol_fee::DiscountTier;
 use polymesh_common_utilities::{
     protocol_fee::ProtocolOp, traits::transaction_payment::CddAndFeeDetails,
 };
 use sp_keyring::AccountKeyring;
+use sp_runtime::Permill;
 
 type Error = pallet_protocol_fee::Error<TestStorage>;
 type ProtocolFee = pallet_protocol_fee::Module<TestStorage>;
+type System = frame_system::Pallet<TestStorage>;
+type Origin = <TestStorage as frame_system::Config>::RuntimeOrigin;
 
 #[test]
 fn can_compute_fee() {
@@ -40,3 +44,72 @@ fn can_charge_fee_batch() {
         );
     });
 }
+
+#[test]
+fn discount_tiers_and_fee_waivers() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+        let alice_did =
+            register_keyring_account_with_balance(AccountKeyring::Alice, 1_000).unwrap();
+        let bob_did = register_keyring_account_with_balance(AccountKeyring::Bob, 1_000).unwrap();
+        let op = ProtocolOp::AssetIssue;
+        let tier = |min_ops, discount| DiscountTier {
+            min_ops,
+            discount: Permill::from_percent(discount),
+        };
+        assert_ok!(ProtocolFee::change_base_fee(root(), op, 100));
+
+        // Only root can set tiers and waivers, and tiers must be strictly increasing.
+        let signed = Origin::signed(alice.clone());
+        assert_noop!(
+            ProtocolFee::set_discount_tiers(signed.clone(), op, vec![tier(2, 50)]),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            ProtocolFee::set_fee_waiver(signed, bob_did, op, true),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            ProtocolFee::set_discount_tiers(root(), op, vec![tier(2, 50), tier(2, 60)]),
+            Error::InvalidDiscountTiers
+        );
+        assert_noop!(
+            ProtocolFee::set_discount_tiers(root(), op, vec![tier(2, 50), tier(3, 40)]),
+            Error::InvalidDiscountTiers
+        );
+        assert_ok!(ProtocolFee::set_discount_tiers(
+            root(),
+            op,
+            vec![tier(2, 50), tier(4, 75)]
+        ));
+        assert_ok!(ProtocolFee::set_discount_period(root(), 10));
+        assert_ok!(ProtocolFee::set_fee_waiver(root(), bob_did, op, true));
+
+        // Discounts apply once enough operations were charged in the period.
+        System::set_block_number(1);
+        TestStorage::set_payer_context(Some(alice));
+        assert_eq!(ProtocolFee::compute_fee_for(Some(alice_did), &[op]), 100);
+        assert_ok!(ProtocolFee::batch_charge_fee(op, 2));
+        assert_eq!(ProtocolFee::compute_fee(&[op]), 50);
+        assert_ok!(ProtocolFee::charge_fees(&[op, op]));
+        assert_eq!(ProtocolFee::compute_fee_for(Some(alice_did), &[op]), 25);
+        // Without an identity, there is no discount.
+        assert_eq!(ProtocolFee::compute_fee_for(None, &[op]), 100);
+
+        // Operations without discount tiers are not counted.
+        let other_op = ProtocolOp::AssetRegisterTicker;
+        assert_ok!(ProtocolFee::change_base_fee(root(), other_op, 10));
+        assert_ok!(ProtocolFee::charge_fees(&[other_op]));
+        assert_eq!(ProtocolFee::ops_usage(alice_did, other_op).count, 0);
+        assert_eq!(ProtocolFee::ops_usage(alice_did, op).count, 4);
+
+        // The count is reset once the period has elapsed.
+        System::set_block_number(11);
+        assert_eq!(ProtocolFee::compute_fee_for(Some(alice_did), &[op]), 100);
+
+        // Waived operations are free, until the waiver is lifted.
+        assert_eq!(ProtocolFee::compute_fee_for(Some(bob_did), &[op]), 0);
+        assert_ok!(ProtocolFee::set_fee_waiver(root(), bob_did, op, false));
+        assert_eq!(ProtocolFee::compute_fee_for(Some(bob_did), &[op]), 100);
+    });
+}
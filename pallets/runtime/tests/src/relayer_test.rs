@@ -1,19 +1,26 @@
 use super::{
+    asset_test::set_timestamp,
     storage::{get_last_auth_id, make_account_without_cdd, RuntimeCall, TestStorage, User},
     ExtBuilder,
 };
 use frame_support::{
     assert_noop, assert_ok,
     dispatch::{DispatchInfo, Pays, PostDispatchInfo, Weight},
+    traits::GetCallMetadata,
     StorageMap,
 };
 use frame_system;
+use pallet_permissions::StoreCallMetadata;
 use pallet_relayer::Subsidy;
 use polymesh_common_utilities::{
-    constants::currency::POLY, protocol_fee::ProtocolOp,
-    traits::transaction_payment::CddAndFeeDetails,
+    constants::currency::POLY,
+    protocol_fee::ProtocolOp,
+    traits::{relayer::SubsidiserTrait, transaction_payment::CddAndFeeDetails},
+};
+use polymesh_primitives::{
+    secondary_key::DispatchableNames, subsidy::SubsidyLimits, AccountId, Balance,
+    ExtrinsicPermissions, PalletPermissions, Signatory, Ticker, TransactionError,
 };
-use polymesh_primitives::{AccountId, Balance, Signatory, Ticker, TransactionError};
 use polymesh_runtime_develop::runtime::{CddHandler, RuntimeCall as DevRuntimeCall};
 use sp_keyring::AccountKeyring;
 use sp_runtime::{
@@ -568,3 +575,124 @@ fn do_relayer_accept_cdd_and_fees_test() {
         Ok(Some(alice.acc()))
     );
 }
+
+#[test]
+fn relayer_subsidy_limits_test() {
+    ExtBuilder::default()
+        .monied(true)
+        .build()
+        .execute_with(&do_relayer_subsidy_limits_test);
+}
+fn do_relayer_subsidy_limits_test() {
+    let bob = User::new(AccountKeyring::Bob);
+    let alice = User::new(AccountKeyring::Alice);
+    let charlie = User::new(AccountKeyring::Charlie);
+
+    setup_subsidy(bob, alice, 1_000 * POLY);
+    set_timestamp(1_000);
+
+    let register_ticker = call_asset_register_ticker(b"ACME").get_call_metadata();
+    let transfer = call_balance_transfer(42).get_call_metadata();
+    let not_subsidised = Err(InvalidTransaction::Custom(
+        TransactionError::CallNotSubsidised as u8,
+    ));
+
+    // Only subsidise `Asset::register_ticker`, 100 POLYX per second, until `5_000`.
+    let limits = SubsidyLimits {
+        calls: ExtrinsicPermissions::elem(PalletPermissions::new(
+            b"Asset".into(),
+            DispatchableNames::elem(b"register_ticker".into()),
+        )),
+        expires_at: Some(5_000),
+        refill_period: 1_000,
+        period_limit: Some(100),
+    };
+
+    // Only the paying key can limit the subsidy, and only until a future time.
+    assert_noop!(
+        Relayer::set_subsidy_limits(charlie.origin(), bob.acc(), Some(limits.clone())),
+        Error::NotPayingKey
+    );
+    let expired = SubsidyLimits {
+        expires_at: Some(1_000),
+        ..limits.clone()
+    };
+    assert_noop!(
+        Relayer::set_subsidy_limits(alice.origin(), bob.acc(), Some(expired)),
+        Error::SubsidyExpiryInPast
+    );
+    assert_ok!(Relayer::set_subsidy_limits(
+        alice.origin(),
+        bob.acc(),
+        Some(limits.clone())
+    ));
+    assert_eq!(Relayer::subsidy_limits(bob.acc()), Some(limits));
+
+    // Calls outside of the limits are not subsidised.
+    assert_eq!(
+        Relayer::check_subsidy(&bob.acc(), 10, Some(&transfer)),
+        not_subsidised
+    );
+    assert_eq!(
+        Relayer::check_subsidy(&bob.acc(), 10, Some(&register_ticker)),
+        Ok(Some(alice.acc()))
+    );
+
+    // Protocol fees are only subsidised while dispatching a subsidised call.
+    StoreCallMetadata::<TestStorage>::set_call_metadata(b"Balances".into(), b"transfer".into());
+    assert_eq!(Relayer::check_subsidy(&bob.acc(), 10, None), not_subsidised);
+    StoreCallMetadata::<TestStorage>::set_call_metadata(b"Asset".into(), b"register_ticker".into());
+    assert_eq!(
+        Relayer::check_subsidy(&bob.acc(), 10, None),
+        Ok(Some(alice.acc()))
+    );
+
+    // The subsidy can't pay more than the period limit.
+    assert_eq!(
+        Relayer::debit_subsidy(&bob.acc(), 60),
+        Ok(Some(alice.acc()))
+    );
+    assert_eq!(
+        Relayer::check_subsidy(&bob.acc(), 50, Some(&register_ticker)),
+        Err(InvalidTransaction::Payment)
+    );
+    assert_eq!(
+        Relayer::check_subsidy(&bob.acc(), 40, Some(&register_ticker)),
+        Ok(Some(alice.acc()))
+    );
+    assert_subsidy(bob, Some((alice, 1_000 * POLY - 60)));
+
+    // The period limit is refilled in the next period.
+    set_timestamp(2_000);
+    assert_eq!(
+        Relayer::check_subsidy(&bob.acc(), 100, Some(&register_ticker)),
+        Ok(Some(alice.acc()))
+    );
+
+    // An expired subsidy doesn't pay the fees anymore.
+    set_timestamp(5_001);
+    assert_eq!(
+        Relayer::check_subsidy(&bob.acc(), 10, Some(&register_ticker)),
+        Ok(None)
+    );
+
+    // Removing the limits subsidises all calls again.
+    assert_ok!(Relayer::set_subsidy_limits(alice.origin(), bob.acc(), None));
+    assert_eq!(
+        Relayer::check_subsidy(&bob.acc(), 10, Some(&transfer)),
+        Ok(Some(alice.acc()))
+    );
+
+    // Removing the paying key removes the limits.
+    assert_ok!(Relayer::set_subsidy_limits(
+        alice.origin(),
+        bob.acc(),
+        Some(SubsidyLimits::default())
+    ));
+    assert_ok!(Relayer::remove_paying_key(
+        bob.origin(),
+        bob.acc(),
+        alice.acc()
+    ));
+    assert_eq!(Relayer::subsidy_limits(bob.acc()), None);
+}
@@ -28,22 +28,31 @@ use polymesh_primitives::asset_metadata::{
     AssetMetadataKey, AssetMetadataLocalKey, AssetMetadataValue,
 };
 use polymesh_primitives::checked_inc::CheckedInc;
+use polymesh_primitives::compliance_manager::{
+    AssetCompliance, ComplianceImpact, ComplianceRequirement,
+};
+use polymesh_primitives::nft::NFTRoyalty;
 use polymesh_primitives::settlement::{
     AffirmationCount, AffirmationStatus, AssetCount, Instruction, InstructionId, InstructionStatus,
     Leg, LegId, LegStatus, Receipt, ReceiptDetails, SettlementType, VenueDetails, VenueId,
     VenueType,
 };
+use polymesh_primitives::statistics::{AssetScope, StatOpType, StatType};
+use polymesh_primitives::transfer_compliance::{AssetTransferCompliance, TransferCondition};
 use polymesh_primitives::{
     AccountId, AuthorizationData, Balance, Claim, Condition, ConditionType, Fund, FundDescription,
     IdentityId, Memo, NFTCollectionKeys, NFTId, NFTMetadataAttribute, NFTs, PortfolioId,
-    PortfolioKind, PortfolioName, PortfolioNumber, Signatory, Ticker, WeightMeter,
+    PortfolioKind, PortfolioName, PortfolioNumber, SecondaryKeyLimits, Signatory, TargetIdentity,
+    Ticker, WeightMeter,
 };
+use sp_arithmetic::Permill;
 use sp_keyring::AccountKeyring;
 
-use super::asset_test::{allow_all_transfers, max_len_bytes};
+use super::asset_test::{allow_all_transfers, max_len_bytes, set_timestamp};
 use super::nft::{create_nft_collection, mint_nft};
 use super::storage::{
-    default_portfolio_vec, make_account_without_cdd, user_portfolio_vec, TestStorage, User,
+    add_secondary_key, default_portfolio_vec, make_account_without_cdd, user_portfolio_vec,
+    TestStorage, User,
 };
 use super::{next_block, ExtBuilder};
 
@@ -63,7 +72,10 @@ type Settlement = pallet_settlement::Module<TestStorage>;
 type System = frame_system::Pallet<TestStorage>;
 type Error = pallet_settlement::Error<TestStorage>;
 type Scheduler = pallet_scheduler::Pallet<TestStorage>;
+type NFT = pallet_nft::Module<TestStorage>;
 type NFTError = pallet_nft::Error<TestStorage>;
+type Statistics = pallet_statistics::Module<TestStorage>;
+type StatisticsError = pallet_statistics::Error<TestStorage>;
 
 const TICKER: Ticker = Ticker::new_unchecked([b'A', b'C', b'M', b'E', 0, 0, 0, 0, 0, 0, 0, 0]);
 const TICKER2: Ticker = Ticker::new_unchecked([b'A', b'C', b'M', b'E', b'2', 0, 0, 0, 0, 0, 0, 0]);
@@ -2699,6 +2711,67 @@ fn add_and_affirm_nft_instruction() {
     });
 }
 
+/// The royalty of an NFT collection is paid out of the fungible legs that pay for its NFTs.
+#[test]
+fn nft_royalty_paid_on_settlement() {
+    test_with_cdd_provider(|_eve| {
+        let alice: User = User::new(AccountKeyring::Alice);
+        let bob: User = User::new(AccountKeyring::Bob);
+        let charlie: User = User::new(AccountKeyring::Charlie);
+        create_nft_collection(
+            alice.clone(),
+            TICKER,
+            AssetType::NonFungible(NonFungibleType::Derivative),
+            Vec::new().into(),
+        );
+        mint_nft(alice.clone(), TICKER, Vec::new(), PortfolioKind::Default);
+        ComplianceManager::pause_asset_compliance(alice.origin(), TICKER).unwrap();
+        create_token(TICKER2, bob);
+        assert_ok!(NFT::set_collection_royalty(
+            alice.origin(),
+            TICKER,
+            Some(NFTRoyalty {
+                beneficiary: charlie.did,
+                percentage: Permill::from_percent(10),
+            })
+        ));
+        let venue_id = create_venue(alice);
+
+        // Alice sells her NFT to Bob for 1_000 TICKER2
+        let instruction_id = Settlement::instruction_counter();
+        let legs: Vec<Leg> = vec![
+            Leg::NonFungible {
+                sender: PortfolioId::default_portfolio(alice.did),
+                receiver: PortfolioId::default_portfolio(bob.did),
+                nfts: NFTs::new_unverified(TICKER, vec![NFTId(1)]),
+            },
+            Leg::Fungible {
+                sender: PortfolioId::default_portfolio(bob.did),
+                receiver: PortfolioId::default_portfolio(alice.did),
+                ticker: TICKER2,
+                amount: 1_000,
+            },
+        ];
+        assert_ok!(Settlement::add_and_affirm_instruction(
+            alice.origin(),
+            venue_id,
+            SettlementType::SettleOnAffirmation,
+            None,
+            None,
+            legs,
+            default_portfolio_vec(alice.did),
+            None,
+        ));
+        assert_affirm_instruction!(bob.origin(), instruction_id, bob.did);
+        next_block();
+
+        assert_eq!(NumberOfNFTs::get(TICKER, bob.did), 1);
+        assert_eq!(Asset::balance_of(TICKER2, bob.did), 99_000);
+        assert_eq!(Asset::balance_of(TICKER2, alice.did), 900);
+        assert_eq!(Asset::balance_of(TICKER2, charlie.did), 100);
+    });
+}
+
 /// Only instructions with NFTS owned by the caller can be affirmed.
 #[test]
 fn add_and_affirm_nft_not_owned() {
@@ -3614,6 +3687,305 @@ fn reject_instruction_cost() {
     });
 }
 
+#[test]
+fn simulate_asset_compliance() {
+    test_with_cdd_provider(|_eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(Settlement::add_instruction(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleOnAffirmation,
+            None,
+            None,
+            vec![Leg::Fungible {
+                sender: PortfolioId::default_portfolio(alice.did),
+                receiver: PortfolioId::default_portfolio(bob.did),
+                ticker: TICKER,
+                amount: 100,
+            }],
+            None,
+        ));
+
+        // Only alice can receive the asset under the proposed requirement.
+        let proposal = |paused| AssetCompliance {
+            paused,
+            requirements: vec![ComplianceRequirement {
+                sender_conditions: vec![],
+                receiver_conditions: vec![ConditionType::IsIdentity(TargetIdentity::Specific(
+                    alice.did,
+                ))
+                .into()],
+                id: 1,
+            }],
+        };
+        let current_compliance = ComplianceManager::asset_compliance(TICKER);
+        assert_eq!(
+            Settlement::simulate_asset_compliance(
+                TICKER,
+                proposal(false),
+                InstructionId(0),
+                None,
+                10
+            ),
+            Ok(ComplianceImpact {
+                holders: vec![],
+                instructions: vec![instruction_id],
+                next_instruction: None,
+                next_holder: None,
+            })
+        );
+        assert_eq!(
+            Settlement::simulate_asset_compliance(
+                TICKER,
+                proposal(true),
+                InstructionId(0),
+                None,
+                10
+            ),
+            Ok(ComplianceImpact::default())
+        );
+        // No requirements means no transfer is compliant, and no one can hold the asset.
+        assert_eq!(
+            Settlement::simulate_asset_compliance(
+                TICKER,
+                AssetCompliance::default(),
+                InstructionId(0),
+                None,
+                10
+            ),
+            Ok(ComplianceImpact {
+                holders: vec![alice.did],
+                instructions: vec![instruction_id],
+                next_instruction: None,
+                next_holder: None,
+            })
+        );
+        // Instructions are checked in pages.
+        assert_eq!(
+            Settlement::simulate_asset_compliance(
+                TICKER,
+                proposal(false),
+                InstructionId(0),
+                None,
+                0
+            ),
+            Ok(ComplianceImpact {
+                holders: vec![],
+                instructions: vec![],
+                next_instruction: Some(instruction_id),
+                next_holder: None,
+            })
+        );
+        assert_eq!(
+            ComplianceManager::asset_compliance(TICKER),
+            current_compliance
+        );
+        assert_instruction_status(instruction_id, InstructionStatus::Pending);
+    });
+}
+
+#[test]
+fn simulate_transfer_compliance() {
+    test_with_cdd_provider(|_eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        let add_instruction = |amounts: &[Balance]| {
+            let instruction_id = Settlement::instruction_counter();
+            assert_ok!(Settlement::add_instruction(
+                alice.origin(),
+                venue_counter,
+                SettlementType::SettleOnAffirmation,
+                None,
+                None,
+                amounts
+                    .iter()
+                    .map(|amount| Leg::Fungible {
+                        sender: PortfolioId::default_portfolio(alice.did),
+                        receiver: PortfolioId::default_portfolio(bob.did),
+                        ticker: TICKER,
+                        amount: *amount,
+                    })
+                    .collect(),
+                None,
+            ));
+            instruction_id
+        };
+        add_instruction(&[100]);
+        let large_instruction_id = add_instruction(&[60_000]);
+        // Each leg is compliant on its own, but not both of them.
+        let multi_leg_instruction_id = add_instruction(&[30_000, 30_000]);
+
+        let max_ownership = |paused| AssetTransferCompliance {
+            paused,
+            requirements: [TransferCondition::MaxInvestorOwnership(
+                Permill::from_percent(50),
+            )]
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .try_into()
+            .unwrap(),
+        };
+
+        // The balance stat must be enabled for the condition to be evaluated.
+        assert_noop!(
+            Settlement::simulate_transfer_compliance(
+                TICKER,
+                max_ownership(false),
+                InstructionId(0),
+                None,
+                10
+            ),
+            StatisticsError::StatTypeMissing
+        );
+        let balance_stat = StatType {
+            op: StatOpType::Balance,
+            claim_issuer: None,
+        };
+        assert_ok!(Statistics::set_active_asset_stats(
+            alice.origin(),
+            TICKER.into(),
+            [balance_stat].into_iter().collect(),
+        ));
+
+        // Alice holds the entire supply, and bob would end up with more than half of it.
+        assert_eq!(
+            Settlement::simulate_transfer_compliance(
+                TICKER,
+                max_ownership(false),
+                InstructionId(0),
+                None,
+                10
+            ),
+            Ok(ComplianceImpact {
+                holders: vec![alice.did],
+                instructions: vec![large_instruction_id, multi_leg_instruction_id],
+                next_instruction: None,
+                next_holder: None,
+            })
+        );
+        // Nothing is in breach of paused transfer conditions.
+        assert_eq!(
+            Settlement::simulate_transfer_compliance(
+                TICKER,
+                max_ownership(true),
+                InstructionId(0),
+                None,
+                10
+            ),
+            Ok(ComplianceImpact::default())
+        );
+        // Instructions are checked in pages.
+        assert_eq!(
+            Settlement::simulate_transfer_compliance(
+                TICKER,
+                max_ownership(false),
+                large_instruction_id,
+                None,
+                1
+            ),
+            Ok(ComplianceImpact {
+                holders: vec![alice.did],
+                instructions: vec![large_instruction_id],
+                next_instruction: Some(multi_leg_instruction_id),
+                next_holder: Some(alice.did),
+            })
+        );
+        // Holders are checked in pages too.
+        assert_eq!(
+            Settlement::simulate_transfer_compliance(
+                TICKER,
+                max_ownership(false),
+                multi_leg_instruction_id,
+                Some(alice.did),
+                1
+            ),
+            Ok(ComplianceImpact {
+                holders: vec![],
+                instructions: vec![multi_leg_instruction_id],
+                next_instruction: None,
+                next_holder: None,
+            })
+        );
+        assert_eq!(
+            Statistics::asset_transfer_compliance(AssetScope::Ticker(TICKER))
+                .requirements
+                .len(),
+            0
+        );
+    });
+}
+
+#[test]
+fn secondary_key_spending_refunded() {
+    test_with_cdd_provider(|_eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let dave = User::new_with(alice.did, AccountKeyring::Dave);
+        add_secondary_key(alice.did, dave.acc());
+        set_timestamp(100);
+        assert_ok!(Identity::set_secondary_key_limits(
+            alice.origin(),
+            dave.acc(),
+            Some(SecondaryKeyLimits {
+                valid_from: None,
+                valid_until: None,
+                spend_period: 1_000,
+                polyx_limit: None,
+                asset_limits: vec![(TICKER, 100)],
+            })
+        ));
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(Settlement::add_instruction(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleOnAffirmation,
+            None,
+            None,
+            vec![Leg::Fungible {
+                sender: PortfolioId::default_portfolio(alice.did),
+                receiver: PortfolioId::default_portfolio(bob.did),
+                ticker: TICKER,
+                amount: 100,
+            }],
+            None,
+        ));
+        let spent = || Identity::key_spending(dave.acc(), Some(TICKER)).spent;
+        let affirm = || {
+            assert_ok!(Settlement::affirm_instruction(
+                dave.origin(),
+                instruction_id,
+                default_portfolio_vec(alice.did),
+            ));
+            assert_eq!(spent(), 100);
+        };
+
+        // Withdrawing the affirmation gives the spending back to the key.
+        affirm();
+        assert_ok!(Settlement::withdraw_affirmation(
+            dave.origin(),
+            instruction_id,
+            default_portfolio_vec(alice.did),
+        ));
+        assert_eq!(spent(), 0);
+        assert_eq!(Settlement::leg_key_spending(instruction_id, LegId(0)), None);
+
+        // So does rejecting the instruction.
+        affirm();
+        assert_ok!(Settlement::reject_instruction(
+            bob.origin(),
+            instruction_id,
+            PortfolioId::default_portfolio(bob.did),
+        ));
+        assert_eq!(spent(), 0);
+        assert_eq!(Settlement::leg_key_spending(instruction_id, LegId(0)), None);
+    });
+}
+
 /// Asserts the storage has been updated after adding an instruction.
 /// While each portfolio in `portfolios_pending_approval` must have a pending `AffirmationStatus`, each portfolio in `portfolios_pre_approved`
 /// must have an affirmed status. The number of pending affirmations must be equal to the number of portfolios in `portfolios_pending_approval` + the number of offchain legs,
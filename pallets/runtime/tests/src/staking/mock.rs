@@ -25,8 +25,9 @@ use frame_support::{
     dispatch::{DispatchInfo, DispatchResult, Weight},
     parameter_types,
     traits::{
-        Contains, Currency, FindAuthor, GenesisBuild as _, Get, Imbalance, KeyOwnerProofSystem,
-        OnFinalize, OnInitialize, OnUnbalanced, OneSessionHandler, SortedMembers,
+        CallMetadata, Contains, Currency, FindAuthor, GenesisBuild as _, Get, Imbalance,
+        KeyOwnerProofSystem, OnFinalize, OnInitialize, OnUnbalanced, OneSessionHandler,
+        SortedMembers,
     },
     weights::constants::RocksDbWeight,
     IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue,
@@ -138,7 +139,7 @@ frame_support::construct_runtime!(
         ProtocolFee: pallet_protocol_fee::{Pallet, Call, Storage, Event<T>, Config},
         Preimage: pallet_preimage::{Pallet, Call, Storage, Event<T>},
         Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>},
-        Treasury: pallet_treasury::{Pallet, Call, Event<T>},
+        Treasury: pallet_treasury::{Pallet, Call, Storage, Event<T>},
         PolymeshCommittee: pallet_committee::<Instance1>::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>},
         Pips: pallet_pips::{Pallet, Call, Storage, Event<T>, Config<T>},
         TestUtils: pallet_test_utils::{Pallet, Call, Storage, Event<T>},
@@ -161,6 +162,9 @@ parameter_types! {
     pub const BlockHashCount: u64 = 250;
     pub const MaxLen: u32 = 256;
     pub const MaxLocks: u32 = 1024;
+    pub const MaxVestingSchedules: u32 = 20;
+    pub const MinVestedTransfer: Balance = 1;
+    pub const MaxVestingDuration: BlockNumber = 1_000;
     pub const MaximumBlockWeight: Weight = Weight::from_ref_time(1024);
     pub BlockWeights: frame_system::limits::BlockWeights =
         frame_system::limits::BlockWeights::simple_max(
@@ -219,6 +223,9 @@ impl pallet_balances::Config for Test {
     type CddChecker = Test;
     type WeightInfo = polymesh_weights::pallet_balances::SubstrateWeight;
     type MaxLocks = MaxLocks;
+    type MaxVestingSchedules = MaxVestingSchedules;
+    type MinVestedTransfer = MinVestedTransfer;
+    type MaxVestingDuration = MaxVestingDuration;
 }
 
 sp_runtime::impl_opaque_keys! {
@@ -268,6 +275,7 @@ impl pallet_treasury::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type Currency = pallet_balances::Module<Self>;
     type WeightInfo = polymesh_weights::pallet_treasury::SubstrateWeight;
+    type MaxMilestones = frame_support::traits::ConstU32<50>;
 }
 
 impl pallet_authorship::Config for Test {
@@ -302,6 +310,8 @@ impl protocol_fee::Config for Test {
     type OnProtocolFeePayment = ();
     type WeightInfo = polymesh_weights::pallet_protocol_fee::SubstrateWeight;
     type Subsidiser = Test;
+    type FeeAssetPayer = ();
+    type MaxDiscountTiers = frame_support::traits::ConstU32<10>;
 }
 
 impl polymesh_common_utilities::traits::identity::Config for Test {
@@ -322,10 +332,13 @@ impl polymesh_common_utilities::traits::identity::Config for Test {
     type SchedulerOrigin = OriginCaller;
     type InitialPOLYX = InitialPOLYX;
     type MultiSigBalanceLimit = polymesh_runtime_common::MultiSigBalanceLimit;
+    type MaxSessionKeyLifetime = MaxSessionKeyLifetime;
+    type FeeAssetPayer = ();
 }
 
 parameter_types! {
     pub const InitialPOLYX: Balance = 0;
+    pub const MaxSessionKeyLifetime: BlockNumber = 100;
     pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * MaximumBlockWeight::get();
     pub const MaxScheduledPerBlock: u32 = 50;
 }
@@ -379,7 +392,7 @@ impl SubsidiserTrait<AccountId> for Test {
     fn check_subsidy(
         _: &AccountId,
         _: Balance,
-        _: Option<&[u8]>,
+        _: Option<&CallMetadata>,
     ) -> Result<Option<AccountId>, InvalidTransaction> {
         Ok(None)
     }
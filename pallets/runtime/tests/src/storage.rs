@@ -236,6 +236,7 @@ parameter_types! {
     pub const ReportLongevity: u64 =
         BondingDuration::get() as u64 * SessionsPerEra::get() as u64 * EpochDuration::get();
     pub const MaxNumberOfCollectionKeys: u8 = u8::MAX;
+    pub const MaxNFTsPerIssue: u32 = 100;
     pub const MaxNumberOfFungibleMoves: u32 = 10;
     pub const MaxNumberOfNFTsMoves: u32 = 100;
     pub const MaxNumberOfOffChainAssets: u32 = 10;
@@ -317,7 +318,7 @@ frame_support::construct_runtime!(
         Settlement: pallet_settlement::{Pallet, Call, Storage, Event<T>, Config} = 37,
         Statistics: pallet_statistics::{Pallet, Call, Storage, Event} = 38,
         Sto: pallet_sto::{Pallet, Call, Storage, Event<T>} = 39,
-        Treasury: pallet_treasury::{Pallet, Call, Event<T>} = 40,
+        Treasury: pallet_treasury::{Pallet, Call, Storage, Event<T>} = 40,
         Utility: pallet_utility::{Pallet, Call, Storage, Event<T>} = 41,
         Base: pallet_base::{Pallet, Call, Event} = 42,
         ExternalAgents: pallet_external_agents::{Pallet, Call, Storage, Event} = 43,
@@ -454,6 +455,9 @@ parameter_types! {
     pub const SS58Prefix: u8 = 12;
     pub const ExistentialDeposit: u64 = 0;
     pub const MaxLocks: u32 = 50;
+    pub const MaxVestingSchedules: u32 = 20;
+    pub const MinVestedTransfer: Balance = 1_000;
+    pub const MaxVestingDuration: BlockNumber = 1_000;
     pub const MaxLen: u32 = 256;
     pub const AssetNameMaxLength: u32 = 128;
     pub const FundingRoundNameMaxLength: u32 = 128;
@@ -477,8 +481,13 @@ parameter_types! {
 
     pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * MaximumBlockWeight::get();
     pub const MaxScheduledPerBlock: u32 = 50;
+    pub const MinRecurrenceInterval: BlockNumber = 2;
+    pub const MaxRecurrences: u32 = 10;
+    pub const MaxScheduledExecutionsPerBlock: u32 = 2;
+    pub MaxScheduledExecutionsWeight: Weight = Perbill::from_percent(20) * MaximumBlockWeight::get();
 
     pub const InitialPOLYX: Balance = 41;
+    pub const MaxSessionKeyLifetime: BlockNumber = 100;
     pub const SignedClaimHandicap: u64 = 2;
     pub const StorageSizeOffset: u32 = 8;
     pub const MaxDepth: u32 = 100;
@@ -639,6 +648,8 @@ impl polymesh_common_utilities::traits::identity::Config for TestStorage {
     type SchedulerOrigin = OriginCaller;
     type InitialPOLYX = InitialPOLYX;
     type MultiSigBalanceLimit = polymesh_runtime_common::MultiSigBalanceLimit;
+    type MaxSessionKeyLifetime = MaxSessionKeyLifetime;
+    type FeeAssetPayer = Asset;
 }
 
 impl example::Config for TestStorage {}
@@ -864,8 +875,10 @@ pub fn next_block() -> Weight {
     let block_number = frame_system::Pallet::<TestStorage>::block_number() + 1;
     frame_system::Pallet::<TestStorage>::set_block_number(block_number);
 
-    // Call the timelocked tx handler.
-    pallet_scheduler::Pallet::<TestStorage>::on_initialize(block_number)
+    // Call the timelocked tx handler and execute the scheduled utility calls.
+    pallet_scheduler::Pallet::<TestStorage>::on_initialize(block_number).saturating_add(
+        pallet_utility::Pallet::<TestStorage>::on_initialize(block_number),
+    )
 }
 
 pub fn fast_forward_to_block(n: u32) {
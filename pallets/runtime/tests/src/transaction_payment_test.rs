@@ -1,8 +1,12 @@
+use super::asset_test::{allow_all_transfers, an_asset};
 use super::ext_builder::ExtBuilder;
-use super::storage::{RuntimeCall, TestStorage};
+use super::storage::{add_secondary_key, root, RuntimeCall, TestStorage, User};
 use codec::Encode;
 use frame_support::{
-    dispatch::{DispatchClass, DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo, Weight},
+    assert_noop, assert_ok,
+    dispatch::{
+        DispatchClass, DispatchError, DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo, Weight,
+    },
     traits::Currency,
     weights::WeightToFee,
 };
@@ -10,6 +14,7 @@ use pallet_balances::Call as BalancesCall;
 use pallet_transaction_payment::{ChargeTransactionPayment, Multiplier, RuntimeDispatchInfo};
 use polymesh_primitives::AccountId;
 use polymesh_primitives::TransactionError;
+use polymesh_primitives::{PortfolioKind, PosRatio};
 use sp_arithmetic::traits::One;
 use sp_keyring::AccountKeyring;
 use sp_runtime::{
@@ -29,6 +34,9 @@ fn call() -> <TestStorage as frame_system::Config>::RuntimeCall {
 type Balances = pallet_balances::Module<TestStorage>;
 type System = frame_system::Pallet<TestStorage>;
 type TransactionPayment = pallet_transaction_payment::Module<TestStorage>;
+type Asset = pallet_asset::Module<TestStorage>;
+type Identity = pallet_identity::Module<TestStorage>;
+type AssetError = pallet_asset::Error<TestStorage>;
 
 /// create a transaction info struct from weight. Handy to avoid building the whole struct.
 pub fn info_from_weight(w: u64) -> DispatchInfo {
@@ -552,3 +560,99 @@ fn operational_tx_with_tip_ext(cdd: AccountId, gc: AccountId) {
         .pre_dispatch(&cdd, &call, &operational_info, len)
         .is_ok());
 }
+
+#[test]
+fn fee_paid_in_asset() {
+    ExtBuilder::default()
+        .monied(true)
+        .transaction_fees(5, 1, 1)
+        .build()
+        .execute_with(fee_paid_in_asset_ext);
+}
+
+fn fee_paid_in_asset_ext() {
+    let alice = User::new(AccountKeyring::Alice);
+    let bob = User::new(AccountKeyring::Bob);
+    let ticker = an_asset(alice, true);
+    allow_all_transfers(ticker, alice);
+
+    // Only root can register fee assets, and keys can only choose registered ones.
+    let fee_asset = Some((ticker, PortfolioKind::Default));
+    assert_noop!(
+        Asset::set_fee_payment_asset(alice.origin(), fee_asset),
+        AssetError::NotAFeeAsset
+    );
+    assert_noop!(
+        Asset::set_fee_asset_rate(alice.origin(), ticker, Some(PosRatio(2, 1))),
+        DispatchError::BadOrigin
+    );
+    assert_noop!(
+        Asset::set_fee_asset_rate(root(), ticker, Some(PosRatio(2, 0))),
+        AssetError::InvalidFeeAssetRate
+    );
+    assert_ok!(Asset::set_fee_asset_rate(
+        root(),
+        ticker,
+        Some(PosRatio(2, 1))
+    ));
+    assert_ok!(Asset::set_fee_collector(root(), Some(bob.did)));
+    assert_ok!(Asset::set_fee_payment_asset(alice.origin(), fee_asset));
+
+    let len = 10;
+    let info = info_from_weight(5);
+    let fee = TransactionPayment::compute_fee(len as u32, &info, 0);
+    let polyx = Balances::free_balance(&alice.acc());
+    let supply = Asset::balance_of(&ticker, alice.did);
+    let pay_fee = |payer: User, actual_weight| {
+        let pre = ChargeTransactionPayment::<TestStorage>::from(0)
+            .pre_dispatch(&payer.acc(), &call(), &info, len)
+            .unwrap();
+        assert_ok!(ChargeTransactionPayment::<TestStorage>::post_dispatch(
+            Some(pre),
+            &info,
+            &post_info_from_weight(actual_weight),
+            len,
+            &Ok(())
+        ));
+    };
+
+    // The fee is paid with two units of the asset per POLYX, to the fee collector.
+    pay_fee(alice, 5);
+    assert_eq!(Balances::free_balance(&alice.acc()), polyx);
+    assert_eq!(Asset::balance_of(&ticker, alice.did), supply - 2 * fee);
+    assert_eq!(Asset::balance_of(&ticker, bob.did), 2 * fee);
+
+    // The part of the fee above the actual fee is refunded in the asset.
+    let actual_fee =
+        TransactionPayment::compute_actual_fee(len as u32, &info, &post_info_from_weight(2), 0);
+    assert!(actual_fee < fee);
+    pay_fee(alice, 2);
+    assert_eq!(Balances::free_balance(&alice.acc()), polyx);
+    assert_eq!(
+        Asset::balance_of(&ticker, alice.did),
+        supply - 2 * fee - 2 * actual_fee
+    );
+    assert_eq!(
+        Asset::balance_of(&ticker, bob.did),
+        2 * fee + 2 * actual_fee
+    );
+
+    // The fee asset of a key is removed along with the key.
+    let dave = User::new_with(alice.did, AccountKeyring::Dave);
+    add_secondary_key(alice.did, dave.acc());
+    assert_ok!(Asset::set_fee_payment_asset(dave.origin(), fee_asset));
+    assert_ok!(Identity::remove_secondary_keys(
+        alice.origin(),
+        vec![dave.acc()]
+    ));
+    assert_eq!(Asset::fee_payment_asset(dave.acc()), None);
+
+    // The fee is paid in POLYX once the asset can't be used for fees anymore.
+    assert_ok!(Asset::set_fee_asset_rate(root(), ticker, None));
+    pay_fee(alice, 5);
+    assert_eq!(Balances::free_balance(&alice.acc()), polyx - fee);
+    assert_eq!(
+        Asset::balance_of(&ticker, bob.did),
+        2 * fee + 2 * actual_fee
+    );
+}
@@ -4,8 +4,12 @@ use super::{
     ExtBuilder,
 };
 
-use polymesh_primitives::{Beneficiary, IdentityId};
+use frame_support::traits::Currency;
+use polymesh_common_utilities::constants::TREASURY_PALLET_ID;
+use polymesh_primitives::treasury::PaymentPlanId;
+use polymesh_primitives::{AccountId, Beneficiary, IdentityId};
 use sp_keyring::AccountKeyring;
+use sp_runtime::traits::AccountIdConversion;
 use sp_runtime::DispatchError;
 
 pub type Balances = pallet_balances::Module<TestStorage>;
@@ -13,6 +17,7 @@ pub type Treasury = pallet_treasury::Module<TestStorage>;
 type TreasuryError = pallet_treasury::Error<TestStorage>;
 type Identity = pallet_identity::Module<TestStorage>;
 type Origin = <TestStorage as frame_system::Config>::RuntimeOrigin;
+type System = frame_system::Pallet<TestStorage>;
 
 fn beneficiary<Balance>(id: IdentityId, amount: Balance) -> Beneficiary<Balance> {
     Beneficiary { id, amount }
@@ -141,3 +146,133 @@ fn bad_disbursement_did_we() {
     // Make sure total POLYX issuance hasn't changed.
     assert_eq!(total_issuance, Balances::total_issuance());
 }
+
+#[test]
+fn payment_plans() {
+    ExtBuilder::default()
+        .balance_factor(10)
+        .build()
+        .execute_with(payment_plans_we);
+}
+
+fn payment_plans_we() {
+    let alice = User::new(AccountKeyring::Alice);
+    let bob = User::new(AccountKeyring::Bob);
+    let paid = |id| Treasury::payment_plans(PaymentPlanId(id)).unwrap().paid;
+    System::set_block_number(1);
+    exec_ok!(Treasury::reimbursement(alice.origin(), 10_000));
+
+    // Only root can create plans, and plans must pay something.
+    exec_noop!(
+        Treasury::create_milestone_plan(alice.origin(), bob.did, vec![1_000]),
+        DispatchError::BadOrigin
+    );
+    exec_noop!(
+        Treasury::create_milestone_plan(root(), bob.did, vec![]),
+        TreasuryError::InvalidPaymentPlan
+    );
+    exec_noop!(
+        Treasury::create_milestone_plan(root(), bob.did, vec![1; 51]),
+        TreasuryError::TooManyMilestones
+    );
+    exec_noop!(
+        Treasury::create_stream_plan(root(), bob.did, 1, 0, 1_000),
+        TreasuryError::InvalidPaymentPlan
+    );
+    exec_noop!(
+        Treasury::create_stream_plan(root(), bob.did, 1, 100, 20_000),
+        TreasuryError::InsufficientBalance
+    );
+
+    // The unpaid part of a plan is kept aside from disbursements.
+    exec_ok!(Treasury::create_milestone_plan(
+        root(),
+        bob.did,
+        vec![1_000, 2_000]
+    ));
+    assert_eq!(Treasury::committed_balance(), 3_000);
+    assert_eq!(Treasury::available_balance(), 7_000);
+    exec_noop!(
+        Treasury::disbursement(root(), vec![beneficiary(alice.did, 8_000)]),
+        TreasuryError::InsufficientBalance
+    );
+
+    // Milestones are paid when released, in any order, once.
+    let bob_balance = Balances::free_balance(&bob.acc());
+    exec_ok!(Treasury::release_milestone(root(), PaymentPlanId(0), 1));
+    assert_eq!(Balances::free_balance(&bob.acc()), bob_balance + 2_000);
+    assert_eq!(Treasury::committed_balance(), 1_000);
+    exec_noop!(
+        Treasury::release_milestone(root(), PaymentPlanId(0), 1),
+        TreasuryError::MilestoneAlreadyReleased
+    );
+    exec_noop!(
+        Treasury::release_milestone(root(), PaymentPlanId(0), 2),
+        TreasuryError::MilestoneNotFound
+    );
+
+    // Streams accrue every block and are claimed by the beneficiary.
+    exec_ok!(Treasury::create_stream_plan(root(), bob.did, 1, 100, 1_000));
+    System::set_block_number(5);
+    exec_noop!(
+        Treasury::claim_stream(alice.origin(), PaymentPlanId(1)),
+        TreasuryError::NotPaymentPlanBeneficiary
+    );
+    exec_noop!(
+        Treasury::claim_stream(bob.origin(), PaymentPlanId(0)),
+        TreasuryError::NotAStream
+    );
+    exec_ok!(Treasury::claim_stream(bob.origin(), PaymentPlanId(1)));
+    assert_eq!(paid(1), 400);
+    exec_noop!(
+        Treasury::claim_stream(bob.origin(), PaymentPlanId(1)),
+        TreasuryError::NothingToClaim
+    );
+    assert_eq!(Treasury::beneficiary_payment_plans(bob.did).len(), 2);
+    assert!(Treasury::beneficiary_payment_plans(alice.did).is_empty());
+
+    // Cancelling pays out what has accrued and returns the remainder.
+    System::set_block_number(8);
+    let bob_balance = Balances::free_balance(&bob.acc());
+    exec_ok!(Treasury::cancel_payment_plan(root(), PaymentPlanId(1)));
+    assert_eq!(Balances::free_balance(&bob.acc()), bob_balance + 300);
+    assert!(Treasury::payment_plans(PaymentPlanId(1)).is_none());
+    exec_ok!(Treasury::cancel_payment_plan(root(), PaymentPlanId(0)));
+    assert_eq!(Treasury::committed_balance(), 0);
+    assert_eq!(Treasury::available_balance(), Treasury::balance());
+
+    // A fully paid out plan is removed.
+    exec_ok!(Treasury::create_stream_plan(root(), bob.did, 8, 100, 250));
+    System::set_block_number(20);
+    exec_ok!(Treasury::claim_stream(bob.origin(), PaymentPlanId(2)));
+    assert!(Treasury::payment_plans(PaymentPlanId(2)).is_none());
+    assert_eq!(Treasury::committed_balance(), 0);
+
+    // If the accrued amount cannot be paid out on cancellation, it stays claimable.
+    exec_ok!(Treasury::create_stream_plan(
+        root(),
+        bob.did,
+        20,
+        100,
+        1_000
+    ));
+    System::set_block_number(23);
+    let treasury: AccountId = TREASURY_PALLET_ID.into_account_truncating();
+    let treasury_balance = Treasury::balance();
+    Balances::make_free_balance_be(&treasury, 0);
+    exec_ok!(Treasury::cancel_payment_plan(root(), PaymentPlanId(3)));
+    assert_eq!(
+        Treasury::payment_plans(PaymentPlanId(3))
+            .unwrap()
+            .remaining(),
+        300
+    );
+    assert_eq!(Treasury::committed_balance(), 300);
+    Balances::make_free_balance_be(&treasury, treasury_balance);
+    System::set_block_number(30);
+    let bob_balance = Balances::free_balance(&bob.acc());
+    exec_ok!(Treasury::claim_stream(bob.origin(), PaymentPlanId(3)));
+    assert_eq!(Balances::free_balance(&bob.acc()), bob_balance + 300);
+    assert!(Treasury::beneficiary_payment_plans(bob.did).is_empty());
+    assert_eq!(Treasury::committed_balance(), 0);
+}
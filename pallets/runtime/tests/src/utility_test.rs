@@ -4,7 +4,7 @@ use frame_support::dispatch::{
     Pays, PostDispatchInfo, Weight,
 };
 use frame_support::error::BadOrigin;
-use frame_support::traits::Contains;
+use frame_support::traits::{Contains, Get};
 use frame_support::{
     assert_err_ignore_postinfo, assert_noop, assert_ok, assert_storage_noop, storage, StorageMap,
 };
@@ -16,16 +16,19 @@ use pallet_balances::Call as BalancesCall;
 use pallet_pips::{ProposalState, SnapshotResult};
 use pallet_portfolio::Call as PortfolioCall;
 use pallet_utility::{
-    self as utility, Call as UtilityCall, Config as UtilityConfig, Event, UniqueCall, WeightInfo,
+    self as utility, BatchCondition, Call as UtilityCall, Config as UtilityConfig, Event, Interval,
+    Recurrence, ScheduleTime, UniqueCall, WeightInfo,
 };
 use polymesh_common_utilities::traits::transaction_payment::CddAndFeeDetails;
+use polymesh_primitives::settlement::{InstructionId, InstructionStatus};
 use polymesh_primitives::{
-    AccountId, Balance, PalletPermissions, Permissions, PortfolioName, PortfolioNumber,
-    SubsetRestriction, Ticker,
+    AccountId, Balance, Claim, ClaimType, PalletPermissions, Permissions, PortfolioId,
+    PortfolioName, PortfolioNumber, Scope, SubsetRestriction, Ticker,
 };
 use sp_core::sr25519::Signature;
 use sp_keyring::AccountKeyring;
 
+use super::asset_test::set_timestamp;
 use super::committee_test::set_members;
 use super::pips_test::{assert_balance, assert_state, committee_proposal, community_proposal};
 use super::storage::example::Call as ExampleCall;
@@ -1038,3 +1041,263 @@ fn as_derivative() {
         );
     });
 }
+
+#[test]
+fn scheduled_calls() {
+    batch_test(|alice, bob| {
+        let alice_did = Identity::get_identity(&alice).unwrap();
+        let origin = RuntimeOrigin::signed(alice.clone());
+        let scheduled_count = || utility::ScheduledCallsCount::<TestStorage>::get(alice_did);
+
+        // A call cannot be scheduled in the past.
+        assert_noop!(
+            Utility::schedule_call(
+                origin.clone(),
+                ScheduleTime::Block(1),
+                Box::new(transfer(bob.clone(), 100))
+            ),
+            Error::ScheduleInPast
+        );
+        assert_noop!(
+            Utility::schedule_batch(origin.clone(), ScheduleTime::Block(3), vec![]),
+            Error::NoCallsToSchedule
+        );
+
+        // A single call is executed at its block.
+        assert_ok!(Utility::schedule_call(
+            origin.clone(),
+            ScheduleTime::Block(3),
+            Box::new(transfer(bob.clone(), 100))
+        ));
+        assert_eq!(scheduled_count(), 1);
+        next_block();
+        assert_balance(bob.clone(), 1000, 0);
+        next_block();
+        assert_balance(bob.clone(), 1100, 0);
+        assert_event_exists!(EventTest::Utility(Event::ScheduledCallExecuted {
+            id: 0,
+            result: Ok(()),
+            ..
+        }));
+        assert_eq!(scheduled_count(), 0);
+
+        // A batch is atomic: the failing transfer reverts the first one.
+        assert_ok!(Utility::schedule_batch(
+            origin.clone(),
+            ScheduleTime::Block(4),
+            vec![transfer(bob.clone(), 100), transfer(bob.clone(), 10_000)]
+        ));
+        next_block();
+        assert_balance(bob.clone(), 1100, 0);
+        assert_eq!(scheduled_count(), 0);
+
+        // A recurring batch is executed `count` times.
+        let recurrence = Recurrence {
+            interval: Interval::Blocks(2),
+            count: 2,
+        };
+        let max_count = <TestStorage as UtilityConfig>::MaxRecurrences::get();
+        for invalid in [
+            Recurrence {
+                interval: Interval::Blocks(1),
+                count: 2,
+            },
+            Recurrence {
+                interval: Interval::Millis(1),
+                count: 2,
+            },
+            Recurrence {
+                interval: Interval::Blocks(2),
+                count: 0,
+            },
+            Recurrence {
+                interval: Interval::Blocks(2),
+                count: max_count + 1,
+            },
+        ] {
+            assert_noop!(
+                Utility::schedule_recurring(
+                    origin.clone(),
+                    ScheduleTime::Block(6),
+                    invalid,
+                    vec![transfer(bob.clone(), 50)]
+                ),
+                Error::InvalidRecurrence
+            );
+        }
+
+        // The weight of every execution is paid when scheduling.
+        let calls = vec![transfer(bob.clone(), 50)];
+        let execution = <TestStorage as UtilityConfig>::WeightInfo::execute_scheduled(1)
+            .saturating_add(calls[0].get_dispatch_info().weight);
+        let schedule = RuntimeCall::Utility(UtilityCall::schedule_recurring {
+            when: ScheduleTime::Block(6),
+            recurrence,
+            calls,
+        });
+        assert_eq!(
+            schedule.get_dispatch_info().weight,
+            <TestStorage as UtilityConfig>::WeightInfo::schedule_recurring(1)
+                .saturating_add(execution.saturating_mul(2))
+        );
+        assert_ok!(Utility::schedule_recurring(
+            origin.clone(),
+            ScheduleTime::Block(6),
+            recurrence,
+            vec![transfer(bob.clone(), 50)]
+        ));
+        for _ in 5..=10 {
+            next_block();
+        }
+        assert_balance(bob.clone(), 1200, 0);
+        assert_eq!(scheduled_count(), 0);
+
+        // A call scheduled at a moment waits for that moment.
+        set_timestamp(1_000);
+        assert_ok!(Utility::schedule_call(
+            origin.clone(),
+            ScheduleTime::Moment(1_012),
+            Box::new(transfer(bob.clone(), 100))
+        ));
+        next_block();
+        next_block();
+        next_block();
+        assert_balance(bob.clone(), 1200, 0);
+        set_timestamp(1_012);
+        next_block();
+        assert_balance(bob.clone(), 1300, 0);
+
+        // Cancelled calls are never executed.
+        assert_ok!(Utility::schedule_call(
+            origin.clone(),
+            ScheduleTime::Block(20),
+            Box::new(transfer(bob.clone(), 100))
+        ));
+        let id = utility::NextScheduledCallId::<TestStorage>::get(alice_did) - 1;
+        assert_ok!(Utility::cancel_scheduled(origin.clone(), id));
+        assert_noop!(
+            Utility::cancel_scheduled(origin.clone(), id),
+            Error::ScheduledCallNotFound
+        );
+        for _ in 0..10 {
+            next_block();
+        }
+        assert_balance(bob.clone(), 1300, 0);
+
+        // Executions beyond `MaxScheduledExecutionsPerBlock` are delayed to the next block.
+        let per_block = <TestStorage as UtilityConfig>::MaxScheduledExecutionsPerBlock::get();
+        let at = System::block_number() + 2;
+        for _ in 0..=per_block {
+            assert_ok!(Utility::schedule_call(
+                origin.clone(),
+                ScheduleTime::Block(at),
+                Box::new(transfer(bob.clone(), 1))
+            ));
+        }
+        next_block();
+        next_block();
+        assert_balance(bob.clone(), 1300 + per_block as u128, 0);
+        assert_eq!(scheduled_count(), 1);
+        next_block();
+        assert_balance(bob.clone(), 1301 + per_block as u128, 0);
+        assert_eq!(scheduled_count(), 0);
+
+        // The queue of each identity is bounded.
+        let max = <TestStorage as UtilityConfig>::MaxScheduledCallsPerIdentity::get();
+        for _ in 0..max {
+            assert_ok!(Utility::schedule_call(
+                origin.clone(),
+                ScheduleTime::Block(100),
+                Box::new(transfer(bob.clone(), 1))
+            ));
+        }
+        assert_noop!(
+            Utility::schedule_call(origin, ScheduleTime::Block(100), Box::new(transfer(bob, 1))),
+            Error::TooManyScheduledCalls
+        );
+    });
+}
+
+#[test]
+fn batch_if_checks_conditions() {
+    batch_test(|alice, bob| {
+        let alice_did = Identity::get_identity(&alice).unwrap();
+        let bob_did = Identity::get_identity(&bob).unwrap();
+        let origin = RuntimeOrigin::signed(alice.clone());
+        let polyx_at_least = |amount| BatchCondition::PolyxBalanceAtLeast {
+            account: alice.clone(),
+            amount,
+        };
+        let instruction_is = |status| BatchCondition::InstructionStatusIs {
+            id: InstructionId(0),
+            status,
+        };
+        let portfolio_at_least = |amount| BatchCondition::PortfolioBalanceAtLeast {
+            portfolio: PortfolioId::default_portfolio(alice_did),
+            ticker: Ticker::from_slice_truncated(b"TICKER"),
+            amount,
+        };
+        let scope = Scope::Identity(alice_did);
+        let claim_present = BatchCondition::ClaimPresent {
+            target: bob_did,
+            claim_type: ClaimType::Accredited,
+            issuer: alice_did,
+            scope: Some(scope.clone()),
+        };
+
+        // All conditions hold.
+        assert_ok!(Utility::batch_if(
+            origin.clone(),
+            vec![
+                polyx_at_least(1000),
+                instruction_is(InstructionStatus::Unknown),
+                portfolio_at_least(0),
+            ],
+            vec![transfer(bob.clone(), 100)]
+        ));
+        assert_balance(bob.clone(), 1100, 0);
+
+        // A condition no longer holds, so nothing is dispatched.
+        for condition in [
+            polyx_at_least(1000),
+            instruction_is(InstructionStatus::Pending),
+            portfolio_at_least(1),
+            claim_present.clone(),
+        ] {
+            assert_err_ignore_postinfo!(
+                Utility::batch_if(
+                    origin.clone(),
+                    vec![polyx_at_least(100), condition],
+                    vec![transfer(bob.clone(), 100)]
+                ),
+                Error::ConditionNotMet
+            );
+            assert_balance(bob.clone(), 1100, 0);
+        }
+
+        // The claim condition holds once the claim is added.
+        assert_ok!(Identity::add_claim(
+            origin.clone(),
+            bob_did,
+            Claim::Accredited(scope),
+            None
+        ));
+        assert_ok!(Utility::batch_if(
+            origin.clone(),
+            vec![claim_present],
+            vec![transfer(bob.clone(), 100)]
+        ));
+        assert_balance(bob.clone(), 1200, 0);
+
+        // The number of conditions is bounded.
+        let max = <TestStorage as UtilityConfig>::MaxBatchConditions::get();
+        assert_noop!(
+            Utility::batch_if(
+                origin,
+                vec![polyx_at_least(0); max as usize + 1],
+                vec![transfer(bob, 100)]
+            ),
+            Error::TooManyConditions
+        );
+    });
+}
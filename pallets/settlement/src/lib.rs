@@ -60,9 +60,12 @@ use frame_support::storage::{
 use frame_support::traits::schedule::{DispatchTime, Named};
 use frame_support::traits::Get;
 use frame_support::weights::Weight;
-use frame_support::{decl_error, decl_module, decl_storage, ensure, IterableStorageDoubleMap};
+use frame_support::{
+    decl_error, decl_module, decl_storage, ensure, IterableStorageDoubleMap, IterableStorageMap,
+};
 use frame_system::{ensure_root, RawOrigin};
 use sp_runtime::traits::{One, Verify};
+use sp_std::collections::btree_map::BTreeMap;
 use sp_std::collections::btree_set::BTreeSet;
 use sp_std::convert::TryFrom;
 use sp_std::prelude::*;
@@ -76,11 +79,15 @@ pub use polymesh_common_utilities::traits::settlement::{Event, RawEvent, WeightI
 use polymesh_common_utilities::traits::{asset, compliance_manager, identity, nft, CommonConfig};
 use polymesh_common_utilities::with_transaction;
 use polymesh_common_utilities::SystematicIssuers::Settlement as SettlementDID;
+use polymesh_primitives::compliance_manager::{AssetCompliance, ComplianceImpact};
+use polymesh_primitives::nft::NFTRoyalty;
 use polymesh_primitives::settlement::{
     AffirmationCount, AffirmationStatus, AssetCount, ExecuteInstructionInfo, FilteredLegs,
     Instruction, InstructionId, InstructionInfo, InstructionStatus, Leg, LegId, LegStatus, Receipt,
     ReceiptDetails, SettlementType, Venue, VenueDetails, VenueId, VenueType,
 };
+use polymesh_primitives::statistics::AssetScope;
+use polymesh_primitives::transfer_compliance::AssetTransferCompliance;
 use polymesh_primitives::{
     storage_migrate_on, storage_migration_ver, Balance, IdentityId, Memo, NFTs, PortfolioId,
     SecondaryKey, Ticker, WeightMeter,
@@ -91,6 +98,8 @@ type System<T> = frame_system::Pallet<T>;
 type Asset<T> = pallet_asset::Module<T>;
 type ExternalAgents<T> = pallet_external_agents::Module<T>;
 type Nft<T> = pallet_nft::Module<T>;
+type ComplianceManager<T> = pallet_compliance_manager::Module<T>;
+type Statistics<T> = pallet_statistics::Module<T>;
 type EnsureValidInstructionResult<AccountId, Moment, BlockNumber> = Result<
     (
         IdentityId,
@@ -292,6 +301,11 @@ decl_storage! {
             double_map hasher(twox_64_concat) InstructionId, hasher(twox_64_concat) LegId => AffirmationStatus;
         /// Tracks the number of signers each venue has.
         pub NumberOfVenueSigners get(fn number_of_venue_signers): map hasher(twox_64_concat) VenueId => u32;
+        /// The secondary key whose spending limit was charged for an affirmed fungible leg,
+        /// and the start of the spending period it was charged to.
+        /// (instruction_id, leg_id) -> (secondary key, period start)
+        pub LegKeySpendings get(fn leg_key_spending):
+            double_map hasher(twox_64_concat) InstructionId, hasher(twox_64_concat) LegId => Option<(T::AccountId, T::Moment)>;
     }
 }
 
@@ -746,6 +760,38 @@ impl<T: Config> Module<T> {
         }
     }
 
+    /// Charges the amount sent by a fungible `leg` to the spending limit of the affirming secondary key.
+    /// The charge is recorded so that it can be refunded if the leg is not executed.
+    fn charge_key_spending_via_leg(
+        id: InstructionId,
+        leg_id: LegId,
+        secondary_key: Option<&SecondaryKey<T::AccountId>>,
+        leg: &Leg,
+    ) -> DispatchResult {
+        if let (Some(sk), Leg::Fungible { ticker, amount, .. }) = (secondary_key, leg) {
+            if let Some(period_start) =
+                Identity::<T>::charge_secondary_key_spending(&sk.key, Some(*ticker), *amount)?
+            {
+                LegKeySpendings::<T>::insert(id, leg_id, (sk.key.clone(), period_start));
+            }
+        }
+        Ok(())
+    }
+
+    /// Refunds the spending limit charged when the fungible `leg` was affirmed, if any.
+    fn refund_key_spending_via_leg(id: InstructionId, leg_id: LegId, leg: &Leg) {
+        if let (Some((key, period_start)), Leg::Fungible { ticker, amount, .. }) =
+            (LegKeySpendings::<T>::take(id, leg_id), leg)
+        {
+            Identity::<T>::refund_secondary_key_spending(
+                &key,
+                Some(*ticker),
+                *amount,
+                period_start,
+            );
+        }
+    }
+
     fn unlock_via_leg(leg: &Leg) -> DispatchResult {
         match leg {
             Leg::Fungible {
@@ -961,6 +1007,7 @@ impl<T: Config> Module<T> {
                 }
                 LegStatus::ExecutionPending => {
                     Self::unlock_via_leg(&leg)?;
+                    Self::refund_key_spending_via_leg(id, *leg_id, &leg);
                 }
                 LegStatus::PendingTokenLock => {
                     return Err(Error::<T>::InstructionNotAffirmed.into());
@@ -1067,10 +1114,14 @@ impl<T: Config> Module<T> {
             InstructionLegs::iter_prefix(&instruction_id).collect();
         instruction_legs.sort_by_key(|leg_id_leg| leg_id_leg.0);
 
+        // Each royalty is an additional fungible transfer.
+        let leg_royalties = Self::leg_royalties(instruction_id, &instruction_legs);
         let instruction_asset_count = AssetCount::from_legs(&instruction_legs);
         weight_meter
             .check_accrue(<T as Config>::WeightInfo::execute_instruction_paused(
-                instruction_asset_count.fungible(),
+                instruction_asset_count
+                    .fungible()
+                    .saturating_add(leg_royalties.len() as u32),
                 instruction_asset_count.non_fungible(),
                 instruction_asset_count.off_chain(),
             ))
@@ -1085,6 +1136,7 @@ impl<T: Config> Module<T> {
             Self::release_asset_locks_and_transfer_pending_legs(
                 instruction_id,
                 &instruction_legs,
+                &leg_royalties,
                 instruction_memo,
                 caller_did,
                 weight_meter,
@@ -1106,6 +1158,7 @@ impl<T: Config> Module<T> {
     fn release_asset_locks_and_transfer_pending_legs(
         instruction_id: InstructionId,
         instruction_legs: &[(LegId, Leg)],
+        leg_royalties: &BTreeMap<LegId, (PortfolioId, Balance)>,
         instruction_memo: Option<Memo>,
         caller_did: IdentityId,
         weight_meter: &mut WeightMeter,
@@ -1120,11 +1173,19 @@ impl<T: Config> Module<T> {
                         ticker,
                         amount,
                     } => {
+                        // The royalty owed by a leg is paid out of the amount sent to the seller.
+                        let (royalty_beneficiary, royalty_amount) = match leg_royalties.get(leg_id)
+                        {
+                            Some((beneficiary, royalty_amount)) => {
+                                (Some(*beneficiary), *royalty_amount)
+                            }
+                            None => (None, 0),
+                        };
                         if <Asset<T>>::base_transfer(
                             *sender,
                             *receiver,
                             &ticker,
-                            *amount,
+                            amount.saturating_sub(royalty_amount),
                             Some(instruction_id),
                             instruction_memo.clone(),
                             caller_did,
@@ -1134,6 +1195,22 @@ impl<T: Config> Module<T> {
                         {
                             return TransactionOutcome::Rollback(Ok(Err(*leg_id)));
                         }
+                        if let Some(beneficiary) = royalty_beneficiary {
+                            if <Asset<T>>::base_transfer(
+                                *sender,
+                                beneficiary,
+                                &ticker,
+                                royalty_amount,
+                                Some(instruction_id),
+                                instruction_memo.clone(),
+                                caller_did,
+                                weight_meter,
+                            )
+                            .is_err()
+                            {
+                                return TransactionOutcome::Rollback(Ok(Err(*leg_id)));
+                            }
+                        }
                     }
                     Leg::NonFungible {
                         sender,
@@ -1161,6 +1238,65 @@ impl<T: Config> Module<T> {
         TransactionOutcome::Commit(Ok(Ok(())))
     }
 
+    /// Returns the royalties owed by the pending fungible legs of an instruction, indexed by [`LegId`].
+    ///
+    /// A fungible leg moving assets from the receiver to the sender of an NFT leg is the payment for those NFTs.
+    /// If the collection of the first such NFT leg has a royalty, its share of the payment is owed to the beneficiary's
+    /// default portfolio. Each payment leg owes at most one royalty.
+    fn leg_royalties(
+        instruction_id: InstructionId,
+        instruction_legs: &[(LegId, Leg)],
+    ) -> BTreeMap<LegId, (PortfolioId, Balance)> {
+        let is_pending = |leg_id: &LegId| {
+            Self::instruction_leg_status(instruction_id, leg_id) == LegStatus::ExecutionPending
+        };
+
+        // The royalty of the first NFT leg between each (buyer, seller) pair.
+        let mut pair_royalties: BTreeMap<(PortfolioId, PortfolioId), NFTRoyalty> = BTreeMap::new();
+        for (leg_id, leg) in instruction_legs {
+            if let Leg::NonFungible {
+                sender,
+                receiver,
+                nfts,
+            } = leg
+            {
+                if pair_royalties.contains_key(&(*receiver, *sender)) || !is_pending(leg_id) {
+                    continue;
+                }
+                if let Some(royalty) = <Nft<T>>::collection_royalty(nfts.ticker()) {
+                    pair_royalties.insert((*receiver, *sender), royalty);
+                }
+            }
+        }
+        if pair_royalties.is_empty() {
+            return BTreeMap::new();
+        }
+
+        let mut leg_royalties = BTreeMap::new();
+        for (leg_id, leg) in instruction_legs {
+            if let Leg::Fungible {
+                sender,
+                receiver,
+                amount,
+                ..
+            } = leg
+            {
+                let royalty = match pair_royalties.get(&(*sender, *receiver)) {
+                    Some(royalty) => royalty,
+                    None => continue,
+                };
+                let beneficiary_portfolio = PortfolioId::default_portfolio(royalty.beneficiary);
+                let royalty_amount = royalty.percentage * *amount;
+                if *receiver == beneficiary_portfolio || royalty_amount == 0 || !is_pending(leg_id)
+                {
+                    continue;
+                }
+                leg_royalties.insert(*leg_id, (beneficiary_portfolio, royalty_amount));
+            }
+        }
+        leg_royalties
+    }
+
     fn prune_instruction(id: InstructionId, executed: bool) {
         let drained_legs: Vec<(LegId, Leg)> = InstructionLegs::drain_prefix(&id).collect();
         let details = <InstructionDetails<T>>::take(id);
@@ -1172,6 +1308,8 @@ impl<T: Config> Module<T> {
         OffChainAffirmations::remove_prefix(id, None);
         #[allow(deprecated)]
         AffirmsReceived::remove_prefix(id, None);
+        #[allow(deprecated)]
+        LegKeySpendings::<T>::remove_prefix(id, None);
 
         if executed {
             InstructionStatuses::<T>::insert(
@@ -1223,6 +1361,7 @@ impl<T: Config> Module<T> {
             Self::ensure_valid_affirmation_count(&filtered_legs, &affirmation_count)?
         }
         for (leg_id, leg) in filtered_legs.sender_subset() {
+            Self::charge_key_spending_via_leg(id, *leg_id, secondary_key, &leg)?;
             Self::lock_via_leg(&leg)?;
             <InstructionLegStatus<T>>::insert(id, leg_id, LegStatus::ExecutionPending);
         }
@@ -1332,6 +1471,12 @@ impl<T: Config> Module<T> {
             Self::ensure_valid_affirmation_count(&filtered_legs, &affirmation_count)?
         }
         for (leg_id, leg) in filtered_legs.sender_subset() {
+            Self::charge_key_spending_via_leg(
+                instruction_id,
+                *leg_id,
+                secondary_key.as_ref(),
+                &leg,
+            )?;
             Self::lock_via_leg(&leg)?;
             <InstructionLegStatus<T>>::insert(instruction_id, leg_id, LegStatus::ExecutionPending);
         }
@@ -1615,6 +1760,10 @@ impl<T: Config> Module<T> {
         )?;
 
         Self::unchecked_release_locks(id, &legs);
+        // Legs of rejected instructions, including failed ones, are never executed.
+        for (leg_id, leg) in &legs {
+            Self::refund_key_spending_via_leg(id, *leg_id, leg);
+        }
         let _ = T::Scheduler::cancel_named(id.execution_name());
         Self::prune_instruction(id, false);
         Self::deposit_event(RawEvent::InstructionRejected(origin_data.primary_did, id));
@@ -2033,7 +2182,11 @@ impl<T: Config> Module<T> {
 
     /// Returns the worst case weight for an instruction with `f` fungible legs, `n` nfts being transferred and `o` offchain assets.
     fn execute_scheduled_instruction_weight_limit(f: u32, n: u32, o: u32) -> Weight {
-        <T as Config>::WeightInfo::execute_scheduled_instruction(f, n, o)
+        <T as Config>::WeightInfo::execute_scheduled_instruction(
+            Self::max_fungible_transfers(f, n),
+            n,
+            o,
+        )
     }
 
     /// Returns the minimum weight for calling the `execute_scheduled_instruction` function.
@@ -2043,7 +2196,21 @@ impl<T: Config> Module<T> {
 
     /// Returns the worst case weight for an instruction with `f` fungible legs, `n` nfts being transferred and `o` offchain assets.
     fn execute_manual_instruction_weight_limit(f: u32, n: u32, o: u32) -> Weight {
-        <T as Config>::WeightInfo::execute_manual_instruction(f, n, o)
+        <T as Config>::WeightInfo::execute_manual_instruction(
+            Self::max_fungible_transfers(f, n),
+            n,
+            o,
+        )
+    }
+
+    /// Returns the maximum number of fungible transfers for an instruction with `f` fungible legs and `n` nfts.
+    /// If nfts are transferred, each fungible leg may also pay a royalty.
+    fn max_fungible_transfers(f: u32, n: u32) -> u32 {
+        if n > 0 {
+            f.saturating_mul(2)
+        } else {
+            f
+        }
     }
 
     /// Returns the minimum weight for calling the `execute_manual_instruction` extrinsic.
@@ -2167,6 +2334,242 @@ impl<T: Config> Module<T> {
             filtered_legs.unfiltered_asset_count().off_chain(),
         )
     }
+
+    /// Returns the holders of `ticker` and the pending instructions that would no longer
+    /// be compliant if the asset's compliance requirements were replaced by `asset_compliance`.
+    ///
+    /// At most `limit` instructions are checked, starting at `start`, and at most `limit` holders,
+    /// following `holders_after`; `next_instruction` and `next_holder` are set in the result
+    /// if there are more instructions or holders to check.
+    /// A holder is in breach if it doesn't satisfy the receiver conditions of any requirement.
+    /// No state is changed.
+    pub fn simulate_asset_compliance(
+        ticker: Ticker,
+        asset_compliance: AssetCompliance,
+        start: InstructionId,
+        holders_after: Option<IdentityId>,
+        limit: u32,
+    ) -> Result<ComplianceImpact, DispatchError> {
+        ComplianceManager::<T>::verify_compliance_complexity(
+            &asset_compliance.requirements,
+            ticker,
+            0,
+        )?;
+        if asset_compliance.paused {
+            return Ok(ComplianceImpact::default());
+        }
+
+        let requirements = &asset_compliance.requirements;
+        let mut weight_meter = WeightMeter::max_limit_no_minimum();
+        let (pending_instructions, next_instruction) =
+            Self::pending_instructions_with(&ticker, start, limit);
+        let (holders, next_holder) = Self::asset_holders(&ticker, holders_after, limit);
+        let mut impact = ComplianceImpact {
+            next_instruction,
+            next_holder,
+            ..Default::default()
+        };
+        for holder in holders {
+            if !ComplianceManager::<T>::is_any_receiver_compliant(
+                &ticker,
+                requirements,
+                holder,
+                &mut weight_meter,
+            )? {
+                impact.holders.push(holder);
+            }
+        }
+        for (instruction_id, legs) in pending_instructions {
+            for (sender, receiver, _) in legs {
+                if !ComplianceManager::<T>::is_any_requirement_compliant(
+                    &ticker,
+                    requirements,
+                    sender.did,
+                    receiver.did,
+                    &mut weight_meter,
+                )? {
+                    impact.instructions.push(instruction_id);
+                    break;
+                }
+            }
+        }
+        Ok(impact)
+    }
+
+    /// Returns the holders of `ticker` and the pending instructions that would no longer
+    /// be compliant if the asset's transfer compliance were replaced by `transfer_compliance`.
+    ///
+    /// At most `limit` instructions are checked, starting at `start`, and at most `limit` holders,
+    /// following `holders_after`; `next_instruction` and `next_holder` are set in the result
+    /// if there are more instructions or holders to check.
+    /// Only fungible legs are checked against transfer conditions, using the balances each
+    /// identity would have after the previous legs of the same instruction. No state is changed.
+    pub fn simulate_transfer_compliance(
+        ticker: Ticker,
+        transfer_compliance: AssetTransferCompliance<T::MaxTransferConditionsPerAsset>,
+        start: InstructionId,
+        holders_after: Option<IdentityId>,
+        limit: u32,
+    ) -> Result<ComplianceImpact, DispatchError> {
+        let asset_scope = AssetScope::Ticker(ticker);
+        let transfer_conditions = Statistics::<T>::ensure_transfer_conditions_valid(
+            asset_scope,
+            transfer_compliance.requirements.into_inner(),
+        )?;
+        if transfer_compliance.paused {
+            return Ok(ComplianceImpact::default());
+        }
+
+        let total_supply = Asset::<T>::total_supply(ticker);
+        let mut weight_meter = WeightMeter::max_limit_no_minimum();
+        let (pending_instructions, next_instruction) =
+            Self::pending_instructions_with(&ticker, start, limit);
+        let (holders, next_holder) = Self::asset_holders(&ticker, holders_after, limit);
+        let mut impact = ComplianceImpact {
+            next_instruction,
+            next_holder,
+            ..Default::default()
+        };
+        for holder in holders {
+            if Statistics::<T>::is_holder_in_breach(
+                &transfer_conditions,
+                asset_scope,
+                &holder,
+                Asset::<T>::balance_of(&ticker, &holder),
+                total_supply,
+            ) {
+                impact.holders.push(holder);
+            }
+        }
+        for (instruction_id, legs) in pending_instructions {
+            // Balances of each identity after the legs of this instruction checked so far.
+            let mut balances: BTreeMap<IdentityId, Balance> = BTreeMap::new();
+            for (sender, receiver, amount) in legs {
+                let amount = match amount {
+                    Some(amount) => amount,
+                    None => continue,
+                };
+                let sender_balance = *balances
+                    .entry(sender.did)
+                    .or_insert_with(|| Asset::<T>::balance_of(&ticker, &sender.did));
+                let receiver_balance = *balances
+                    .entry(receiver.did)
+                    .or_insert_with(|| Asset::<T>::balance_of(&ticker, &receiver.did));
+                if Statistics::<T>::verify_requirements(
+                    &transfer_conditions,
+                    asset_scope,
+                    &sender.did,
+                    &receiver.did,
+                    sender_balance,
+                    receiver_balance,
+                    amount,
+                    total_supply,
+                    &mut weight_meter,
+                )
+                .is_err()
+                {
+                    impact.instructions.push(instruction_id);
+                    break;
+                }
+                if sender.did != receiver.did {
+                    balances.insert(sender.did, sender_balance.saturating_sub(amount));
+                    balances.insert(receiver.did, receiver_balance.saturating_add(amount));
+                }
+            }
+        }
+        Ok(impact)
+    }
+
+    /// Returns the identities with a non-zero balance of `ticker` among the `limit` balance entries
+    /// following the holder `after`, in storage order.
+    ///
+    /// The last identity checked is also returned if there may be more holders, to be used as the
+    /// next `after`.
+    fn asset_holders(
+        ticker: &Ticker,
+        after: Option<IdentityId>,
+        limit: u32,
+    ) -> (Vec<IdentityId>, Option<IdentityId>) {
+        let entries: Vec<(IdentityId, bool)> = match Asset::<T>::nft_asset(ticker) {
+            Some(true) => match after {
+                Some(after) => pallet_nft::NumberOfNFTs::iter_prefix_from(
+                    ticker,
+                    pallet_nft::NumberOfNFTs::hashed_key_for(ticker, after),
+                ),
+                None => pallet_nft::NumberOfNFTs::iter_prefix(ticker),
+            }
+            .take(limit as usize)
+            .map(|(did, count)| (did, count > 0))
+            .collect(),
+            _ => match after {
+                Some(after) => pallet_asset::BalanceOf::iter_prefix_from(
+                    ticker,
+                    pallet_asset::BalanceOf::hashed_key_for(ticker, after),
+                ),
+                None => pallet_asset::BalanceOf::iter_prefix(ticker),
+            }
+            .take(limit as usize)
+            .map(|(did, balance)| (did, balance > 0))
+            .collect(),
+        };
+        let next_holder = match entries.last() {
+            Some((did, _)) if entries.len() == limit as usize => Some(*did),
+            _ => None,
+        };
+        let holders = entries
+            .into_iter()
+            .filter(|(_, holds)| *holds)
+            .map(|(did, _)| did)
+            .collect();
+        (holders, next_holder)
+    }
+
+    /// Returns the pending instructions with legs of `ticker`, along with the sender, receiver and,
+    /// for fungible legs, the amount of each of those legs.
+    ///
+    /// Only the `limit` instructions starting at `start` are checked. The id of the next
+    /// instruction to check is also returned, if there is one.
+    fn pending_instructions_with(
+        ticker: &Ticker,
+        start: InstructionId,
+        limit: u32,
+    ) -> (
+        Vec<(
+            InstructionId,
+            Vec<(PortfolioId, PortfolioId, Option<Balance>)>,
+        )>,
+        Option<InstructionId>,
+    ) {
+        let start = start.0.max(1);
+        let end = Self::instruction_counter()
+            .0
+            .min(start.saturating_add(limit as u64));
+        let next_instruction = (end < Self::instruction_counter().0).then(|| InstructionId(end));
+        let pending_instructions = (start..end)
+            .map(InstructionId)
+            .filter(|id| Self::instruction_status(id) == InstructionStatus::Pending)
+            .filter_map(|instruction_id| {
+                let legs: Vec<_> = InstructionLegs::iter_prefix(instruction_id)
+                    .filter_map(|(_, leg)| match leg {
+                        Leg::Fungible {
+                            sender,
+                            receiver,
+                            ticker: leg_ticker,
+                            amount,
+                        } if leg_ticker == *ticker => Some((sender, receiver, Some(amount))),
+                        Leg::NonFungible {
+                            sender,
+                            receiver,
+                            nfts,
+                        } if nfts.ticker() == ticker => Some((sender, receiver, None)),
+                        _ => None,
+                    })
+                    .collect();
+                (!legs.is_empty()).then(|| (instruction_id, legs))
+            })
+            .collect();
+        (pending_instructions, next_instruction)
+    }
 }
 
 pub mod migration {
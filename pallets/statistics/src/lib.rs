@@ -272,24 +272,11 @@ impl<T: Config> Module<T> {
         // Check EA permissions for asset.
         let did = Self::ensure_asset_perms(origin, asset)?;
 
-        // TODO: Use complexity instead of count to limit TransferConditions per asset.
-        // converting from a btreeset to a bounded version
-        let transfer_conditions: BoundedBTreeSet<_, T::MaxTransferConditionsPerAsset> =
-            transfer_conditions
-                .try_into()
-                .map_err(|_| Error::<T>::TransferConditionLimitReached)?;
+        let transfer_conditions =
+            Self::ensure_transfer_conditions_valid(asset, transfer_conditions)?;
 
         // Commit changes to storage.
         if transfer_conditions.len() > 0 {
-            // Check if required Stats are enabled.
-            for condition in transfer_conditions.iter() {
-                let stat_type = condition.get_stat_type();
-                ensure!(
-                    Self::is_asset_stat_active(asset, stat_type),
-                    Error::<T>::StatTypeMissing
-                );
-            }
-
             AssetTransferCompliances::<T>::mutate(&asset, |old| {
                 old.requirements = transfer_conditions.clone()
             });
@@ -306,6 +293,31 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    /// Ensures `transfer_conditions` are within the limit for an asset and that all the stats they need are enabled.
+    pub fn ensure_transfer_conditions_valid(
+        asset: AssetScope,
+        transfer_conditions: BTreeSet<TransferCondition>,
+    ) -> Result<BoundedBTreeSet<TransferCondition, T::MaxTransferConditionsPerAsset>, DispatchError>
+    {
+        // TODO: Use complexity instead of count to limit TransferConditions per asset.
+        // converting from a btreeset to a bounded version
+        let transfer_conditions: BoundedBTreeSet<_, T::MaxTransferConditionsPerAsset> =
+            transfer_conditions
+                .try_into()
+                .map_err(|_| Error::<T>::TransferConditionLimitReached)?;
+
+        // Check if required Stats are enabled.
+        for condition in transfer_conditions.iter() {
+            let stat_type = condition.get_stat_type();
+            ensure!(
+                Self::is_asset_stat_active(asset, stat_type),
+                Error::<T>::StatTypeMissing
+            );
+        }
+
+        Ok(transfer_conditions)
+    }
+
     fn base_set_entities_exempt(
         origin: T::RuntimeOrigin,
         is_exempt: bool,
@@ -855,7 +867,7 @@ impl<T: Config> Module<T> {
     }
 
     /// Returns `true` if all `requirements` are met, otherwise returns `false`.
-    fn verify_requirements<S: Get<u32>>(
+    pub fn verify_requirements<S: Get<u32>>(
         transfer_conditions: &BoundedBTreeSet<TransferCondition, S>,
         asset_scope: AssetScope,
         sender_did: &IdentityId,
@@ -934,6 +946,47 @@ impl<T: Config> Module<T> {
         Ok(transfer_conditions)
     }
 
+    /// Returns `true` if the current holding of `holder_did` already breaches any of the `transfer_conditions`.
+    ///
+    /// Only conditions that can be attributed to a single holder are considered:
+    /// the holder's own percentage ownership, and the count or ownership limits of a claim the holder has.
+    /// Exempt holders are never in breach.
+    pub fn is_holder_in_breach<S: Get<u32>>(
+        transfer_conditions: &BoundedBTreeSet<TransferCondition, S>,
+        asset: AssetScope,
+        holder_did: &IdentityId,
+        holder_balance: Balance,
+        total_supply: Balance,
+    ) -> bool {
+        transfer_conditions.iter().any(|condition| {
+            let key1 = Stat1stKey {
+                asset,
+                stat_type: condition.get_stat_type(),
+            };
+            let breached = match condition {
+                TransferCondition::MaxInvestorCount(_) => false,
+                TransferCondition::MaxInvestorOwnership(max_percentage) => {
+                    sp_arithmetic::Permill::from_rational(holder_balance, total_supply)
+                        > *max_percentage
+                }
+                TransferCondition::ClaimCount(claim, _, _, max) => {
+                    let key2 = claim.into();
+                    Self::has_matching_claim(holder_did, &key1, &key2)
+                        && max.map_or(false, |max| AssetStats::get(key1, key2) > max as u128)
+                }
+                TransferCondition::ClaimOwnership(claim, _, _, max_percentage) => {
+                    let key2 = claim.into();
+                    Self::has_matching_claim(holder_did, &key1, &key2)
+                        && sp_arithmetic::Permill::from_rational(
+                            AssetStats::get(key1, key2),
+                            total_supply,
+                        ) > *max_percentage
+                }
+            };
+            breached && !Self::is_exempt(asset, condition, holder_did, holder_did)
+        })
+    }
+
     /// Helper function to get investor count for tests.
     pub fn investor_count(ticker: Ticker) -> u128 {
         AssetStats::get(Stat1stKey::investor_count(ticker), Stat2ndKey::NoClaimStat)
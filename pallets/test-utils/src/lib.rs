@@ -52,7 +52,9 @@ use frame_support::{
 use frame_system::{ensure_signed, RawOrigin};
 use pallet_identity::PermissionedCallOriginData;
 use polymesh_common_utilities::{
-    protocol_fee::ProtocolOp, traits::identity::Config as IdentityConfig, TestUtilsFn,
+    protocol_fee::{ChargeProtocolFee, ProtocolOp},
+    traits::identity::Config as IdentityConfig,
+    TestUtilsFn,
 };
 use polymesh_primitives::{secondary_key, CddId, Claim, IdentityId, SecondaryKey};
 use sp_std::{prelude::*, vec};
@@ -114,7 +116,8 @@ decl_module! {
         /// - `AlreadyLinked` if the caller account or if any of the given `secondary_keys` has already linked to an `IdentityID`
         /// - `SecondaryKeysContainPrimaryKey` if `secondary_keys` contains the caller account.
         /// - `DidAlreadyExists` if auto-generated DID already exists.
-        #[weight = <T as Config>::WeightInfo::register_did(secondary_keys.len() as u32)]
+        #[weight = <T as Config>::WeightInfo::register_did(secondary_keys.len() as u32)
+            .saturating_add(T::ProtocolFee::charge_fee_weight())]
         pub fn register_did(
             origin,
             secondary_keys: Vec<SecondaryKey<T::AccountId>>,
@@ -135,7 +138,7 @@ decl_module! {
         /// claims.
         /// - `target_account` (primary key of the new Identity) can be linked to just one and only
         /// one identity.
-        #[weight = <T as Config>::WeightInfo::mock_cdd_register_did()]
+        #[weight = <T as Config>::WeightInfo::mock_cdd_register_did().saturating_add(T::ProtocolFee::charge_fee_weight())]
         pub fn mock_cdd_register_did(origin, target_account: T::AccountId) {
             let (cdd_did, target_did) = Identity::<T>::base_cdd_register_did(origin, target_account, vec![])?;
 
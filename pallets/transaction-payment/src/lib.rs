@@ -65,7 +65,7 @@ use polymesh_common_utilities::traits::{
     group::GroupTrait,
     identity::IdentityFnTrait,
     relayer::SubsidiserTrait,
-    transaction_payment::{CddAndFeeDetails, ChargeTxFee},
+    transaction_payment::{CddAndFeeDetails, ChargeTxFee, FeeAssetPayer, FeeAssetPayment},
 };
 use polymesh_primitives::TransactionError;
 use scale_info::TypeInfo;
@@ -355,6 +355,11 @@ pub trait Config: frame_system::Config + pallet_timestamp::Config {
     // Polymesh note: This was specifically added for Polymesh
     /// Identity functionality.
     type Identity: IdentityFnTrait<Self::AccountId>;
+
+    // Polymesh note: This was specifically added for Polymesh
+    /// Connection to the `Asset` pallet.
+    /// Used to charge transaction fees in the fee asset chosen by the payer, if any.
+    type FeeAssetPayer: FeeAssetPayer<Self::AccountId>;
 }
 
 decl_storage! {
@@ -613,6 +618,28 @@ where
         )
     }
 
+    /// Pays the fee of a transaction with the fee asset chosen by `payer`.
+    ///
+    /// The fee includes the weight of paying it in the asset and refunding its unused part,
+    /// which is added to the block weight.
+    /// Returns `None` if `payer` didn't choose a fee asset or the payment failed.
+    fn pay_fee_in_asset(
+        payer: &T::AccountId,
+        len: u32,
+        info: &DispatchInfoOf<T::RuntimeCall>,
+        tip: BalanceOf<T>,
+    ) -> Option<(BalanceOf<T>, FeeAssetPayment)>
+    where
+        T::RuntimeCall: Dispatchable<Info = DispatchInfo>,
+        BalanceOf<T>: Into<u128>,
+    {
+        let payment_weight = T::FeeAssetPayer::fee_asset_payment_weight(payer)?;
+        let fee = Self::compute_fee(len, &with_extra_weight(info, payment_weight), tip);
+        let payment = T::FeeAssetPayer::pay_fee_in_asset(payer, fee.into())?;
+        frame_system::Pallet::<T>::register_extra_weight_unchecked(payment_weight, info.class);
+        Some((fee, payment))
+    }
+
     fn compute_fee_raw(
         len: u32,
         weight: Weight,
@@ -684,10 +711,19 @@ where
     }
 }
 
+/// Returns `info` with `weight` added to its weight.
+fn with_extra_weight(info: &DispatchInfo, weight: Weight) -> DispatchInfo {
+    DispatchInfo {
+        weight: info.weight.saturating_add(weight),
+        ..*info
+    }
+}
+
 pub type WithdrawFeeInfo<T, AccountId> = (
     BalanceOf<T>,
     <<T as Config>::OnChargeTransaction as OnChargeTransaction<T>>::LiquidityInfo,
     Option<AccountId>,
+    Option<FeeAssetPayment>,
 );
 
 /// Require the transactor pay for themselves and maybe include a tip to gain additional priority
@@ -722,7 +758,7 @@ where
         // Only mess with balances if fee is not zero.
         if fee.is_zero() {
             let liquidity_info = Default::default();
-            return Ok((fee, liquidity_info, None));
+            return Ok((fee, liquidity_info, None, None));
         }
 
         // Get the payer for this transaction.
@@ -731,11 +767,18 @@ where
 
         // Check if the payer is being subsidised.
         let metadata = call.get_call_metadata();
-        let subsidiser = T::Subsidiser::check_subsidy(
-            &payer_key,
-            fee.into(),
-            Some(metadata.pallet_name.as_bytes()),
-        )?;
+        let subsidiser = T::Subsidiser::check_subsidy(&payer_key, fee.into(), Some(&metadata))?;
+
+        // An unsubsidised payer may pay the fee with its fee asset, falling back to POLYX.
+        // The unused part of the fee is refunded in the asset after dispatch.
+        if subsidiser.is_none() {
+            if let Some((fee, payment)) =
+                Module::<T>::pay_fee_in_asset(&payer_key, len as u32, info, tip)
+            {
+                T::CddHandler::set_payer_context(Some(payer_key));
+                return Ok((fee, Default::default(), None, Some(payment)));
+            }
+        }
 
         // key to pay the fee.
         let fee_key = subsidiser.as_ref().unwrap_or(&payer_key);
@@ -744,7 +787,7 @@ where
                 fee_key, call, info, fee, tip,
             )?;
         T::CddHandler::set_payer_context(Some(payer_key));
-        Ok((fee, liquidity_info, subsidiser))
+        Ok((fee, liquidity_info, subsidiser, None))
     }
 
     // Polymesh: Used to allow GC/CDD member to include a `tip`.
@@ -812,6 +855,8 @@ where
         <<T as Config>::OnChargeTransaction as OnChargeTransaction<T>>::LiquidityInfo,
         // Polymesh: Subsidiser
         Option<Self::AccountId>,
+        // Polymesh: The fee was paid in a non-POLYX asset.
+        Option<FeeAssetPayment>,
     );
     fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
         Ok(())
@@ -826,7 +871,7 @@ where
     ) -> TransactionValidity {
         let tip = self.ensure_valid_tip(who, info)?;
 
-        let (_fee, _, _) = self.withdraw_fee(who, call, info, len)?;
+        let (_fee, _, _, _) = self.withdraw_fee(who, call, info, len)?;
         // Polymesh: `tip` can only be used by GC/CDD members.
         Ok(ValidTransaction {
             priority: tip.saturated_into::<TransactionPriority>(),
@@ -842,8 +887,9 @@ where
         len: usize,
     ) -> Result<Self::Pre, TransactionValidityError> {
         let tip = self.ensure_valid_tip(who, info)?;
-        let (_fee, imbalance, subsidiser) = self.withdraw_fee(who, call, info, len)?;
-        Ok((tip, who.clone(), imbalance, subsidiser))
+        let (_fee, imbalance, subsidiser, asset_payment) =
+            self.withdraw_fee(who, call, info, len)?;
+        Ok((tip, who.clone(), imbalance, subsidiser, asset_payment))
     }
 
     fn post_dispatch(
@@ -853,7 +899,7 @@ where
         len: usize,
         _result: &DispatchResult,
     ) -> Result<(), TransactionValidityError> {
-        let (tip, who, imbalance, subsidiser) = match pre {
+        let (tip, who, imbalance, subsidiser, asset_payment) = match pre {
             Some(pre) => pre,
             None => return Ok(()),
         };
@@ -863,6 +909,26 @@ where
         // If payer context is empty, the fee is returned to the caller account.
         let payer = T::CddHandler::get_payer_from_context().unwrap_or(who.clone());
 
+        if let Some(payment) = asset_payment {
+            // The fee was paid with the fee asset of the payer, refund the unused part in that asset.
+            // The weight of the payment and of its refund is charged in full.
+            let post_info = PostDispatchInfo {
+                actual_weight: post_info
+                    .actual_weight
+                    .map(|weight| weight.saturating_add(payment.weight)),
+                ..*post_info
+            };
+            let actual_fee = Module::<T>::compute_actual_fee(
+                len as u32,
+                &with_extra_weight(info, payment.weight),
+                &post_info,
+                tip,
+            );
+            T::FeeAssetPayer::refund_fee_in_asset(&payer, payment, actual_fee.into());
+            T::CddHandler::clear_context();
+            return Ok(());
+        }
+
         // `fee_key` is either a subsidiser or the original payer.
         let fee_key = if let Some(subsidiser_key) = subsidiser {
             // Debit the actual fee from the subsidy.
@@ -892,13 +958,16 @@ where
 // Polymesh note: This was specifically added for Polymesh
 impl<T: Config> ChargeTxFee for Module<T>
 where
-    BalanceOf<T>: FixedPointOperand,
+    BalanceOf<T>: FixedPointOperand + Into<u128>,
     T::RuntimeCall: Dispatchable<Info = DispatchInfo>,
 {
     fn charge_fee(len: u32, info: DispatchInfoOf<T::RuntimeCall>) -> TransactionValidity {
         let fee = Self::compute_fee(len as u32, &info, 0u32.into());
         if let Some(payer) = T::CddHandler::get_payer_from_context() {
-            T::OnChargeTransaction::charge_fee(&payer, fee)?;
+            // Pay with the fee asset of the payer, falling back to POLYX.
+            if Self::pay_fee_in_asset(&payer, len, &info, 0u32.into()).is_none() {
+                T::OnChargeTransaction::charge_fee(&payer, fee)?;
+            }
         }
         Ok(ValidTransaction::default())
     }
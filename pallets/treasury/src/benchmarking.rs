@@ -18,7 +18,7 @@ use crate::*;
 use frame_benchmarking::benchmarks;
 use frame_system::RawOrigin;
 use polymesh_common_utilities::{
-    benchs::{AccountIdOf, UserBuilder},
+    benchs::{AccountIdOf, User, UserBuilder},
     TestUtilsFn,
 };
 use sp_std::vec::Vec;
@@ -26,6 +26,20 @@ use sp_std::vec::Vec;
 const MAX_BENEFICIARIES: u32 = 128;
 const REWARD: u32 = 10;
 
+/// Refills the treasury with `amount` and returns a beneficiary identity.
+fn setup_plan<T: Config + TestUtilsFn<AccountIdOf<T>>>(amount: u32) -> User<T> {
+    let refiller = UserBuilder::<T>::default()
+        .balance(amount + 1_000)
+        .generate_did()
+        .build("refiller");
+    Module::<T>::reimbursement(refiller.origin().into(), amount.into())
+        .expect("Tresury cannot be refill");
+    UserBuilder::<T>::default()
+        .balance(100u32)
+        .generate_did()
+        .build("beneficiary")
+}
+
 benchmarks! {
     where_clause { where T: TestUtilsFn<AccountIdOf<T>> }
 
@@ -62,4 +76,66 @@ benchmarks! {
     verify {
         assert_eq!(Module::<T>::balance(), (initial_balance + 500u32.into()));
     }
+
+    create_milestone_plan {
+        let m in 1..T::MaxMilestones::get();
+        let beneficiary = setup_plan::<T>(REWARD * m);
+        let milestones = (0..m).map(|_| REWARD.into()).collect::<Vec<_>>();
+    }: _(RawOrigin::Root, beneficiary.did(), milestones)
+    verify {
+        assert_eq!(Module::<T>::committed_balance(), (REWARD * m).into());
+    }
+
+    create_stream_plan {
+        let beneficiary = setup_plan::<T>(100);
+    }: _(RawOrigin::Root, beneficiary.did(), 0u32.into(), 1u32.into(), 100u32.into())
+    verify {
+        assert_eq!(Module::<T>::committed_balance(), 100u32.into());
+    }
+
+    release_milestone {
+        let beneficiary = setup_plan::<T>(2 * REWARD);
+        let id = Module::<T>::payment_plan_counter();
+        Module::<T>::create_milestone_plan(
+            RawOrigin::Root.into(),
+            beneficiary.did(),
+            vec![REWARD.into(), REWARD.into()],
+        ).unwrap();
+    }: _(RawOrigin::Root, id, 0)
+    verify {
+        assert_eq!(Module::<T>::committed_balance(), REWARD.into());
+    }
+
+    claim_stream {
+        let beneficiary = setup_plan::<T>(100);
+        let id = Module::<T>::payment_plan_counter();
+        Module::<T>::create_stream_plan(
+            RawOrigin::Root.into(),
+            beneficiary.did(),
+            0u32.into(),
+            1u32.into(),
+            100u32.into(),
+        ).unwrap();
+        frame_system::Pallet::<T>::set_block_number(50u32.into());
+    }: _(beneficiary.origin(), id)
+    verify {
+        assert_eq!(Module::<T>::committed_balance(), 50u32.into());
+    }
+
+    cancel_payment_plan {
+        let beneficiary = setup_plan::<T>(100);
+        let id = Module::<T>::payment_plan_counter();
+        Module::<T>::create_stream_plan(
+            RawOrigin::Root.into(),
+            beneficiary.did(),
+            0u32.into(),
+            1u32.into(),
+            100u32.into(),
+        ).unwrap();
+        frame_system::Pallet::<T>::set_block_number(50u32.into());
+    }: _(RawOrigin::Root, id)
+    verify {
+        assert_eq!(Module::<T>::committed_balance(), 0u32.into());
+        assert!(Module::<T>::payment_plans(id).is_none());
+    }
 }
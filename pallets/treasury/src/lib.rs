@@ -25,10 +25,23 @@
 //!
 //! The disbursement mechanism is designed to incentivize Polymesh Improvement Proposals.
 //!
+//! ### Payment plans
+//!
+//! Governance can also grant funds to an identity through a payment plan, paid out either
+//! milestone by milestone, as governance releases them, or as a stream accruing every block,
+//! which the beneficiary claims. The unpaid part of active plans is kept aside in the treasury
+//! and cannot be disbursed. When a plan is cancelled, the beneficiary keeps what it has been paid
+//! (and what its stream has accrued), and the remainder is returned to the treasury funds.
+//!
 //! ## Dispatchable Functions
 //!
 //! - [disbursement](Module::disbursement) - Transfers from the treasury to the given benericiaries.
 //! - [reimbursement](Module::reimbursement) - Transfers to the treasury.
+//! - [create_milestone_plan](Module::create_milestone_plan) - Creates a milestone payment plan.
+//! - [create_stream_plan](Module::create_stream_plan) - Creates a streaming payment plan.
+//! - [release_milestone](Module::release_milestone) - Pays out a milestone of a payment plan.
+//! - [claim_stream](Module::claim_stream) - Pays out the accrued part of a streaming payment plan.
+//! - [cancel_payment_plan](Module::cancel_payment_plan) - Cancels a payment plan.
 //!
 #![cfg_attr(not(feature = "std"), no_std)]
 #![recursion_limit = "256"]
@@ -36,21 +49,23 @@
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
 
-use frame_support::traits::{StorageInfo, StorageInfoTrait};
 use frame_support::{
-    decl_error, decl_event, decl_module,
+    decl_error, decl_event, decl_module, decl_storage,
     dispatch::{DispatchError, DispatchResult},
     ensure,
-    traits::{Currency, ExistenceRequirement, Imbalance, OnUnbalanced},
+    storage::IterableStorageDoubleMap,
+    traits::{Currency, ExistenceRequirement, Get, Imbalance, OnUnbalanced},
     weights::Weight,
 };
 use frame_system::ensure_root;
+use pallet_base::try_next_post;
 use pallet_identity as identity;
 use polymesh_common_utilities::{
     constants::TREASURY_PALLET_ID, traits::balances::Config as BalancesConfig, Context, GC_DID,
 };
-use polymesh_primitives::{Beneficiary, IdentityId};
-use sp_runtime::traits::{AccountIdConversion, Saturating};
+use polymesh_primitives::treasury::{Milestone, PaymentPlan, PaymentPlanId, PaymentSchedule};
+use polymesh_primitives::{Balance, Beneficiary, IdentityId};
+use sp_runtime::traits::{AccountIdConversion, Saturating, Zero};
 use sp_std::prelude::*;
 
 pub type ProposalIndex = u32;
@@ -66,16 +81,26 @@ pub trait Config: frame_system::Config + BalancesConfig {
     // The overarching event type.
     type RuntimeEvent: From<Event<Self>> + Into<<Self as frame_system::Config>::RuntimeEvent>;
     /// The native currency.
-    type Currency: Currency<Self::AccountId>;
+    type Currency: Currency<Self::AccountId, Balance = Balance>;
     /// Weight information for extrinsics in the identity pallet.
     type WeightInfo: WeightInfo;
+    /// Maximum number of milestones of a payment plan.
+    type MaxMilestones: Get<u32>;
 }
 
 pub trait WeightInfo {
     fn reimbursement() -> Weight;
     fn disbursement(beneficiary_count: u32) -> Weight;
+    fn create_milestone_plan(milestones: u32) -> Weight;
+    fn create_stream_plan() -> Weight;
+    fn release_milestone() -> Weight;
+    fn claim_stream() -> Weight;
+    fn cancel_payment_plan() -> Weight;
 }
 
+/// The payment plan type used by the pallet.
+pub type PaymentPlanOf<T> = PaymentPlan<<T as frame_system::Config>::BlockNumber>;
+
 decl_event!(
     pub enum Event<T>
     where
@@ -96,6 +121,32 @@ decl_event!(
         ///
         /// (source identity, amount)
         TreasuryReimbursement(IdentityId, Balance),
+
+        /// A payment plan was created.
+        ///
+        /// (treasury identity, plan id, beneficiary identity, total amount)
+        PaymentPlanCreated(IdentityId, PaymentPlanId, IdentityId, Balance),
+
+        /// A payment of a payment plan was made.
+        ///
+        /// (plan id, beneficiary identity, beneficiary primary key, amount)
+        PaymentPlanPaid(PaymentPlanId, IdentityId, AccountId, Balance),
+
+        /// A payment plan was fully paid out.
+        ///
+        /// (plan id)
+        PaymentPlanCompleted(PaymentPlanId),
+
+        /// A payment plan was cancelled and its unpaid remainder returned to the treasury funds.
+        ///
+        /// (treasury identity, plan id, remainder)
+        PaymentPlanCancelled(IdentityId, PaymentPlanId, Balance),
+
+        /// The accrued amount of a cancelled payment plan could not be paid out,
+        /// and stays claimable by the beneficiary.
+        ///
+        /// (plan id, amount)
+        PaymentPlanPaymentDeferred(PaymentPlanId, Balance),
     }
 );
 
@@ -106,6 +157,37 @@ decl_error! {
         InsufficientBalance,
         /// Invalid identity for disbursement.
         InvalidIdentity,
+        /// The payment plan pays nothing.
+        InvalidPaymentPlan,
+        /// The payment plan has more than `MaxMilestones` milestones.
+        TooManyMilestones,
+        /// The payment plan does not exist.
+        PaymentPlanNotFound,
+        /// The payment plan has no such milestone.
+        MilestoneNotFound,
+        /// The milestone was already released.
+        MilestoneAlreadyReleased,
+        /// The payment plan is not a stream.
+        NotAStream,
+        /// The caller is not the beneficiary of the payment plan.
+        NotPaymentPlanBeneficiary,
+        /// The stream has accrued nothing since the last claim.
+        NothingToClaim,
+    }
+}
+
+decl_storage! {
+    trait Store for Module<T: Config> as Treasury {
+        /// Active payment plans, by id.
+        pub PaymentPlans get(fn payment_plans):
+            map hasher(twox_64_concat) PaymentPlanId => Option<PaymentPlanOf<T>>;
+        /// The active payment plans of a beneficiary.
+        pub BeneficiaryPaymentPlans:
+            double_map hasher(identity) IdentityId, hasher(twox_64_concat) PaymentPlanId => bool;
+        /// The id of the next payment plan.
+        pub PaymentPlanCounter get(fn payment_plan_counter): PaymentPlanId;
+        /// Unpaid total of the active payment plans, kept aside in the treasury.
+        pub CommittedBalance get(fn committed_balance): Balance;
     }
 }
 
@@ -120,7 +202,8 @@ decl_module! {
         ///
         /// # Error
         /// * `BadOrigin`: Only root can execute transaction.
-        /// * `InsufficientBalance`: If treasury balances is not enough to cover all beneficiaries.
+        /// * `InsufficientBalance`: If treasury balances, without the amount committed to payment plans,
+        ///    is not enough to cover all beneficiaries.
         /// * `InvalidIdentity`: If one of the beneficiaries has an invalid identity.
         #[weight = <T as Config>::WeightInfo::disbursement(beneficiaries.len() as u32)]
         pub fn disbursement(origin, beneficiaries: Vec<Beneficiary<BalanceOf<T>>>) {
@@ -134,6 +217,143 @@ decl_module! {
         pub fn reimbursement(origin, amount: BalanceOf<T>) {
             Self::base_reimbursement(origin, amount)?;
         }
+
+        /// Creates a payment plan paying `milestones` to `beneficiary`, each one when released
+        /// by governance.
+        ///
+        /// # Error
+        /// * `BadOrigin`: Only root can execute transaction.
+        /// * `InvalidPaymentPlan`: If there is no milestone or a milestone pays nothing.
+        /// * `TooManyMilestones`: If there are more than `MaxMilestones` milestones.
+        /// * `InvalidIdentity`: If `beneficiary` is not a valid identity.
+        /// * `InsufficientBalance`: If the uncommitted treasury balance does not cover the plan.
+        #[weight = <T as Config>::WeightInfo::create_milestone_plan(milestones.len() as u32)]
+        pub fn create_milestone_plan(origin, beneficiary: IdentityId, milestones: Vec<Balance>) {
+            ensure_root(origin)?;
+            ensure!(
+                milestones.len() as u32 <= T::MaxMilestones::get(),
+                Error::<T>::TooManyMilestones
+            );
+            ensure!(
+                !milestones.is_empty() && milestones.iter().all(|amount| !amount.is_zero()),
+                Error::<T>::InvalidPaymentPlan
+            );
+
+            let total = milestones
+                .iter()
+                .fold(Balance::zero(), |total, amount| total.saturating_add(*amount));
+            let milestones = milestones
+                .into_iter()
+                .map(|amount| Milestone { amount, released: false })
+                .collect();
+            Self::base_create_payment_plan(beneficiary, total, PaymentSchedule::Milestones(milestones))?;
+        }
+
+        /// Creates a payment plan streaming `total` to `beneficiary`, accruing `per_block` every
+        /// block from `start`.
+        ///
+        /// # Error
+        /// * `BadOrigin`: Only root can execute transaction.
+        /// * `InvalidPaymentPlan`: If `per_block` or `total` is zero.
+        /// * `InvalidIdentity`: If `beneficiary` is not a valid identity.
+        /// * `InsufficientBalance`: If the uncommitted treasury balance does not cover the plan.
+        #[weight = <T as Config>::WeightInfo::create_stream_plan()]
+        pub fn create_stream_plan(
+            origin,
+            beneficiary: IdentityId,
+            start: T::BlockNumber,
+            per_block: Balance,
+            total: Balance
+        ) {
+            ensure_root(origin)?;
+            ensure!(!per_block.is_zero() && !total.is_zero(), Error::<T>::InvalidPaymentPlan);
+            Self::base_create_payment_plan(beneficiary, total, PaymentSchedule::Stream { start, per_block })?;
+        }
+
+        /// Pays out the milestone at `index` of the payment plan `id` to its beneficiary.
+        ///
+        /// # Error
+        /// * `BadOrigin`: Only root can execute transaction.
+        /// * `PaymentPlanNotFound`: If there is no plan `id`.
+        /// * `MilestoneNotFound`: If the plan has no milestone at `index`.
+        /// * `MilestoneAlreadyReleased`: If the milestone was already released.
+        #[weight = <T as Config>::WeightInfo::release_milestone()]
+        pub fn release_milestone(origin, id: PaymentPlanId, index: u32) {
+            ensure_root(origin)?;
+            let mut plan = Self::payment_plans(id).ok_or(Error::<T>::PaymentPlanNotFound)?;
+            let amount = match &mut plan.schedule {
+                PaymentSchedule::Milestones(milestones) => {
+                    let milestone = milestones
+                        .get_mut(index as usize)
+                        .ok_or(Error::<T>::MilestoneNotFound)?;
+                    ensure!(!milestone.released, Error::<T>::MilestoneAlreadyReleased);
+                    milestone.released = true;
+                    milestone.amount
+                }
+                PaymentSchedule::Stream { .. } => return Err(Error::<T>::MilestoneNotFound.into()),
+            };
+            Self::pay(id, &mut plan, amount)?;
+            Self::store_payment_plan(id, plan);
+        }
+
+        /// Pays out the amount accrued by the streaming payment plan `id` since the last claim.
+        ///
+        /// Only the beneficiary of the plan can claim it.
+        ///
+        /// # Error
+        /// * `PaymentPlanNotFound`: If there is no plan `id`.
+        /// * `NotAStream`: If the plan is paid by milestones.
+        /// * `NotPaymentPlanBeneficiary`: If the caller is not the beneficiary of the plan.
+        /// * `NothingToClaim`: If the stream has accrued nothing since the last claim.
+        #[weight = <T as Config>::WeightInfo::claim_stream()]
+        pub fn claim_stream(origin, id: PaymentPlanId) {
+            let caller_did = Identity::<T>::ensure_perms(origin)?;
+            let mut plan = Self::payment_plans(id).ok_or(Error::<T>::PaymentPlanNotFound)?;
+            ensure!(
+                matches!(plan.schedule, PaymentSchedule::Stream { .. }),
+                Error::<T>::NotAStream
+            );
+            ensure!(plan.beneficiary == caller_did, Error::<T>::NotPaymentPlanBeneficiary);
+
+            let amount = plan.claimable_at(frame_system::Pallet::<T>::block_number());
+            ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+            Self::pay(id, &mut plan, amount)?;
+            Self::store_payment_plan(id, plan);
+        }
+
+        /// Cancels the payment plan `id`, returning its unpaid remainder to the treasury funds.
+        ///
+        /// The amount accrued by a stream and not claimed yet is paid out to the beneficiary first.
+        /// If that payment fails, the accrued amount stays committed to the plan,
+        /// and the beneficiary can still claim it.
+        ///
+        /// # Error
+        /// * `BadOrigin`: Only root can execute transaction.
+        /// * `PaymentPlanNotFound`: If there is no plan `id`.
+        #[weight = <T as Config>::WeightInfo::cancel_payment_plan()]
+        pub fn cancel_payment_plan(origin, id: PaymentPlanId) {
+            ensure_root(origin)?;
+            let mut plan = Self::payment_plans(id).ok_or(Error::<T>::PaymentPlanNotFound)?;
+
+            let accrued = plan.claimable_at(frame_system::Pallet::<T>::block_number());
+            let deferred = if accrued.is_zero() || Self::pay(id, &mut plan, accrued).is_ok() {
+                Zero::zero()
+            } else {
+                Self::deposit_event(RawEvent::PaymentPlanPaymentDeferred(id, accrued));
+                accrued
+            };
+
+            let remainder = plan.remaining().saturating_sub(deferred);
+            CommittedBalance::mutate(|committed| *committed = committed.saturating_sub(remainder));
+            if deferred.is_zero() {
+                Self::remove_payment_plan(id, plan.beneficiary);
+            } else {
+                // Only the deferred amount is left to claim.
+                plan.total = plan.paid.saturating_add(deferred);
+                PaymentPlans::<T>::insert(id, plan);
+            }
+            Self::deposit_event(RawEvent::PaymentPlanCancelled(GC_DID, id, remainder));
+        }
     }
 }
 
@@ -159,7 +379,7 @@ impl<T: Config> Module<T> {
 
         // Ensure treasury has enough balance.
         ensure!(
-            Self::balance() >= total_amount,
+            Self::available_balance() >= total_amount,
             Error::<T>::InsufficientBalance
         );
 
@@ -227,6 +447,86 @@ impl<T: Config> Module<T> {
     pub fn balance() -> BalanceOf<T> {
         T::Currency::free_balance(&Self::account_id())
     }
+
+    /// Returns the balance of the treasury not committed to payment plans.
+    pub fn available_balance() -> BalanceOf<T> {
+        Self::balance().saturating_sub(Self::committed_balance())
+    }
+
+    /// Returns the active payment plans of `beneficiary`.
+    pub fn beneficiary_payment_plans(
+        beneficiary: IdentityId,
+    ) -> Vec<(PaymentPlanId, PaymentPlanOf<T>)> {
+        BeneficiaryPaymentPlans::iter_key_prefix(beneficiary)
+            .filter_map(|id| Some((id, Self::payment_plans(id)?)))
+            .collect()
+    }
+
+    fn base_create_payment_plan(
+        beneficiary: IdentityId,
+        total: Balance,
+        schedule: PaymentSchedule<T::BlockNumber>,
+    ) -> DispatchResult {
+        ensure!(
+            Identity::<T>::get_primary_key(beneficiary).is_some(),
+            Error::<T>::InvalidIdentity
+        );
+        ensure!(
+            Self::available_balance() >= total,
+            Error::<T>::InsufficientBalance
+        );
+
+        let id = PaymentPlanCounter::try_mutate(try_next_post::<T, _>)?;
+        let plan = PaymentPlan {
+            beneficiary,
+            total,
+            paid: Zero::zero(),
+            schedule,
+        };
+        PaymentPlans::<T>::insert(id, plan);
+        BeneficiaryPaymentPlans::insert(beneficiary, id, true);
+        CommittedBalance::mutate(|committed| *committed = committed.saturating_add(total));
+        Self::deposit_event(RawEvent::PaymentPlanCreated(GC_DID, id, beneficiary, total));
+        Ok(())
+    }
+
+    /// Pays `amount` of the payment plan `id` to the primary key of its beneficiary.
+    fn pay(id: PaymentPlanId, plan: &mut PaymentPlanOf<T>, amount: Balance) -> DispatchResult {
+        let primary_key =
+            Identity::<T>::get_primary_key(plan.beneficiary).ok_or(Error::<T>::InvalidIdentity)?;
+        T::Currency::transfer(
+            &Self::account_id(),
+            &primary_key,
+            amount,
+            ExistenceRequirement::AllowDeath,
+        )?;
+
+        plan.paid = plan.paid.saturating_add(amount);
+        CommittedBalance::mutate(|committed| *committed = committed.saturating_sub(amount));
+        Self::deposit_event(RawEvent::PaymentPlanPaid(
+            id,
+            plan.beneficiary,
+            primary_key,
+            amount,
+        ));
+        Ok(())
+    }
+
+    /// Stores the payment plan `id`, or removes it once fully paid out.
+    fn store_payment_plan(id: PaymentPlanId, plan: PaymentPlanOf<T>) {
+        if plan.remaining().is_zero() {
+            Self::remove_payment_plan(id, plan.beneficiary);
+            Self::deposit_event(RawEvent::PaymentPlanCompleted(id));
+        } else {
+            PaymentPlans::<T>::insert(id, plan);
+        }
+    }
+
+    /// Removes the payment plan `id` of `beneficiary`.
+    fn remove_payment_plan(id: PaymentPlanId, beneficiary: IdentityId) {
+        PaymentPlans::<T>::remove(id);
+        BeneficiaryPaymentPlans::remove(beneficiary, id);
+    }
 }
 
 /// That trait implementation is needed to receive a portion of the fees from transactions.
@@ -239,9 +539,3 @@ impl<T: Config> OnUnbalanced<NegativeImbalanceOf<T>> for Module<T> {
         Self::deposit_event(RawEvent::TreasuryReimbursement(current_did, numeric_amount));
     }
 }
-
-impl<T: Config> StorageInfoTrait for Module<T> {
-    fn storage_info() -> Vec<StorageInfo> {
-        Vec::new()
-    }
-}
@@ -26,6 +26,7 @@ use sp_runtime::MultiSignature;
 
 use polymesh_common_utilities::benchs::{user, AccountIdOf, User, UserBuilder};
 use polymesh_common_utilities::traits::TestUtilsFn;
+use polymesh_primitives::Claim;
 
 use super::*;
 
@@ -196,4 +197,103 @@ benchmarks! {
         let alice = user::<T>("Alice", 0);
         let call = Box::new(frame_system::Call::remark { remark: vec![] }.into());
     }: _(alice.origin, index, call)
+
+    // POLYMESH:
+    schedule_call {
+        let alice = UserBuilder::<T>::default().generate_did().build("ALICE");
+        let call = Box::new(frame_system::Call::remark { remark: vec![] }.into());
+        let when = ScheduleTime::Block(10u32.into());
+    }: _(alice.origin, when, call)
+    verify {
+        assert_eq!(ScheduledCallsCount::<T>::get(alice.did()), 1);
+    }
+
+    // POLYMESH:
+    schedule_batch {
+        let c in 1..MAX_CALLS;
+
+        let alice = UserBuilder::<T>::default().generate_did().build("ALICE");
+        let calls = make_calls::<T>(c);
+        let when = ScheduleTime::Block(10u32.into());
+    }: _(alice.origin, when, calls)
+    verify {
+        assert_eq!(ScheduledCallsCount::<T>::get(alice.did()), 1);
+    }
+
+    // POLYMESH:
+    schedule_recurring {
+        let c in 1..MAX_CALLS;
+
+        let alice = UserBuilder::<T>::default().generate_did().build("ALICE");
+        let calls = make_calls::<T>(c);
+        let when = ScheduleTime::Block(10u32.into());
+        let recurrence = Recurrence {
+            interval: Interval::Blocks(T::MinRecurrenceInterval::get()),
+            count: T::MaxRecurrences::get(),
+        };
+    }: _(alice.origin, when, recurrence, calls)
+    verify {
+        assert_eq!(ScheduledCallsCount::<T>::get(alice.did()), 1);
+    }
+
+    // POLYMESH:
+    cancel_scheduled {
+        let alice = UserBuilder::<T>::default().generate_did().build("ALICE");
+        Pallet::<T>::schedule_batch(
+            alice.origin().into(),
+            ScheduleTime::Block(10u32.into()),
+            make_calls::<T>(1),
+        ).unwrap();
+    }: _(alice.origin, 0)
+    verify {
+        assert_eq!(ScheduledCallsCount::<T>::get(alice.did()), 0);
+    }
+
+    // POLYMESH:
+    execute_scheduled {
+        let c in 1..MAX_CALLS;
+
+        let alice = UserBuilder::<T>::default().generate_did().build("ALICE");
+        let recurrence = Recurrence {
+            interval: Interval::Blocks(T::MinRecurrenceInterval::get()),
+            count: T::MaxRecurrences::get(),
+        };
+        Pallet::<T>::schedule_recurring(
+            alice.origin().into(),
+            ScheduleTime::Block(10u32.into()),
+            recurrence,
+            make_calls::<T>(c),
+        ).unwrap();
+        let scheduled = ScheduledCalls::<T>::get(alice.did(), 0).unwrap();
+    }: {
+        Pallet::<T>::execute_scheduled(alice.did(), 0, scheduled);
+    }
+    verify {
+        let scheduled = ScheduledCalls::<T>::get(alice.did(), 0).unwrap();
+        assert_eq!(scheduled.recurrence.unwrap().count, T::MaxRecurrences::get() - 1);
+    }
+
+    // POLYMESH:
+    batch_if {
+        let n in 0..T::MaxBatchConditions::get();
+
+        // Checking a claim is the most expensive condition, as it also checks the claim expiry.
+        let alice = UserBuilder::<T>::default().generate_did().build("ALICE");
+        let issuer = UserBuilder::<T>::default().generate_did().build("ISSUER");
+        let scope = Scope::Identity(alice.did());
+        Identity::<T>::unverified_add_claim_with_scope(
+            alice.did(),
+            Claim::Accredited(scope.clone()),
+            Some(scope.clone()),
+            issuer.did(),
+            Some(u64::MAX.saturated_into()),
+        );
+        let condition = BatchCondition::ClaimPresent {
+            target: alice.did(),
+            claim_type: ClaimType::Accredited,
+            issuer: issuer.did(),
+            scope: Some(scope),
+        };
+        let conditions = vec![condition; n as usize];
+    }: _(alice.origin, conditions, vec![])
 }
@@ -55,13 +55,23 @@
 //!   The whole transaction will rollback and fail if any of the calls failed.
 //! * `force_batch` - Send a batch of dispatch calls. Unlike `batch`, it allows errors and
 //!   won't interrupt.
+//! * `batch_if` - Checks a list of conditions on the chain state, then atomically executes
+//!   a batch of dispatch calls. The whole transaction will fail if any condition is not met.
 //! * `dispatch_as` - Dispatches a function call with a provided origin.
 //! * `with_weight` - Dispatch a function call with a specified weight.
 //!
+//! #### For scheduled dispatch
+//! * `schedule_call` - Schedules a call for a future block or moment.
+//! * `schedule_batch` - Schedules a batch of calls, executed atomically, for a future block or moment.
+//! * `schedule_recurring` - Schedules a batch of calls to be executed periodically.
+//! * `cancel_scheduled` - Cancels a scheduled call of the caller's identity.
+//!
 //! ## POLYMESH
 //! * Removed `as_derivative`.
 //! * Added `relay_tx`.
 //! * Added as deprecated: `batch_old`, `batch_atomic`, `batch_optimistic`.
+//! * Added scheduled and recurring calls, which are dispatched by the key that scheduled them,
+//!   under the identity and permissions of that key at execution time.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -80,15 +90,16 @@ use scale_info::TypeInfo;
 use sp_core::Get;
 use sp_io::hashing::blake2_256;
 use sp_runtime::traits::TrailingZeroInput;
-use sp_runtime::traits::{BadOrigin, Dispatchable};
+use sp_runtime::traits::{BadOrigin, Dispatchable, One, SaturatedConversion, Saturating};
 use sp_runtime::{traits::Verify, DispatchError, RuntimeDebug};
 use sp_std::prelude::*;
 
 use pallet_permissions::with_call_metadata;
 use polymesh_common_utilities::balances::{CheckCdd, Config as BalancesConfig};
 use polymesh_common_utilities::identity::{AuthorizationNonce, Config as IdentityConfig};
-use polymesh_common_utilities::Context;
-use polymesh_primitives::IdentityId;
+use polymesh_common_utilities::{bucket_queue, Context};
+use polymesh_primitives::settlement::{InstructionId, InstructionStatus};
+use polymesh_primitives::{Balance, ClaimType, IdentityId, PortfolioId, Scope, Ticker};
 
 type Identity<T> = pallet_identity::Module<T>;
 
@@ -105,6 +116,12 @@ pub trait WeightInfo {
     fn batch_atomic(c: u32) -> Weight;
     fn batch_optimistic(c: u32) -> Weight;
     fn as_derivative() -> Weight;
+    fn schedule_call() -> Weight;
+    fn schedule_batch(c: u32) -> Weight;
+    fn schedule_recurring(c: u32) -> Weight;
+    fn cancel_scheduled() -> Weight;
+    fn execute_scheduled(c: u32) -> Weight;
+    fn batch_if(n: u32) -> Weight;
 }
 
 // POLYMESH:
@@ -135,6 +152,113 @@ impl<C> UniqueCall<C> {
     }
 }
 
+/// The time at which a scheduled call is executed.
+/// POLYMESH: used for scheduled calls.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum ScheduleTime<BlockNumber, Moment> {
+    /// At the given block.
+    Block(BlockNumber),
+    /// At the first block that starts once the given moment has passed.
+    Moment(Moment),
+}
+
+/// The interval between two executions of a recurring call.
+/// POLYMESH: used for scheduled calls.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum Interval<BlockNumber, Moment> {
+    /// A number of blocks.
+    Blocks(BlockNumber),
+    /// A duration in milliseconds.
+    Millis(Moment),
+}
+
+/// How a scheduled call recurs.
+/// POLYMESH: used for scheduled calls.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Recurrence<BlockNumber, Moment> {
+    /// The interval between two executions.
+    pub interval: Interval<BlockNumber, Moment>,
+    /// The number of executions left.
+    pub count: u32,
+}
+
+/// A call, or a batch of calls, scheduled by an identity.
+/// POLYMESH: used for scheduled calls.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ScheduledCall<AccountId, BlockNumber, Moment, C> {
+    /// The key which scheduled the calls, and from which they are dispatched.
+    pub caller: AccountId,
+    /// The calls, executed atomically.
+    pub calls: Vec<C>,
+    /// The time of the next execution.
+    pub next: ScheduleTime<BlockNumber, Moment>,
+    /// The recurrence of the calls, if any.
+    pub recurrence: Option<Recurrence<BlockNumber, Moment>>,
+    /// The block of `ScheduledExecutions` in which the next execution is queued.
+    pub queued_at: u64,
+}
+
+/// POLYMESH: the `ScheduledCall` of a runtime.
+pub type ScheduledCallOf<T> = ScheduledCall<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::BlockNumber,
+    <T as pallet_timestamp::Config>::Moment,
+    <T as Config>::RuntimeCall,
+>;
+
+/// POLYMESH: the `ScheduleTime` of a runtime.
+pub type ScheduleTimeOf<T> =
+    ScheduleTime<<T as frame_system::Config>::BlockNumber, <T as pallet_timestamp::Config>::Moment>;
+
+/// POLYMESH: the `Recurrence` of a runtime.
+pub type RecurrenceOf<T> =
+    Recurrence<<T as frame_system::Config>::BlockNumber, <T as pallet_timestamp::Config>::Moment>;
+
+/// A condition on the chain state, checked before dispatching the calls of `batch_if`.
+/// POLYMESH: used for `batch_if`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum BatchCondition<AccountId, BlockNumber> {
+    /// The free POLYX balance of `account` is at least `amount`.
+    PolyxBalanceAtLeast { account: AccountId, amount: Balance },
+    /// The unlocked balance of `ticker` in `portfolio` is at least `amount`.
+    PortfolioBalanceAtLeast {
+        portfolio: PortfolioId,
+        ticker: Ticker,
+        amount: Balance,
+    },
+    /// The status of the settlement instruction `id` is `status`.
+    InstructionStatusIs {
+        id: InstructionId,
+        status: InstructionStatus<BlockNumber>,
+    },
+    /// `target` has a non-expired claim of `claim_type` in `scope`, issued by `issuer`.
+    ClaimPresent {
+        target: IdentityId,
+        claim_type: ClaimType,
+        issuer: IdentityId,
+        scope: Option<Scope>,
+    },
+}
+
+/// POLYMESH: the `BatchCondition` of a runtime.
+pub type BatchConditionOf<T> = BatchCondition<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::BlockNumber,
+>;
+
+/// Checks `BatchCondition`s against the state of the pallets they refer to.
+/// POLYMESH: used for `batch_if`.
+pub trait ConditionChecker<AccountId, BlockNumber> {
+    /// Returns `true` if `condition` holds.
+    fn check(condition: &BatchCondition<AccountId, BlockNumber>) -> bool;
+}
+
+impl<AccountId, BlockNumber> ConditionChecker<AccountId, BlockNumber> for () {
+    fn check(_condition: &BatchCondition<AccountId, BlockNumber>) -> bool {
+        false
+    }
+}
+
 pub use pallet::*;
 
 #[frame_support::pallet]
@@ -172,6 +296,42 @@ pub mod pallet {
 
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
+
+        /// Maximum number of pending scheduled calls per identity.
+        /// POLYMESH: added.
+        #[pallet::constant]
+        type MaxScheduledCallsPerIdentity: Get<u32>;
+
+        /// Minimum interval, in blocks, between two executions of a recurring call.
+        /// POLYMESH: added.
+        #[pallet::constant]
+        type MinRecurrenceInterval: Get<Self::BlockNumber>;
+
+        /// Maximum number of executions of a recurring call, which are all paid when scheduling it.
+        /// POLYMESH: added.
+        #[pallet::constant]
+        type MaxRecurrences: Get<u32>;
+
+        /// Maximum number of scheduled executions processed in a block.
+        /// Executions beyond it are delayed to the following blocks.
+        /// POLYMESH: added.
+        #[pallet::constant]
+        type MaxScheduledExecutionsPerBlock: Get<u32>;
+
+        /// Maximum weight of the scheduled executions of a block.
+        /// Executions beyond it are delayed to the following blocks, except the first one of a block.
+        /// POLYMESH: added.
+        #[pallet::constant]
+        type MaxScheduledExecutionsWeight: Get<Weight>;
+
+        /// Checks the conditions of `batch_if`.
+        /// POLYMESH: added.
+        type ConditionChecker: ConditionChecker<Self::AccountId, Self::BlockNumber>;
+
+        /// Maximum number of conditions of `batch_if`.
+        /// POLYMESH: added.
+        #[pallet::constant]
+        type MaxBatchConditions: Get<u32>;
     }
 
     #[pallet::event]
@@ -211,6 +371,24 @@ pub mod pallet {
         /// Includes a vector of event counts for each dispatch.
         /// POLYMESH: event deprecated.
         BatchCompletedOld(EventCounts),
+        /// A call, or a batch of calls, was scheduled.
+        /// POLYMESH: event.
+        CallScheduled {
+            caller_did: IdentityId,
+            id: u64,
+            when: ScheduleTimeOf<T>,
+            recurrence: Option<RecurrenceOf<T>>,
+        },
+        /// A scheduled call was cancelled.
+        /// POLYMESH: event.
+        ScheduledCallCancelled { caller_did: IdentityId, id: u64 },
+        /// A scheduled call was executed.
+        /// POLYMESH: event.
+        ScheduledCallExecuted {
+            caller_did: IdentityId,
+            id: u64,
+            result: DispatchResult,
+        },
     }
 
     // Align the call size to 1KB. As we are currently compiling the runtime for native/wasm
@@ -237,6 +415,10 @@ pub mod pallet {
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            Self::execute_due_calls(n)
+        }
+
         fn integrity_test() {
             // If you hit this error, you need to try to `Box` big dispatchable parameters.
             assert!(
@@ -264,6 +446,30 @@ pub mod pallet {
         InvalidNonce,
         /// Decoding derivative account Id failed.
         UnableToDeriveAccountId,
+        /// The time of a scheduled call is not in the future.
+        /// POLYMESH error
+        ScheduleInPast,
+        /// There are no calls to schedule.
+        /// POLYMESH error
+        NoCallsToSchedule,
+        /// The identity has too many pending scheduled calls.
+        /// POLYMESH error
+        TooManyScheduledCalls,
+        /// The recurrence interval is below the minimum, or its count is zero or above the maximum.
+        /// POLYMESH error
+        InvalidRecurrence,
+        /// The scheduled call does not exist.
+        /// POLYMESH error
+        ScheduledCallNotFound,
+        /// The key which scheduled the call no longer belongs to the identity.
+        /// POLYMESH error
+        SchedulingKeyRemoved,
+        /// Too many conditions.
+        /// POLYMESH error
+        TooManyConditions,
+        /// A condition of the batch is not met.
+        /// POLYMESH error
+        ConditionNotMet,
     }
 
     /// Nonce for `relay_tx`.
@@ -273,6 +479,48 @@ pub mod pallet {
     pub(super) type Nonces<T: Config> =
         StorageMap<_, Twox64Concat, T::AccountId, AuthorizationNonce, ValueQuery>;
 
+    /// Pending scheduled calls of an identity, by id.
+    /// POLYMESH: added.
+    #[pallet::storage]
+    #[pallet::getter(fn scheduled_calls)]
+    #[pallet::unbounded]
+    pub type ScheduledCalls<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        IdentityId,
+        Twox64Concat,
+        u64,
+        ScheduledCallOf<T>,
+        OptionQuery,
+    >;
+
+    /// Number of pending scheduled calls of an identity.
+    /// POLYMESH: added.
+    #[pallet::storage]
+    #[pallet::getter(fn scheduled_calls_count)]
+    pub type ScheduledCallsCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, IdentityId, u32, ValueQuery>;
+
+    /// The id of the next scheduled call of an identity.
+    /// POLYMESH: added.
+    #[pallet::storage]
+    #[pallet::getter(fn next_scheduled_call_id)]
+    pub type NextScheduledCallId<T: Config> =
+        StorageMap<_, Blake2_128Concat, IdentityId, u64, ValueQuery>;
+
+    /// Queue of the next executions of scheduled calls, by the block they are due in.
+    /// POLYMESH: added.
+    #[pallet::storage]
+    #[pallet::getter(fn scheduled_executions)]
+    pub type ScheduledExecutions<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, u64, Twox64Concat, (IdentityId, u64), (), OptionQuery>;
+
+    /// The earliest block of `ScheduledExecutions` that may hold executions.
+    /// POLYMESH: added.
+    #[pallet::storage]
+    #[pallet::getter(fn next_scheduled_execution_block)]
+    pub type NextScheduledExecutionBlock<T: Config> = StorageValue<_, u64, OptionQuery>;
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Send a batch of dispatch calls.
@@ -812,6 +1060,181 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             Self::base_as_derivative(origin, index, call)
         }
+
+        /// Schedules `call` to be dispatched at `when` from the sender's key.
+        ///
+        /// The call is dispatched under the identity and permissions of the sender's key
+        /// at execution time. A call scheduled at a moment is executed in the first block
+        /// which starts after that moment. The weight of the execution is paid when scheduling.
+        /// Executions are delayed to the following blocks when too many of them are due in a block.
+        ///
+        /// # Errors
+        /// - `ScheduleInPast` if `when` is not in the future.
+        /// - `TooManyScheduledCalls` if the sender's identity has too many pending scheduled calls.
+        ///
+        /// POLYMESH: added.
+        #[pallet::call_index(10)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::schedule_call()
+                .saturating_add(Pallet::<T>::executions_weight(sp_std::iter::once(&**call), 1))
+        )]
+        pub fn schedule_call(
+            origin: OriginFor<T>,
+            when: ScheduleTimeOf<T>,
+            call: Box<<T as Config>::RuntimeCall>,
+        ) -> DispatchResult {
+            Self::base_schedule(origin, when, None, sp_std::vec![*call])
+        }
+
+        /// Schedules a batch of `calls` to be atomically dispatched at `when` from the sender's key.
+        ///
+        /// See `schedule_call`. If any of the calls fails, the whole batch is rolled back.
+        ///
+        /// # Errors
+        /// - `NoCallsToSchedule` if `calls` is empty.
+        /// - `TooManyCalls` if `calls` exceeds the `batched_calls_limit`.
+        /// - `ScheduleInPast` if `when` is not in the future.
+        /// - `TooManyScheduledCalls` if the sender's identity has too many pending scheduled calls.
+        ///
+        /// POLYMESH: added.
+        #[pallet::call_index(11)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::schedule_batch(calls.len() as u32)
+                .saturating_add(Pallet::<T>::executions_weight(calls.iter(), 1))
+        )]
+        pub fn schedule_batch(
+            origin: OriginFor<T>,
+            when: ScheduleTimeOf<T>,
+            calls: Vec<<T as Config>::RuntimeCall>,
+        ) -> DispatchResult {
+            Self::base_schedule(origin, when, None, calls)
+        }
+
+        /// Schedules a batch of `calls` to be atomically dispatched at `when` from the sender's key,
+        /// and then again after every interval of `recurrence`, until `recurrence.count` executions
+        /// were made or the scheduled call is cancelled.
+        ///
+        /// A failed execution does not stop the recurrence.
+        /// The weight of all the executions is paid when scheduling.
+        ///
+        /// # Errors
+        /// - `InvalidRecurrence` if the interval is below `MinRecurrenceInterval`,
+        ///   or if the count is zero or above `MaxRecurrences`.
+        /// - See `schedule_batch` for other errors.
+        ///
+        /// POLYMESH: added.
+        #[pallet::call_index(12)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::schedule_recurring(calls.len() as u32)
+                .saturating_add(Pallet::<T>::executions_weight(calls.iter(), recurrence.count))
+        )]
+        pub fn schedule_recurring(
+            origin: OriginFor<T>,
+            when: ScheduleTimeOf<T>,
+            recurrence: RecurrenceOf<T>,
+            calls: Vec<<T as Config>::RuntimeCall>,
+        ) -> DispatchResult {
+            Self::base_schedule(origin, when, Some(recurrence), calls)
+        }
+
+        /// Cancels the pending scheduled call `id` of the sender's identity.
+        ///
+        /// # Errors
+        /// - `ScheduledCallNotFound` if the identity has no pending scheduled call `id`.
+        ///
+        /// POLYMESH: added.
+        #[pallet::call_index(13)]
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_scheduled())]
+        pub fn cancel_scheduled(origin: OriginFor<T>, id: u64) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            let caller_did = CallPermissions::<T>::ensure_call_permissions(&sender)?.primary_did;
+            let scheduled = ScheduledCalls::<T>::get(caller_did, id)
+                .ok_or(Error::<T>::ScheduledCallNotFound)?;
+
+            ScheduledExecutions::<T>::remove(scheduled.queued_at, (caller_did, id));
+            Self::remove_scheduled(caller_did, id);
+            Self::deposit_event(Event::<T>::ScheduledCallCancelled { caller_did, id });
+            Ok(())
+        }
+
+        /// Checks that all `conditions` hold, then sends a batch of dispatch calls and atomically
+        /// executes them, as `batch_all` does.
+        ///
+        /// The conditions are checked in order, before any call is dispatched. If any of them
+        /// does not hold, the whole transaction fails, which lets callers abort when the state
+        /// they relied on has changed.
+        ///
+        /// May be called from any origin except `None`.
+        ///
+        /// - `conditions`: The conditions to check. Their number must not exceed the constant
+        ///   `MaxBatchConditions`.
+        /// - `calls`: The calls to be dispatched from the same origin. The number of call must not
+        ///   exceed the constant: `batched_calls_limit` (available in constant metadata).
+        ///
+        /// # Errors
+        /// - `TooManyConditions` if there are more than `MaxBatchConditions` conditions.
+        /// - `ConditionNotMet` if a condition does not hold.
+        ///
+        /// POLYMESH: added.
+        #[pallet::call_index(15)]
+        #[pallet::weight({
+                let dispatch_infos = calls.iter().map(|call| call.get_dispatch_info()).collect::<Vec<_>>();
+                let dispatch_weight = dispatch_infos.iter()
+                    .map(|di| di.weight)
+                    .fold(Weight::zero(), |total: Weight, weight: Weight| total.saturating_add(weight))
+                    .saturating_add(<T as Config>::WeightInfo::batch_all(calls.len() as u32))
+                    .saturating_add(<T as Config>::WeightInfo::batch_if(conditions.len() as u32));
+                let dispatch_class = {
+                    let all_operational = dispatch_infos.iter()
+                        .map(|di| di.class)
+                        .all(|class| class == DispatchClass::Operational);
+                    if all_operational {
+                        DispatchClass::Operational
+                    } else {
+                        DispatchClass::Normal
+                    }
+                };
+                (dispatch_weight, dispatch_class)
+            })]
+        pub fn batch_if(
+            origin: OriginFor<T>,
+            conditions: Vec<BatchConditionOf<T>>,
+            calls: Vec<<T as Config>::RuntimeCall>,
+        ) -> DispatchResultWithPostInfo {
+            // Do not allow the `None` origin.
+            if ensure_none(origin.clone()).is_ok() {
+                return Err(BadOrigin.into());
+            }
+
+            ensure!(
+                conditions.len() <= T::MaxBatchConditions::get() as usize,
+                Error::<T>::TooManyConditions
+            );
+            let conditions_weight = <T as Config>::WeightInfo::batch_if(conditions.len() as u32);
+            ensure!(
+                conditions.iter().all(T::ConditionChecker::check),
+                DispatchErrorWithPostInfo {
+                    post_info: Some(conditions_weight).into(),
+                    error: Error::<T>::ConditionNotMet.into(),
+                }
+            );
+
+            match Self::batch_all(origin, calls) {
+                Ok(mut info) => {
+                    info.actual_weight = info
+                        .actual_weight
+                        .map(|w| w.saturating_add(conditions_weight));
+                    Ok(info)
+                }
+                Err(mut err) => {
+                    err.post_info.actual_weight = err
+                        .post_info
+                        .actual_weight
+                        .map(|w| w.saturating_add(conditions_weight));
+                    Err(err)
+                }
+            }
+        }
     }
 }
 
@@ -971,6 +1394,254 @@ impl<T: Config> Pallet<T> {
             .map_err(|_| Error::<T>::UnableToDeriveAccountId.into())
     }
 
+    fn base_schedule(
+        origin: T::RuntimeOrigin,
+        when: ScheduleTimeOf<T>,
+        recurrence: Option<RecurrenceOf<T>>,
+        calls: Vec<<T as Config>::RuntimeCall>,
+    ) -> DispatchResult {
+        let caller = ensure_signed(origin)?;
+        let caller_did = CallPermissions::<T>::ensure_call_permissions(&caller)?.primary_did;
+
+        ensure!(!calls.is_empty(), Error::<T>::NoCallsToSchedule);
+        ensure!(
+            calls.len() <= Self::batched_calls_limit() as usize,
+            Error::<T>::TooManyCalls
+        );
+        let in_future = match when {
+            ScheduleTime::Block(at) => at > System::<T>::block_number(),
+            ScheduleTime::Moment(at) => at > pallet_timestamp::Pallet::<T>::get(),
+        };
+        ensure!(in_future, Error::<T>::ScheduleInPast);
+        if let Some(recurrence) = &recurrence {
+            let min_interval = T::MinRecurrenceInterval::get().max(One::one());
+            let interval_ok = match recurrence.interval {
+                Interval::Blocks(blocks) => blocks >= min_interval,
+                Interval::Millis(millis) => {
+                    millis.saturated_into::<u64>()
+                        >= Self::expected_block_time()
+                            .saturating_mul(min_interval.saturated_into::<u64>())
+                }
+            };
+            ensure!(
+                interval_ok && (1..=T::MaxRecurrences::get()).contains(&recurrence.count),
+                Error::<T>::InvalidRecurrence
+            );
+        }
+        let count = ScheduledCallsCount::<T>::get(caller_did);
+        ensure!(
+            count < T::MaxScheduledCallsPerIdentity::get(),
+            Error::<T>::TooManyScheduledCalls
+        );
+
+        let id = NextScheduledCallId::<T>::get(caller_did);
+        let mut scheduled = ScheduledCall {
+            caller,
+            calls,
+            next: when,
+            recurrence,
+            queued_at: 0,
+        };
+        Self::queue_execution(caller_did, id, &mut scheduled);
+
+        ScheduledCalls::<T>::insert(caller_did, id, scheduled);
+        ScheduledCallsCount::<T>::insert(caller_did, count.saturating_add(1));
+        NextScheduledCallId::<T>::insert(caller_did, id.saturating_add(1));
+        Self::deposit_event(Event::<T>::CallScheduled {
+            caller_did,
+            id,
+            when,
+            recurrence,
+        });
+        Ok(())
+    }
+
+    /// Returns the weight of `executions` executions of the scheduled `calls`,
+    /// which is paid when scheduling them.
+    pub fn executions_weight<'a>(
+        calls: impl Iterator<Item = &'a <T as Config>::RuntimeCall>,
+        executions: u32,
+    ) -> Weight {
+        let (len, calls_weight) = calls.fold((0u32, Weight::zero()), |(len, total), call| {
+            (
+                len.saturating_add(1),
+                total.saturating_add(call.get_dispatch_info().weight),
+            )
+        });
+        <T as Config>::WeightInfo::execute_scheduled(len)
+            .saturating_add(calls_weight)
+            .saturating_mul(executions.into())
+    }
+
+    /// Returns the expected time between two blocks, in milliseconds.
+    fn expected_block_time() -> u64 {
+        <T as pallet_timestamp::Config>::MinimumPeriod::get()
+            .saturating_mul(2u32.into())
+            .saturated_into::<u64>()
+            .max(1)
+    }
+
+    /// Queues the next execution of `scheduled` in `ScheduledExecutions`.
+    ///
+    /// A call scheduled at a moment is queued at the block expected to start after that moment,
+    /// and queued again if that block started too early.
+    fn queue_execution(caller_did: IdentityId, id: u64, scheduled: &mut ScheduledCallOf<T>) {
+        let now = System::<T>::block_number();
+        let at = match scheduled.next {
+            ScheduleTime::Block(at) => at,
+            ScheduleTime::Moment(at) => {
+                // Estimate the block of `at` from the expected block time.
+                let block_time = Self::expected_block_time();
+                let millis = at
+                    .saturating_sub(pallet_timestamp::Pallet::<T>::get())
+                    .saturated_into::<u64>();
+                let blocks =
+                    (millis.saturating_add(block_time - 1) / block_time).saturated_into::<u32>();
+                now.saturating_add(blocks.into())
+            }
+        };
+        scheduled.queued_at =
+            bucket_queue::queue::<ScheduledExecutions<T>, NextScheduledExecutionBlock<T>, _, _>(
+                at.max(now.saturating_add(One::one())).saturated_into(),
+                Self::next_scheduled_execution_block().unwrap_or_default(),
+                (caller_did, id),
+                (),
+            );
+    }
+
+    /// Executes the scheduled calls due by block `now`.
+    ///
+    /// At most `MaxScheduledExecutionsPerBlock` queue entries are processed, and executions stop
+    /// once their weight would exceed `MaxScheduledExecutionsWeight`.
+    /// The remaining executions are processed in the following blocks.
+    fn execute_due_calls(now: T::BlockNumber) -> Weight {
+        let db = <T as frame_system::Config>::DbWeight::get();
+        let ready_until = now.saturated_into::<u64>().saturating_add(1);
+        let max_weight = T::MaxScheduledExecutionsWeight::get();
+        let mut executions_weight = Weight::zero();
+        let mut weight = db.reads(1);
+        for _ in 0..T::MaxScheduledExecutionsPerBlock::get() {
+            let (_, due) =
+                bucket_queue::take::<ScheduledExecutions<T>, NextScheduledExecutionBlock<T>, _, _>(
+                    ready_until,
+                    1,
+                );
+            weight = weight.saturating_add(db.reads_writes(2, 2));
+            let (caller_did, id) = match due.into_iter().next() {
+                Some((key, ())) => key,
+                // The block at the cursor was empty, move on to the next one if it is due.
+                None if Self::next_scheduled_execution_block()
+                    .map_or(false, |next| next < ready_until) =>
+                {
+                    continue
+                }
+                None => break,
+            };
+            // Cancelled scheduled calls are removed from the queue.
+            let mut scheduled = match ScheduledCalls::<T>::get(caller_did, id) {
+                Some(scheduled) => scheduled,
+                None => continue,
+            };
+            let execution_weight = Self::executions_weight(scheduled.calls.iter(), 1);
+            if !executions_weight.is_zero()
+                && executions_weight
+                    .saturating_add(execution_weight)
+                    .any_gt(max_weight)
+            {
+                // Delay the execution to the next block.
+                Self::queue_execution(caller_did, id, &mut scheduled);
+                ScheduledCalls::<T>::insert(caller_did, id, scheduled);
+                break;
+            }
+            executions_weight = executions_weight.saturating_add(execution_weight);
+            Self::execute_scheduled(caller_did, id, scheduled);
+        }
+        weight.saturating_add(executions_weight)
+    }
+
+    /// Executes the due scheduled call `id` of `caller_did`, then queues its next execution, if any.
+    fn execute_scheduled(caller_did: IdentityId, id: u64, mut scheduled: ScheduledCallOf<T>) {
+        // The moment was not reached yet, try again later.
+        if let ScheduleTime::Moment(at) = scheduled.next {
+            if at > pallet_timestamp::Pallet::<T>::get() {
+                return Self::reschedule(caller_did, id, scheduled);
+            }
+        }
+
+        let key_removed = Identity::<T>::get_identity(&scheduled.caller) != Some(caller_did);
+        let result = if key_removed {
+            Err(Error::<T>::SchedulingKeyRemoved.into())
+        } else {
+            Self::dispatch_scheduled(caller_did, &scheduled)
+        };
+        Self::deposit_event(Event::<T>::ScheduledCallExecuted {
+            caller_did,
+            id,
+            result,
+        });
+
+        // The calls may have cancelled their own schedule.
+        if !ScheduledCalls::<T>::contains_key(caller_did, id) {
+            return;
+        }
+        match scheduled.recurrence.as_mut() {
+            Some(recurrence) if !key_removed && recurrence.count > 1 => {
+                recurrence.count = recurrence.count.saturating_sub(1);
+                scheduled.next = match recurrence.interval {
+                    Interval::Blocks(blocks) => {
+                        let last = match scheduled.next {
+                            ScheduleTime::Block(at) => at,
+                            ScheduleTime::Moment(_) => System::<T>::block_number(),
+                        };
+                        ScheduleTime::Block(last.saturating_add(blocks))
+                    }
+                    Interval::Millis(millis) => {
+                        let last = match scheduled.next {
+                            ScheduleTime::Moment(at) => at,
+                            ScheduleTime::Block(_) => pallet_timestamp::Pallet::<T>::get(),
+                        };
+                        ScheduleTime::Moment(last.saturating_add(millis))
+                    }
+                };
+                Self::reschedule(caller_did, id, scheduled)
+            }
+            _ => Self::remove_scheduled(caller_did, id),
+        }
+    }
+
+    /// Queues the next execution of `scheduled`.
+    fn reschedule(caller_did: IdentityId, id: u64, mut scheduled: ScheduledCallOf<T>) {
+        Self::queue_execution(caller_did, id, &mut scheduled);
+        ScheduledCalls::<T>::insert(caller_did, id, scheduled);
+    }
+
+    /// Atomically dispatches the calls of `scheduled` from its caller key.
+    fn dispatch_scheduled(
+        caller_did: IdentityId,
+        scheduled: &ScheduledCallOf<T>,
+    ) -> DispatchResult {
+        with_transaction(|| -> TransactionOutcome<DispatchResult> {
+            for call in scheduled.calls.iter().cloned() {
+                let result = Self::run_with_temporary_did_and_payer(
+                    RawOrigin::Signed(scheduled.caller.clone()).into(),
+                    Some(scheduled.caller.clone()),
+                    Some(caller_did),
+                    Box::new(call),
+                    false,
+                );
+                if let Err(e) = result {
+                    return TransactionOutcome::Rollback(Err(e.error));
+                }
+            }
+            TransactionOutcome::Commit(Ok(()))
+        })
+    }
+
+    fn remove_scheduled(caller_did: IdentityId, id: u64) {
+        ScheduledCalls::<T>::remove(caller_did, id);
+        ScheduledCallsCount::<T>::mutate(caller_did, |count| *count = count.saturating_sub(1));
+    }
+
     /// Dispatches `call` Setting CurrentDid and CurrentPayer to `did` and `account_id`.
     /// The values are reset once the call is done.
     fn run_with_temporary_did_and_payer(
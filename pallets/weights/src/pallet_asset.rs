@@ -695,4 +695,19 @@ impl pallet_asset::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().reads(1))
             .saturating_add(DbWeight::get().writes(1))
     }
+    // Estimated weights.
+    // The calls below have not been benchmarked on the reference hardware yet.
+    // Their weights are estimated from the storage they access and from similar calls,
+    // and have to be regenerated with the benchmark CLI.
+    fn set_fee_asset_rate() -> Weight {
+        Weight::from_ref_time(18_051_000).saturating_add(DbWeight::get().writes(1))
+    }
+    fn set_fee_collector() -> Weight {
+        Weight::from_ref_time(16_503_000).saturating_add(DbWeight::get().writes(1))
+    }
+    fn set_fee_payment_asset() -> Weight {
+        Weight::from_ref_time(42_873_000)
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().writes(1))
+    }
 }
@@ -129,4 +129,31 @@ impl pallet_balances::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().reads(3))
             .saturating_add(DbWeight::get().writes(1))
     }
+    // Estimated weights.
+    // The calls below have not been benchmarked on the reference hardware yet.
+    // Their weights are estimated from the storage they access and from similar calls,
+    // and have to be regenerated with the benchmark CLI.
+    fn vested_transfer() -> Weight {
+        Weight::from_ref_time(151_204_000)
+            .saturating_add(DbWeight::get().reads(12))
+            .saturating_add(DbWeight::get().writes(6))
+    }
+    fn vest(n: u32) -> Weight {
+        Weight::from_ref_time(63_540_000)
+            .saturating_add(Weight::from_ref_time(1_950_000).saturating_mul(n.into()))
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().writes(4))
+    }
+    fn revoke_vesting(n: u32) -> Weight {
+        Weight::from_ref_time(86_019_000)
+            .saturating_add(Weight::from_ref_time(2_100_000).saturating_mul(n.into()))
+            .saturating_add(DbWeight::get().reads(7))
+            .saturating_add(DbWeight::get().writes(5))
+    }
+    fn reject_vesting(n: u32) -> Weight {
+        Weight::from_ref_time(86_019_000)
+            .saturating_add(Weight::from_ref_time(2_100_000).saturating_mul(n.into()))
+            .saturating_add(DbWeight::get().reads(7))
+            .saturating_add(DbWeight::get().writes(5))
+    }
 }
@@ -419,4 +419,130 @@ impl pallet_identity::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().reads(3))
             .saturating_add(DbWeight::get().writes(3))
     }
+    // Estimated weights.
+    // The calls below have not been benchmarked on the reference hardware yet.
+    // Their weights are estimated from the storage they access and from similar calls,
+    // and have to be regenerated with the benchmark CLI.
+    fn disclose_claim() -> Weight {
+        Weight::from_ref_time(47_391_000)
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().writes(2))
+    }
+    fn update_credential_signers(n: u32) -> Weight {
+        Weight::from_ref_time(30_118_000)
+            .saturating_add(Weight::from_ref_time(5_904_227).saturating_mul(n.into()))
+            .saturating_add(DbWeight::get().reads(1))
+            .saturating_add(DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+            .saturating_add(DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+    }
+    fn import_claim_credential() -> Weight {
+        Weight::from_ref_time(125_902_000)
+            .saturating_add(DbWeight::get().reads(10))
+            .saturating_add(DbWeight::get().writes(3))
+    }
+    fn set_recovery_config(n: u32) -> Weight {
+        Weight::from_ref_time(40_503_119)
+            .saturating_add(Weight::from_ref_time(312_847).saturating_mul(n.into()))
+            .saturating_add(DbWeight::get().reads(2))
+            .saturating_add(DbWeight::get().writes(2))
+    }
+    fn remove_recovery_config() -> Weight {
+        Weight::from_ref_time(41_265_000)
+            .saturating_add(DbWeight::get().reads(3))
+            .saturating_add(DbWeight::get().writes(2))
+    }
+    fn approve_recovery() -> Weight {
+        Weight::from_ref_time(54_391_000)
+            .saturating_add(DbWeight::get().reads(5))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    fn cancel_recovery() -> Weight {
+        Weight::from_ref_time(34_780_000)
+            .saturating_add(DbWeight::get().reads(2))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    fn execute_recovery() -> Weight {
+        Weight::from_ref_time(73_448_000)
+            .saturating_add(DbWeight::get().reads(6))
+            .saturating_add(DbWeight::get().writes(6))
+    }
+    fn set_secondary_key_limits(n: u32) -> Weight {
+        Weight::from_ref_time(44_270_512)
+            .saturating_add(Weight::from_ref_time(96_318).saturating_mul(n.into()))
+            .saturating_add(DbWeight::get().reads(3))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    fn add_session_key() -> Weight {
+        Weight::from_ref_time(95_163_000)
+            .saturating_add(DbWeight::get().reads(5))
+            .saturating_add(DbWeight::get().writes(5))
+    }
+    fn expire_session_keys(n: u32) -> Weight {
+        Weight::from_ref_time(7_032_418)
+            .saturating_add(Weight::from_ref_time(22_480_613).saturating_mul(n.into()))
+            .saturating_add(DbWeight::get().reads(1))
+            .saturating_add(DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+            .saturating_add(DbWeight::get().writes(1))
+            .saturating_add(DbWeight::get().writes((4_u64).saturating_mul(n.into())))
+    }
+    fn prune_expired_authorizations(n: u32) -> Weight {
+        Weight::from_ref_time(9_126_705)
+            .saturating_add(Weight::from_ref_time(16_372_940).saturating_mul(n.into()))
+            .saturating_add(DbWeight::get().reads(3))
+            .saturating_add(DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+            .saturating_add(DbWeight::get().writes(1))
+            .saturating_add(DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+    }
+    fn set_cdd_lifecycle_config() -> Weight {
+        Weight::from_ref_time(9_804_000).saturating_add(DbWeight::get().writes(1))
+    }
+    fn request_cdd_renewal() -> Weight {
+        Weight::from_ref_time(22_740_000)
+            .saturating_add(DbWeight::get().reads(3))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    fn renew_cdd() -> Weight {
+        Weight::from_ref_time(50_391_000)
+            .saturating_add(DbWeight::get().reads(9))
+            .saturating_add(DbWeight::get().writes(6))
+    }
+    fn notify_cdd_expiries(n: u32) -> Weight {
+        Weight::from_ref_time(9_387_214)
+            .saturating_add(Weight::from_ref_time(12_806_331).saturating_mul(n.into()))
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+            .saturating_add(DbWeight::get().writes(1))
+            .saturating_add(DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+    }
+    fn prune_cdd_expiries(n: u32) -> Weight {
+        Weight::from_ref_time(8_013_870)
+            .saturating_add(Weight::from_ref_time(4_381_502).saturating_mul(n.into()))
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+            .saturating_add(DbWeight::get().writes(1))
+            .saturating_add(DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+    }
+    fn set_parent_permissions() -> Weight {
+        Weight::from_ref_time(20_512_000)
+            .saturating_add(DbWeight::get().reads(2))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    fn call_as_child() -> Weight {
+        Weight::from_ref_time(43_205_000)
+            .saturating_add(DbWeight::get().reads(11))
+            .saturating_add(DbWeight::get().writes(6))
+    }
+    fn import_identity_export(n: u32) -> Weight {
+        Weight::from_ref_time(146_730_512)
+            .saturating_add(Weight::from_ref_time(38_615_207).saturating_mul(n.into()))
+            .saturating_add(DbWeight::get().reads(7))
+            .saturating_add(DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+            .saturating_add(DbWeight::get().writes(1))
+            .saturating_add(DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+    }
+    fn set_export_attester() -> Weight {
+        Weight::from_ref_time(18_204_000)
+            .saturating_add(DbWeight::get().reads(1))
+            .saturating_add(DbWeight::get().writes(1))
+    }
 }
@@ -522,4 +522,45 @@ impl pallet_multisig::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().reads(4))
             .saturating_add(DbWeight::get().writes(1))
     }
+    // Estimated weights.
+    // The calls below have not been benchmarked on the reference hardware yet.
+    // Their weights are estimated from the storage they access and from similar calls,
+    // and have to be regenerated with the benchmark CLI.
+    fn set_signer_weight() -> Weight {
+        Weight::from_ref_time(42_310_000)
+            .saturating_add(DbWeight::get().reads(7))
+            .saturating_add(DbWeight::get().writes(2))
+    }
+    fn set_threshold_rules(r: u32) -> Weight {
+        Weight::from_ref_time(36_120_000)
+            .saturating_add(Weight::from_ref_time(1_846_220).saturating_mul(r.into()))
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    fn set_execution_delay() -> Weight {
+        Weight::from_ref_time(32_117_000)
+            .saturating_add(DbWeight::get().reads(1))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    fn set_guardian() -> Weight {
+        Weight::from_ref_time(39_904_000)
+            .saturating_add(DbWeight::get().reads(2))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    fn veto_proposal() -> Weight {
+        Weight::from_ref_time(77_352_000)
+            .saturating_add(DbWeight::get().reads(7))
+            .saturating_add(DbWeight::get().writes(3))
+    }
+    fn set_proposal_retention_period() -> Weight {
+        Weight::from_ref_time(13_301_000).saturating_add(DbWeight::get().writes(1))
+    }
+    fn prune_proposals(n: u32) -> Weight {
+        Weight::from_ref_time(11_263_000)
+            .saturating_add(Weight::from_ref_time(34_118_761).saturating_mul(n.into()))
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+            .saturating_add(DbWeight::get().writes(1))
+            .saturating_add(DbWeight::get().writes((5_u64).saturating_mul(n.into())))
+    }
 }
@@ -117,6 +117,8 @@ impl pallet_nft::WeightInfo for SubstrateWeight {
     // Proof Skipped: NFT CollectionKeys (max_values: None, max_size: None, mode: Measured)
     // Storage: NFT NFTsInCollection (r:1 w:1)
     // Proof Skipped: NFT NFTsInCollection (max_values: None, max_size: None, mode: Measured)
+    // Storage: NFT CollectionMaxSupply (r:1 w:0)
+    // Proof Skipped: NFT CollectionMaxSupply (max_values: None, max_size: None, mode: Measured)
     // Storage: NFT NumberOfNFTs (r:1 w:1)
     // Proof Skipped: NFT NumberOfNFTs (max_values: None, max_size: None, mode: Measured)
     // Storage: NFT NextNFTId (r:1 w:1)
@@ -133,7 +135,7 @@ impl pallet_nft::WeightInfo for SubstrateWeight {
         Weight::from_ref_time(94_209_062)
             // Standard Error: 74_707
             .saturating_add(Weight::from_ref_time(5_216_435).saturating_mul(n.into()))
-            .saturating_add(DbWeight::get().reads(9))
+            .saturating_add(DbWeight::get().reads(10))
             .saturating_add(DbWeight::get().writes(5))
             .saturating_add(DbWeight::get().writes((1_u64).saturating_mul(n.into())))
     }
@@ -224,4 +226,42 @@ impl pallet_nft::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().writes(2))
             .saturating_add(DbWeight::get().writes((3_u64).saturating_mul(n.into())))
     }
+    // Estimated weights.
+    // The calls below have not been benchmarked on the reference hardware yet.
+    // Their weights are estimated from the storage they access and from similar calls,
+    // and have to be regenerated with the benchmark CLI.
+    fn set_nft_metadata_value() -> Weight {
+        Weight::from_ref_time(71_482_000)
+            .saturating_add(DbWeight::get().reads(9))
+            .saturating_add(DbWeight::get().writes(2))
+    }
+    fn freeze_nft() -> Weight {
+        Weight::from_ref_time(54_316_000)
+            .saturating_add(DbWeight::get().reads(6))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    fn unfreeze_nft() -> Weight {
+        Weight::from_ref_time(53_904_000)
+            .saturating_add(DbWeight::get().reads(6))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    fn set_collection_royalty() -> Weight {
+        Weight::from_ref_time(49_873_000)
+            .saturating_add(DbWeight::get().reads(6))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    fn fractionalise_nft() -> Weight {
+        Weight::from_ref_time(187_529_000)
+            .saturating_add(DbWeight::get().reads(24))
+            .saturating_add(DbWeight::get().writes(11))
+    }
+    fn redeem_nft_fractions() -> Weight {
+        Weight::from_ref_time(171_208_000)
+            .saturating_add(DbWeight::get().reads(18))
+            .saturating_add(DbWeight::get().writes(9))
+    }
+    fn issue_nfts(n: u32, k: u32) -> Weight {
+        // Issuing `n` NFTs at once costs at most as much as issuing each of them on its own.
+        <Self as pallet_nft::WeightInfo>::issue_nft(k).saturating_mul(n.into())
+    }
 }
@@ -65,4 +65,24 @@ impl pallet_protocol_fee::WeightInfo for SubstrateWeight {
         // Minimum execution time: 29_085 nanoseconds.
         Weight::from_ref_time(31_388_000).saturating_add(DbWeight::get().writes(1))
     }
+    // Estimated weights.
+    // The calls below have not been benchmarked on the reference hardware yet.
+    // Their weights are estimated from the storage they access and from similar calls,
+    // and have to be regenerated with the benchmark CLI.
+    fn set_discount_tiers(t: u32) -> Weight {
+        Weight::from_ref_time(31_027_000)
+            .saturating_add(Weight::from_ref_time(412_000).saturating_mul(t.into()))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    fn set_discount_period() -> Weight {
+        Weight::from_ref_time(27_311_000).saturating_add(DbWeight::get().writes(1))
+    }
+    fn set_fee_waiver() -> Weight {
+        Weight::from_ref_time(30_142_000).saturating_add(DbWeight::get().writes(1))
+    }
+    fn charge_fee() -> Weight {
+        Weight::from_ref_time(68_514_000)
+            .saturating_add(DbWeight::get().reads(12))
+            .saturating_add(DbWeight::get().writes(2))
+    }
 }
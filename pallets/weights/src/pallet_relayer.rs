@@ -131,4 +131,14 @@ impl pallet_relayer::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().reads(2))
             .saturating_add(DbWeight::get().writes(1))
     }
+    // Estimated weights.
+    // The calls below have not been benchmarked on the reference hardware yet.
+    // Their weights are estimated from the storage they access and from similar calls,
+    // and have to be regenerated with the benchmark CLI.
+    fn set_subsidy_limits(c: u32) -> Weight {
+        Weight::from_ref_time(51_204_000)
+            .saturating_add(Weight::from_ref_time(1_387_000).saturating_mul(c.into()))
+            .saturating_add(DbWeight::get().reads(3))
+            .saturating_add(DbWeight::get().writes(2))
+    }
 }
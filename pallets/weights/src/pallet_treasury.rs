@@ -92,4 +92,34 @@ impl pallet_treasury::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().reads(8))
             .saturating_add(DbWeight::get().writes(2))
     }
+    // Estimated weights.
+    // The calls below have not been benchmarked on the reference hardware yet.
+    // Their weights are estimated from the storage they access and from similar calls,
+    // and have to be regenerated with the benchmark CLI.
+    fn create_milestone_plan(m: u32) -> Weight {
+        Weight::from_ref_time(39_870_145)
+            .saturating_add(Weight::from_ref_time(291_532).saturating_mul(m.into()))
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().writes(4))
+    }
+    fn create_stream_plan() -> Weight {
+        Weight::from_ref_time(39_118_000)
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().writes(4))
+    }
+    fn release_milestone() -> Weight {
+        Weight::from_ref_time(124_571_000)
+            .saturating_add(DbWeight::get().reads(11))
+            .saturating_add(DbWeight::get().writes(5))
+    }
+    fn claim_stream() -> Weight {
+        Weight::from_ref_time(136_392_000)
+            .saturating_add(DbWeight::get().reads(12))
+            .saturating_add(DbWeight::get().writes(5))
+    }
+    fn cancel_payment_plan() -> Weight {
+        Weight::from_ref_time(129_763_000)
+            .saturating_add(DbWeight::get().reads(11))
+            .saturating_add(DbWeight::get().writes(5))
+    }
 }
@@ -179,4 +179,41 @@ impl pallet_utility::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().reads(3))
             .saturating_add(DbWeight::get().writes(2))
     }
+    // Estimated weights.
+    // The calls below have not been benchmarked on the reference hardware yet.
+    // Their weights are estimated from the storage they access and from similar calls,
+    // and have to be regenerated with the benchmark CLI.
+    fn schedule_call() -> Weight {
+        Weight::from_ref_time(63_204_000)
+            .saturating_add(DbWeight::get().reads(7))
+            .saturating_add(DbWeight::get().writes(5))
+    }
+    fn schedule_batch(c: u32) -> Weight {
+        Weight::from_ref_time(62_948_310)
+            .saturating_add(Weight::from_ref_time(1_472_518).saturating_mul(c.into()))
+            .saturating_add(DbWeight::get().reads(7))
+            .saturating_add(DbWeight::get().writes(5))
+    }
+    fn schedule_recurring(c: u32) -> Weight {
+        Weight::from_ref_time(64_015_722)
+            .saturating_add(Weight::from_ref_time(1_480_236).saturating_mul(c.into()))
+            .saturating_add(DbWeight::get().reads(7))
+            .saturating_add(DbWeight::get().writes(5))
+    }
+    fn cancel_scheduled() -> Weight {
+        Weight::from_ref_time(57_102_000)
+            .saturating_add(DbWeight::get().reads(7))
+            .saturating_add(DbWeight::get().writes(4))
+    }
+    fn execute_scheduled(c: u32) -> Weight {
+        Weight::from_ref_time(49_813_640)
+            .saturating_add(Weight::from_ref_time(9_611_045).saturating_mul(c.into()))
+            .saturating_add(DbWeight::get().reads(7))
+            .saturating_add(DbWeight::get().writes(5))
+    }
+    fn batch_if(n: u32) -> Weight {
+        Weight::from_ref_time(2_687_000)
+            .saturating_add(Weight::from_ref_time(8_905_113).saturating_mul(n.into()))
+            .saturating_add(DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+    }
 }
@@ -190,16 +190,37 @@ impl From<v1::GranularCanTransferResult> for GranularCanTransferResult {
                 .into_iter()
                 .map(|tm| tm.into())
                 .collect(),
-            compliance_result: old.compliance_result,
+            compliance_result: old.compliance_result.into(),
             consumed_weight: None,
             result: old.result,
         }
     }
 }
 
+impl From<v2::GranularCanTransferResult> for GranularCanTransferResult {
+    fn from(old: v2::GranularCanTransferResult) -> Self {
+        Self {
+            invalid_granularity: old.invalid_granularity,
+            self_transfer: old.self_transfer,
+            invalid_receiver_cdd: old.invalid_receiver_cdd,
+            invalid_sender_cdd: old.invalid_sender_cdd,
+            receiver_custodian_error: old.receiver_custodian_error,
+            sender_custodian_error: old.sender_custodian_error,
+            sender_insufficient_balance: old.sender_insufficient_balance,
+            portfolio_validity_result: old.portfolio_validity_result,
+            asset_frozen: old.asset_frozen,
+            transfer_condition_result: old.transfer_condition_result,
+            compliance_result: old.compliance_result.into(),
+            consumed_weight: old.consumed_weight,
+            result: old.result,
+        }
+    }
+}
+
 /// Deprecated v1 GranularCanTransferResult.
 pub mod v1 {
     use super::*;
+    use crate::compliance_manager::v1::AssetComplianceResult;
     use crate::statistics::v1::TransferManagerResult;
 
     /// Result of a granular can transfer.
@@ -232,3 +253,41 @@ pub mod v1 {
         pub result: bool,
     }
 }
+
+/// Deprecated v2 GranularCanTransferResult, without attestation counts in the compliance result.
+pub mod v2 {
+    use super::*;
+    use crate::compliance_manager::v1::AssetComplianceResult;
+
+    /// Result of a granular can transfer.
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+    #[derive(Decode, Encode, Clone, PartialEq, Eq)]
+    pub struct GranularCanTransferResult {
+        /// Granularity check failed.
+        pub invalid_granularity: bool,
+        /// Receiver is equal to sender.
+        pub self_transfer: bool,
+        /// Receiver is missing cdd.
+        pub invalid_receiver_cdd: bool,
+        /// Sender is missing cdd.
+        pub invalid_sender_cdd: bool,
+        /// Receiver had a custodian error.
+        pub receiver_custodian_error: bool,
+        /// Sender had a custodian error.
+        pub sender_custodian_error: bool,
+        /// Sender had an insufficient balance.
+        pub sender_insufficient_balance: bool,
+        /// Portfolio validity result.
+        pub portfolio_validity_result: PortfolioValidityResult,
+        /// Asset is frozen.
+        pub asset_frozen: bool,
+        /// Result of transfer condition check.
+        pub transfer_condition_result: Vec<TransferConditionResult>,
+        /// Result of compliance check.
+        pub compliance_result: AssetComplianceResult,
+        /// Final evaluation result.
+        pub result: bool,
+        /// The weight for checking the asset's compliance and transfer restrictions.
+        pub consumed_weight: Option<Weight>,
+    }
+}
@@ -18,6 +18,8 @@ use crate::Url;
 use codec::{Decode, DecodeAll, Encode};
 use polymesh_primitives_derive::VecU8StrongTyped;
 use scale_info::{PortableRegistry, TypeInfo};
+#[cfg(feature = "std")]
+use sp_runtime::{Deserialize, Serialize};
 use sp_std::prelude::Vec;
 
 /// Asset Metadata Name.
@@ -26,18 +28,21 @@ use sp_std::prelude::Vec;
 pub struct AssetMetadataName(pub Vec<u8>);
 
 /// Asset Metadata Global Key.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, TypeInfo)]
 #[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, Ord, PartialOrd)]
 pub struct AssetMetadataGlobalKey(pub u64);
 impl_checked_inc!(AssetMetadataGlobalKey);
 
 /// Asset Metadata Local Key.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, TypeInfo)]
 #[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, Ord, PartialOrd)]
 pub struct AssetMetadataLocalKey(pub u64);
 impl_checked_inc!(AssetMetadataLocalKey);
 
 /// Asset Metadata Key.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, TypeInfo)]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Ord, PartialOrd)]
 pub enum AssetMetadataKey {
@@ -60,6 +65,7 @@ impl From<AssetMetadataGlobalKey> for AssetMetadataKey {
 }
 
 /// Asset Metadata Value.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, TypeInfo, VecU8StrongTyped)]
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct AssetMetadataValue(pub Vec<u8>);
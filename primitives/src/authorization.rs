@@ -131,7 +131,7 @@ impl From<AuthorizationError> for DispatchError {
 }
 
 /// Authorization struct
-#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Debug)]
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Authorization<AccountId, Moment> {
     /// Enum that contains authorization type and data
@@ -0,0 +1,78 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2023 Polymesh Association
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use codec::{Decode, Encode};
+use polymesh_primitives_derive::SliceU8StrongTyped;
+#[cfg(feature = "std")]
+use polymesh_primitives_derive::{DeserializeU8StrongTyped, SerializeU8StrongTyped};
+use scale_info::TypeInfo;
+use sp_io::hashing::blake2_256;
+
+use crate::Claim;
+
+/// The salt used to build a [`ClaimCommitment`]. It is only known by the claim issuer and its target.
+pub type ClaimSalt = [u8; 32];
+
+/// A claim commitment only reveals the committed claim to those who know its salt.
+///
+/// It is built as `blake2_256(SCALE(claim, salt))`.
+#[derive(Encode, Decode, TypeInfo, SliceU8StrongTyped)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "std",
+    derive(SerializeU8StrongTyped, DeserializeU8StrongTyped)
+)]
+pub struct ClaimCommitment([u8; 32]);
+
+impl ClaimCommitment {
+    /// Creates the commitment of `claim` using the given `salt`.
+    pub fn new(claim: &Claim, salt: &ClaimSalt) -> Self {
+        Self((claim, salt).using_encoded(blake2_256))
+    }
+
+    /// Check if the commitment is the default value (all zeros).
+    pub fn is_default_commitment(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns `true` if this commitment was built from `claim` and `salt`.
+    pub fn is_opened_by(&self, claim: &Claim, salt: &ClaimSalt) -> bool {
+        *self == Self::new(claim, salt)
+    }
+}
+
+impl From<[u8; 32]> for ClaimCommitment {
+    #[inline]
+    fn from(data: [u8; 32]) -> Self {
+        Self(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Claim, ClaimCommitment, CountryCode, IdentityId, Scope};
+
+    #[test]
+    fn claim_commitment_is_opened_by() {
+        let scope = Scope::Identity(IdentityId::from(1));
+        let claim = Claim::Jurisdiction(CountryCode::CA, scope.clone());
+        let commitment = ClaimCommitment::new(&claim, &[7u8; 32]);
+
+        assert!(!commitment.is_default_commitment());
+        assert!(commitment.is_opened_by(&claim, &[7u8; 32]));
+        assert!(!commitment.is_opened_by(&claim, &[8u8; 32]));
+        assert!(!commitment.is_opened_by(&Claim::Jurisdiction(CountryCode::US, scope), &[7u8; 32]));
+    }
+}
@@ -14,6 +14,8 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use crate::condition::{conditions_total_counts, Condition};
+use crate::settlement::InstructionId;
+use crate::IdentityId;
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
@@ -95,6 +97,9 @@ pub struct ConditionResult {
     pub condition: Condition,
     /// Result of evaluation
     pub result: bool,
+    /// Number of distinct trusted issuers that attested the claim.
+    /// Only set for `ConditionType::IsAttested` conditions.
+    pub attestations: Option<u32>,
 }
 
 impl From<Condition> for ConditionResult {
@@ -102,6 +107,7 @@ impl From<Condition> for ConditionResult {
         Self {
             condition,
             result: true,
+            attestations: None,
         }
     }
 }
@@ -141,3 +147,94 @@ impl From<AssetCompliance> for AssetComplianceResult {
         }
     }
 }
+
+/// The effect a proposed compliance change would have on an asset.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo, Default, Clone, PartialEq, Eq, Debug)]
+pub struct ComplianceImpact {
+    /// Current holders of the asset that would be in breach of the proposed rules.
+    pub holders: Vec<IdentityId>,
+    /// Pending instructions that would fail to execute under the proposed rules.
+    pub instructions: Vec<InstructionId>,
+    /// The next instruction to check, if not all instructions were checked.
+    pub next_instruction: Option<InstructionId>,
+    /// The last holder checked, if not all holders were checked.
+    pub next_holder: Option<IdentityId>,
+}
+
+/// Deprecated v1 compliance results, without attestation counts.
+pub mod v1 {
+    use super::*;
+
+    /// A compliance requirement along with its evaluation result
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, Hash)]
+    pub struct ComplianceRequirementResult {
+        /// List of sender conditions
+        pub sender_conditions: Vec<ConditionResult>,
+        /// List of receiver conditions
+        pub receiver_conditions: Vec<ConditionResult>,
+        /// Unique identifier of the compliance requirement.
+        pub id: u32,
+        /// Result of this transfer condition's evaluation.
+        pub result: bool,
+    }
+
+    /// An individual condition along with its evaluation result
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, Hash)]
+    pub struct ConditionResult {
+        /// Condition being evaluated
+        pub condition: Condition,
+        /// Result of evaluation
+        pub result: bool,
+    }
+
+    /// Asset compliance and it's evaluation result.
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, Hash)]
+    pub struct AssetComplianceResult {
+        /// This flag indicates if asset compliance should be enforced.
+        pub paused: bool,
+        /// List of compliance requirements.
+        pub requirements: Vec<ComplianceRequirementResult>,
+        /// Final evaluation result of the asset compliance.
+        pub result: bool,
+    }
+}
+
+impl From<v1::ConditionResult> for ConditionResult {
+    fn from(old: v1::ConditionResult) -> Self {
+        Self {
+            condition: old.condition,
+            result: old.result,
+            attestations: None,
+        }
+    }
+}
+
+impl From<v1::ComplianceRequirementResult> for ComplianceRequirementResult {
+    fn from(old: v1::ComplianceRequirementResult) -> Self {
+        let from_conds = |conds: Vec<_>| conds.into_iter().map(ConditionResult::from).collect();
+        Self {
+            sender_conditions: from_conds(old.sender_conditions),
+            receiver_conditions: from_conds(old.receiver_conditions),
+            id: old.id,
+            result: old.result,
+        }
+    }
+}
+
+impl From<v1::AssetComplianceResult> for AssetComplianceResult {
+    fn from(old: v1::AssetComplianceResult) -> Self {
+        Self {
+            paused: old.paused,
+            requirements: old
+                .requirements
+                .into_iter()
+                .map(ComplianceRequirementResult::from)
+                .collect(),
+            result: old.result,
+        }
+    }
+}
@@ -48,6 +48,8 @@ pub enum ConditionType {
     IsNoneOf(Vec<Claim>),
     /// Condition to ensure that the sender/receiver is a particular identity or an external agent.
     IsIdentity(TargetIdentity),
+    /// Condition to ensure that the claim is issued by at least `u32` distinct trusted issuers.
+    IsAttested(Claim, u32),
 }
 
 impl ConditionType {
@@ -56,7 +58,8 @@ impl ConditionType {
         match self {
             ConditionType::IsIdentity(..)
             | ConditionType::IsPresent(..)
-            | ConditionType::IsAbsent(..) => 1,
+            | ConditionType::IsAbsent(..)
+            | ConditionType::IsAttested(..) => 1,
             ConditionType::IsNoneOf(claims) | ConditionType::IsAnyOf(claims) => claims.len(),
         }
     }
@@ -189,7 +192,9 @@ impl Condition {
     /// Returns all the claims in the condition.
     pub fn claims(&self) -> impl Iterator<Item = &Claim> {
         match &self.condition_type {
-            ConditionType::IsPresent(c) | ConditionType::IsAbsent(c) => Either::Left(iter::once(c)),
+            ConditionType::IsPresent(c)
+            | ConditionType::IsAbsent(c)
+            | ConditionType::IsAttested(c, _) => Either::Left(iter::once(c)),
             ConditionType::IsAnyOf(cs) | ConditionType::IsNoneOf(cs) => Either::Right(cs.iter()),
             ConditionType::IsIdentity(_) => Either::Right([].iter()),
         }
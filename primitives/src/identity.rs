@@ -18,6 +18,9 @@ use sp_runtime::{Deserialize, Serialize};
 
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
+use sp_std::prelude::*;
+
+use crate::{Authorization, Hash, IdentityClaim, IdentityId, SecondaryKey, Signatory};
 
 #[cfg(feature = "running-ci")]
 /// Defines the constants for the identity pallet.
@@ -32,6 +35,20 @@ pub mod limits {
     pub const MAX_PALLETS: u32 = 4;
     /// Maximum number of extrinsics allowed.
     pub const MAX_EXTRINSICS: u32 = 4;
+    /// Maximum number of recovery guardians allowed.
+    pub const MAX_RECOVERY_GUARDIANS: u32 = 4;
+    /// Maximum number of session keys expiring in the same block.
+    pub const MAX_SESSION_KEYS_PER_BLOCK: u32 = 4;
+    /// Maximum number of expired authorizations removed per block.
+    pub const MAX_EXPIRED_AUTHS_PER_BLOCK: u32 = 4;
+    /// Maximum number of CDD expiries processed per block.
+    pub const MAX_CDD_EXPIRIES_PER_BLOCK: u32 = 4;
+    /// Maximum number of days `cdd_expiring_within` looks ahead.
+    pub const MAX_CDD_EXPIRING_WITHIN_DAYS: u32 = 366;
+    /// Maximum number of ancestors of a child identity.
+    pub const MAX_CHILD_IDENTITY_DEPTH: u32 = 4;
+    /// Maximum number of claims in an identity export.
+    pub const MAX_EXPORTED_CLAIMS: u32 = 4;
 }
 
 #[cfg(not(feature = "running-ci"))]
@@ -47,6 +64,20 @@ pub mod limits {
     pub const MAX_PALLETS: usize = 80;
     /// Maximum number of extrinsics allowed.
     pub const MAX_EXTRINSICS: usize = 80;
+    /// Maximum number of recovery guardians allowed.
+    pub const MAX_RECOVERY_GUARDIANS: u32 = 20;
+    /// Maximum number of session keys expiring in the same block.
+    pub const MAX_SESSION_KEYS_PER_BLOCK: u32 = 100;
+    /// Maximum number of expired authorizations removed per block.
+    pub const MAX_EXPIRED_AUTHS_PER_BLOCK: u32 = 50;
+    /// Maximum number of CDD expiries processed per block.
+    pub const MAX_CDD_EXPIRIES_PER_BLOCK: u32 = 50;
+    /// Maximum number of days `cdd_expiring_within` looks ahead.
+    pub const MAX_CDD_EXPIRING_WITHIN_DAYS: u32 = 366;
+    /// Maximum number of ancestors of a child identity.
+    pub const MAX_CHILD_IDENTITY_DEPTH: u32 = 4;
+    /// Maximum number of claims in an identity export.
+    pub const MAX_EXPORTED_CLAIMS: u32 = 100;
 }
 
 /// Identity record.
@@ -76,3 +107,61 @@ impl<AccountId> DidRecord<AccountId> {
         }
     }
 }
+
+/// Social recovery configuration of an identity.
+///
+/// Once `threshold` of the `guardians` approve the same new primary key, the rotation
+/// can be executed after `delay` blocks, unless the current primary key cancels it.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RecoveryConfig<AccountId, BlockNumber> {
+    /// Identities or keys allowed to approve a recovery.
+    pub guardians: Vec<Signatory<AccountId>>,
+    /// Number of guardian approvals needed.
+    pub threshold: u32,
+    /// Number of blocks between reaching the threshold and executing the recovery.
+    pub delay: BlockNumber,
+}
+
+/// An ongoing social recovery of an identity.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ActiveRecovery<AccountId, BlockNumber> {
+    /// Guardians that approved the recovery, with the new primary key each of them approved.
+    pub approvals: Vec<(Signatory<AccountId>, AccountId)>,
+    /// The key approved by the threshold of guardians and the block from which
+    /// it can execute the recovery.
+    pub approved: Option<(AccountId, BlockNumber)>,
+}
+
+/// Current version of the `IdentityExport` format.
+pub const IDENTITY_EXPORT_VERSION: u8 = 1;
+
+/// A portable snapshot of an identity's keys, claims and authorizations,
+/// used to move an identity between chains without repeating its KYC.
+///
+/// The bundle is signed off-chain by `primary_key` over its SCALE encoding,
+/// and can be shared either SCALE or JSON encoded.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct IdentityExport<AccountId, Moment> {
+    /// Version of the export format.
+    pub version: u8,
+    /// Genesis hash of the chain the identity was exported from.
+    pub source_chain: Hash,
+    /// When the identity was exported.
+    pub exported_at: Moment,
+    /// The exported identity.
+    pub did: IdentityId,
+    /// The primary key of the identity, which signs the export.
+    pub primary_key: AccountId,
+    /// The secondary keys of the identity.
+    pub secondary_keys: Vec<SecondaryKey<AccountId>>,
+    /// The unexpired claims of the identity.
+    pub claims: Vec<IdentityClaim>,
+    /// The pending authorizations targeting the identity.
+    pub authorizations: Vec<Authorization<AccountId, Moment>>,
+}
@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
-use crate::{identity_id::IdentityId, impl_checked_inc, CddId, Moment, Ticker};
+use crate::{identity_id::IdentityId, impl_checked_inc, CddId, ClaimCommitment, Moment, Ticker};
 
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
@@ -96,6 +96,8 @@ pub enum Claim {
     Blocked(Scope),
     /// Custom claim with an optional scope.
     Custom(CustomClaimTypeId, Option<Scope>),
+    /// A claim of the given type and scope whose data is only revealed through its commitment.
+    Commitment(ClaimType, Scope, ClaimCommitment),
 }
 
 impl Claim {
@@ -112,6 +114,7 @@ impl Claim {
             Claim::Exempted(..) => ClaimType::Exempted,
             Claim::Blocked(..) => ClaimType::Blocked,
             Claim::Custom(cc_id, _) => ClaimType::Custom(*cc_id),
+            Claim::Commitment(claim_type, ..) => *claim_type,
         }
     }
 
@@ -125,7 +128,8 @@ impl Claim {
             | Claim::KnowYourCustomer(scope)
             | Claim::Jurisdiction(.., scope)
             | Claim::Exempted(scope)
-            | Claim::Blocked(scope) => Some(scope),
+            | Claim::Blocked(scope)
+            | Claim::Commitment(_, scope, _) => Some(scope),
             Claim::Custom(_, scope) => scope.as_ref(),
             Claim::CustomerDueDiligence(..) => None,
         }
@@ -135,6 +139,12 @@ impl Claim {
     pub fn default_cdd_id() -> Claim {
         Claim::CustomerDueDiligence(CddId::default())
     }
+
+    /// It returns a commitment claim with a default `ClaimCommitment`,
+    /// which matches any commitment of the given `claim_type` and `scope`.
+    pub fn default_commitment(claim_type: ClaimType, scope: Scope) -> Claim {
+        Claim::Commitment(claim_type, scope, ClaimCommitment::default())
+    }
 }
 
 /// Claim type represent the claim without its data.
@@ -180,6 +190,38 @@ pub struct IdentityClaim {
     pub claim: Claim,
 }
 
+/// A claim issued off-chain by `issuer` for `target`.
+///
+/// Anyone can import it into the chain with a signature over the SCALE encoding of
+/// `(genesis hash, credential)` made by one of the credential signers registered by `issuer`.
+/// The genesis hash binds the credential to a single chain.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+pub struct ClaimCredential<Moment> {
+    /// Unique credential number set by the issuer.
+    pub uid: u64,
+    /// Issuer of the claim.
+    pub issuer: IdentityId,
+    /// Identity receiving the claim.
+    pub target: IdentityId,
+    /// Claim data.
+    pub claim: Claim,
+    /// Expiry date.
+    pub expiry: Option<Moment>,
+    /// Time after which the credential can no longer be imported.
+    pub valid_until: Moment,
+}
+
+/// Governance settings for the lifecycle of CDD claims.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo, Clone, Default, PartialEq, Eq, Debug)]
+pub struct CddLifecycleConfig<Moment> {
+    /// How long an identity keeps a valid CDD after its CDD claims expire.
+    pub grace_period: Moment,
+    /// How long before the expiry of a CDD claim a `CddExpiringSoon` event is emitted.
+    pub notice_period: Moment,
+}
+
 impl From<Claim> for IdentityClaim {
     fn from(data: Claim) -> Self {
         IdentityClaim {
@@ -120,7 +120,9 @@ pub use identity_id::{
 /// Identity information.
 /// Each DID is associated with this kind of record.
 pub mod identity;
-pub use identity::DidRecord;
+pub use identity::{
+    ActiveRecovery, DidRecord, IdentityExport, RecoveryConfig, IDENTITY_EXPORT_VERSION,
+};
 
 /// Provides the `CheckedInc` trait.
 pub mod checked_inc;
@@ -129,10 +131,16 @@ pub mod checked_inc;
 pub mod cdd_id;
 pub use cdd_id::CddId;
 
+/// Claim commitment.
+pub mod claim_commitment;
+pub use claim_commitment::{ClaimCommitment, ClaimSalt};
+
 /// Claim information.
 /// Each claim is associated with this kind of record.
 pub mod identity_claim;
-pub use identity_claim::{Claim, ClaimType, CustomClaimTypeId, IdentityClaim, Scope};
+pub use identity_claim::{
+    CddLifecycleConfig, Claim, ClaimCredential, ClaimType, CustomClaimTypeId, IdentityClaim, Scope,
+};
 
 // Defining and enumerating jurisdictions.
 pub mod jurisdiction;
@@ -144,8 +152,8 @@ pub mod migrate;
 /// This module contains entities related with secondary keys.
 pub mod secondary_key;
 pub use secondary_key::{
-    AssetPermissions, ExtrinsicPermissions, KeyRecord, PalletPermissions, Permissions,
-    PortfolioPermissions, SecondaryKey, Signatory,
+    AssetPermissions, ExtrinsicPermissions, KeyRecord, KeySpending, PalletPermissions, Permissions,
+    PortfolioPermissions, SecondaryKey, SecondaryKeyLimits, SessionKey, Signatory,
 };
 
 /// Subset type.
@@ -228,6 +236,12 @@ pub mod constants;
 /// Multisig type definitions.
 pub mod multisig;
 
+/// Relayer subsidy type definitions.
+pub mod subsidy;
+
+/// Treasury payment plan type definitions.
+pub mod treasury;
+
 /// Represents custom transaction errors.
 #[repr(u8)]
 pub enum TransactionError {
@@ -241,6 +255,10 @@ pub enum TransactionError {
     InvalidAuthorization = 3,
     /// Subsidy is not available for this pallet.
     PalletNotSubsidised = 4,
+    /// The session key has no calls left.
+    SessionKeyCallsExhausted = 5,
+    /// Subsidy is not available for this call.
+    CallNotSubsidised = 6,
 }
 
 /// Represents the target identity and the amount requested by a beneficiary.
@@ -14,14 +14,22 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use codec::{Decode, Encode};
+use frame_support::traits::CallMetadata;
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use sp_std::vec::Vec;
+
+use crate::{Balance, DispatchableName, PalletName, Signatory};
 
 /// Details of a multisig proposal.
 #[derive(Clone, Debug, Decode, Default, Encode, Eq, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ProposalDetails<T> {
-    /// Number of yes votes
+    /// Sum of the weights of the signers that voted yes
     pub approvals: u64,
-    /// Number of no votes
+    /// Sum of the weights of the signers that voted no
     pub rejections: u64,
     /// Status of the proposal
     pub status: ProposalStatus,
@@ -46,6 +54,7 @@ impl<T: Default> ProposalDetails<T> {
 
 /// Status of a multisig proposal.
 #[derive(Clone, Debug, Decode, Default, Encode, Eq, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum ProposalStatus {
     /// Proposal does not exist
     #[default]
@@ -56,6 +65,80 @@ pub enum ProposalStatus {
     ExecutionSuccessful,
     /// Proposal was accepted and execution was tried but it failed
     ExecutionFailed,
-    /// Proposal was rejected
+    /// Proposal was rejected or vetoed
     Rejected,
+    /// Proposal was approved and is waiting for the multisig's execution delay to pass
+    Queued,
+}
+
+/// Selects the multisig proposals a [`ThresholdRule`] applies to.
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CallFilter {
+    /// Pallet of the proposed call.
+    pub pallet_name: PalletName,
+    /// Extrinsic of the proposed call, `None` matches every extrinsic of the pallet.
+    pub dispatchable_name: Option<DispatchableName>,
+    /// When set, only calls moving more than this amount of POLYX match.
+    pub min_polyx: Option<Balance>,
+}
+
+impl CallFilter {
+    /// Returns `true` if a call with the given `metadata`, moving `polyx` POLYX, matches the filter.
+    pub fn matches(&self, metadata: &CallMetadata, polyx: Option<Balance>) -> bool {
+        self.pallet_name.as_slice() == metadata.pallet_name.as_bytes()
+            && self.dispatchable_name.as_ref().map_or(true, |name| {
+                name.as_slice() == metadata.function_name.as_bytes()
+            })
+            && self
+                .min_polyx
+                .map_or(true, |min| polyx.map_or(false, |value| value > min))
+    }
+}
+
+/// A multisig approval threshold applied to the proposals matched by `filter`.
+///
+/// The calls wrapped by a proposal, e.g. in a batch, are matched as well.
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ThresholdRule {
+    /// The proposals this rule applies to.
+    pub filter: CallFilter,
+    /// Total signer weight required to execute a matching proposal.
+    pub threshold: u64,
+}
+
+/// An active multisig proposal, as returned by the multisig runtime API.
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ProposalInfo<AccountId, Moment, Call> {
+    /// Id of the proposal.
+    pub proposal_id: u64,
+    /// Pallet of the proposed call.
+    pub pallet_name: PalletName,
+    /// Extrinsic of the proposed call.
+    pub dispatchable_name: DispatchableName,
+    /// The proposed call.
+    pub call: Call,
+    /// Votes, status and expiry of the proposal.
+    pub details: ProposalDetails<Moment>,
+    /// Total signer weight required to execute the proposal.
+    pub approvals_required: u64,
+    /// Signers that already voted on the proposal.
+    pub voters: Vec<Signatory<AccountId>>,
+}
+
+impl<AccountId, Moment, Call> ProposalInfo<AccountId, Moment, Call> {
+    /// Returns this proposal with its call converted by `f`.
+    pub fn map_call<C>(self, f: impl FnOnce(Call) -> C) -> ProposalInfo<AccountId, Moment, C> {
+        ProposalInfo {
+            proposal_id: self.proposal_id,
+            pallet_name: self.pallet_name,
+            dispatchable_name: self.dispatchable_name,
+            call: f(self.call),
+            details: self.details,
+            approvals_required: self.approvals_required,
+            voters: self.voters,
+        }
+    }
 }
@@ -3,12 +3,13 @@ use serde::{Deserialize, Serialize};
 
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
+use sp_arithmetic::Permill;
 use sp_std::collections::btree_set::BTreeSet;
 use sp_std::vec::IntoIter;
 use sp_std::vec::Vec;
 
 use crate::asset_metadata::{AssetMetadataKey, AssetMetadataValue};
-use crate::{impl_checked_inc, Ticker};
+use crate::{impl_checked_inc, IdentityId, Ticker};
 
 /// Controls the total number of NFTs per identity.
 pub type NFTCount = u64;
@@ -118,9 +119,19 @@ impl From<Vec<AssetMetadataKey>> for NFTCollectionKeys {
 
 /// Defines a metadata attribute which is a composed of a key and a value.
 #[derive(Clone, Debug, Decode, Encode, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct NFTMetadataAttribute {
     /// The metadata key.
     pub key: AssetMetadataKey,
     /// The metadata value.
     pub value: AssetMetadataValue,
 }
+
+/// Defines the royalty paid to a beneficiary when NFTs of a collection are exchanged for fungible assets.
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo)]
+pub struct NFTRoyalty {
+    /// The [`IdentityId`] that receives the royalty in its default portfolio.
+    pub beneficiary: IdentityId,
+    /// The share of each fungible payment that is owed to the beneficiary.
+    pub percentage: Permill,
+}
@@ -33,6 +33,10 @@ impl<C> Proposition<C> for IsIdentityProposition {
 ///     - The `exists(Claim::CustomerDueDiligence(a_valid_cdd_id))` matches only for the given
 ///     `a_valid_cdd_id`.
 ///
+/// # `Commitment` default search
+/// Similarly, `exists(Claim::Commitment(claim_type, scope, ClaimCommitment::default()))` matches
+/// with any commitment claim of `claim_type` and `scope`.
+///
 #[derive(Clone)]
 #[cfg_attr(feature = "std", derive(Debug))]
 pub struct ExistentialProposition<'a> {
@@ -40,19 +44,61 @@ pub struct ExistentialProposition<'a> {
     pub claim: &'a Claim,
 }
 
-impl<C: Iterator<Item = Claim>> Proposition<C> for ExistentialProposition<'_> {
-    fn evaluate(&self, mut context: Context<C>) -> bool {
+impl ExistentialProposition<'_> {
+    /// Returns `true` if `ctx_claim` matches the searched claim.
+    pub fn matches(&self, ctx_claim: &Claim) -> bool {
         match &self.claim {
-            // The default search only double-checks if any CDD claim is in the context.
-            Claim::CustomerDueDiligence(cdd_id) if cdd_id.is_default_cdd() => context
-                .claims
-                .any(|ctx_claim| matches!(ctx_claim, Claim::CustomerDueDiligence(..))),
+            // The default search only double-checks if `ctx_claim` is any CDD claim.
+            Claim::CustomerDueDiligence(cdd_id) if cdd_id.is_default_cdd() => {
+                matches!(ctx_claim, Claim::CustomerDueDiligence(..))
+            }
+            // The default search only checks if `ctx_claim` is a commitment of the same type and scope.
+            Claim::Commitment(claim_type, scope, commitment)
+                if commitment.is_default_commitment() =>
+            {
+                matches!(ctx_claim, Claim::Commitment(ctx_type, ctx_scope, _) if ctx_type == claim_type && ctx_scope == scope)
+            }
             // In regular claim evaluation, the data of the claim has to match too.
-            _ => context.claims.any(|ctx_claim| &ctx_claim == self.claim),
+            _ => ctx_claim == self.claim,
         }
     }
 }
 
+impl<C: Iterator<Item = Claim>> Proposition<C> for ExistentialProposition<'_> {
+    fn evaluate(&self, mut context: Context<C>) -> bool {
+        context.claims.any(|ctx_claim| self.matches(&ctx_claim))
+    }
+}
+
+// ThresholdProposition
+// ======================================================
+
+/// It checks that a claim is found at least `threshold` times in the context.
+///
+/// The context is expected to hold at most one claim per trusted issuer,
+/// so this checks that `threshold` distinct issuers attested the claim.
+/// Default searches behave as in `ExistentialProposition`.
+#[derive(Clone)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct ThresholdProposition<'a> {
+    /// Claim we want to find in context.
+    pub claim: &'a Claim,
+    /// Minimum number of times the claim must be found.
+    pub threshold: u32,
+}
+
+impl<C: Iterator<Item = Claim>> Proposition<C> for ThresholdProposition<'_> {
+    fn evaluate(&self, context: Context<C>) -> bool {
+        let existential = ExistentialProposition { claim: self.claim };
+        let found = context
+            .claims
+            .filter(|ctx_claim| existential.matches(ctx_claim))
+            .take(self.threshold as usize)
+            .count();
+        found >= self.threshold as usize
+    }
+}
+
 // AndProposition
 // ======================================================
 
@@ -167,7 +213,8 @@ impl<C: Iterator<Item = Claim>> Proposition<C> for AnyProposition<'_> {
 mod tests {
     use crate::{
         proposition::{self, Context, Proposition},
-        CddId, Claim, Condition, ConditionType, CountryCode, IdentityId, Scope, TargetIdentity,
+        CddId, Claim, ClaimCommitment, ClaimType, Condition, ConditionType, CountryCode,
+        IdentityId, Scope, TargetIdentity,
     };
     use std::convert::From;
     use std::vec::IntoIter;
@@ -206,6 +253,60 @@ mod tests {
         assert_eq!(affiliate_and_cdd_pred.evaluate(context), true);
     }
 
+    #[test]
+    fn commitment_default_search_test() {
+        let scope = Scope::Identity(IdentityId::from(0));
+        let commitment = ClaimCommitment::new(
+            &Claim::Jurisdiction(CountryCode::CA, scope.clone()),
+            &[1u8; 32],
+        );
+        let context = mk_ctx(vec![Claim::Commitment(
+            ClaimType::Jurisdiction,
+            scope.clone(),
+            commitment,
+        )]);
+
+        let any_jurisdiction = Claim::default_commitment(ClaimType::Jurisdiction, scope.clone());
+        assert_eq!(
+            proposition::exists(&any_jurisdiction).evaluate(context.clone()),
+            true
+        );
+
+        let any_accredited = Claim::default_commitment(ClaimType::Accredited, scope);
+        assert_eq!(
+            proposition::exists(&any_accredited).evaluate(context),
+            false
+        );
+    }
+
+    #[test]
+    fn threshold_operator_test() {
+        let scope = Scope::Identity(IdentityId::from(0));
+        let accredited = Claim::Accredited(scope.clone());
+        let context = mk_ctx(vec![
+            accredited.clone(),
+            Claim::Affiliate(scope.clone()),
+            accredited.clone(),
+        ]);
+
+        assert_eq!(
+            proposition::attested(&accredited, 2).evaluate(context.clone()),
+            true
+        );
+        assert_eq!(
+            proposition::attested(&accredited, 3).evaluate(context.clone()),
+            false
+        );
+
+        // Default CDD search counts any CDD claim.
+        let context = mk_ctx(vec![
+            Claim::CustomerDueDiligence(CddId::from([1u8; 32])),
+            Claim::CustomerDueDiligence(CddId::from([2u8; 32])),
+        ]);
+        let any_cdd = Claim::CustomerDueDiligence(CddId::default());
+        assert_eq!(proposition::attested(&any_cdd, 2).evaluate(context), true);
+    }
+
     #[test]
     fn collection_operators_test() {
         let scope = Scope::Identity(IdentityId::from(0));
@@ -79,7 +79,7 @@ impl<C, F: Fn(Context<C>) -> bool> Proposition<C> for F {
 pub mod base;
 pub use base::{
     AndProposition, AnyProposition, ExistentialProposition, IsIdentityProposition, NotProposition,
-    OrProposition,
+    OrProposition, ThresholdProposition,
 };
 
 // Helper functions
@@ -97,6 +97,12 @@ pub fn any(claims: &[Claim]) -> AnyProposition<'_> {
     AnyProposition { claims }
 }
 
+/// It creates a proposition to evaluate if `claim` is found at least `threshold` times in the context.
+#[inline]
+pub fn attested(claim: &Claim, threshold: u32) -> ThresholdProposition<'_> {
+    ThresholdProposition { claim, threshold }
+}
+
 /// It create a negate proposition of `proposition`.
 #[inline]
 pub fn not<P: Proposition<C>, C>(proposition: P) -> NotProposition<P> {
@@ -118,5 +124,8 @@ pub fn run<C: Iterator<Item = Claim>, E: Proposition<C>>(
             IsIdentityProposition { identity: *id }.evaluate(context)
         }
         ConditionType::IsIdentity(TargetIdentity::ExternalAgent) => ea_prop.evaluate(context),
+        ConditionType::IsAttested(claim, threshold) => {
+            attested(claim, *threshold).evaluate(context)
+        }
     }
 }
@@ -13,7 +13,9 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
-use crate::{DispatchableName, IdentityId, PalletName, PortfolioId, SubsetRestriction, Ticker};
+use crate::{
+    Balance, DispatchableName, IdentityId, PalletName, PortfolioId, SubsetRestriction, Ticker,
+};
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
@@ -24,6 +26,7 @@ use sp_std::{
     convert::TryInto,
     iter,
     mem::size_of,
+    vec::Vec,
 };
 
 // We need to set a minimum complexity for pallet/dispatchable names
@@ -434,9 +437,74 @@ impl<AccountId> SecondaryKey<AccountId> {
     }
 }
 
+/// Validity window and spending limits of a secondary key.
+///
+/// Spending limits are reset every `spend_period`; a zero period never resets them.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SecondaryKeyLimits<Moment> {
+    /// The key can't be used before this time.
+    pub valid_from: Option<Moment>,
+    /// The key can't be used after this time.
+    pub valid_until: Option<Moment>,
+    /// The length of a spending period.
+    pub spend_period: Moment,
+    /// Maximum amount of POLYX the key can transfer per period.
+    pub polyx_limit: Option<Balance>,
+    /// Maximum amount of each asset the key can send per period.
+    pub asset_limits: Vec<(Ticker, Balance)>,
+}
+
+impl<Moment: PartialOrd> SecondaryKeyLimits<Moment> {
+    /// Returns `true` if the key can be used at `now`.
+    pub fn is_valid_at(&self, now: &Moment) -> bool {
+        self.valid_from.as_ref().map_or(true, |from| from <= now)
+            && self.valid_until.as_ref().map_or(true, |until| now <= until)
+    }
+
+    /// Returns the spending limit of `ticker`, or of POLYX if `ticker` is `None`.
+    pub fn spend_limit(&self, ticker: Option<&Ticker>) -> Option<Balance> {
+        match ticker {
+            None => self.polyx_limit,
+            Some(ticker) => self
+                .asset_limits
+                .iter()
+                .find(|(limited, _)| limited == ticker)
+                .map(|(_, limit)| *limit),
+        }
+    }
+}
+
+/// The amount spent by a secondary key in its current spending period.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct KeySpending<Moment> {
+    /// The start of the current spending period.
+    pub period_start: Moment,
+    /// The amount spent since `period_start`.
+    pub spent: Balance,
+}
+
+/// A short-lived secondary key delegated by the primary key of an identity, e.g., to a dApp.
+///
+/// The key is unlinked at `expires_at`. It can't call extrinsics once `remaining_calls` is zero.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SessionKey<BlockNumber> {
+    /// The block at which the key is unlinked.
+    pub expires_at: BlockNumber,
+    /// The number of extrinsics the key can still call.
+    pub remaining_calls: u32,
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{Permissions, PortfolioId, SecondaryKey, Signatory, SubsetRestriction};
+    use super::{
+        Permissions, PortfolioId, SecondaryKey, SecondaryKeyLimits, Signatory, SubsetRestriction,
+    };
     use crate::{IdentityId, Ticker};
     use sp_core::sr25519::Public;
     use std::convert::{From, TryFrom};
@@ -501,4 +569,27 @@ mod tests {
         assert_eq!(iden_sig, iden);
         assert_ne!(iden_sig, Signatory::Account(key));
     }
+
+    #[test]
+    fn secondary_key_limits_test() {
+        let ticker1 = Ticker::from_slice_truncated(&[1][..]);
+        let ticker2 = Ticker::from_slice_truncated(&[2][..]);
+        let limits = SecondaryKeyLimits {
+            valid_from: Some(10u64),
+            valid_until: Some(20u64),
+            spend_period: 5u64,
+            polyx_limit: Some(100),
+            asset_limits: vec![(ticker1, 50)],
+        };
+
+        assert!(!limits.is_valid_at(&9));
+        assert!(limits.is_valid_at(&10));
+        assert!(limits.is_valid_at(&20));
+        assert!(!limits.is_valid_at(&21));
+        assert!(SecondaryKeyLimits::<u64>::default().is_valid_at(&0));
+
+        assert_eq!(limits.spend_limit(None), Some(100));
+        assert_eq!(limits.spend_limit(Some(&ticker1)), Some(50));
+        assert_eq!(limits.spend_limit(Some(&ticker2)), None);
+    }
 }
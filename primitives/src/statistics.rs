@@ -129,6 +129,8 @@ pub enum Stat2ndKey {
 impl Stat2ndKey {
     /// Create a `Stat2ndKey` from a `ClaimType` and optional `Claim`.
     pub fn new_from(claim_type: &ClaimType, claim: Option<Claim>) -> Self {
+        // Committed claims don't reveal their data, so they are treated as missing claims.
+        let claim = claim.filter(|claim| !matches!(claim, Claim::Commitment(..)));
         match (claim_type, claim) {
             // No Accredited claim.
             (ClaimType::Accredited, None) => Self::Claim(StatClaim::Accredited(false)),
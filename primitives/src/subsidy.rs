@@ -0,0 +1,50 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymesh Association
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Balance, DispatchableName, ExtrinsicPermissions, PalletName};
+
+/// Restrictions that a paying key puts on the subsidy of a user key.
+///
+/// The spending limit is refilled every `refill_period`; a zero period never refills it.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SubsidyLimits<Moment> {
+    /// The calls whose transaction fees are subsidised.
+    pub calls: ExtrinsicPermissions,
+    /// The subsidy can't be used after this time.
+    pub expires_at: Option<Moment>,
+    /// The length of a spending period.
+    pub refill_period: Moment,
+    /// Maximum amount of POLYX the subsidy can pay per period.
+    pub period_limit: Option<Balance>,
+}
+
+impl<Moment: PartialOrd> SubsidyLimits<Moment> {
+    /// Returns `true` if the subsidy can be used at `now`.
+    pub fn is_valid_at(&self, now: &Moment) -> bool {
+        self.expires_at.as_ref().map_or(true, |until| now <= until)
+    }
+
+    /// Returns `true` if the transaction fees of the call `function_name` of `pallet_name` are subsidised.
+    pub fn covers(&self, pallet_name: &PalletName, function_name: &DispatchableName) -> bool {
+        self.calls.sufficient_for(pallet_name, function_name)
+    }
+}
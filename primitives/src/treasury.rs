@@ -0,0 +1,93 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymath
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::traits::{AtLeast32BitUnsigned, SaturatedConversion, Zero};
+use sp_std::vec::Vec;
+
+use crate::{impl_checked_inc, Balance, IdentityId};
+
+/// A global and unique treasury payment plan ID.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub struct PaymentPlanId(pub u64);
+impl_checked_inc!(PaymentPlanId);
+
+/// A milestone of a payment plan, paid out when released by governance.
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Milestone {
+    /// The amount paid out when the milestone is released.
+    pub amount: Balance,
+    /// Whether the milestone was already released.
+    pub released: bool,
+}
+
+/// How the amount of a payment plan is paid out.
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PaymentSchedule<BlockNumber> {
+    /// Paid out milestone by milestone, as governance releases them.
+    Milestones(Vec<Milestone>),
+    /// Accrues `per_block` every block from `start` until the total is reached,
+    /// and is claimed by the beneficiary.
+    Stream {
+        /// The block from which the stream accrues.
+        start: BlockNumber,
+        /// The amount accrued per block.
+        per_block: Balance,
+    },
+}
+
+/// A treasury payment plan for a beneficiary identity.
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PaymentPlan<BlockNumber> {
+    /// The identity whose primary key receives the payments.
+    pub beneficiary: IdentityId,
+    /// The total amount of the plan.
+    pub total: Balance,
+    /// The amount paid out so far.
+    pub paid: Balance,
+    /// How the total is paid out.
+    pub schedule: PaymentSchedule<BlockNumber>,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> PaymentPlan<BlockNumber> {
+    /// Returns the amount of a stream accrued at block `now` and not paid out yet.
+    ///
+    /// Milestone plans never accrue, as they are paid out on release.
+    pub fn claimable_at(&self, now: BlockNumber) -> Balance {
+        match self.schedule {
+            PaymentSchedule::Stream { start, per_block } if now > start => {
+                let blocks = (now - start).saturated_into::<u128>();
+                per_block
+                    .saturating_mul(blocks)
+                    .min(self.total)
+                    .saturating_sub(self.paid)
+            }
+            _ => Zero::zero(),
+        }
+    }
+
+    /// Returns the amount of the plan not paid out yet.
+    pub fn remaining(&self) -> Balance {
+        self.total.saturating_sub(self.paid)
+    }
+}
@@ -32,7 +32,7 @@ pub type CanTransferResult = Result<u8, Error>;
 sp_api::decl_runtime_apis! {
 
     /// The API to interact with Asset.
-    #[api_version(3)]
+    #[api_version(4)]
     pub trait AssetApi<AccountId>
     where
         AccountId: Codec,
@@ -64,6 +64,16 @@ sp_api::decl_runtime_apis! {
             value: Balance
         ) -> Result<GranularCanTransferResult, DispatchError>;
 
+        #[changed_in(4)]
+        fn can_transfer_granular(
+            from_custodian: Option<IdentityId>,
+            from_portfolio: PortfolioId,
+            to_custodian: Option<IdentityId>,
+            to_portfolio: PortfolioId,
+            ticker: &Ticker,
+            value: Balance
+        ) -> Result<polymesh_primitives::asset::v2::GranularCanTransferResult, DispatchError>;
+
         #[changed_in(3)]
         fn can_transfer_granular(
             from_custodian: Option<IdentityId>,
@@ -72,7 +82,7 @@ sp_api::decl_runtime_apis! {
             to_portfolio: PortfolioId,
             ticker: &Ticker,
             value: Balance
-        ) -> GranularCanTransferResult;
+        ) -> polymesh_primitives::asset::v2::GranularCanTransferResult;
 
         #[changed_in(2)]
         fn can_transfer_granular(
@@ -0,0 +1,90 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2023 Polymesh Association
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for simulating compliance changes.
+
+use frame_support::dispatch::result::Result;
+use frame_support::pallet_prelude::DispatchError;
+use sp_std::collections::btree_set::BTreeSet;
+
+use polymesh_primitives::compliance_manager::{AssetCompliance, ComplianceImpact};
+use polymesh_primitives::settlement::InstructionId;
+use polymesh_primitives::transfer_compliance::TransferCondition;
+use polymesh_primitives::{IdentityId, Ticker};
+
+sp_api::decl_runtime_apis! {
+    pub trait ComplianceApi {
+        /// Returns the current holders of `ticker` that would be in breach of `asset_compliance`, and the
+        /// pending instructions that would fail if it replaced the asset's compliance requirements.
+        ///
+        /// At most `limit` instructions are checked, starting at `start`, and at most `limit` holders,
+        /// following `holders_after`. If there are more, `next_instruction` and `next_holder` are set
+        /// in the result and can be used as the next `start` and `holders_after`.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "compliance_simulateAssetCompliance",
+        ///     "params":[
+        ///       "0x5449434B4552303030303031",
+        ///       { "paused": false, "requirements": [] },
+        ///       1,
+        ///       null,
+        ///       1000
+        ///     ]
+        ///   }'
+        /// ```
+        fn simulate_asset_compliance(
+            ticker: Ticker,
+            asset_compliance: AssetCompliance,
+            start: InstructionId,
+            holders_after: Option<IdentityId>,
+            limit: u32
+        ) -> Result<ComplianceImpact, DispatchError>;
+
+        /// Returns the current holders of `ticker` that would be in breach of `transfer_conditions`, and the
+        /// pending instructions that would fail if they replaced the asset's transfer conditions.
+        /// If `paused` is set, the transfer conditions are not enforced and nothing is in breach.
+        ///
+        /// At most `limit` instructions are checked, starting at `start`, and at most `limit` holders,
+        /// following `holders_after`. If there are more, `next_instruction` and `next_holder` are set
+        /// in the result and can be used as the next `start` and `holders_after`.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "compliance_simulateTransferCompliance",
+        ///     "params":[
+        ///       "0x5449434B4552303030303031",
+        ///       false,
+        ///       [{ "MaxInvestorCount": 10 }],
+        ///       1,
+        ///       null,
+        ///       1000
+        ///     ]
+        ///   }'
+        /// ```
+        fn simulate_transfer_compliance(
+            ticker: Ticker,
+            paused: bool,
+            transfer_conditions: BTreeSet<TransferCondition>,
+            start: InstructionId,
+            holders_after: Option<IdentityId>,
+            limit: u32
+        ) -> Result<ComplianceImpact, DispatchError>;
+    }
+}
@@ -1,13 +1,15 @@
 use codec::Codec;
 use pallet_identity::types::{
-    AssetDidResult, CddStatus, DidStatus, KeyIdentityData, RpcDidRecords,
+    v1, AssetDidResult, CddStatus, DidStatus, KeyIdentityData, RpcAuthorization, RpcDidRecords,
+};
+use polymesh_primitives::{
+    Authorization, AuthorizationType, IdentityClaim, IdentityExport, Signatory,
 };
-use polymesh_primitives::{Authorization, AuthorizationType, IdentityClaim, Signatory};
 use sp_std::prelude::*;
 
 sp_api::decl_runtime_apis! {
     /// Identity runtime API.
-    #[api_version(3)]
+    #[api_version(8)]
     pub trait IdentityApi<IdentityId, Ticker, AccountId, Moment> where
         IdentityId: Codec,
         Ticker: Codec,
@@ -20,9 +22,12 @@ sp_api::decl_runtime_apis! {
         /// Returns DID of an asset
         fn get_asset_did(ticker: Ticker) -> AssetDidResult;
 
-        /// Retrieve DidRecord for a given `did`.
+        /// Retrieve DidRecord for a given `did`, including its parent and child identities.
         fn get_did_records(did: IdentityId) -> RpcDidRecords<AccountId>;
 
+        #[changed_in(7)]
+        fn get_did_records(did: IdentityId) -> v1::RpcDidRecords<AccountId>;
+
         /// Retrieve list of a authorization for a given signatory
         fn get_filtered_authorizations(
             signatory: Signatory<AccountId>,
@@ -36,10 +41,14 @@ sp_api::decl_runtime_apis! {
         /// Provide the `KeyIdentityData` from a given `AccountId`, including:
         /// - the corresponding DID,
         /// - whether the `AccountId` is a primary or secondary key,
-        /// - any permissions related to the key.
+        /// - any permissions related to the key,
+        /// - the validity window and spending limits of a secondary key.
         ///
         /// This is an aggregate call provided for UX convenience.
-        fn get_key_identity_data(acc: AccountId) -> Option<KeyIdentityData<IdentityId>>;
+        fn get_key_identity_data(acc: AccountId) -> Option<KeyIdentityData<IdentityId, Moment>>;
+
+        #[changed_in(4)]
+        fn get_key_identity_data(acc: AccountId) -> Option<v1::KeyIdentityData<IdentityId>>;
 
         /// Returns all valid [`IdentityClaim`] of type `CustomerDueDiligence` for the given `target_identity`.
         ///
@@ -56,5 +65,74 @@ sp_api::decl_runtime_apis! {
         /// ```
         #[api_version(3)]
         fn valid_cdd_claims(target_identity: IdentityId, cdd_checker_leeway: Option<u64>) -> Vec<IdentityClaim>;
+
+        /// Returns up to `limit` authorizations received by `signatory`, following the authorization `after` in storage order.
+        /// The id of the last authorization returned can be used as `after` to get the next page.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "identity_getAuthorizationsInbox",
+        ///     "params":[{ "Identity": "0x0100000000000000000000000000000000000000000000000000000000000000" }, null, false, 1, 100]
+        ///   }'
+        /// ```
+        #[api_version(5)]
+        fn get_authorizations_inbox(
+            signatory: Signatory<AccountId>,
+            auth_type: Option<AuthorizationType>,
+            allow_expired: bool,
+            after: Option<u64>,
+            limit: u32
+        ) -> Vec<RpcAuthorization<AccountId, Moment>>;
+
+        /// Returns up to `limit` authorizations issued by `issuer`, following the authorization `after` in storage order.
+        /// The id of the last authorization returned can be used as `after` to get the next page.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "identity_getAuthorizationsOutbox",
+        ///     "params":["0x0100000000000000000000000000000000000000000000000000000000000000", null, false, 1, 100]
+        ///   }'
+        /// ```
+        #[api_version(5)]
+        fn get_authorizations_outbox(
+            issuer: IdentityId,
+            auth_type: Option<AuthorizationType>,
+            allow_expired: bool,
+            after: Option<u64>,
+            limit: u32
+        ) -> Vec<RpcAuthorization<AccountId, Moment>>;
+
+        /// Returns the identities whose CDD expires within `days` days, or is within its grace period,
+        /// along with their CDD expiry, ordered by expiry. `days` is capped at 366.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "identity_getCddExpiringWithin",
+        ///     "params":[30]
+        ///   }'
+        /// ```
+        #[api_version(6)]
+        fn cdd_expiring_within(days: u32) -> Vec<(IdentityId, Moment)>;
+
+        /// Returns the keys, unexpired claims and pending authorizations of `did` as a versioned bundle,
+        /// to be signed by its primary key and by an export attester of this chain,
+        /// and imported on another chain with `import_identity_export`.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "identity_exportIdentity",
+        ///     "params":["0x0100000000000000000000000000000000000000000000000000000000000000"]
+        ///   }'
+        /// ```
+        #[api_version(8)]
+        fn export_identity(did: IdentityId) -> Option<IdentityExport<AccountId, Moment>>;
     }
 }
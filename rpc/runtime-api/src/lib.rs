@@ -17,8 +17,11 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod asset;
+pub mod compliance;
 pub mod identity;
+pub mod multisig;
 pub mod nft;
 pub mod pips;
 pub mod settlement;
 pub mod transaction_payment;
+pub mod treasury;
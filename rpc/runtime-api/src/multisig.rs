@@ -0,0 +1,43 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymesh Association
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for MultiSig module.
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+use polymesh_primitives::multisig::ProposalInfo;
+
+sp_api::decl_runtime_apis! {
+    pub trait MultiSigApi<AccountId, Moment>
+    where
+        AccountId: Codec,
+        Moment: Codec
+    {
+        /// Returns up to `limit` proposals of `multisig` that are open for voting or queued for execution,
+        /// following the proposal `after` in storage order, with their SCALE encoded call, voters, status and expiry.
+        /// The encoded call can be decoded with the runtime metadata.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "multiSig_getActiveProposals",
+        ///     "params": ["5EYCAe5ijiYfyeZ2JJCGq56LmPyNRAKzpG4QkoQkkQNB5e6Z", null, 10]
+        ///   }'
+        /// ```
+        fn active_proposals(multisig: AccountId, after: Option<u64>, limit: u32) -> Vec<ProposalInfo<AccountId, Moment, Vec<u8>>>;
+    }
+}
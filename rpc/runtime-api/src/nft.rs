@@ -17,10 +17,13 @@
 
 use frame_support::dispatch::DispatchResult;
 
-use polymesh_primitives::{NFTs, PortfolioId};
+use sp_std::vec::Vec;
+
+use polymesh_primitives::{NFTId, NFTMetadataAttribute, NFTs, PortfolioId, Ticker};
 
 sp_api::decl_runtime_apis! {
 
+    #[api_version(2)]
     pub trait NFTApi {
         /// Verifies if the given NFTs can be transferred from `sender_portfolio` to `receiver_portfolio`.
         /// In order for the transfer to be successfull, the following conditions must hold:
@@ -40,5 +43,59 @@ sp_api::decl_runtime_apis! {
         ///   }'
         /// ```
         fn validate_nft_transfer(sender_portfolio: &PortfolioId, receiver_portfolio: &PortfolioId, nfts: &NFTs) -> DispatchResult;
+
+        /// Returns the NFTs of the collection identified by `ticker` with ids from `start` (inclusive) to `start + limit` (exclusive),
+        /// along with the id to start the next page at, if there are more ids to check. Redeemed NFTs are skipped.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "nft_getCollectionNFTs",
+        ///     "params":["0x5449434B4552303030303031", 1, 100]
+        ///   }'
+        /// ```
+        #[api_version(2)]
+        fn collection_nfts(ticker: &Ticker, start: Option<NFTId>, limit: u32) -> (Vec<NFTId>, Option<NFTId>);
+
+        /// Returns up to `limit` NFTs held by `portfolio`, following `after` in storage order.
+        /// The last NFT returned can be used as `after` to get the next page.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "nft_getPortfolioNFTs",
+        ///     "params":[{ "did": "0x0100000000000000000000000000000000000000000000000000000000000000", "kind": "Default"}, null, 100]
+        ///   }'
+        /// ```
+        #[api_version(2)]
+        fn portfolio_nfts(portfolio: &PortfolioId, after: Option<(Ticker, NFTId)>, limit: u32) -> Vec<(Ticker, NFTId)>;
+
+        /// Returns all metadata attributes of the NFT, or `None` if the NFT doesn't exist.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "nft_getNFTMetadata",
+        ///     "params":["0x5449434B4552303030303031", 1]
+        ///   }'
+        /// ```
+        #[api_version(2)]
+        fn nft_metadata(ticker: &Ticker, nft_id: &NFTId) -> Option<Vec<NFTMetadataAttribute>>;
+
+        /// Returns the portfolio that owns the NFT, or `None` if the NFT doesn't exist.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "nft_getNFTOwner",
+        ///     "params":["0x5449434B4552303030303031", 1]
+        ///   }'
+        /// ```
+        #[api_version(2)]
+        fn nft_owner(ticker: &Ticker, nft_id: &NFTId) -> Option<PortfolioId>;
     }
 }
@@ -0,0 +1,42 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymesh Association
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for Treasury module.
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+use polymesh_primitives::treasury::{PaymentPlan, PaymentPlanId};
+use polymesh_primitives::IdentityId;
+
+sp_api::decl_runtime_apis! {
+    pub trait TreasuryApi<BlockNumber>
+    where
+        BlockNumber: Codec
+    {
+        /// Returns the active payment plans of `beneficiary`, with their schedule and the amount
+        /// paid out so far.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "treasury_getPaymentPlans",
+        ///     "params": ["0x0100000000000000000000000000000000000000000000000000000000000000"]
+        ///   }'
+        /// ```
+        fn payment_plans(beneficiary: IdentityId) -> Vec<(PaymentPlanId, PaymentPlan<BlockNumber>)>;
+    }
+}
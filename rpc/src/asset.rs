@@ -109,7 +109,7 @@ where
             )))?;
 
         let api_call_result = {
-            if api_version >= 3 {
+            if api_version >= 4 {
                 api.can_transfer_granular(
                     at_hash,
                     from_custodian,
@@ -119,6 +119,18 @@ where
                     &ticker,
                     value.into(),
                 )
+            } else if api_version == 3 {
+                #[allow(deprecated)]
+                api.can_transfer_granular_before_version_4(
+                    at_hash,
+                    from_custodian,
+                    from_portfolio,
+                    to_custodian,
+                    to_portfolio,
+                    &ticker,
+                    value.into(),
+                )
+                .map(|value| value.map(GranularCanTransferResult::from))
             } else if api_version == 2 {
                 #[allow(deprecated)]
                 api.can_transfer_granular_before_version_3(
@@ -130,7 +142,7 @@ where
                     &ticker,
                     value.into(),
                 )
-                .map(|value| Ok(value))
+                .map(|value| Ok(GranularCanTransferResult::from(value)))
             } else {
                 #[allow(deprecated)]
                 api.can_transfer_granular_before_version_2(
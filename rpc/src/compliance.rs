@@ -0,0 +1,147 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2023 Polymesh Association
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use frame_support::dispatch::result::Result;
+use frame_support::pallet_prelude::DispatchError;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use jsonrpsee::types::error::{CallError, ErrorObject};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+pub use node_rpc_runtime_api::compliance::ComplianceApi as ComplianceRuntimeApi;
+use polymesh_primitives::compliance_manager::{AssetCompliance, ComplianceImpact};
+use polymesh_primitives::settlement::InstructionId;
+use polymesh_primitives::transfer_compliance::TransferCondition;
+use polymesh_primitives::{IdentityId, Ticker};
+
+use crate::Error;
+
+#[rpc(client, server)]
+pub trait ComplianceApi<BlockHash> {
+    #[method(name = "compliance_simulateAssetCompliance")]
+    fn simulate_asset_compliance(
+        &self,
+        ticker: Ticker,
+        asset_compliance: AssetCompliance,
+        start: InstructionId,
+        holders_after: Option<IdentityId>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Result<ComplianceImpact, DispatchError>>;
+
+    #[method(name = "compliance_simulateTransferCompliance")]
+    fn simulate_transfer_compliance(
+        &self,
+        ticker: Ticker,
+        paused: bool,
+        transfer_conditions: BTreeSet<TransferCondition>,
+        start: InstructionId,
+        holders_after: Option<IdentityId>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Result<ComplianceImpact, DispatchError>>;
+}
+
+/// An implementation of compliance simulation RPC methods.
+pub struct Compliance<T, U> {
+    client: Arc<T>,
+    _marker: std::marker::PhantomData<U>,
+}
+
+impl<T, U> Compliance<T, U> {
+    /// Creates a new `Compliance` with the given reference to the client.
+    pub fn new(client: Arc<T>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T, Block> ComplianceApiServer<<Block as BlockT>::Hash> for Compliance<T, Block>
+where
+    Block: BlockT,
+    T: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    T::Api: ComplianceRuntimeApi<Block>,
+{
+    fn simulate_asset_compliance(
+        &self,
+        ticker: Ticker,
+        asset_compliance: AssetCompliance,
+        start: InstructionId,
+        holders_after: Option<IdentityId>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Result<ComplianceImpact, DispatchError>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.simulate_asset_compliance(
+            at_hash,
+            ticker,
+            asset_compliance,
+            start,
+            holders_after,
+            limit,
+        )
+        .map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                Error::RuntimeError.into(),
+                "Unable to call simulate_asset_compliance runtime",
+                Some(e.to_string()),
+            ))
+            .into()
+        })
+    }
+
+    fn simulate_transfer_compliance(
+        &self,
+        ticker: Ticker,
+        paused: bool,
+        transfer_conditions: BTreeSet<TransferCondition>,
+        start: InstructionId,
+        holders_after: Option<IdentityId>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Result<ComplianceImpact, DispatchError>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.simulate_transfer_compliance(
+            at_hash,
+            ticker,
+            paused,
+            transfer_conditions,
+            start,
+            holders_after,
+            limit,
+        )
+        .map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                Error::RuntimeError.into(),
+                "Unable to call simulate_transfer_compliance runtime",
+                Some(e.to_string()),
+            ))
+            .into()
+        })
+    }
+}
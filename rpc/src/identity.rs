@@ -10,9 +10,11 @@ use sp_runtime::traits::{Block as BlockT, Zero};
 
 pub use node_rpc_runtime_api::identity::IdentityApi as IdentityRuntimeApi;
 pub use pallet_identity::types::{
-    AssetDidResult, CddStatus, DidStatus, KeyIdentityData, RpcDidRecords,
+    AssetDidResult, CddStatus, DidStatus, KeyIdentityData, RpcAuthorization, RpcDidRecords,
+};
+use polymesh_primitives::{
+    Authorization, AuthorizationType, IdentityClaim, IdentityExport, Signatory,
 };
-use polymesh_primitives::{Authorization, AuthorizationType, IdentityClaim, Signatory};
 
 use super::Error;
 
@@ -63,7 +65,8 @@ pub trait IdentityApi<BlockHash, IdentityId, Ticker, AccountId, Moment> {
     /// Provide the `KeyIdentityData` from a given `AccountId`, including:
     /// - the corresponding DID,
     /// - whether the `AccountId` is a primary or secondary key,
-    /// - any permissions related to the key.
+    /// - any permissions related to the key,
+    /// - the validity window and spending limits of a secondary key.
     ///
     /// This is an aggregate call provided for UX convenience.
     #[method(name = "identity_getKeyIdentityData")]
@@ -71,7 +74,7 @@ pub trait IdentityApi<BlockHash, IdentityId, Ticker, AccountId, Moment> {
         &self,
         acc: AccountId,
         at: Option<BlockHash>,
-    ) -> RpcResult<Option<KeyIdentityData<IdentityId>>>;
+    ) -> RpcResult<Option<KeyIdentityData<IdentityId, Moment>>>;
 
     /// Returns all valid [`IdentityClaim`] of type `CustomerDueDiligence` for the given `target_identity`.
     #[method(name = "identity_validCDDClaims")]
@@ -81,6 +84,47 @@ pub trait IdentityApi<BlockHash, IdentityId, Ticker, AccountId, Moment> {
         cdd_checker_leeway: Option<u64>,
         at: Option<BlockHash>,
     ) -> RpcResult<Vec<IdentityClaim>>;
+
+    /// Returns up to `limit` authorizations received by `signatory`, following the authorization `after`.
+    #[method(name = "identity_getAuthorizationsInbox")]
+    fn get_authorizations_inbox(
+        &self,
+        signatory: Signatory<AccountId>,
+        auth_type: Option<AuthorizationType>,
+        allow_expired: bool,
+        after: Option<u64>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<RpcAuthorization<AccountId, Moment>>>;
+
+    /// Returns up to `limit` authorizations issued by `issuer`, following the authorization `after`.
+    #[method(name = "identity_getAuthorizationsOutbox")]
+    fn get_authorizations_outbox(
+        &self,
+        issuer: IdentityId,
+        auth_type: Option<AuthorizationType>,
+        allow_expired: bool,
+        after: Option<u64>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<RpcAuthorization<AccountId, Moment>>>;
+
+    /// Returns the identities whose CDD expires within `days` days, or is within its grace period.
+    #[method(name = "identity_getCddExpiringWithin")]
+    fn cdd_expiring_within(
+        &self,
+        days: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(IdentityId, Moment)>>;
+
+    /// Returns the keys, unexpired claims and pending authorizations of `did` as a versioned bundle,
+    /// to be signed by its primary key and imported on another chain.
+    #[method(name = "identity_exportIdentity")]
+    fn export_identity(
+        &self,
+        did: IdentityId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<IdentityExport<AccountId, Moment>>>;
 }
 
 /// A struct that implements the [`IdentityApi`].
@@ -169,7 +213,11 @@ where
             })?;
 
         match api_version {
-            Some(version) if version >= 2 => api.get_did_records(at_hash, did),
+            Some(version) if version >= 7 => api.get_did_records(at_hash, did),
+            #[allow(deprecated)]
+            Some(version) if version >= 2 => api
+                .get_did_records_before_version_7(at_hash, did)
+                .map(RpcDidRecords::from),
             _ => {
                 return Err(CallError::Custom(ErrorObject::owned(
                     ErrorCode::MethodNotFound.code(),
@@ -247,15 +295,44 @@ where
         &self,
         acc: AccountId,
         at: Option<<Block as BlockT>::Hash>,
-    ) -> RpcResult<Option<KeyIdentityData<IdentityId>>> {
-        rpc_forward_call!(
-            self,
-            at,
-            |api: ApiRef<<C as ProvideRuntimeApi<Block>>::Api>, at| {
-                api.get_key_identity_data(at, acc)
-            },
-            "Unable to query `get_key_identity_data`."
-        )
+    ) -> RpcResult<Option<KeyIdentityData<IdentityId, Moment>>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        // Gets the api version, returns an error if not found.
+        let api_version = api
+            .api_version::<dyn IdentityRuntimeApi<Block, IdentityId, Ticker, AccountId, Moment>>(
+                at_hash,
+            )
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to find the api version",
+                    Some(e.to_string()),
+                ))
+            })?
+            .ok_or(CallError::Custom(ErrorObject::owned(
+                Error::RuntimeError.into(),
+                "Api version cannot be None",
+                Some("None version"),
+            )))?;
+
+        let api_call_result = if api_version >= 4 {
+            api.get_key_identity_data(at_hash, acc)
+        } else {
+            #[allow(deprecated)]
+            api.get_key_identity_data_before_version_4(at_hash, acc)
+                .map(|data| data.map(KeyIdentityData::from))
+        };
+
+        api_call_result.map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                Error::RuntimeError.into(),
+                "Unable to query `get_key_identity_data`.",
+                Some(e.to_string()),
+            ))
+            .into()
+        })
     }
 
     fn valid_cdd_claims(
@@ -278,4 +355,90 @@ where
                 .into()
             })
     }
+
+    fn get_authorizations_inbox(
+        &self,
+        signatory: Signatory<AccountId>,
+        auth_type: Option<AuthorizationType>,
+        allow_expired: bool,
+        after: Option<u64>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<RpcAuthorization<AccountId, Moment>>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.get_authorizations_inbox(at_hash, signatory, auth_type, allow_expired, after, limit)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to call get_authorizations_inbox runtime",
+                    Some(e.to_string()),
+                ))
+                .into()
+            })
+    }
+
+    fn get_authorizations_outbox(
+        &self,
+        issuer: IdentityId,
+        auth_type: Option<AuthorizationType>,
+        allow_expired: bool,
+        after: Option<u64>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<RpcAuthorization<AccountId, Moment>>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.get_authorizations_outbox(at_hash, issuer, auth_type, allow_expired, after, limit)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to call get_authorizations_outbox runtime",
+                    Some(e.to_string()),
+                ))
+                .into()
+            })
+    }
+
+    fn cdd_expiring_within(
+        &self,
+        days: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(IdentityId, Moment)>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.cdd_expiring_within(at_hash, days).map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                Error::RuntimeError.into(),
+                "Unable to call cdd_expiring_within runtime",
+                Some(e.to_string()),
+            ))
+            .into()
+        })
+    }
+
+    fn export_identity(
+        &self,
+        did: IdentityId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<IdentityExport<AccountId, Moment>>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.export_identity(at_hash, did).map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                Error::RuntimeError.into(),
+                "Unable to call export_identity runtime",
+                Some(e.to_string()),
+            ))
+            .into()
+        })
+    }
 }
@@ -0,0 +1,84 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymesh Association
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use jsonrpsee::types::error::{CallError, ErrorObject};
+use sp_api::{ApiRef, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use sp_std::vec::Vec;
+
+pub use node_rpc_runtime_api::multisig::MultiSigApi as MultiSigRuntimeApi;
+use polymesh_primitives::multisig::ProposalInfo;
+
+/// MultiSig RPC methods.
+#[rpc(client, server)]
+pub trait MultiSigApi<BlockHash, AccountId, Moment> {
+    /// Retrieves up to `limit` proposals of `multisig` that are open for voting or queued for execution,
+    /// following the proposal `after`.
+    #[method(name = "multiSig_getActiveProposals")]
+    fn active_proposals(
+        &self,
+        multisig: AccountId,
+        after: Option<u64>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<ProposalInfo<AccountId, Moment, Vec<u8>>>>;
+}
+
+/// An implementation of multisig specific RPC methods.
+pub struct MultiSig<T, U> {
+    client: Arc<T>,
+    _marker: std::marker::PhantomData<U>,
+}
+
+impl<T, U> MultiSig<T, U> {
+    /// Create new `MultiSig` with the given reference to the client.
+    pub fn new(client: Arc<T>) -> Self {
+        MultiSig {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, AccountId, Moment> MultiSigApiServer<<Block as BlockT>::Hash, AccountId, Moment>
+    for MultiSig<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: MultiSigRuntimeApi<Block, AccountId, Moment>,
+    AccountId: Codec,
+    Moment: Codec,
+{
+    fn active_proposals(
+        &self,
+        multisig: AccountId,
+        after: Option<u64>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<ProposalInfo<AccountId, Moment, Vec<u8>>>> {
+        rpc_forward_call!(
+            self,
+            at,
+            |api: ApiRef<<C as ProvideRuntimeApi<Block>>::Api>, at| api
+                .active_proposals(at, multisig, after, limit),
+            "Unable to query `active_proposals`."
+        )
+    }
+}
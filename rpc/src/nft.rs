@@ -18,13 +18,13 @@ use std::sync::Arc;
 use frame_support::dispatch::DispatchResult;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
-use jsonrpsee::types::error::{CallError, ErrorObject};
-use sp_api::ProvideRuntimeApi;
+use jsonrpsee::types::error::{CallError, ErrorCode, ErrorObject};
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_runtime::traits::Block as BlockT;
 
 pub use node_rpc_runtime_api::nft::NFTApi as NFTRuntimeApi;
-use polymesh_primitives::{NFTs, PortfolioId};
+use polymesh_primitives::{NFTId, NFTMetadataAttribute, NFTs, PortfolioId, Ticker};
 
 use crate::Error;
 
@@ -38,6 +38,40 @@ pub trait NFTApi<BlockHash> {
         nfts: NFTs,
         at: Option<BlockHash>,
     ) -> RpcResult<DispatchResult>;
+
+    #[method(name = "nft_getCollectionNFTs")]
+    fn collection_nfts(
+        &self,
+        ticker: Ticker,
+        start: Option<NFTId>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<(Vec<NFTId>, Option<NFTId>)>;
+
+    #[method(name = "nft_getPortfolioNFTs")]
+    fn portfolio_nfts(
+        &self,
+        portfolio: PortfolioId,
+        after: Option<(Ticker, NFTId)>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(Ticker, NFTId)>>;
+
+    #[method(name = "nft_getNFTMetadata")]
+    fn nft_metadata(
+        &self,
+        ticker: Ticker,
+        nft_id: NFTId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<Vec<NFTMetadataAttribute>>>;
+
+    #[method(name = "nft_getNFTOwner")]
+    fn nft_owner(
+        &self,
+        ticker: Ticker,
+        nft_id: NFTId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<PortfolioId>>;
 }
 
 /// An implementation of NFT specific RPC methods.
@@ -56,6 +90,36 @@ impl<T, U> NFT<T, U> {
     }
 }
 
+impl<T, Block> NFT<T, Block>
+where
+    Block: BlockT,
+    T: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    T::Api: NFTRuntimeApi<Block>,
+{
+    /// Returns an error if the runtime at `at_hash` doesn't support version 2 of the NFT runtime api.
+    fn ensure_api_version_2(&self, at_hash: <Block as BlockT>::Hash) -> Result<(), CallError> {
+        let api_version = self
+            .client
+            .runtime_api()
+            .api_version::<dyn NFTRuntimeApi<Block>>(at_hash)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to fetch the NFT runtime api version",
+                    Some(e.to_string()),
+                ))
+            })?;
+        match api_version {
+            Some(version) if version >= 2 => Ok(()),
+            _ => Err(CallError::Custom(ErrorObject::owned(
+                ErrorCode::MethodNotFound.code(),
+                format!("Version 2 of `NFTApi` is not available for block {at_hash:?}"),
+                None::<()>,
+            ))),
+        }
+    }
+}
+
 impl<T, Block> NFTApiServer<<Block as BlockT>::Hash> for NFT<T, Block>
 where
     Block: BlockT,
@@ -83,4 +147,92 @@ where
                 .into()
             })
     }
+
+    fn collection_nfts(
+        &self,
+        ticker: Ticker,
+        start: Option<NFTId>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<(Vec<NFTId>, Option<NFTId>)> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.ensure_api_version_2(at_hash)?;
+
+        api.collection_nfts(at_hash, &ticker, start, limit)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to call collection_nfts runtime",
+                    Some(e.to_string()),
+                ))
+                .into()
+            })
+    }
+
+    fn portfolio_nfts(
+        &self,
+        portfolio: PortfolioId,
+        after: Option<(Ticker, NFTId)>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(Ticker, NFTId)>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.ensure_api_version_2(at_hash)?;
+
+        api.portfolio_nfts(at_hash, &portfolio, after, limit)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to call portfolio_nfts runtime",
+                    Some(e.to_string()),
+                ))
+                .into()
+            })
+    }
+
+    fn nft_metadata(
+        &self,
+        ticker: Ticker,
+        nft_id: NFTId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<Vec<NFTMetadataAttribute>>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.ensure_api_version_2(at_hash)?;
+
+        api.nft_metadata(at_hash, &ticker, &nft_id).map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                Error::RuntimeError.into(),
+                "Unable to call nft_metadata runtime",
+                Some(e.to_string()),
+            ))
+            .into()
+        })
+    }
+
+    fn nft_owner(
+        &self,
+        ticker: Ticker,
+        nft_id: NFTId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<PortfolioId>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.ensure_api_version_2(at_hash)?;
+
+        api.nft_owner(at_hash, &ticker, &nft_id).map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                Error::RuntimeError.into(),
+                "Unable to call nft_owner runtime",
+                Some(e.to_string()),
+            ))
+            .into()
+        })
+    }
 }
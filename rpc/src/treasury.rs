@@ -0,0 +1,79 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymesh Association
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use jsonrpsee::types::error::{CallError, ErrorObject};
+use sp_api::{ApiRef, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use sp_std::vec::Vec;
+
+pub use node_rpc_runtime_api::treasury::TreasuryApi as TreasuryRuntimeApi;
+use polymesh_primitives::treasury::{PaymentPlan, PaymentPlanId};
+use polymesh_primitives::IdentityId;
+
+/// Treasury RPC methods.
+#[rpc(client, server)]
+pub trait TreasuryApi<BlockHash, BlockNumber> {
+    /// Retrieves the active payment plans of `beneficiary`.
+    #[method(name = "treasury_getPaymentPlans")]
+    fn payment_plans(
+        &self,
+        beneficiary: IdentityId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(PaymentPlanId, PaymentPlan<BlockNumber>)>>;
+}
+
+/// An implementation of treasury specific RPC methods.
+pub struct Treasury<T, U> {
+    client: Arc<T>,
+    _marker: std::marker::PhantomData<U>,
+}
+
+impl<T, U> Treasury<T, U> {
+    /// Create new `Treasury` with the given reference to the client.
+    pub fn new(client: Arc<T>) -> Self {
+        Treasury {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, BlockNumber> TreasuryApiServer<<Block as BlockT>::Hash, BlockNumber>
+    for Treasury<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: TreasuryRuntimeApi<Block, BlockNumber>,
+    BlockNumber: Codec,
+{
+    fn payment_plans(
+        &self,
+        beneficiary: IdentityId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(PaymentPlanId, PaymentPlan<BlockNumber>)>> {
+        rpc_forward_call!(
+            self,
+            at,
+            |api: ApiRef<<C as ProvideRuntimeApi<Block>>::Api>, at| api
+                .payment_plans(at, beneficiary),
+            "Unable to query `payment_plans`."
+        )
+    }
+}
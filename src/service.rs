@@ -109,9 +109,12 @@ pub trait RuntimeApiCollection:
     + node_rpc_runtime_api::identity::IdentityApi<Block, IdentityId, Ticker, AccountId, Moment>
     + pallet_protocol_fee_rpc_runtime_api::ProtocolFeeApi<Block>
     + node_rpc_runtime_api::asset::AssetApi<Block, AccountId>
+    + node_rpc_runtime_api::compliance::ComplianceApi<Block>
     + pallet_group_rpc_runtime_api::GroupApi<Block>
     + node_rpc_runtime_api::nft::NFTApi<Block>
+    + node_rpc_runtime_api::multisig::MultiSigApi<Block, AccountId, Moment>
     + node_rpc_runtime_api::settlement::SettlementApi<Block>
+    + node_rpc_runtime_api::treasury::TreasuryApi<Block, BlockNumber>
 where
     <Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {
@@ -135,9 +138,12 @@ where
         + node_rpc_runtime_api::identity::IdentityApi<Block, IdentityId, Ticker, AccountId, Moment>
         + pallet_protocol_fee_rpc_runtime_api::ProtocolFeeApi<Block>
         + node_rpc_runtime_api::asset::AssetApi<Block, AccountId>
+        + node_rpc_runtime_api::compliance::ComplianceApi<Block>
         + pallet_group_rpc_runtime_api::GroupApi<Block>
         + node_rpc_runtime_api::nft::NFTApi<Block>
-        + node_rpc_runtime_api::settlement::SettlementApi<Block>,
+        + node_rpc_runtime_api::multisig::MultiSigApi<Block, AccountId, Moment>
+        + node_rpc_runtime_api::settlement::SettlementApi<Block>
+        + node_rpc_runtime_api::treasury::TreasuryApi<Block, BlockNumber>,
     <Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {
 }